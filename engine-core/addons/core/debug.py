@@ -162,6 +162,19 @@ class DebugManager:
                 if flow_id in self.intercepted_flows:
                     del self.intercepted_flows[flow_id]
 
+    def list_intercepted(self) -> List[Dict[str, Any]]:
+        """Snapshot of paused flows: [{id, phase, ruleId, flow: HTTPFlow}]"""
+        with self.lock:
+            return [
+                {
+                    "id": flow_id,
+                    "phase": info["phase"],
+                    "ruleId": (info.get("rule") or {}).get("id"),
+                    "flow": info["flow"],
+                }
+                for flow_id, info in self.intercepted_flows.items()
+            ]
+
     def resume_flow(self, flow_id: str, modified_data: Optional[Dict[str, Any]] = None) -> bool:
         """Signal a flow to resume, optionally applying modifications.
 
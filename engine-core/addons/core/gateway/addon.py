@@ -87,7 +87,7 @@ class GatewayAddon:
             rules_applied = []
             for h in hits:
                 t = h.get("type", "")
-                if t in ("map_local", "map_remote", "rewrite_header", "rewrite_body", "throttle", "block_request"):
+                if t in ("map_local", "map_remote", "redirect", "rewrite_header", "rewrite_body", "throttle", "breakpoint", "block_request"):
                     rules_applied.append({"id": h.get("id", ""), "type": t, "name": h.get("name", "")})
 
             gateway_route_id = matched_route.get("id") if matched_route else None
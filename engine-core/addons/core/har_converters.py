@@ -1,6 +1,8 @@
+import base64
+import binascii
 import time
 import uuid
-from typing import Any, Dict, List, Tuple
+from typing import IO, Any, Dict, Iterator, List, Tuple
 from urllib.parse import urlparse
 
 
@@ -34,6 +36,40 @@ def query_to_har(query: Any) -> List[Dict[str, str]]:
     return result
 
 
+WS_FRAME_TYPES = {1: "text", 2: "binary", 8: "close", 9: "ping", 10: "pong"}
+
+
+def websocket_frames_from_har(flow_id: str, messages: list) -> List[Dict[str, Any]]:
+    """Rebuild RelayCraft frames from a Chrome-style `_webSocketMessages` list."""
+    frames = []
+    for seq, message in enumerate(messages or []):
+        message = message or {}
+        opcode = message.get("opcode") or 1
+        data = message.get("data") or ""
+        is_text = opcode == 1
+        if is_text:
+            length = len(data.encode("utf-8"))
+        else:
+            try:
+                length = len(base64.b64decode(data, validate=True))
+            except (binascii.Error, ValueError):
+                length = len(data)
+        frames.append(
+            {
+                "id": f"{flow_id}-{seq}",
+                "flowId": flow_id,
+                "seq": seq,
+                "type": WS_FRAME_TYPES.get(opcode, "binary"),
+                "fromClient": message.get("type") == "send",
+                "content": data,
+                "encoding": "text" if is_text else "base64",
+                "timestamp": float(message.get("time") or 0) * 1000,
+                "length": length,
+            }
+        )
+    return frames
+
+
 def normalize_har_entries(entries: list) -> Tuple[list, list]:
     flows = []
     indices = []
@@ -50,6 +86,15 @@ def normalize_har_entries(entries: list) -> Tuple[list, list]:
         resp = entry.get("response") or {}
         resp_content = resp.get("content") or {}
         rc = entry.get("_rc") or {}
+        ws_messages = entry.get("_webSocketMessages")
+        if ws_messages is not None and not rc.get("websocketFrames"):
+            frames = websocket_frames_from_har(flow_id, ws_messages)
+            rc = {
+                **rc,
+                "isWebsocket": True,
+                "websocketFrameCount": len(frames),
+                "websocketFrames": frames,
+            }
 
         msg_ts = base_ts + idx * 0.001
 
@@ -101,3 +146,23 @@ def normalize_har_entries(entries: list) -> Tuple[list, list]:
         )
 
     return flows, indices
+
+
+def iter_har_flow_batches(fh: IO[bytes], batch_size: int = 500) -> Iterator[list]:
+    """Stream `log.entries` from a HAR file, yielding normalized flows in batches.
+
+    Only one batch of entries is held in memory at a time, so multi-gigabyte
+    browser exports import without loading the whole document.
+    """
+    import ijson
+
+    batch = []
+    for entry in ijson.items(fh, "log.entries.item"):
+        batch.append(entry)
+        if len(batch) >= batch_size:
+            flows, _ = normalize_har_entries(batch)
+            yield flows
+            batch = []
+    if batch:
+        flows, _ = normalize_har_entries(batch)
+        yield flows
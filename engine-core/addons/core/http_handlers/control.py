@@ -37,6 +37,18 @@ def _handle_breakpoints(monitor: Any, flow: Any, Response: Any) -> None:
     flow.response = Response.make(200, b"OK", CORS_HEADERS)
 
 
+def _handle_intercepted(monitor: Any, flow: Any, Response: Any) -> None:
+    entries = []
+    for entry in monitor.debug_mgr.list_intercepted():
+        flow_data = monitor.process_flow(entry.pop("flow"))
+        if flow_data:
+            entry["flow"] = flow_data
+            entries.append(entry)
+    flow.response = Response.make(
+        200, json.dumps(entries, default=str, ensure_ascii=False).encode("utf-8"), JSON_HEADERS,
+    )
+
+
 def _handle_resume(monitor: Any, flow: Any, Response: Any) -> None:
     data = json.loads(flow.request.content.decode("utf-8"))
     flow_id = data.get("id")
@@ -158,3 +170,19 @@ def _handle_scripts_load_status(monitor: Any, flow: Any, Response: Any) -> None:
     report = get_report()
     json_str = json.dumps(report, ensure_ascii=False)
     flow.response = Response.make(200, json_str.encode("utf-8"), JSON_HEADERS)
+
+
+def _handle_reload_rules(monitor: Any, flow: Any, Response: Any) -> None:
+    """Force the rule loader to re-read the rules directory immediately."""
+    import mitmproxy.ctx as mctx
+
+    main = getattr(mctx.master, "relaycraft_main", None) if hasattr(mctx, "master") else None
+    if main is None:
+        json_str = json.dumps({"success": False, "message": "Rule engine not available"})
+        flow.response = Response.make(503, json_str.encode("utf-8"), JSON_HEADERS)
+        return
+
+    count = main.rule_engine.loader.force_reload()
+    monitor.logger.info(f"Rules reloaded on request: {count} rule(s) active")
+    json_str = json.dumps({"success": True, "count": count})
+    flow.response = Response.make(200, json_str.encode("utf-8"), JSON_HEADERS)
@@ -57,6 +57,24 @@ def _handle_stats(monitor: Any, flow: Any, Response: Any) -> None:
     flow.response = Response.make(200, json_str.encode("utf-8"), JSON_HEADERS)
 
 
+def _handle_capture_count(monitor: Any, flow: Any, Response: Any) -> None:
+    result = {
+        "captured": monitor.captured_count,
+        "failed": monitor.store_failures,
+    }
+    json_str = json.dumps(result, ensure_ascii=False)
+    flow.response = Response.make(200, json_str.encode("utf-8"), JSON_HEADERS)
+
+
+def _handle_throughput(monitor: Any, flow: Any, Response: Any) -> None:
+    result = {
+        "rx_bytes": monitor.bytes_in,
+        "tx_bytes": monitor.bytes_out,
+    }
+    json_str = json.dumps(result, ensure_ascii=False)
+    flow.response = Response.make(200, json_str.encode("utf-8"), JSON_HEADERS)
+
+
 def _handle_traffic_active(monitor: Any, flow: Any, Response: Any) -> None:
     from ..main import is_traffic_active, set_traffic_active
 
@@ -144,9 +144,11 @@ def _handle_explain_path(monitor: Any, flow: Any, Response: Any) -> None:
             if t in (
                 "map_local",
                 "map_remote",
+                "redirect",
                 "rewrite_header",
                 "rewrite_body",
                 "throttle",
+                "breakpoint",
                 "block_request",
             ):
                 rules_applied.append(
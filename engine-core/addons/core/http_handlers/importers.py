@@ -7,8 +7,9 @@ from ..flowdb import (
     store_flows_batch,
     update_session_flow_count,
     update_session_import_status,
+    update_session_metadata,
 )
-from ..har_converters import normalize_har_entries
+from ..har_converters import iter_har_flow_batches, normalize_har_entries
 
 
 def _handle_import_session(monitor: Any, flow: Any, Response: Any) -> None:
@@ -291,27 +292,16 @@ def _handle_import_har_file(monitor: Any, flow: Any, Response: Any) -> None:
 
             def stream_har_worker() -> None:
                 try:
-                    import ijson
                     import time
 
+                    imported = 0
                     with open(file_path, "rb") as fh:
-                        entries_stream = ijson.items(fh, "log.entries.item")
-
-                        batch = []
-                        batch_size = 500
-
-                        for entry in entries_stream:
-                            batch.append(entry)
-
-                            if len(batch) >= batch_size:
-                                flows, _ = normalize_har_entries(batch)
-                                store_flows_batch(monitor.db, flows, session_id=session_id)
-                                batch = []
-                                time.sleep(0.01)
-
-                        if batch:
-                            flows, _ = normalize_har_entries(batch)
+                        for flows in iter_har_flow_batches(fh):
                             store_flows_batch(monitor.db, flows, session_id=session_id)
+                            imported += len(flows)
+                            # Progress for the import poller
+                            update_session_metadata(monitor.db, session_id, {"imported_count": imported})
+                            time.sleep(0.01)
 
                         update_session_flow_count(monitor.db, session_id)
                         update_session_import_status(monitor.db, session_id, "ready")
@@ -5,6 +5,8 @@ from .connectivity import _handle_connectivity
 from .control import (
     _handle_breakpoints,
     _handle_database_reset,
+    _handle_intercepted,
+    _handle_reload_rules,
     _handle_resume,
     _handle_scripts_load_status,
     _handle_session_activate,
@@ -16,11 +18,13 @@ from .control import (
     _handle_sessions_post,
 )
 from .data import (
+    _handle_capture_count,
     _handle_export_har,
     _handle_export_progress,
     _handle_export_session,
     _handle_search,
     _handle_stats,
+    _handle_throughput,
     _handle_traffic_active,
 )
 from .explain import _handle_explain_path
@@ -66,6 +70,7 @@ def handle_control_routes(monitor: Any, flow: Any, route_key: str, Response: Any
     route_map = {
         "relay_breakpoints": lambda: _handle_breakpoints(monitor, flow, Response),
         "relay_database_reset": lambda: _handle_database_reset(monitor, flow, Response),
+        "relay_intercepted": lambda: _handle_intercepted(monitor, flow, Response),
         "relay_resume": lambda: _handle_resume(monitor, flow, Response),
         "relay_sessions_delete_all": lambda: _handle_sessions_delete_all(monitor, flow, Response),
         "relay_sessions_get": lambda: _handle_sessions_get(monitor, flow, Response),
@@ -76,6 +81,7 @@ def handle_control_routes(monitor: Any, flow: Any, route_key: str, Response: Any
         "relay_session_clear": lambda: _handle_session_clear(monitor, flow, Response),
         "relay_scripts_load_status": lambda: _handle_scripts_load_status(monitor, flow, Response),
         "relay_connectivity": lambda: _handle_connectivity(monitor, flow, Response),
+        "relay_reload_rules": lambda: _handle_reload_rules(monitor, flow, Response),
     }
     return _dispatch(route_map, route_key, monitor, flow, Response)
 
@@ -90,7 +96,9 @@ def handle_data_routes(
     route_map = {
         "relay_search": lambda: _handle_search(monitor, flow, Response),
         "relay_stats": lambda: _handle_stats(monitor, flow, Response),
+        "relay_capture_count": lambda: _handle_capture_count(monitor, flow, Response),
         "relay_traffic_active": lambda: _handle_traffic_active(monitor, flow, Response),
+        "relay_throughput": lambda: _handle_throughput(monitor, flow, Response),
         "relay_export_session": lambda: _handle_export_session(monitor, flow, Response, safe_json_default),
         "relay_export_har": lambda: _handle_export_har(monitor, flow, Response, safe_json_default),
         "relay_export_progress": lambda: _handle_export_progress(monitor, flow, Response),
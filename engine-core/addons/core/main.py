@@ -42,7 +42,11 @@ class CoreAddon:
 
     async def running(self) -> None:
         """Called when proxy is up and running."""
-        pass
+        # Upstream proxy credentials come through the environment rather than
+        # the command line, where any local user could read them
+        upstream_auth = os.environ.pop("RELAYCRAFT_UPSTREAM_AUTH", "")
+        if upstream_auth:
+            ctx.options.update(upstream_auth=upstream_auth)
 
     async def request(self, flow: http.HTTPFlow) -> None:
 
@@ -102,7 +106,7 @@ class CoreAddon:
                     self.traffic_monitor._store_flow(flow_data)
 
             # 2. Interception (Manual/Breakpoint) - Asynchronous
-            matched_rule = self.debug_mgr.should_intercept(flow)
+            matched_rule = self.debug_mgr.should_intercept(flow) or self.rule_engine.breakpoint_for(flow, "request")
             if matched_rule:
                 def push_paused():
                     f_data = self.traffic_monitor.process_flow(flow)
@@ -125,7 +129,7 @@ class CoreAddon:
             self.rule_engine.handle_response(flow)
 
             # 2. Interception (Manual/Breakpoint) - Asynchronous
-            matched_rule = self.debug_mgr.should_intercept(flow, "response")
+            matched_rule = self.debug_mgr.should_intercept(flow, "response") or self.rule_engine.breakpoint_for(flow, "response")
             if matched_rule:
                 def push_paused_res():
                     f_data = self.traffic_monitor.process_flow(flow)
@@ -239,9 +243,11 @@ class CoreAddon:
                 if t in (
                     "map_local",
                     "map_remote",
+                    "redirect",
                     "rewrite_header",
                     "rewrite_body",
                     "throttle",
+                    "breakpoint",
                     "block_request",
                 ):
                     rules_applied.append(
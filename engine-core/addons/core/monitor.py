@@ -56,6 +56,15 @@ class TrafficMonitor:
         self.db = FlowDatabase()
         self.logger.info("FlowDatabase initialized for traffic persistence")
 
+        # Cumulative capture counters, sampled by the app to compute flows/sec
+        self.captured_count = 0
+        self.store_failures = 0
+
+        # Cumulative HTTP bytes exchanged with upstream servers, sampled by the
+        # app to compute proxy throughput (rx = responses, tx = requests)
+        self.bytes_in = 0
+        self.bytes_out = 0
+
         # SSE state (used by sse_processor module)
         self._sse_lock = threading.Lock()
         self._sse_states: Dict[str, Dict[str, Any]] = {}
@@ -543,6 +552,8 @@ class TrafficMonitor:
             return "relay_breakpoints"
         if "/_relay/resume" in path:
             return "relay_resume"
+        if "/_relay/intercepted" in path:
+            return "relay_intercepted"
         if "/_relay/database/reset" in path:
             return "relay_database_reset"
         if "/_relay/sessions/delete_all" in path:
@@ -563,10 +574,16 @@ class TrafficMonitor:
             return "relay_search"
         if "/_relay/stats" in path:
             return "relay_stats"
+        if "/_relay/capture_count" in path:
+            return "relay_capture_count"
         if "/_relay/traffic_active" in path:
             return "relay_traffic_active"
+        if "/_relay/throughput" in path:
+            return "relay_throughput"
         if "/_relay/connectivity" in path:
             return "relay_connectivity"
+        if "/_relay/reload_rules" in path and method == "POST":
+            return "relay_reload_rules"
         if "/_relay/scripts/load_status" in path:
             return "relay_scripts_load_status"
         if "/_relay/export_session" in path:
@@ -680,15 +697,34 @@ class TrafficMonitor:
         """Store flow data to database."""
         try:
             _store_flow_repo(self.db, flow_data)
+            self.captured_count += 1
         except Exception as e:
+            self.store_failures += 1
             import traceback
             self.logger.error(f"Error storing flow to database: {e}")
             self.logger.error(traceback.format_exc())
 
+    @staticmethod
+    def _wire_size(message: Any) -> int:
+        """Approximate on-the-wire size of a request/response: headers plus encoded body."""
+        if message is None:
+            return 0
+        size = len(message.raw_content or b"")
+        fields = getattr(getattr(message, "headers", None), "fields", None) or ()
+        for name, value in fields:
+            size += len(name) + len(value) + 4  # ": " and CRLF
+        return size
+
+    def record_throughput(self, flow: http.HTTPFlow) -> None:
+        """Add a completed exchange to the cumulative byte counters."""
+        self.bytes_out += self._wire_size(flow.request)
+        self.bytes_in += self._wire_size(flow.response)
+
     def handle_response(self, flow: http.HTTPFlow) -> None:
         """Capture flows on response."""
         if flow.request.path.startswith("/_relay"):
             return
+        self.record_throughput(flow)
         flow_data = self.process_flow(flow)
         if flow_data:
             self._store_flow(flow_data)
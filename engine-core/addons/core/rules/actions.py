@@ -2,15 +2,80 @@ import re
 import json
 import time
 from pathlib import Path
+from urllib.parse import unquote
 from typing import Optional, Any, Dict, List, Union
 from mitmproxy import http, ctx
 from mitmproxy.http import Response
 from ..utils import get_mime_type, setup_logging
 
+_MASK64 = (1 << 64) - 1
+
+
+def resolve_in_dir(base_dir: str, request_path: str) -> Optional[Path]:
+    """Map a request path onto a file inside a Map Local directory.
+
+    Query and fragment are dropped, each segment is percent-decoded,
+    directories resolve to `index.html`, and None means the path would climb
+    out of `base_dir`.
+    """
+    path = re.split(r"[?#]", request_path, maxsplit=1)[0]
+    resolved = Path(base_dir)
+    for segment in path.split("/"):
+        segment = unquote(segment)
+        if segment in ("", "."):
+            continue
+        if segment == ".." or any(c in segment for c in "/\\:"):
+            return None
+        resolved = resolved / segment
+    if resolved.is_dir():
+        resolved = resolved / "index.html"
+    return resolved
+
+
+class JitterRng:
+    """SplitMix64, mirrored by the Rust jitter preview (rules/jitter.rs)."""
+
+    def __init__(self, seed: int):
+        self.state = seed & _MASK64
+
+    def next_u64(self) -> int:
+        self.state = (self.state + 0x9E3779B97F4A7C15) & _MASK64
+        z = self.state
+        z = ((z ^ (z >> 30)) * 0xBF58476D1CE4E5B9) & _MASK64
+        z = ((z ^ (z >> 27)) * 0x94D049BB133111EB) & _MASK64
+        return z ^ (z >> 31)
+
+    def next_delay(self, delay_ms: int, jitter_ms: int) -> int:
+        if jitter_ms <= 0:
+            return delay_ms
+        offset = self.next_u64() % (2 * jitter_ms + 1) - jitter_ms
+        return max(0, delay_ms + offset)
+
+
 class ActionExecutor:
     def __init__(self, engine: Any):
         self.engine = engine  # Reference back to engine if needed
         self.logger = setup_logging()
+        # (rule id, action index) -> JitterRng for the currently loaded rules
+        self._jitter_rngs: Dict[tuple, JitterRng] = {}
+        self._jitter_rules: Any = None
+
+    def _jitter_rng(self, action: Dict[str, Any]) -> JitterRng:
+        # Actions are copied per flow, so the RNG is keyed by the action's place
+        # in its rule. A reload loads new rule objects and starts sequences over.
+        rules = self.engine.loader.rules
+        if rules is not self._jitter_rules:
+            self._jitter_rngs = {}
+            self._jitter_rules = rules
+        key = (action.get("_rule_id"), action.get("_action_index"))
+        rng = self._jitter_rngs.get(key)
+        if rng is None:
+            seed = action.get("jitterSeed")
+            if seed is None:
+                seed = time.time_ns()
+            rng = JitterRng(int(seed))
+            self._jitter_rngs[key] = rng
+        return rng
 
     def apply_rewrite_body(self, flow: http.HTTPFlow, action: Dict[str, Any], url_match: Optional[re.Match] = None) -> None:
         """Apply body modifications (Text/Regex/JSON) to Request or Response"""
@@ -200,6 +265,10 @@ class ActionExecutor:
             self.logger.info(f"Map Local (Manual Mock): {len(body)} bytes, status {status_code}")
             return
 
+        if action.get("dir"):
+            self._serve_from_dir(flow, action, status_code, content_type)
+            return
+
         # --- File Source Logic ---
         local_path = action.get("localPath", "")
         # Perform regex substitution if match object exists
@@ -268,6 +337,28 @@ class ActionExecutor:
             
             # Do NOT set flow.response, allowing fallback to network (or next rule)
     
+    def _serve_from_dir(self, flow: http.HTTPFlow, action: Dict[str, Any], status_code: int, content_type: str) -> None:
+        """Map Local directory mode: 403 outside the folder, 404 when missing"""
+        file_path = resolve_in_dir(action["dir"], flow.request.path)
+        if file_path is None:
+            flow.response = Response.make(403, b"Forbidden", {"Content-Type": "text/plain"})
+            return
+        if not file_path.is_file():
+            flow.response = Response.make(404, b"Not Found", {"Content-Type": "text/plain"})
+            rule_id = action.get("_rule_id")
+            if rule_id:
+                temp_rule = {"id": rule_id, "name": action.get("_rule_name", "Unknown"), "type": "map_local"}
+                self.engine.record_rule_hit(flow, temp_rule, status="file_not_found", message=str(file_path))
+            return
+
+        content = file_path.read_bytes()
+        headers = {"Content-Type": content_type or get_mime_type(str(file_path))}
+        flow.response = Response.make(status_code, content, headers)
+        headers_config = action.get("headers")
+        if headers_config:
+            self.apply_rewrite_header(flow, headers_config, "response")
+        self.logger.info(f"Map Local (Dir): {file_path}, status {status_code}")
+
     def apply_map_remote(self, flow: http.HTTPFlow, action: Dict[str, Any], url_match: Optional[re.Match] = None) -> None:
         """Apply URL redirection with regex substitution support"""
         target_url = action.get("targetUrl", "")
@@ -351,6 +442,27 @@ class ActionExecutor:
                 "request": request_headers
             }, "request")
     
+    def apply_redirect(self, flow: http.HTTPFlow, action: Dict[str, Any], url_match: Optional[re.Match] = None) -> None:
+        """Answer with a 3xx so the client follows the redirect itself"""
+        target_url = action.get("targetUrl", "")
+        status_code = action.get("statusCode", 302)
+        if status_code not in (301, 302, 307, 308):
+            status_code = 302
+
+        # Same $1 / \1 group references as Map Remote
+        if url_match and re.search(r'(?<!\\)(\$\d|\\\d)', target_url):
+            try:
+                target_url = url_match.expand(re.sub(r'\$(\d+)', r'\\\1', target_url))
+            except Exception as e:
+                self.logger.error(f"Error expanding regex in redirect URL: {e}")
+
+        if action.get("preserveQuery") and flow.request.query:
+            query = flow.request.url.split("?", 1)[1]
+            target_url = f"{target_url}{'&' if '?' in target_url else '?'}{query}"
+
+        flow.response = Response.make(status_code, b"", {"Location": target_url})
+        self.logger.info(f"Pipeline: [REDIRECT] {status_code} -> {target_url}")
+
     def apply_rewrite_header(self, flow: http.HTTPFlow, headers_config: Dict[str, Any], phase: str) -> None:
         # V3: headers_config is { "request": [...], "response": [...] }
         operations = headers_config.get(phase, [])
@@ -381,10 +493,13 @@ class ActionExecutor:
         import random
         
         delay_ms = action.get("delayMs") or 0
+        jitter_ms = action.get("jitterMs") or 0
         packet_loss = action.get("packetLoss") or 0
         bandwidth_kbps = action.get("bandwidthKbps") or 0
         
         # 1. Latency (request phase)
+        if phase == "request" and jitter_ms > 0:
+            delay_ms = self._jitter_rng(action).next_delay(int(delay_ms), int(jitter_ms))
         if phase == "request" and delay_ms > 0:
             time.sleep(delay_ms / 1000.0)
             # ctx.log.info(f"Latency delay: {delay_ms}ms")
@@ -15,6 +15,11 @@ class RuleEngine:
         self.matcher = RuleMatcher()
         self.executor = ActionExecutor(self)
 
+    @property
+    def times_used(self) -> Dict[str, int]:
+        """Hits per rule ID for rules with `execution.times`, kept by the loader"""
+        return self.loader.times_used
+
     def handle_request(self, flow: http.HTTPFlow, match_only: bool = False) -> None:
         """Standard matching and request-phase pipeline execution.
 
@@ -45,6 +50,10 @@ class RuleEngine:
         for rule in candidates:
             # Check enabled status (should be True by default)
             if not rule.get("execution", {}).get("enabled", True):
+                # Re-enabling a spent rule starts its count over
+                self.times_used.pop(rule.get("id", ""), None)
+                continue
+            if self.is_spent(rule):
                 continue
 
             matched, url_match = self.matcher.match_rule(flow, rule, self.loader._compiled_patterns)
@@ -65,6 +74,17 @@ class RuleEngine:
 
                 # Record hit
                 self.record_rule_hit(flow, rule)
+                if not match_only:
+                    # Counted by the app into per-rule stats (rules/stats.rs)
+                    print(f"[RULE_HIT] {rule.get('id', '')} {int(time.time() * 1000)}", flush=True)
+
+                # One-shot rules stop matching right away; the spent marker
+                # tells the app to persist the disabled state.
+                if not match_only and rule.get("execution", {}).get("disableAfterHit"):
+                    rule.setdefault("execution", {})["enabled"] = False
+                    print(f"[RULE_SPENT] {rule.get('id', '')}", flush=True)
+                if not match_only:
+                    self.count_limited_hit(rule)
 
                 # stopOnMatch prevents further matching
                 if rule.get("execution", {}).get("stopOnMatch", False):
@@ -75,6 +95,28 @@ class RuleEngine:
             if not match_only:
                 self.execute_pipeline(flow, "request")
 
+    def is_spent(self, rule: Dict[str, Any]) -> bool:
+        """Whether a rule limited by `execution.times` has used up its hits"""
+        times = rule.get("execution", {}).get("times")
+        if not times:
+            return False
+        return self.times_used.get(rule.get("id", ""), 0) >= times
+
+    def count_limited_hit(self, rule: Dict[str, Any]) -> None:
+        """Count a hit against `execution.times`.
+
+        On the last allowed hit a `[RULE_SPENT] <rule_id>` line tells the app
+        to disable the rule on disk and reload. The loader resets the count
+        once it reads the rule disabled.
+        """
+        execution = rule.get("execution", {})
+        if not execution.get("times"):
+            return
+        rule_id = rule.get("id", "")
+        self.times_used[rule_id] = self.times_used.get(rule_id, 0) + 1
+        if self.is_spent(rule):
+            print(f"[RULE_SPENT] {rule_id}", flush=True)
+
     def handle_response(self, flow: http.HTTPFlow) -> None:
         """Execute response-phase pipeline for already matched rules"""
         # Skip if flow was terminated in request phase (e.g. Map Local / Block)
@@ -84,6 +126,21 @@ class RuleEngine:
         # Execute Response Pipeline
         self.execute_pipeline(flow, "response")
 
+    def breakpoint_for(self, flow: http.HTTPFlow, phase: str) -> Optional[Dict[str, Any]]:
+        """Matched breakpoint rule that pauses the flow at `phase`.
+
+        Returns a rule dict in the DebugManager breakpoint shape, or None.
+        """
+        if flow.metadata.get("_relaycraft_terminated"):
+            return None
+        for rule in flow.metadata.get("_relaycraft_matched_rules", []):
+            for action in rule.get("actions", []):
+                if action.get("type") != "breakpoint":
+                    continue
+                if action.get("phase", "request") in (phase, "both"):
+                    return {"id": rule.get("id"), "pattern": rule.get("name") or rule.get("id")}
+        return None
+
     def execute_pipeline(self, flow: http.HTTPFlow, phase: str) -> None:
         """Execute actions in a deterministic pipeline order"""
         matched_rules = flow.metadata.get("_relaycraft_matched_rules", [])
@@ -95,9 +152,10 @@ class RuleEngine:
         for rule in matched_rules:
             url_match = rule.get("_url_match_transient")
 
-            for action in rule.get("actions", []):
+            for index, action in enumerate(rule.get("actions", [])):
                 a = action.copy()
                 a["_rule_id"] = rule.get("id")
+                a["_action_index"] = index
                 a["_rule_name"] = rule.get("name")
                 a["_url_match_data"] = rule.get("_url_match_data")
                 a["_url_match_transient"] = url_match
@@ -118,6 +176,12 @@ class RuleEngine:
                 flow.metadata["_relaycraft_terminated"] = True
                 return
 
+            # Redirect (client-visible 3xx)
+            for a in [act for act in all_actions if act.get("type") == "redirect"]:
+                self.executor.apply_redirect(flow, a, a.get("_url_match_transient") or a.get("_url_match_data"))
+                flow.metadata["_relaycraft_terminated"] = True
+                return
+
             # Map Local / Map Remote
             for a in [act for act in all_actions if act.get("type") in ["map_local", "map_remote"]]:
                 if a.get("type") == "map_local":
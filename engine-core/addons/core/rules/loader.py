@@ -37,6 +37,10 @@ class RuleLoader:
         self._last_check_time = 0
         self._last_file_count = -1
         self._compiled_patterns: Dict[str, "re.Pattern"] = {}
+        # Hits per rule ID for rules with `execution.times`; survives reloads
+        # unless the rule is disabled or its limit changes
+        self.times_used: Dict[str, int] = {}
+        self._limits: Dict[str, tuple] = {}
         self.logger.info(f"RuleLoader initialized. Dir: {self.rules_dir}, File: {self.rules_file}")
         self.load_rules()
 
@@ -59,6 +63,7 @@ class RuleLoader:
             # 3. Scan files if needed
             yaml_files = list(self.rules_dir.rglob("*.yaml"))
             if not yaml_files:
+                self._reset_limit_counts([])
                 self.rules = []
                 return
             
@@ -72,6 +77,12 @@ class RuleLoader:
             max_mtime = max(f.stat().st_mtime for f in yaml_files)
             current_mtime = max(max_mtime, dir_mtime)
 
+            # Solo marker written by the app; switching the soloed rule only
+            # rewrites the file, so its own mtime has to be considered too
+            solo_file = self.rules_dir / ".solo"
+            if solo_file.exists():
+                current_mtime = max(current_mtime, solo_file.stat().st_mtime)
+
             if current_mtime <= self._last_load_time and current_file_count == self._last_file_count and self.rules:
                 return
 
@@ -91,6 +102,13 @@ class RuleLoader:
                 except Exception as e:
                     self.logger.warn(f"Failed to load rule {p}: {e}")
 
+            self._reset_limit_counts(new_rules)
+
+            solo_id = self._read_solo(solo_file)
+            if solo_id:
+                new_rules = [r for r in new_rules if r.get("id") == solo_id]
+                self.logger.info(f"Rule solo active: {solo_id}")
+
             self.rules = new_rules
             # Sort rules: priority (asc) -> name (asc) -> id (asc)
             self.rules.sort(key=lambda r: (
@@ -108,6 +126,37 @@ class RuleLoader:
             self.logger.error(f"Error loading rules: {e}")
             self.rules = []
 
+    def force_reload(self) -> int:
+        """Bypass throttling and change detection, re-reading every rule file now."""
+        self._last_check_time = 0
+        self._last_load_time = 0
+        self._last_file_count = -1
+        self.load_rules()
+        return len(self.rules)
+
+    def _reset_limit_counts(self, new_rules: List[Dict[str, Any]]) -> None:
+        """Forget `execution.times` counts of rules that were removed, are
+        disabled, or had their enabled state or limit edited, so a spent rule
+        that is enabled again starts a fresh count."""
+        limits = {}
+        for rule in new_rules:
+            execution = rule.get("execution") or {}
+            limits[rule.get("id", "")] = (execution.get("enabled", True), execution.get("times"))
+        for rule_id in list(self.times_used):
+            limit = limits.get(rule_id)
+            if limit is None or not limit[0] or limit != self._limits.get(rule_id):
+                del self.times_used[rule_id]
+        self._limits = limits
+
+    def _read_solo(self, solo_file: Path) -> Optional[str]:
+        """Return the soloed rule id, or None when solo mode is off"""
+        try:
+            if solo_file.exists():
+                return solo_file.read_text(encoding="utf-8").strip() or None
+        except Exception as e:
+            self.logger.warn(f"Failed to read rule solo marker: {e}")
+        return None
+
     def _process_and_index_rules(self) -> None:
         """Pre-compile regexes and categorize rules into buckets for O(1) optimization"""
         exact: Dict[str, List[Dict[str, Any]]] = {}
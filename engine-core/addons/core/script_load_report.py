@@ -3,10 +3,11 @@ In-memory report of user script load results for the current engine process.
 Populated at addon import time (entry.py); read via GET /_relay/scripts/load_status.
 """
 
-from typing import Any, Dict, List
+from typing import Any, Dict, List, Optional
 
 _loaded: List[str] = []
 _failed: List[Dict[str, str]] = []
+_preprocessor: Dict[str, Any] = {"ok": None, "error": None}
 
 
 def reset() -> None:
@@ -28,10 +29,17 @@ def record_failed(path: str, name: str, error: str) -> None:
     )
 
 
+def record_preprocessor(ok: bool, error: Optional[str] = None) -> None:
+    """Result of the tracking injector self-test run at startup."""
+    _preprocessor["ok"] = ok
+    _preprocessor["error"] = error
+
+
 def get_report() -> Dict[str, Any]:
     return {
         "loaded": list(_loaded),
         "failed": list(_failed),
         "loaded_count": len(_loaded),
         "failed_count": len(_failed),
+        "preprocessor": dict(_preprocessor),
     }
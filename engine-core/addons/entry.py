@@ -15,8 +15,13 @@ sys.path.append(str(Path(__file__).parent))
 from typing import List, Any, Optional, Tuple
 from core import CoreAddon
 from core.gateway.addon import GatewayAddon
-from core.script_load_report import record_failed, record_loaded, reset as reset_script_load_report
-from injector import inject_tracking
+from core.script_load_report import (
+    record_failed,
+    record_loaded,
+    record_preprocessor,
+    reset as reset_script_load_report,
+)
+from injector import inject_tracking, manifest_name, self_test as injector_self_test
 
 
 def _safe_print(message: str) -> None:
@@ -87,6 +92,9 @@ def _setup_asyncio_exception_handler():
 # Setup asyncio exception handler early
 _setup_asyncio_exception_handler()
 
+# Scripts directory managed by the app; script names are relative to it
+_scripts_dir = os.environ.get("RELAYCRAFT_SCRIPTS_DIR")
+
 # Global temp directory for preprocessed scripts
 _preprocessed_dir = None
 
@@ -151,7 +159,7 @@ def _preprocess_and_load_script(source_path: str) -> Tuple[Optional[Any], Option
     """
     global _preprocessed_dir
 
-    script_name = Path(source_path).name if source_path else "unknown"
+    script_name = manifest_name(source_path, _scripts_dir) if source_path else "unknown"
 
     try:
         source = Path(source_path)
@@ -191,11 +199,13 @@ def _preprocess_and_load_script(source_path: str) -> Tuple[Optional[Any], Option
             return None, err
 
         # Inject tracking code (with path for better error messages)
-        modified_code = inject_tracking(original_code, script_path=source_path)
+        modified_code = inject_tracking(original_code, script_path=source_path, script_name=script_name)
 
-        # Write to temp file
-        temp_path = Path(_preprocessed_dir) / source.name
+        # Write to temp file, mirroring the folder layout so that scripts with
+        # the same file name in different folders do not overwrite each other
+        temp_path = Path(_preprocessed_dir) / script_name
         try:
+            temp_path.parent.mkdir(parents=True, exist_ok=True)
             with open(temp_path, "w", encoding="utf-8") as f:
                 f.write(modified_code)
         except IOError as e:
@@ -272,6 +282,11 @@ user_scripts_env = os.environ.get("RELAYCRAFT_USER_SCRIPTS", "")
 loaded_count = 0
 failed_count = 0
 
+preprocessor_ok, preprocessor_error = injector_self_test()
+record_preprocessor(preprocessor_ok, preprocessor_error)
+if not preprocessor_ok:
+    _log_message("warn", f"Script tracking injection unavailable, scripts load without it: {preprocessor_error}")
+
 if user_scripts_env:
     script_paths = [p for p in user_scripts_env.split(";") if p.strip()]
     if script_paths:
@@ -282,7 +297,7 @@ if user_scripts_env:
         if not path_str:
             continue
 
-        script_name = Path(path_str).name
+        script_name = manifest_name(path_str, _scripts_dir)
         module, load_error = _preprocess_and_load_script(path_str)
         if module is not None:
             addons.append(module)
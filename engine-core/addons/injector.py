@@ -9,6 +9,7 @@ import sys
 import ast
 import argparse
 import logging
+from pathlib import Path
 
 logger = logging.getLogger(__name__)
 
@@ -16,7 +17,8 @@ logger = logging.getLogger(__name__)
 class TrackingInjector(ast.NodeTransformer):
     """AST transformer to inject record_hit calls into hook functions"""
 
-    def __init__(self):
+    def __init__(self, script_name=None):
+        self.script_name = script_name
         self.hooks = {'request', 'response', 'error', 'websocket_start', 'websocket_message', 'websocket_end'}
         self.injected_count = 0
 
@@ -30,7 +32,7 @@ try:
         try:
             if not hasattr(flow, "_relaycraft_script_hits"):
                 flow._relaycraft_script_hits = []
-            script_name = os.path.basename(script_path)
+            script_name = _RC_SCRIPT_NAME or os.path.basename(script_path)
             hit_id = "script:" + script_name
             # Check for duplicate
             for existing in flow._relaycraft_script_hits:
@@ -56,11 +58,24 @@ try:
             return path.startswith("/_relay") or host == "relay.guide"
         except Exception:
             return False
+    import json as _rc_json
+    import traceback as _rc_traceback
+    def _rc_report_error(script_path, exc):
+        # One JSON line the app turns into a `script-error` event
+        try:
+            print("[RELAYCRAFT][SCRIPT][ERROR] " + _rc_json.dumps({
+                "script_name": _RC_SCRIPT_NAME or os.path.basename(script_path),
+                "message": f"{type(exc).__name__}: {exc}",
+                "traceback": _rc_traceback.format_exc(),
+            }), flush=True)
+        except Exception:
+            pass
 except Exception as e:
     print(f"[RELAYCRAFT] Failed to setup _rc_record_hit: {e}", flush=True)
     # Fallback stubs — keep user scripts running even if setup fails
     def _rc_record_hit(flow, script_path): pass
     def _rc_should_skip_internal(flow): return False
+    def _rc_report_error(script_path, exc): pass
 
 # Robust logging helper for scripts
 def _rc_log(level, msg):
@@ -76,7 +91,9 @@ def _rc_log(level, msg):
     print(f"[RELAYCRAFT][SCRIPT][{level.upper()}] {msg}", flush=True)
 """
         try:
-            helper_ast = ast.parse(helper_code).body
+            # The staged copy's file name is not unique across folders, so
+            # hits are keyed by the name the app's manifest uses
+            helper_ast = ast.parse(f"_RC_SCRIPT_NAME = {self.script_name!r}\n" + helper_code).body
 
             # Preserve module docstring if present
             # A docstring is the first statement and is a constant string expression
@@ -158,8 +175,36 @@ def _rc_log(level, msg):
             func_node.body.insert(0, safe_call)
             self.injected_count += 1
 
+        # 4. Report exceptions raised by the hook, then let mitmproxy see them
+        func_node.body = [self._create_error_guard(func_node.body)]
+
         return func_node
 
+    def _create_error_guard(self, body):
+        """Wrap a hook body:
+        try: <body>; except Exception as _rc_exc: _rc_report_error(__file__, _rc_exc); raise
+        """
+        return ast.Try(
+            body=body,
+            handlers=[ast.ExceptHandler(
+                type=ast.Name(id='Exception', ctx=ast.Load()),
+                name='_rc_exc',
+                body=[
+                    ast.Expr(value=ast.Call(
+                        func=ast.Name(id='_rc_report_error', ctx=ast.Load()),
+                        args=[
+                            ast.Name(id='__file__', ctx=ast.Load()),
+                            ast.Name(id='_rc_exc', ctx=ast.Load())
+                        ],
+                        keywords=[]
+                    )),
+                    ast.Raise()
+                ]
+            )],
+            orelse=[],
+            finalbody=[]
+        )
+
     def visit_FunctionDef(self, node):
         """Visit function definitions"""
         if node.name in self.hooks:
@@ -207,11 +252,27 @@ def _rc_log(level, msg):
 
 
 
-def inject_tracking(source_code, script_path=None):
+def manifest_name(script_path, scripts_dir=None):
+    """
+    Name of a user script as the app's manifest lists it: its path relative to
+    scripts_dir with forward slashes, or the file name when it lives elsewhere.
+    """
+    path = Path(script_path)
+    if scripts_dir:
+        try:
+            return path.resolve().relative_to(Path(scripts_dir).resolve()).as_posix()
+        except ValueError:
+            pass
+    return path.name
+
+
+def inject_tracking(source_code, script_path=None, script_name=None):
     """
     Inject record_hit() calls into script hooks.
     Returns the modified source code.
     If script_path is provided, errors will include the path for debugging.
+    script_name is the manifest name hits are recorded under; without it the
+    file name of the loaded copy is used.
     """
     path_info = f" ({script_path})" if script_path else ""
     try:
@@ -219,7 +280,7 @@ def inject_tracking(source_code, script_path=None):
         tree = ast.parse(source_code)
 
         # Transform AST
-        injector = TrackingInjector()
+        injector = TrackingInjector(script_name)
         new_tree = injector.visit(tree)
 
         # Fix missing locations
@@ -245,6 +306,29 @@ def inject_tracking(source_code, script_path=None):
         logger.warning(f"AST Injection failed{path_info}: {type(e).__name__}: {e}")
         return source_code
 
+_SELF_TEST_SCRIPT = """
+def request(flow):
+    pass
+"""
+
+
+def self_test():
+    """
+    Check that injection actually works in this interpreter.
+    inject_tracking falls back to the original source on failure, so a broken
+    injector would otherwise go unnoticed.
+    Returns (ok, error_message).
+    """
+    try:
+        result = inject_tracking(_SELF_TEST_SCRIPT, script_path="<self-test>")
+        if "_rc_record_hit(flow, __file__)" not in result:
+            return False, f"Tracking calls were not injected (Python {sys.version.split()[0]})"
+        compile(result, "<self-test>", "exec")
+        return True, None
+    except Exception as e:
+        return False, f"{type(e).__name__}: {e}"
+
+
 if __name__ == "__main__":
     parser = argparse.ArgumentParser(description='Inject tracking code into RelayCraft scripts')
     parser.add_argument('input_file', help='Input script file')
@@ -1,7 +1,9 @@
 import unittest
 import sys
 import os
-from unittest.mock import MagicMock
+import tempfile
+from pathlib import Path
+from unittest.mock import MagicMock, patch
 
 # Add parent and core to sys.path
 current_dir = os.path.dirname(os.path.abspath(__file__))
@@ -11,7 +13,7 @@ sys.path.append(addons_dir)
 # Mock mitmproxy before importing engine
 import tests.mock_mitmproxy as mock_env
 
-from core.rules.actions import ActionExecutor
+from core.rules.actions import ActionExecutor, JitterRng, resolve_in_dir
 from core.rules.engine import RuleEngine
 
 class TestActions(unittest.TestCase):
@@ -114,6 +116,54 @@ class TestActions(unittest.TestCase):
         self.assertEqual(flow.request.port, 8080)
         self.assertEqual(flow.request.path, "/foo")
 
+    def test_redirect_preserves_query(self):
+        flow = mock_env.get_mock_flow(url="https://old.com/login?next=%2Fhome")
+        flow.request.query = {"next": "/home"}
+
+        action = {"targetUrl": "https://new.com/signin", "statusCode": 308, "preserveQuery": True}
+
+        with patch("core.rules.actions.Response") as response:
+            self.executor.apply_redirect(flow, action)
+
+        response.make.assert_called_once_with(308, b"", {"Location": "https://new.com/signin?next=%2Fhome"})
+
+    def test_redirect_rejects_non_redirect_status(self):
+        flow = mock_env.get_mock_flow(url="https://old.com/")
+        flow.request.query = {}
+
+        with patch("core.rules.actions.Response") as response:
+            self.executor.apply_redirect(flow, {"targetUrl": "https://new.com/", "statusCode": 200})
+
+        response.make.assert_called_once_with(302, b"", {"Location": "https://new.com/"})
+
+    def test_map_local_dir_resolution(self):
+        with tempfile.TemporaryDirectory() as site:
+            os.makedirs(os.path.join(site, "docs"))
+            with open(os.path.join(site, "docs", "index.html"), "w") as f:
+                f.write("docs")
+
+            self.assertEqual(resolve_in_dir(site, "/docs?x=1"), Path(site) / "docs" / "index.html")
+            self.assertEqual(resolve_in_dir(site, "/a%20b.js"), Path(site) / "a b.js")
+            for escape in (
+                "/../secret",
+                "/docs/../../secret",
+                "/%2e%2e/secret",
+                "/a%2F..%2Fsecret",
+                "/a%5csecret",
+                "/C:%5Cwindows",
+            ):
+                self.assertIsNone(resolve_in_dir(site, escape), escape)
+
+            action = {"source": "file", "dir": site}
+            with patch("core.rules.actions.Response") as response:
+                self.executor.apply_map_local(mock_env.get_mock_flow(path="/docs/"), action)
+                self.executor.apply_map_local(mock_env.get_mock_flow(path="/missing.js"), action)
+                self.executor.apply_map_local(mock_env.get_mock_flow(path="/../etc/passwd"), action)
+
+            statuses = [c.args[0] for c in response.make.call_args_list]
+            self.assertEqual(statuses, [200, 404, 403])
+            self.assertEqual(response.make.call_args_list[0].args[1], b"docs")
+
     def test_rewrite_body_replace_applies_response_status_and_content_type(self):
         flow = mock_env.get_mock_flow(url="https://example.com/api")
         flow.response.text = "hello world"
@@ -158,5 +208,21 @@ class TestActions(unittest.TestCase):
         self.assertEqual(flow.response.status_code, 204)
         self.assertEqual(flow.response.headers.get("Content-Type"), "application/json; charset=utf-8")
 
+    def test_seeded_jitter_continues_across_flows_and_restarts_on_reload(self):
+        action = {"type": "throttle", "delayMs": 100, "jitterMs": 50, "jitterSeed": 7}
+
+        def next_delay():
+            # execute_pipeline hands each flow its own copy of the action
+            a = dict(action, _rule_id="slow", _action_index=0)
+            return self.executor._jitter_rng(a).next_delay(100, 50)
+
+        expected = JitterRng(7)
+        first = [expected.next_delay(100, 50) for _ in range(3)]
+        self.assertEqual([next_delay() for _ in range(3)], first)
+
+        self.engine.loader.rules = []
+        self.assertEqual(next_delay(), first[0])
+        self.assertEqual(len(self.executor._jitter_rngs), 1)
+
 if __name__ == "__main__":
     unittest.main()
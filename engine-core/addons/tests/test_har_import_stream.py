@@ -0,0 +1,57 @@
+import io
+import json
+import os
+import sys
+import types
+import unittest
+
+# Add parent addon directory to sys.path
+current_dir = os.path.dirname(os.path.abspath(__file__))
+addons_dir = os.path.dirname(current_dir)
+sys.path.append(addons_dir)
+
+from core.har_converters import iter_har_flow_batches
+
+try:
+    import ijson  # noqa: F401
+
+    HAS_IJSON = True
+except ImportError:
+    HAS_IJSON = False
+
+
+def _synthetic_har(count: int) -> bytes:
+    entries = [
+        {
+            "startedDateTime": "2024-01-01T00:00:00Z",
+            "time": 1,
+            "request": {"method": "GET", "url": f"https://example.com/item/{i}", "headers": []},
+            "response": {"status": 200, "content": {"size": 2, "mimeType": "text/plain", "text": "ok"}},
+        }
+        for i in range(count)
+    ]
+    return json.dumps({"log": {"version": "1.2", "entries": entries}}).encode("utf-8")
+
+
+@unittest.skipUnless(HAS_IJSON, "ijson is not installed")
+class TestHarImportStream(unittest.TestCase):
+    def test_large_har_is_yielded_in_batches(self):
+        batches = iter_har_flow_batches(io.BytesIO(_synthetic_har(5000)), batch_size=500)
+        self.assertIsInstance(batches, types.GeneratorType)
+
+        sizes = []
+        last_path = None
+        for flows in batches:
+            sizes.append(len(flows))
+            last_path = flows[-1]["path"]
+        self.assertEqual(sum(sizes), 5000)
+        self.assertEqual(max(sizes), 500)
+        self.assertEqual(last_path, "/item/4999")
+
+    def test_partial_last_batch(self):
+        sizes = [len(flows) for flows in iter_har_flow_batches(io.BytesIO(_synthetic_har(1201)), batch_size=500)]
+        self.assertEqual(sizes, [500, 500, 201])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,89 @@
+import json
+import os
+import sys
+import tempfile
+import unittest
+from types import SimpleNamespace
+
+# Add parent addon directory to sys.path
+current_dir = os.path.dirname(os.path.abspath(__file__))
+addons_dir = os.path.dirname(current_dir)
+sys.path.append(addons_dir)
+
+# Mock mitmproxy modules before importing handlers
+from tests import mock_mitmproxy
+
+from core.flow_database import FlowDatabase
+from core.http_handlers import handle_import_routes, handle_realtime_routes
+
+_ = mock_mitmproxy
+
+
+class _FakeResponse:
+    @staticmethod
+    def make(status_code, content, headers):
+        return SimpleNamespace(status_code=status_code, content=content, headers=headers)
+
+
+def _make_flow(query=None, content=b"", method="GET"):
+    req = SimpleNamespace(query=query or {}, content=content, method=method, headers={})
+    return SimpleNamespace(request=req, response=None)
+
+
+def _websocket_har() -> bytes:
+    entry = {
+        "startedDateTime": "2024-01-01T00:00:00Z",
+        "time": 5,
+        "request": {"method": "GET", "url": "wss://example.com/socket", "headers": []},
+        "response": {"status": 101, "headers": [], "content": {"size": 0, "mimeType": ""}},
+        "_resourceType": "websocket",
+        "_webSocketMessages": [
+            {"type": "send", "time": 1700000000.0, "opcode": 1, "data": '{"op":"subscribe"}'},
+            {"type": "receive", "time": 1700000000.5, "opcode": 2, "data": "AAEC"},
+        ],
+    }
+    return json.dumps({"log": {"version": "1.2", "entries": [entry]}}).encode("utf-8")
+
+
+class TestHarWebSocketImport(unittest.TestCase):
+    def setUp(self):
+        self.temp = tempfile.TemporaryDirectory()
+        self.db = FlowDatabase(
+            db_path=os.path.join(self.temp.name, "flows.db"),
+            body_dir=os.path.join(self.temp.name, "bodies"),
+        )
+        self.monitor = SimpleNamespace(db=self.db, logger=self.db.logger)
+
+    def tearDown(self):
+        self.db.close()
+        self.temp.cleanup()
+
+    def test_imported_frames_are_served_by_flow_detail(self):
+        flow = _make_flow(content=_websocket_har(), method="POST")
+        handle_import_routes(self.monitor, flow, "relay_import_har", _FakeResponse)
+        self.assertEqual(flow.response.status_code, 200)
+        indices = json.loads(flow.response.content)["indices"]
+        self.assertEqual(len(indices), 1)
+        flow_id = indices[0]["id"]
+
+        detail = _make_flow(query={"id": flow_id})
+        handle_realtime_routes(self.monitor, detail, "relay_detail", _FakeResponse, str)
+        self.assertEqual(detail.response.status_code, 200)
+        rc = json.loads(detail.response.content)["_rc"]
+
+        self.assertTrue(rc["isWebsocket"])
+        self.assertEqual(rc["websocketFrameCount"], 2)
+        sent, received = rc["websocketFrames"]
+        self.assertEqual(sent["type"], "text")
+        self.assertTrue(sent["fromClient"])
+        self.assertEqual(sent["content"], '{"op":"subscribe"}')
+        self.assertEqual(sent["timestamp"], 1700000000000.0)
+        self.assertEqual(received["type"], "binary")
+        self.assertFalse(received["fromClient"])
+        self.assertEqual(received["encoding"], "base64")
+        self.assertEqual(received["length"], 3)
+        self.assertEqual(received["flowId"], flow_id)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -6,7 +6,7 @@ import ast
 # Add parent to sys.path
 sys.path.append(os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
 
-from injector import inject_tracking
+from injector import inject_tracking, manifest_name, self_test
 
 class TestInjector(unittest.TestCase):
     def test_basic_injection(self):
@@ -67,5 +67,79 @@ def request(flow):
         self.assertIn("[SCRIPT] ", modified)
         self.assertIn("Original Message", modified)
 
+    def test_hook_errors_are_reported(self):
+        import io
+        import json
+        from contextlib import redirect_stdout
+        from unittest.mock import MagicMock
+
+        source = """
+def request(flow):
+    raise ValueError("boom")
+"""
+        scope = {"__file__": "/tmp/relaycraft_scripts_x/auth/login.py"}
+        exec(compile(inject_tracking(source, script_name="auth/login.py"), "login.py", "exec"), scope)
+
+        flow = MagicMock()
+        flow.request.path = "/api"
+        flow.request.host = "example.com"
+        out = io.StringIO()
+        with redirect_stdout(out):
+            # The hook still raises so mitmproxy handles it as before
+            with self.assertRaises(ValueError):
+                scope["request"](flow)
+
+        line = next(l for l in out.getvalue().splitlines() if "[SCRIPT][ERROR]" in l)
+        payload = json.loads(line.split("[RELAYCRAFT][SCRIPT][ERROR] ", 1)[1])
+        self.assertEqual(payload["script_name"], "auth/login.py")
+        self.assertEqual(payload["message"], "ValueError: boom")
+        self.assertIn("Traceback", payload["traceback"])
+
+    def test_same_named_scripts_in_folders_are_tracked_apart(self):
+        import importlib.util
+        import tempfile
+        from pathlib import Path
+        from unittest.mock import MagicMock
+
+        source = """
+def request(flow):
+    pass
+"""
+        with tempfile.TemporaryDirectory() as scripts_dir, tempfile.TemporaryDirectory() as staging_dir:
+            flow = MagicMock()
+            flow.request.path = "/api"
+            flow.request.host = "example.com"
+            flow._relaycraft_script_hits = []
+
+            staged = []
+            for folder in ("auth", "admin"):
+                script = Path(scripts_dir) / folder / "login.py"
+                script.parent.mkdir()
+                script.write_text(source, encoding="utf-8")
+
+                # Same staging steps as entry.py
+                name = manifest_name(str(script), scripts_dir)
+                temp_path = Path(staging_dir) / name
+                temp_path.parent.mkdir(parents=True, exist_ok=True)
+                temp_path.write_text(inject_tracking(source, str(script), name), encoding="utf-8")
+                staged.append(temp_path)
+
+                spec = importlib.util.spec_from_file_location(f"{folder}_login", temp_path)
+                module = importlib.util.module_from_spec(spec)
+                spec.loader.exec_module(module)
+                module.request(flow)
+
+            self.assertNotEqual(staged[0], staged[1])
+            self.assertTrue(all(p.exists() for p in staged))
+            self.assertEqual(
+                [hit["id"] for hit in flow._relaycraft_script_hits],
+                ["script:auth/login.py", "script:admin/login.py"],
+            )
+
+    def test_self_test_passes(self):
+        ok, error = self_test()
+        self.assertTrue(ok)
+        self.assertIsNone(error)
+
 if __name__ == "__main__":
     unittest.main()
@@ -28,11 +28,13 @@ class TestMonitorRouteResolution(unittest.TestCase):
             ("GET", "/_relay/detail?id=f1", "127.0.0.1", "relay_detail"),
             ("GET", "/_relay/sse?flow_id=f1", "127.0.0.1", "relay_sse"),
             ("POST", "/_relay/ws/inject", "127.0.0.1", "relay_ws_inject"),
+            ("GET", "/_relay/intercepted", "127.0.0.1", "relay_intercepted"),
             ("POST", "/_relay/import_session", "127.0.0.1", "relay_import_session"),
             ("POST", "/_relay/import_session_file", "127.0.0.1", "relay_import_session_file"),
             ("POST", "/_relay/import_har", "127.0.0.1", "relay_import_har"),
             ("POST", "/_relay/import_har_file", "127.0.0.1", "relay_import_har_file"),
             ("GET", "/_relay/scripts/load_status", "127.0.0.1", "relay_scripts_load_status"),
+            ("GET", "/_relay/throughput", "127.0.0.1", "relay_throughput"),
             ("GET", "/_relay/export_session?session_id=s1", "127.0.0.1", "relay_export_session"),
             ("GET", "/_relay/export_har?session_id=s1", "127.0.0.1", "relay_export_har"),
             ("GET", "/cert", "127.0.0.1", "cert_serve"),
@@ -47,5 +49,25 @@ class TestMonitorRouteResolution(unittest.TestCase):
                 self.assertEqual(route, expected)
 
 
+class TestThroughputCounters(unittest.TestCase):
+    def test_record_throughput_counts_headers_and_body(self):
+        monitor = object.__new__(TrafficMonitor)
+        monitor.bytes_in = 0
+        monitor.bytes_out = 0
+        flow = SimpleNamespace(
+            request=SimpleNamespace(
+                raw_content=b"abc",
+                headers=SimpleNamespace(fields=[(b"Host", b"x.test")]),
+            ),
+            response=SimpleNamespace(raw_content=b"0123456789", headers=None),
+        )
+
+        monitor.record_throughput(flow)
+        monitor.record_throughput(flow)
+
+        self.assertEqual(monitor.bytes_out, 2 * (3 + 4 + 6 + 4))
+        self.assertEqual(monitor.bytes_in, 20)
+
+
 if __name__ == "__main__":
     unittest.main()
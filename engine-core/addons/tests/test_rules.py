@@ -91,5 +91,143 @@ class TestRules(unittest.TestCase):
             self.engine.handle_request(flow)
             self.assertTrue(mock_exec.called)
 
+    def test_breakpoint_rule_phases(self):
+        rule = {
+            "id": "bp",
+            "name": "Pause login",
+            "execution": {"enabled": True},
+            "match": {
+                "request": [
+                    {"type": "url", "matchType": "contains", "value": "example.com/login"}
+                ]
+            },
+            "actions": [{"type": "breakpoint", "phase": "response"}]
+        }
+        self.set_mock_rules([rule])
+
+        flow = mock_env.get_mock_flow(url="http://example.com/login")
+        flow.metadata = {}
+        self.engine.handle_request(flow)
+        self.assertIsNone(self.engine.breakpoint_for(flow, "request"))
+        self.assertEqual(self.engine.breakpoint_for(flow, "response")["id"], "bp")
+
+        rule["actions"] = [{"type": "breakpoint", "phase": "both"}]
+        flow = mock_env.get_mock_flow(url="http://example.com/login")
+        flow.metadata = {}
+        self.engine.handle_request(flow)
+        self.assertEqual(self.engine.breakpoint_for(flow, "request")["pattern"], "Pause login")
+
+    def test_times_limit_counts_down(self):
+        rule = {
+            "id": "twice",
+            "name": "Mock twice",
+            "execution": {"enabled": True, "times": 2},
+            "match": {
+                "request": [
+                    {"type": "url", "matchType": "contains", "value": "example.com/api"}
+                ]
+            },
+            "actions": [{"type": "block_request"}]
+        }
+        self.set_mock_rules([rule])
+
+        def matched():
+            flow = mock_env.get_mock_flow(url="http://example.com/api")
+            flow.metadata = {}
+            self.engine.handle_request(flow, match_only=True)
+            return bool(flow.metadata.get("_relaycraft_matched_rules"))
+
+        def hit():
+            flow = mock_env.get_mock_flow(url="http://example.com/api")
+            flow.metadata = {}
+            with unittest.mock.patch("builtins.print") as printed:
+                self.engine.handle_request(flow)
+            return [c.args[0] for c in printed.call_args_list]
+
+        # Dry runs do not use up the limit
+        self.assertTrue(matched())
+        self.assertEqual(self.engine.times_used, {})
+
+        self.assertFalse(any(line.startswith("[RULE_SPENT]") for line in hit()))
+        self.assertEqual(self.engine.times_used["twice"], 1)
+        self.assertIn("[RULE_SPENT] twice", hit())
+        self.assertFalse(matched())
+        self.assertEqual(self.engine.times_used["twice"], 2)
+
+        # Seeing it disabled, then enabled again, starts a fresh count
+        rule["execution"]["enabled"] = False
+        self.assertFalse(matched())
+        rule["execution"]["enabled"] = True
+        self.assertTrue(matched())
+
+    def test_spent_rule_fires_again_after_reenable_and_reload(self):
+        import tempfile
+        import yaml
+        from pathlib import Path
+
+        with tempfile.TemporaryDirectory() as rules_dir:
+            rule_file = Path(rules_dir) / "twice.yaml"
+
+            def write_rule(enabled):
+                rule = {
+                    "id": "twice",
+                    "name": "Mock twice",
+                    "execution": {"enabled": enabled, "times": 2},
+                    "match": {
+                        "request": [
+                            {"type": "url", "matchType": "contains", "value": "example.com/api"}
+                        ]
+                    },
+                    "actions": [{"type": "block_request"}]
+                }
+                rule_file.write_text(yaml.safe_dump({"rule": rule}), encoding="utf-8")
+
+            write_rule(True)
+            with unittest.mock.patch.dict(os.environ, {"RELAYCRAFT_RULES_DIR": rules_dir}):
+                engine = RuleEngine()
+
+            def hit():
+                flow = mock_env.get_mock_flow(url="http://example.com/api")
+                flow.metadata = {}
+                with unittest.mock.patch("builtins.print"):
+                    engine.handle_request(flow)
+                return bool(flow.metadata.get("_relaycraft_matched_rules"))
+
+            self.assertTrue(hit())
+            self.assertTrue(hit())
+            self.assertFalse(hit())
+
+            # The app disables the spent rule on disk and reloads; the user
+            # then enables it again. No request sees it disabled in between.
+            write_rule(False)
+            engine.loader.force_reload()
+            write_rule(True)
+            engine.loader.force_reload()
+
+            self.assertTrue(hit())
+            self.assertEqual(engine.times_used["twice"], 1)
+
+    def test_one_shot_rule_reports_spent(self):
+        rule = {
+            "id": "once",
+            "name": "Mock once",
+            "execution": {"enabled": True, "disableAfterHit": True},
+            "match": {
+                "request": [
+                    {"type": "url", "matchType": "contains", "value": "example.com/api"}
+                ]
+            },
+            "actions": [{"type": "block_request"}]
+        }
+        self.set_mock_rules([rule])
+
+        flow = mock_env.get_mock_flow(url="http://example.com/api")
+        flow.metadata = {}
+        with unittest.mock.patch("builtins.print") as printed:
+            self.engine.handle_request(flow)
+        lines = [c.args[0] for c in printed.call_args_list]
+        self.assertIn("[RULE_SPENT] once", lines)
+        self.assertFalse(rule["execution"]["enabled"])
+
 if __name__ == "__main__":
     unittest.main()
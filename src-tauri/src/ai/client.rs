@@ -95,14 +95,297 @@ struct ChatCompletionRequest {
     tool_choice: Option<ToolChoice>,
 }
 
-fn drain_sse_events(buffer: &mut String) -> Vec<String> {
-    let mut events = Vec::new();
-    while let Some(idx) = buffer.find("\n\n") {
-        let event = buffer[..idx].to_string();
-        buffer.drain(..idx + 2);
-        events.push(event);
+/// `anthropic-version` header sent with every Messages API call
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Request body of Anthropic's native `POST /v1/messages`
+#[derive(Debug, Serialize)]
+struct AnthropicMessagesRequest {
+    model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<String>,
+    messages: Vec<AnthropicMessage>,
+    max_tokens: u32,
+    temperature: f32,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<AnthropicTool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct AnthropicMessage {
+    role: String,
+    content: String,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct AnthropicTool {
+    name: String,
+    description: String,
+    input_schema: serde_json::Value,
+}
+
+/// Map an OpenAI `tool_choice` onto the Messages API one. `"none"` has no
+/// counterpart, so the caller sends no tools at all in that case.
+fn anthropic_tool_choice(choice: &ToolChoice) -> Option<serde_json::Value> {
+    match choice {
+        ToolChoice::Mode(mode) if mode == "auto" => Some(serde_json::json!({ "type": "auto" })),
+        ToolChoice::Mode(mode) if mode == "required" => Some(serde_json::json!({ "type": "any" })),
+        ToolChoice::Mode(_) => None,
+        ToolChoice::Function(choice) => Some(serde_json::json!({
+            "type": "tool",
+            "name": choice.function.name,
+        })),
+    }
+}
+
+/// Translate OpenAI-style messages: system prompts move to the top-level
+/// `system` field, every non-assistant turn becomes `user`, and consecutive
+/// turns of the same role are merged since the API expects them to alternate.
+///
+/// Tool definitions become `tools` with their JSON schema as `input_schema`.
+/// Tool results are sent as plain user text: the history does not keep the
+/// assistant's `tool_use` blocks that a `tool_result` block would refer to.
+fn build_anthropic_request(
+    model: &str,
+    messages: &[ChatMessage],
+    tools: Option<&[Tool]>,
+    tool_choice: Option<&ToolChoice>,
+    temperature: f32,
+    max_tokens: u32,
+    stream: bool,
+) -> AnthropicMessagesRequest {
+    let mut system: Vec<&str> = Vec::new();
+    let mut turns: Vec<AnthropicMessage> = Vec::new();
+    for message in messages {
+        let Some(content) = message.content.as_deref().filter(|c| !c.is_empty()) else {
+            continue;
+        };
+        if message.role == "system" {
+            system.push(content);
+            continue;
+        }
+        let role = if message.role == "assistant" {
+            "assistant"
+        } else {
+            "user"
+        };
+        match turns.last_mut() {
+            Some(last) if last.role == role => {
+                last.content.push_str("\n\n");
+                last.content.push_str(content);
+            }
+            _ => turns.push(AnthropicMessage {
+                role: role.to_string(),
+                content: content.to_string(),
+            }),
+        }
+    }
+
+    let tools_disabled = matches!(tool_choice, Some(ToolChoice::Mode(mode)) if mode == "none");
+    let tools = tools
+        .filter(|t| !t.is_empty() && !tools_disabled)
+        .map(|tools| {
+            tools
+                .iter()
+                .map(|tool| AnthropicTool {
+                    name: tool.function.name.clone(),
+                    description: tool.function.description.clone(),
+                    input_schema: tool.function.parameters.clone(),
+                })
+                .collect::<Vec<_>>()
+        });
+    let tool_choice = tools
+        .as_ref()
+        .and(tool_choice)
+        .and_then(anthropic_tool_choice);
+
+    AnthropicMessagesRequest {
+        model: model.to_string(),
+        system: (!system.is_empty()).then(|| system.join("\n\n")),
+        messages: turns,
+        max_tokens,
+        // The Messages API only accepts 0.0 - 1.0
+        temperature: temperature.clamp(0.0, 1.0),
+        stream,
+        tools,
+        tool_choice,
+    }
+}
+
+/// Map a Messages API `stop_reason` onto the OpenAI `finish_reason` values
+fn anthropic_finish_reason(stop_reason: &str) -> String {
+    match stop_reason {
+        "end_turn" | "stop_sequence" => "stop",
+        "max_tokens" => "length",
+        "tool_use" => "tool_calls",
+        other => other,
+    }
+    .to_string()
+}
+
+/// Convert a non-streaming Messages API response body
+fn parse_anthropic_response(body: &serde_json::Value) -> Result<ChatCompletionResponse, AIError> {
+    let blocks = body
+        .get("content")
+        .and_then(|c| c.as_array())
+        .ok_or_else(|| AIError::ParseError("Messages response has no content".to_string()))?;
+    let text: String = blocks
+        .iter()
+        .filter(|block| block["type"] == "text")
+        .filter_map(|block| block.get("text").and_then(|t| t.as_str()))
+        .collect();
+    let tool_calls: Vec<ToolCall> = blocks
+        .iter()
+        .filter(|block| block["type"] == "tool_use")
+        .map(|block| ToolCall {
+            id: block["id"].as_str().unwrap_or_default().to_string(),
+            tool_type: "function".to_string(),
+            function: FunctionCall {
+                name: block["name"].as_str().unwrap_or_default().to_string(),
+                arguments: block
+                    .get("input")
+                    .map(|input| input.to_string())
+                    .unwrap_or_else(|| "{}".to_string()),
+            },
+        })
+        .collect();
+
+    Ok(ChatCompletionResponse {
+        choices: vec![Choice {
+            message: ResponseMessage {
+                role: "assistant".to_string(),
+                content: Some(text),
+                tool_calls: (!tool_calls.is_empty()).then_some(tool_calls),
+            },
+            finish_reason: body
+                .get("stop_reason")
+                .and_then(|r| r.as_str())
+                .map(anthropic_finish_reason),
+        }],
+        usage: body.get("usage").cloned(),
+    })
+}
+
+/// Accumulates raw SSE bytes and yields complete events.
+///
+/// Network frames do not respect line boundaries, so bytes are only decoded
+/// once a whole line has arrived; a `data:` line or a multi-byte character
+/// split across two frames is reassembled instead of being dropped.
+#[derive(Default)]
+struct SseBuffer {
+    bytes: Vec<u8>,
+    event: String,
+}
+
+impl SseBuffer {
+    /// Take the next complete line (without its terminator), if any.
+    fn next_line(&mut self) -> Option<String> {
+        let pos = self.bytes.iter().position(|b| *b == b'\n' || *b == b'\r')?;
+        let mut end = pos + 1;
+        if self.bytes[pos] == b'\r' {
+            match self.bytes.get(pos + 1) {
+                Some(b'\n') => end += 1,
+                Some(_) => {}
+                // The `\n` of a CRLF may still be in flight
+                None => return None,
+            }
+        }
+        let line = String::from_utf8_lossy(&self.bytes[..pos]).into_owned();
+        self.bytes.drain(..end);
+        Some(line)
+    }
+
+    /// Append a frame and return every event completed by it.
+    fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.bytes.extend_from_slice(chunk);
+        let mut events = Vec::new();
+        while let Some(line) = self.next_line() {
+            if line.is_empty() {
+                if !self.event.is_empty() {
+                    events.push(std::mem::take(&mut self.event));
+                }
+            } else {
+                self.event.push_str(&line);
+                self.event.push('\n');
+            }
+        }
+        events
+    }
+
+    /// Flush whatever is left once the stream has ended.
+    fn finish(&mut self) -> Option<String> {
+        let rest = String::from_utf8_lossy(&std::mem::take(&mut self.bytes)).into_owned();
+        let mut event = std::mem::take(&mut self.event);
+        event.push_str(&rest);
+        if event.trim().is_empty() {
+            None
+        } else {
+            Some(event)
+        }
     }
-    events
+}
+
+type SseEventParser = fn(&str) -> Result<Option<ChatCompletionChunk>, AIError>;
+
+/// Turn a raw SSE byte stream into parsed completion chunks.
+fn parse_sse_stream<S, B, E>(stream: S) -> ChatChunkStream
+where
+    S: futures_util::Stream<Item = Result<B, E>> + Send + Unpin + 'static,
+    B: AsRef<[u8]> + Send + 'static,
+    E: std::fmt::Display + Send + 'static,
+{
+    parse_sse_stream_with(stream, parse_sse_event)
+}
+
+fn parse_sse_stream_with<S, B, E>(stream: S, parse_event: SseEventParser) -> ChatChunkStream
+where
+    S: futures_util::Stream<Item = Result<B, E>> + Send + Unpin + 'static,
+    B: AsRef<[u8]> + Send + 'static,
+    E: std::fmt::Display + Send + 'static,
+{
+    let parsed_stream = futures_util::stream::try_unfold(
+        (
+            stream,
+            SseBuffer::default(),
+            VecDeque::<ChatCompletionChunk>::new(),
+        ),
+        move |(mut stream, mut buffer, mut queued)| async move {
+            loop {
+                if let Some(chunk) = queued.pop_front() {
+                    return Ok(Some((chunk, (stream, buffer, queued))));
+                }
+
+                match stream.next().await {
+                    Some(Ok(bytes)) => {
+                        for event in buffer.push(bytes.as_ref()) {
+                            if let Some(parsed) = parse_event(&event)? {
+                                queued.push_back(parsed);
+                            }
+                        }
+                    }
+                    Some(Err(e)) => {
+                        log::error!("AI Stream bytes error: {}", e);
+                        return Err(AIError::NetworkError(e.to_string()));
+                    }
+                    None => {
+                        if let Some(event) = buffer.finish() {
+                            if let Some(parsed) = parse_event(&event)? {
+                                queued.push_back(parsed);
+                            }
+                            continue;
+                        }
+                        return Ok(None);
+                    }
+                }
+            }
+        },
+    );
+
+    Box::pin(parsed_stream)
 }
 
 fn parse_sse_event(event: &str) -> Result<Option<ChatCompletionChunk>, AIError> {
@@ -134,6 +417,92 @@ fn parse_sse_event(event: &str) -> Result<Option<ChatCompletionChunk>, AIError>
         })
 }
 
+/// Parse one Messages API stream event. Text deltas, tool calls and the final
+/// stop reason become chunks; `message_start`, `ping` and other bookkeeping
+/// events are skipped, and an `error` event ends the stream with an error.
+///
+/// A `tool_use` block start carries the call's id and name, and its
+/// `input_json_delta`s carry the argument fragments, keyed by block index.
+fn parse_anthropic_sse_event(event: &str) -> Result<Option<ChatCompletionChunk>, AIError> {
+    let payload = event
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("data:"))
+        .map(str::trim_start)
+        .collect::<Vec<_>>()
+        .join("\n");
+    if payload.is_empty() {
+        return Ok(None);
+    }
+    let value: serde_json::Value = serde_json::from_str(&payload).map_err(|e| {
+        log::debug!("Failed to parse SSE event payload: {}", payload);
+        AIError::ParseError(format!("Failed to parse SSE event: {}", e))
+    })?;
+
+    let chunk = |content: Option<String>, finish_reason: Option<String>| ChatCompletionChunk {
+        choices: vec![ChunkChoice {
+            delta: ChunkDelta {
+                content,
+                tool_calls: None,
+            },
+            finish_reason,
+        }],
+    };
+    let tool_chunk =
+        |id: Option<String>, name: Option<String>, arguments: String| ChatCompletionChunk {
+            choices: vec![ChunkChoice {
+                delta: ChunkDelta {
+                    content: None,
+                    tool_calls: Some(vec![StreamingToolCall {
+                        index: value["index"].as_u64().unwrap_or(0) as usize,
+                        id,
+                        function: Some(StreamingFunctionCall {
+                            name,
+                            arguments: Some(arguments),
+                        }),
+                    }]),
+                },
+                finish_reason: None,
+            }],
+        };
+    match value.get("type").and_then(|t| t.as_str()) {
+        Some("content_block_start") => {
+            let block = &value["content_block"];
+            if block["type"].as_str() != Some("tool_use") {
+                return Ok(None);
+            }
+            Ok(Some(tool_chunk(
+                block["id"].as_str().map(str::to_string),
+                block["name"].as_str().map(str::to_string),
+                String::new(),
+            )))
+        }
+        Some("content_block_delta") => {
+            if let Some(partial) = value
+                .pointer("/delta/partial_json")
+                .and_then(|j| j.as_str())
+            {
+                return Ok(Some(tool_chunk(None, None, partial.to_string())));
+            }
+            Ok(value
+                .pointer("/delta/text")
+                .and_then(|t| t.as_str())
+                .map(|text| chunk(Some(text.to_string()), None)))
+        }
+        Some("message_delta") => Ok(value
+            .pointer("/delta/stop_reason")
+            .and_then(|r| r.as_str())
+            .map(|reason| chunk(None, Some(anthropic_finish_reason(reason))))),
+        Some("error") => Err(AIError::APIError(
+            value
+                .pointer("/error/message")
+                .and_then(|m| m.as_str())
+                .unwrap_or("Unknown stream error")
+                .to_string(),
+        )),
+        _ => Ok(None),
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
 pub struct ChatCompletionResponse {
@@ -305,6 +674,74 @@ impl AIClient {
         }
     }
 
+    /// Anthropic is the one provider that does not speak `/chat/completions`
+    fn uses_messages_api(&self) -> bool {
+        self.config.provider == "anthropic"
+    }
+
+    /// POST a Messages API request with Anthropic's auth and version headers
+    async fn send_anthropic_request(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+        tool_choice: Option<&ToolChoice>,
+        temperature: f32,
+        stream: bool,
+    ) -> Result<reqwest::Response, AIError> {
+        let endpoint = format!(
+            "{}/messages",
+            self.config.get_endpoint().trim_end_matches('/')
+        );
+        let request = build_anthropic_request(
+            &self.config.model,
+            messages,
+            tools,
+            tool_choice,
+            temperature,
+            self.config.max_tokens,
+            stream,
+        );
+
+        let _ = logging::write_domain_log(
+            "audit",
+            &format!(
+                "AI {}Request: endpoint={}, model={}, max_tokens={}, messages_count={}, tools_enabled={}",
+                if stream { "Stream " } else { "" },
+                endpoint,
+                self.config.model,
+                self.config.max_tokens,
+                request.messages.len(),
+                request.tools.is_some()
+            ),
+        );
+        log::info!("Sending AI request to: {}", endpoint);
+
+        let response = self
+            .client
+            .post(&endpoint)
+            .header("Content-Type", "application/json")
+            .header("x-api-key", &self.config.api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| {
+                log::error!("AI Network Error: {}", e);
+                AIError::NetworkError(e.to_string())
+            })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            log::error!("AI API Error ({}): {}", status, error_text);
+            return Err(AIError::APIError(format!(
+                "API Error ({}): {}",
+                status, error_text
+            )));
+        }
+        Ok(response)
+    }
+
     async fn do_chat_completion(
         &self,
         messages: Vec<ChatMessage>,
@@ -312,6 +749,44 @@ impl AIClient {
         tool_choice: Option<ToolChoice>,
         temp_override: Option<f32>,
     ) -> Result<ChatCompletionResponse, AIError> {
+        if self.uses_messages_api() {
+            let temperature = self.resolve_temperature(temp_override);
+            let body = self
+                .send_anthropic_request(
+                    &messages,
+                    tools.as_deref(),
+                    tool_choice.as_ref(),
+                    temperature,
+                    false,
+                )
+                .await?
+                .json::<serde_json::Value>()
+                .await
+                .map_err(|e| AIError::ParseError(e.to_string()))?;
+            let response_body = parse_anthropic_response(&body)?;
+
+            let message_chars: usize = messages
+                .iter()
+                .map(|m| m.content.as_ref().map(|v| v.len()).unwrap_or(0))
+                .sum();
+            let approx_prompt_tokens = u32::try_from(message_chars / 4).unwrap_or(u32::MAX);
+            let approx_completion_tokens = estimate_output_tokens(&response_body);
+            let (prompt_tokens, completion_tokens, total_tokens, usage_source) =
+                usage_tokens_from_response(
+                    response_body.usage.as_ref(),
+                    approx_prompt_tokens,
+                    approx_completion_tokens,
+                );
+            let _ = logging::write_domain_log(
+                "audit",
+                &format!(
+                    "AI Usage: model={}, prompt_tokens={}, completion_tokens={}, total_tokens={}, usage_source={}",
+                    self.config.model, prompt_tokens, completion_tokens, total_tokens, usage_source
+                ),
+            );
+            return Ok(response_body);
+        }
+
         let base_endpoint = self.config.get_endpoint();
         let base_endpoint = base_endpoint.trim_end_matches('/');
         let endpoint = format!("{}/chat/completions", base_endpoint);
@@ -456,6 +931,23 @@ impl AIClient {
         tool_choice: Option<ToolChoice>,
         temp_override: Option<f32>,
     ) -> Result<ChatChunkStream, AIError> {
+        if self.uses_messages_api() {
+            let temperature = self.resolve_temperature(temp_override);
+            let response = self
+                .send_anthropic_request(
+                    &messages,
+                    tools.as_deref(),
+                    tool_choice.as_ref(),
+                    temperature,
+                    true,
+                )
+                .await?;
+            return Ok(parse_sse_stream_with(
+                response.bytes_stream(),
+                parse_anthropic_sse_event,
+            ));
+        }
+
         let base_endpoint = self.config.get_endpoint();
         let base_endpoint = base_endpoint.trim_end_matches('/');
         let endpoint = format!("{}/chat/completions", base_endpoint);
@@ -531,53 +1023,7 @@ impl AIClient {
             )));
         }
 
-        let stream = response.bytes_stream();
-        let parsed_stream = futures_util::stream::try_unfold(
-            (
-                stream,
-                String::new(),
-                VecDeque::<ChatCompletionChunk>::new(),
-            ),
-            |(mut stream, mut pending, mut queued)| async move {
-                loop {
-                    if let Some(chunk) = queued.pop_front() {
-                        return Ok(Some((chunk, (stream, pending, queued))));
-                    }
-
-                    match stream.next().await {
-                        Some(Ok(bytes)) => {
-                            let text = String::from_utf8_lossy(&bytes)
-                                .replace("\r\n", "\n")
-                                .replace('\r', "\n");
-                            pending.push_str(&text);
-
-                            let events = drain_sse_events(&mut pending);
-                            for event in events {
-                                if let Some(parsed) = parse_sse_event(&event)? {
-                                    queued.push_back(parsed);
-                                }
-                            }
-                        }
-                        Some(Err(e)) => {
-                            log::error!("AI Stream bytes error: {}", e);
-                            return Err(AIError::NetworkError(e.to_string()));
-                        }
-                        None => {
-                            if !pending.trim().is_empty() {
-                                if let Some(parsed) = parse_sse_event(&pending)? {
-                                    queued.push_back(parsed);
-                                }
-                                pending.clear();
-                                continue;
-                            }
-                            return Ok(None);
-                        }
-                    }
-                }
-            },
-        );
-
-        Ok(Box::pin(parsed_stream))
+        Ok(parse_sse_stream(response.bytes_stream()))
     }
 
     #[allow(dead_code)]
@@ -691,6 +1137,122 @@ impl AIClient {
 
         extract_tools_probe_result(&response)
     }
+
+    /// Check the endpoint with a `GET {endpoint}/models` instead of a chat
+    /// completion, so setup problems can be told apart without spending tokens.
+    pub async fn probe_endpoint(&self) -> EndpointProbe {
+        let endpoint = self.config.get_endpoint();
+        let url = format!("{}/models", endpoint.trim_end_matches('/'));
+
+        let mut request = self
+            .client
+            .get(&url)
+            .timeout(std::time::Duration::from_secs(10));
+        if self.uses_messages_api() {
+            request = request
+                .header("x-api-key", &self.config.api_key)
+                .header("anthropic-version", ANTHROPIC_VERSION);
+        } else if !self.config.api_key.is_empty() {
+            request = request.header("Authorization", format!("Bearer {}", self.config.api_key));
+        }
+
+        let started = std::time::Instant::now();
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                return EndpointProbe {
+                    status: EndpointStatus::Unreachable,
+                    endpoint,
+                    http_status: None,
+                    latency_ms: None,
+                    model_found: None,
+                    message: e.to_string(),
+                }
+            }
+        };
+        let latency_ms = started.elapsed().as_millis() as u64;
+        let http_status = response.status().as_u16();
+        let body = response.text().await.unwrap_or_default();
+
+        let status = classify_probe_status(http_status);
+        let model_found = if status == EndpointStatus::Ok {
+            models_list_contains(&body, &self.config.model)
+        } else {
+            None
+        };
+        let message = match status {
+            EndpointStatus::Ok => match model_found {
+                Some(false) => format!(
+                    "Endpoint OK, but model '{}' is not in its model list",
+                    self.config.model
+                ),
+                _ => "Endpoint OK".to_string(),
+            },
+            EndpointStatus::Reachable => {
+                "Endpoint reachable, but it has no /models listing, so the API key was not checked"
+                    .to_string()
+            }
+            EndpointStatus::AuthFailed => "Authentication failed, check the API key".to_string(),
+            EndpointStatus::Unreachable | EndpointStatus::Error => {
+                format!("Endpoint returned HTTP {}", http_status)
+            }
+        };
+
+        EndpointProbe {
+            status,
+            endpoint,
+            http_status: Some(http_status),
+            latency_ms: Some(latency_ms),
+            model_found,
+            message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EndpointStatus {
+    /// `/models` answered successfully with the given key
+    Ok,
+    /// The server answered but has no `/models` route
+    Reachable,
+    AuthFailed,
+    Unreachable,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EndpointProbe {
+    pub status: EndpointStatus,
+    pub endpoint: String,
+    pub http_status: Option<u16>,
+    pub latency_ms: Option<u64>,
+    /// Whether the configured model appears in the `/models` list, when one was returned
+    pub model_found: Option<bool>,
+    pub message: String,
+}
+
+fn classify_probe_status(http_status: u16) -> EndpointStatus {
+    match http_status {
+        200..=299 => EndpointStatus::Ok,
+        401 | 403 => EndpointStatus::AuthFailed,
+        404 | 405 => EndpointStatus::Reachable,
+        _ => EndpointStatus::Error,
+    }
+}
+
+/// Look for `model` in an OpenAI-style `{"data": [{"id": ...}]}` listing.
+fn models_list_contains(body: &str, model: &str) -> Option<bool> {
+    if model.is_empty() {
+        return None;
+    }
+    let value: serde_json::Value = serde_json::from_str(body).ok()?;
+    let models = value.get("data")?.as_array()?;
+    Some(
+        models
+            .iter()
+            .any(|m| m.get("id").and_then(|id| id.as_str()) == Some(model)),
+    )
 }
 
 fn extract_tools_probe_result(response: &ChatCompletionResponse) -> Result<String, AIError> {
@@ -715,10 +1277,15 @@ fn extract_tools_probe_result(response: &ChatCompletionResponse) -> Result<Strin
 #[cfg(test)]
 mod tests {
     use super::{
-        drain_sse_events, estimate_output_tokens, extract_tools_probe_result, parse_sse_event,
-        usage_tokens_from_response, ChatCompletionRequest, ChatCompletionResponse, ChatMessage,
-        Choice, FunctionCall, ResponseMessage, ToolCall, ToolChoice,
+        build_anthropic_request, classify_probe_status, estimate_output_tokens,
+        extract_tools_probe_result, models_list_contains, parse_anthropic_response,
+        parse_anthropic_sse_event, parse_sse_event, parse_sse_stream, parse_sse_stream_with,
+        usage_tokens_from_response, AnthropicMessage, ChatCompletionRequest,
+        ChatCompletionResponse, ChatMessage, Choice, EndpointStatus, FunctionCall,
+        FunctionDefinition, ResponseMessage, SseBuffer, Tool, ToolCall, ToolChoice,
+        ToolChoiceFunction, ToolChoiceFunctionChoice,
     };
+    use futures_util::StreamExt;
 
     #[test]
     fn serializes_auto_tool_choice_as_string() {
@@ -743,10 +1310,37 @@ mod tests {
 
     #[test]
     fn drains_multiple_sse_events_from_single_chunk() {
-        let mut buffer = "data: {\"choices\":[{\"delta\":{\"content\":\"A\",\"tool_calls\":null},\"finish_reason\":null}]}\n\ndata: {\"choices\":[{\"delta\":{\"content\":\"B\",\"tool_calls\":null},\"finish_reason\":null}]}\n\n".to_string();
-        let events = drain_sse_events(&mut buffer);
+        let chunk = "data: {\"choices\":[{\"delta\":{\"content\":\"A\",\"tool_calls\":null},\"finish_reason\":null}]}\n\ndata: {\"choices\":[{\"delta\":{\"content\":\"B\",\"tool_calls\":null},\"finish_reason\":null}]}\n\n";
+        let mut buffer = SseBuffer::default();
+        let events = buffer.push(chunk.as_bytes());
         assert_eq!(events.len(), 2);
-        assert!(buffer.is_empty());
+        assert!(buffer.finish().is_none());
+    }
+
+    #[test]
+    fn reassembles_event_split_across_frames() {
+        let event = "data: {\"choices\":[{\"delta\":{\"content\":\"h\u{e9}llo\",\"tool_calls\":null},\"finish_reason\":null}]}\r\n\r\n";
+        let bytes = event.as_bytes();
+        // Split inside the two-byte `é` so neither half is valid UTF-8 on its own
+        let split = event.find('\u{e9}').unwrap() + 1;
+        let frames: Vec<Result<Vec<u8>, String>> =
+            vec![Ok(bytes[..split].to_vec()), Ok(bytes[split..].to_vec())];
+
+        let chunks: Vec<_> = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(parse_sse_stream(futures_util::stream::iter(frames)).collect::<Vec<_>>());
+
+        assert_eq!(chunks.len(), 1);
+        let chunk = chunks
+            .into_iter()
+            .next()
+            .unwrap()
+            .expect("chunk should parse");
+        assert_eq!(
+            chunk.choices[0].delta.content.as_deref(),
+            Some("h\u{e9}llo")
+        );
     }
 
     #[test]
@@ -760,6 +1354,210 @@ mod tests {
         assert_eq!(parsed.choices[0].delta.content.as_deref(), Some("hello"));
     }
 
+    fn message(role: &str, content: &str) -> ChatMessage {
+        ChatMessage {
+            role: role.to_string(),
+            content: Some(content.to_string()),
+            name: None,
+            tool_call_id: None,
+        }
+    }
+
+    #[test]
+    fn builds_anthropic_messages_request() {
+        let messages = vec![
+            message("system", "You are terse."),
+            message("system", "Answer in English."),
+            message("user", "hi"),
+            message("user", "are you there?"),
+            message("assistant", "yes"),
+            message("tool", "{\"ok\":true}"),
+        ];
+        let request =
+            build_anthropic_request("claude-sonnet-4-5", &messages, None, None, 1.4, 512, true);
+
+        assert_eq!(
+            request.system.as_deref(),
+            Some("You are terse.\n\nAnswer in English.")
+        );
+        assert_eq!(
+            request.messages,
+            vec![
+                AnthropicMessage {
+                    role: "user".to_string(),
+                    content: "hi\n\nare you there?".to_string(),
+                },
+                AnthropicMessage {
+                    role: "assistant".to_string(),
+                    content: "yes".to_string(),
+                },
+                AnthropicMessage {
+                    role: "user".to_string(),
+                    content: "{\"ok\":true}".to_string(),
+                },
+            ]
+        );
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["temperature"], 1.0);
+        assert_eq!(json["max_tokens"], 512);
+        assert_eq!(json["stream"], true);
+
+        let plain =
+            build_anthropic_request("m", &[message("user", "hi")], None, None, 0.2, 16, false);
+        let json = serde_json::to_value(&plain).unwrap();
+        assert!(json.get("system").is_none());
+        assert!(json.get("stream").is_none());
+    }
+
+    #[test]
+    fn parses_anthropic_response_and_stream() {
+        let body = serde_json::json!({
+            "content": [{ "type": "text", "text": "Hello" }],
+            "stop_reason": "end_turn",
+            "usage": { "input_tokens": 12, "output_tokens": 3 }
+        });
+        let response = parse_anthropic_response(&body).unwrap();
+        assert_eq!(
+            response.choices[0].message.content.as_deref(),
+            Some("Hello")
+        );
+        assert_eq!(response.choices[0].finish_reason.as_deref(), Some("stop"));
+        let (prompt, completion, _, _) = usage_tokens_from_response(response.usage.as_ref(), 0, 0);
+        assert_eq!((prompt, completion), (12, 3));
+
+        let sse = concat!(
+            "event: message_start\n",
+            "data: {\"type\":\"message_start\",\"message\":{\"id\":\"msg_1\",\"role\":\"assistant\"}}\n\n",
+            "event: content_block_start\n",
+            "data: {\"type\":\"content_block_start\",\"index\":0,\"content_block\":{\"type\":\"text\",\"text\":\"\"}}\n\n",
+            "event: ping\n",
+            "data: {\"type\":\"ping\"}\n\n",
+            "event: content_block_delta\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"stream\"}}\n\n",
+            "event: content_block_delta\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\" ok\"}}\n\n",
+            "event: content_block_stop\n",
+            "data: {\"type\":\"content_block_stop\",\"index\":0}\n\n",
+            "event: message_delta\n",
+            "data: {\"type\":\"message_delta\",\"delta\":{\"stop_reason\":\"max_tokens\"},\"usage\":{\"output_tokens\":2}}\n\n",
+            "event: message_stop\n",
+            "data: {\"type\":\"message_stop\"}\n\n",
+        );
+        let frames: Vec<Result<Vec<u8>, String>> = vec![Ok(sse.as_bytes().to_vec())];
+        let chunks: Vec<_> = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(
+                parse_sse_stream_with(
+                    futures_util::stream::iter(frames),
+                    parse_anthropic_sse_event,
+                )
+                .collect::<Vec<_>>(),
+            )
+            .into_iter()
+            .map(|chunk| chunk.expect("chunk should parse"))
+            .collect();
+
+        let text: String = chunks
+            .iter()
+            .filter_map(|c| c.choices[0].delta.content.clone())
+            .collect();
+        assert_eq!(text, "stream ok");
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(
+            chunks[2].choices[0].finish_reason.as_deref(),
+            Some("length")
+        );
+
+        let error = "event: error\ndata: {\"type\":\"error\",\"error\":{\"type\":\"overloaded_error\",\"message\":\"Overloaded\"}}";
+        assert!(parse_anthropic_sse_event(error).is_err());
+    }
+
+    #[test]
+    fn maps_tools_onto_anthropic_tool_use() {
+        let tools = vec![Tool {
+            tool_type: "function".to_string(),
+            function: FunctionDefinition {
+                name: "create_rule".to_string(),
+                description: "Create a rule".to_string(),
+                parameters: serde_json::json!({ "type": "object", "properties": {} }),
+            },
+        }];
+        let messages = [message("user", "block ads")];
+
+        let request = build_anthropic_request(
+            "m",
+            &messages,
+            Some(&tools),
+            Some(&ToolChoice::Mode("required".to_string())),
+            0.2,
+            16,
+            false,
+        );
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["tools"][0]["name"], "create_rule");
+        assert_eq!(json["tools"][0]["input_schema"]["type"], "object");
+        assert_eq!(json["tool_choice"], serde_json::json!({ "type": "any" }));
+
+        let forced = ToolChoice::Function(ToolChoiceFunctionChoice {
+            tool_type: "function".to_string(),
+            function: ToolChoiceFunction {
+                name: "create_rule".to_string(),
+            },
+        });
+        let request =
+            build_anthropic_request("m", &messages, Some(&tools), Some(&forced), 0.2, 16, false);
+        assert_eq!(
+            serde_json::to_value(&request).unwrap()["tool_choice"],
+            serde_json::json!({ "type": "tool", "name": "create_rule" })
+        );
+
+        // `none` has no Messages API equivalent, so no tools are offered
+        let none = ToolChoice::Mode("none".to_string());
+        let request =
+            build_anthropic_request("m", &messages, Some(&tools), Some(&none), 0.2, 16, false);
+        let json = serde_json::to_value(&request).unwrap();
+        assert!(json.get("tools").is_none());
+        assert!(json.get("tool_choice").is_none());
+
+        let body = serde_json::json!({
+            "content": [
+                { "type": "text", "text": "Creating it." },
+                { "type": "tool_use", "id": "toolu_1", "name": "create_rule", "input": { "name": "ads" } }
+            ],
+            "stop_reason": "tool_use"
+        });
+        let response = parse_anthropic_response(&body).unwrap();
+        let calls = response.choices[0].message.tool_calls.as_ref().unwrap();
+        assert_eq!(calls[0].id, "toolu_1");
+        assert_eq!(calls[0].function.name, "create_rule");
+        assert_eq!(calls[0].function.arguments, r#"{"name":"ads"}"#);
+        assert_eq!(
+            response.choices[0].finish_reason.as_deref(),
+            Some("tool_calls")
+        );
+
+        let start = "data: {\"type\":\"content_block_start\",\"index\":1,\"content_block\":{\"type\":\"tool_use\",\"id\":\"toolu_1\",\"name\":\"create_rule\",\"input\":{}}}";
+        let start = parse_anthropic_sse_event(start).unwrap().unwrap();
+        let call = &start.choices[0].delta.tool_calls.as_ref().unwrap()[0];
+        assert_eq!(call.index, 1);
+        assert_eq!(call.id.as_deref(), Some("toolu_1"));
+        assert_eq!(
+            call.function.as_ref().unwrap().name.as_deref(),
+            Some("create_rule")
+        );
+
+        let delta = "data: {\"type\":\"content_block_delta\",\"index\":1,\"delta\":{\"type\":\"input_json_delta\",\"partial_json\":\"{\\\"name\\\":\"}}";
+        let delta = parse_anthropic_sse_event(delta).unwrap().unwrap();
+        let call = &delta.choices[0].delta.tool_calls.as_ref().unwrap()[0];
+        assert_eq!(call.index, 1);
+        assert_eq!(
+            call.function.as_ref().unwrap().arguments.as_deref(),
+            Some("{\"name\":")
+        );
+    }
+
     #[test]
     fn tools_probe_requires_tool_calls() {
         let response = ChatCompletionResponse {
@@ -838,4 +1636,22 @@ mod tests {
         assert_eq!(total, prompt + completion);
         assert_eq!(source, "estimated_chars_div_4");
     }
+
+    #[test]
+    fn probe_status_separates_auth_from_reachability() {
+        assert_eq!(classify_probe_status(200), EndpointStatus::Ok);
+        assert_eq!(classify_probe_status(401), EndpointStatus::AuthFailed);
+        assert_eq!(classify_probe_status(403), EndpointStatus::AuthFailed);
+        assert_eq!(classify_probe_status(404), EndpointStatus::Reachable);
+        assert_eq!(classify_probe_status(502), EndpointStatus::Error);
+    }
+
+    #[test]
+    fn models_list_lookup_matches_model_id() {
+        let body = r#"{"object":"list","data":[{"id":"gpt-5-mini"},{"id":"gpt-4o"}]}"#;
+        assert_eq!(models_list_contains(body, "gpt-4o"), Some(true));
+        assert_eq!(models_list_contains(body, "llama"), Some(false));
+        assert_eq!(models_list_contains("not json", "gpt-4o"), None);
+        assert_eq!(models_list_contains(body, ""), None);
+    }
 }
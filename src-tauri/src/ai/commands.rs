@@ -1,3 +1,4 @@
+use crate::ai::client::EndpointProbe;
 use crate::ai::profiles::{self, AIProviderProfile};
 use crate::ai::tool_args::normalize_and_validate_tool_calls;
 use crate::ai::{crypto, AIClient, AIConfig, ChatCompletionChunk, ChatMessage, Tool, ToolChoice};
@@ -16,6 +17,21 @@ pub struct ToolCompletionResult {
     pub tool_calls: Option<Vec<crate::ai::client::ToolCall>>,
 }
 
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ConfigFieldDrift {
+    pub field: String,
+    pub disk: serde_json::Value,
+    pub memory: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AIConfigDrift {
+    pub in_sync: bool,
+    pub fields: Vec<ConfigFieldDrift>,
+    /// The API key lives in the key store, never in config.json
+    pub api_key_stored: bool,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct CapabilityProbeItem {
     pub ok: bool,
@@ -56,6 +72,36 @@ fn normalize_profile_for_provider(config: &mut AIConfig) {
     }
 }
 
+/// Field-by-field differences between two configs, ignoring the API key,
+/// which is never written to disk.
+fn diff_ai_configs(disk: &AIConfig, memory: &AIConfig) -> Vec<ConfigFieldDrift> {
+    let to_map = |config: &AIConfig| {
+        let mut value = serde_json::to_value(config).unwrap_or_default();
+        if let Some(map) = value.as_object_mut() {
+            map.remove("apiKey");
+        }
+        value
+    };
+    let (disk, memory) = (to_map(disk), to_map(memory));
+    let empty = serde_json::Map::new();
+    let disk = disk.as_object().unwrap_or(&empty);
+    let memory = memory.as_object().unwrap_or(&empty);
+
+    let mut fields: Vec<&String> = disk.keys().chain(memory.keys()).collect();
+    fields.sort();
+    fields.dedup();
+
+    fields
+        .into_iter()
+        .filter(|field| disk.get(*field) != memory.get(*field))
+        .map(|field| ConfigFieldDrift {
+            field: field.clone(),
+            disk: disk.get(field).cloned().unwrap_or_default(),
+            memory: memory.get(field).cloned().unwrap_or_default(),
+        })
+        .collect()
+}
+
 fn build_tool_completion_result(
     choice: &crate::ai::client::Choice,
     normalized_tool_calls: Option<Vec<crate::ai::client::ToolCall>>,
@@ -169,12 +215,126 @@ pub async fn save_ai_config(config: AIConfig, state: State<'_, AIState>) -> Resu
 
     // Persist to config.json
     let mut app_config = crate::config::load_config().unwrap_or_default();
+    remember_provider_slot(&mut app_config, &config);
     app_config.ai_config = config;
     crate::config::save_config(app_config)?;
 
     Ok(())
 }
 
+/// Keep `config` as the saved settings of its provider (key excluded).
+fn remember_provider_slot(app_config: &mut crate::config::AppConfig, config: &AIConfig) {
+    let mut slot = config.clone();
+    slot.api_key = String::new();
+    app_config
+        .ai_provider_configs
+        .insert(config.provider.clone(), slot);
+}
+
+/// Make `provider` the active provider, restoring the model, endpoint and
+/// tuning it was last saved with. The current provider's settings are kept
+/// so switching back restores them too.
+#[tauri::command]
+pub async fn switch_ai_provider(
+    provider: String,
+    state: State<'_, AIState>,
+) -> Result<AIConfig, String> {
+    let mut app_config = crate::config::load_config().unwrap_or_default();
+    let mut guard = state
+        .config
+        .lock()
+        .map_err(|e| format!("Config lock poisoned: {}", e))?;
+
+    remember_provider_slot(&mut app_config, &guard);
+
+    let mut next = app_config
+        .ai_provider_configs
+        .get(&provider)
+        .cloned()
+        .unwrap_or_else(|| AIConfig::for_provider(&provider));
+    next.enabled = guard.enabled;
+    normalize_profile_for_provider(&mut next);
+
+    *guard = next.clone();
+    app_config.ai_config = next.clone();
+    crate::config::save_config(app_config)?;
+
+    log::info!("Switched AI provider to {}", provider);
+    apply_stored_api_key(&mut next);
+    Ok(next)
+}
+
+/// Compare the AI config in config.json with the in-memory state
+#[tauri::command]
+pub async fn check_ai_config_drift(state: State<'_, AIState>) -> Result<AIConfigDrift, String> {
+    let disk = crate::config::load_config()?.ai_config;
+    let memory = state
+        .config
+        .lock()
+        .map_err(|e| format!("Config lock poisoned: {}", e))?
+        .clone();
+
+    let fields = diff_ai_configs(&disk, &memory);
+    Ok(AIConfigDrift {
+        in_sync: fields.is_empty(),
+        fields,
+        api_key_stored: crypto::retrieve_api_key(&memory.provider)
+            .map(|key| !key.is_empty())
+            .unwrap_or(false),
+    })
+}
+
+/// Re-sync the AI config. `source` is "disk" (reload config.json into memory)
+/// or "memory" (write the in-memory config to config.json).
+#[tauri::command]
+pub async fn reconcile_ai_config(
+    source: String,
+    state: State<'_, AIState>,
+) -> Result<AIConfig, String> {
+    let mut guard = state
+        .config
+        .lock()
+        .map_err(|e| format!("Config lock poisoned: {}", e))?;
+
+    match source.as_str() {
+        "disk" => {
+            *guard = crate::config::load_config()?.ai_config;
+        }
+        "memory" => {
+            let mut app_config = crate::config::load_config()?;
+            app_config.ai_config = guard.clone();
+            crate::config::save_config(app_config)?;
+        }
+        other => return Err(format!("Unknown reconcile source: {}", other)),
+    }
+
+    log::info!("Reconciled AI config from {}", source);
+    Ok(guard.clone())
+}
+
+/// Check endpoint reachability and credentials for a (possibly unsaved) config
+/// without running a chat completion.
+#[tauri::command]
+pub async fn probe_endpoint(mut config: AIConfig) -> Result<EndpointProbe, String> {
+    normalize_profile_for_provider(&mut config);
+    if config.api_key.is_empty() {
+        apply_stored_api_key(&mut config);
+    }
+    let probe = AIClient::new(config).probe_endpoint().await;
+    log::info!(
+        "AI endpoint probe: endpoint={}, status={:?}",
+        probe.endpoint,
+        probe.status
+    );
+    Ok(probe)
+}
+
+/// Suggest a provider for a pasted API key. Heuristic only; never blocks saving.
+#[tauri::command]
+pub fn detect_provider(api_key: String) -> Option<String> {
+    profiles::detect_provider_from_key(&api_key).map(str::to_string)
+}
+
 #[tauri::command]
 pub async fn test_ai_connection(state: State<'_, AIState>) -> Result<String, String> {
     let (client, config) = build_ai_client(&state, false)?;
@@ -322,8 +482,8 @@ pub async fn ai_chat_completion_stream(
 #[cfg(test)]
 mod tests {
     use super::{
-        build_tool_completion_result, normalize_profile_for_provider,
-        tuple_messages_to_chat_messages,
+        build_tool_completion_result, detect_provider, diff_ai_configs,
+        normalize_profile_for_provider, tuple_messages_to_chat_messages,
     };
     use crate::ai::client::{Choice, FunctionCall, ResponseMessage, ToolCall};
     use crate::ai::AIConfig;
@@ -426,6 +586,45 @@ mod tests {
         normalize_profile_for_provider(&mut config);
         assert_eq!(config.profile_id.as_deref(), Some("groq-default"));
     }
+
+    #[test]
+    fn diff_ai_configs_reports_changed_fields_but_not_api_key() {
+        let disk = AIConfig::default();
+        let memory = AIConfig {
+            model: "changed-model".to_string(),
+            api_key: "secret".to_string(),
+            ..AIConfig::default()
+        };
+
+        let drift = diff_ai_configs(&disk, &memory);
+        assert_eq!(drift.len(), 1);
+        assert_eq!(drift[0].field, "model");
+        assert_eq!(drift[0].memory, "changed-model");
+        assert!(diff_ai_configs(&disk, &disk.clone()).is_empty());
+    }
+
+    #[test]
+    fn detect_provider_prefers_specific_key_prefixes() {
+        let cases = [
+            ("sk-or-v1-abc", Some("openrouter")),
+            ("sk-ant-api03-abc", Some("anthropic")),
+            ("sk-proj-abc", Some("openai")),
+            ("  sk-abc123  ", Some("openai")),
+            ("gsk_abc", Some("groq")),
+            ("0123456789abcdef0123456789abcdef.Secret123", Some("zhipu")),
+            ("eyJhbGciOiJIUzI1NiJ9.eyJzdWIiOiIxIn0.sig", Some("minimax")),
+            ("random-key", None),
+            ("", None),
+        ];
+        for (key, expected) in cases {
+            assert_eq!(
+                detect_provider(key.to_string()).as_deref(),
+                expected,
+                "{}",
+                key
+            );
+        }
+    }
 }
 
 #[tauri::command]
@@ -7,17 +7,16 @@ pub struct AIConfig {
     /// Whether AI features are enabled
     pub enabled: bool,
 
-    /// Provider type: "openai", "custom"
+    /// Provider type: "openai", "anthropic", "custom"
     pub provider: String,
 
     /// Provider profile id (new path for profile-based config)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub profile_id: Option<String>,
 
-    /// Adapter mode (e.g. openai_compatible).
-    /// Reserved for future provider-specific protocol adaptation.
-    /// Currently all providers use OpenAI-compatible format; this field
-    /// exists to support non-OpenAI adapter modes without schema migration.
+    /// Adapter mode (e.g. openai_compatible, anthropic).
+    /// Every provider except `anthropic` speaks the OpenAI-compatible format;
+    /// Anthropic uses its native Messages API.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub adapter_mode: Option<String>,
 
@@ -81,6 +80,23 @@ impl Default for AIConfig {
 }
 
 impl AIConfig {
+    /// Fresh settings for a provider that has never been configured,
+    /// using its default profile and model.
+    pub fn for_provider(provider: &str) -> Self {
+        let mut config = Self {
+            provider: provider.to_string(),
+            profile_id: None,
+            adapter_mode: None,
+            ..Self::default()
+        };
+        if let Some(profile) = profiles::default_profile_for_provider(provider) {
+            config.profile_id = Some(profile.id);
+            config.adapter_mode = Some(profile.adapter_mode);
+            config.model = profile.default_model;
+        }
+        config
+    }
+
     /// Get the API endpoint based on provider
     pub fn get_endpoint(&self) -> String {
         if let Some(endpoint) = &self.custom_endpoint {
@@ -130,6 +146,20 @@ mod tests {
         assert!(config.enable_caching);
     }
 
+    #[test]
+    fn test_for_provider_uses_default_profile() {
+        let config = AIConfig::for_provider("zhipu");
+        assert_eq!(config.provider, "zhipu");
+        assert_eq!(config.profile_id.as_deref(), Some("zhipu-cn"));
+        assert_eq!(
+            config.get_endpoint(),
+            "https://open.bigmodel.cn/api/paas/v4"
+        );
+
+        let custom = AIConfig::for_provider("custom");
+        assert_eq!(custom.profile_id, None);
+    }
+
     #[test]
     fn test_get_endpoint_with_custom() {
         let mut config = AIConfig::default();
@@ -1,18 +1,37 @@
 /**
- * API Key Storage - File-based with XOR obfuscation.
+ * API Key Storage - File-based with AES-256-GCM encryption.
  *
- * XOR is NOT encryption — goal is to avoid accidental exposure (e.g. screen sharing),
- * not protect against attackers with filesystem access.
- * Acceptable for a local dev tool; avoids OS keyring prompts.
+ * Each key is sealed with a random 256-bit master key kept in the OS keyring,
+ * so copying the secrets directory does not leak usable keys. When no keyring
+ * is available the master key falls back to `secrets/master.key` (owner-only
+ * on unix) and a warning is logged. A keyring that is present but refuses to
+ * hand the key back is reported as an error rather than replaced, since a new
+ * key would orphan every sealed secret. The key is read once per launch.
+ *
+ * Files written by older versions were XOR-masked; they are still read and
+ * re-encrypted transparently on first retrieval.
  */
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
 use std::error::Error;
+use std::fmt::Display;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
-// Simple XOR key for obfuscation (not encryption, just masking)
+// Single-byte XOR key used by the legacy masking format
 const MASK_KEY: u8 = 0x5A;
 
-fn get_secret_path(provider: &str) -> Result<PathBuf, Box<dyn Error>> {
+/// Prefix of encrypted secret files: magic, then a 12-byte nonce and the ciphertext.
+const SECRET_MAGIC: &[u8; 4] = b"RCK1";
+const NONCE_LEN: usize = 12;
+const MASTER_KEY_FILE: &str = "master.key";
+const KEYRING_SERVICE: &str = "relaycraft";
+const KEYRING_USER: &str = "api-key-master";
+
+static MASTER_KEY: OnceLock<[u8; 32]> = OnceLock::new();
+
+fn get_secrets_dir() -> Result<PathBuf, Box<dyn Error>> {
     let data_dir =
         crate::config::get_data_dir().map_err(|e| format!("Failed to get data dir: {}", e))?;
     let secrets_dir = data_dir.join("secrets");
@@ -21,11 +40,105 @@ fn get_secret_path(provider: &str) -> Result<PathBuf, Box<dyn Error>> {
     if !secrets_dir.exists() {
         std::fs::create_dir_all(&secrets_dir)?;
     }
+    Ok(secrets_dir)
+}
 
+fn get_secret_path(provider: &str) -> Result<PathBuf, Box<dyn Error>> {
     let filename = format!("{}.dat", provider);
-    Ok(secrets_dir.join(filename))
+    Ok(get_secrets_dir()?.join(filename))
+}
+
+/// Load the fallback master key file from `dir`, creating it on first use.
+fn load_or_create_master_key(dir: &Path) -> Result<[u8; 32], Box<dyn Error>> {
+    let path = dir.join(MASTER_KEY_FILE);
+    if path.exists() {
+        return decode_master_key(&fs::read(&path)?);
+    }
+
+    let key: [u8; 32] = Aes256Gcm::generate_key(OsRng).into();
+    fs::write(&path, key)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+    }
+    log::info!("[KeyStore] Generated new master key");
+    Ok(key)
+}
+
+fn decode_master_key(bytes: &[u8]) -> Result<[u8; 32], Box<dyn Error>> {
+    <[u8; 32]>::try_from(bytes).map_err(|_| "Master key is corrupted".into())
+}
+
+/// Create the master key in an empty OS keyring entry. A key left in `dir`
+/// by the file fallback is moved into the keyring so secrets sealed with it
+/// stay readable.
+fn create_keyring_master_key(
+    entry: &keyring::Entry,
+    dir: &Path,
+) -> Result<[u8; 32], Box<dyn Error>> {
+    let file = dir.join(MASTER_KEY_FILE);
+    let key = if file.exists() {
+        decode_master_key(&fs::read(&file)?)?
+    } else {
+        Aes256Gcm::generate_key(OsRng).into()
+    };
+    entry.set_password(&hex::encode(key))?;
+
+    // Only drop the file once the keyring has proven it can hand the key back
+    if decode_master_key(&hex::decode(entry.get_password()?)?)? != key {
+        return Err("OS keyring returned a different master key".into());
+    }
+    if file.exists() {
+        fs::remove_file(&file)?;
+        log::info!("[KeyStore] Moved master key into the OS keyring");
+    } else {
+        log::info!("[KeyStore] Generated new master key in the OS keyring");
+    }
+    Ok(key)
+}
+
+/// Fall back to the key file in `dir` because the keyring cannot be used.
+fn file_master_key(dir: &Path, reason: &dyn Display) -> Result<[u8; 32], Box<dyn Error>> {
+    log::warn!(
+        "[KeyStore] OS keyring unavailable ({}); keeping the master key in {:?}",
+        reason,
+        dir.join(MASTER_KEY_FILE)
+    );
+    load_or_create_master_key(dir)
+}
+
+/// Master key for this launch: the OS keyring, or the key file when no
+/// keyring is available.
+fn master_key() -> Result<[u8; 32], Box<dyn Error>> {
+    if let Some(key) = MASTER_KEY.get() {
+        return Ok(*key);
+    }
+
+    let dir = get_secrets_dir()?;
+    let key = match keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER) {
+        Ok(entry) => match entry.get_password() {
+            Ok(stored) => decode_master_key(&hex::decode(stored.trim())?)?,
+            Err(keyring::Error::NoEntry) => match create_keyring_master_key(&entry, &dir) {
+                Ok(key) => key,
+                Err(e) => file_master_key(&dir, &e)?,
+            },
+            // The keyring may hold the key existing secrets were sealed with;
+            // generating a new one here would make them unreadable
+            Err(e) if !dir.join(MASTER_KEY_FILE).exists() => {
+                return Err(
+                    format!("Could not read the master key from the OS keyring: {}", e).into(),
+                );
+            }
+            Err(e) => file_master_key(&dir, &e)?,
+        },
+        Err(e) => file_master_key(&dir, &e)?,
+    };
+    Ok(*MASTER_KEY.get_or_init(|| key))
 }
 
+/// Legacy XOR masking, only used to build fixtures for migration tests.
+#[cfg(test)]
 fn mask_data(data: &str) -> Vec<u8> {
     data.bytes().map(|b| b ^ MASK_KEY).collect()
 }
@@ -35,7 +148,60 @@ fn unmask_data(data: &[u8]) -> String {
     String::from_utf8(bytes).unwrap_or_default()
 }
 
-/// Store API key in local file (with XOR obfuscation)
+/// Encrypt `plaintext`, binding the ciphertext to `provider` as associated data.
+fn encrypt_secret(
+    master: &[u8; 32],
+    provider: &str,
+    plaintext: &str,
+) -> Result<Vec<u8>, Box<dyn Error>> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(master));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: plaintext.as_bytes(),
+                aad: provider.as_bytes(),
+            },
+        )
+        .map_err(|_| "Failed to encrypt API key")?;
+
+    let mut blob = Vec::with_capacity(SECRET_MAGIC.len() + NONCE_LEN + ciphertext.len());
+    blob.extend_from_slice(SECRET_MAGIC);
+    blob.extend_from_slice(&nonce);
+    blob.extend_from_slice(&ciphertext);
+    Ok(blob)
+}
+
+/// Decode a stored secret. Returns the key and whether it was in the legacy
+/// XOR format (and so should be re-encrypted).
+fn decode_secret(
+    master: &[u8; 32],
+    provider: &str,
+    blob: &[u8],
+) -> Result<(String, bool), Box<dyn Error>> {
+    let Some(rest) = blob.strip_prefix(SECRET_MAGIC.as_slice()) else {
+        return Ok((unmask_data(blob), true));
+    };
+    if rest.len() < NONCE_LEN {
+        return Err("API key file is truncated".into());
+    }
+
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(master));
+    let plaintext = cipher
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad: provider.as_bytes(),
+            },
+        )
+        .map_err(|_| "Failed to decrypt API key (wrong master key or tampered file)")?;
+    Ok((String::from_utf8(plaintext)?, false))
+}
+
+/// Store API key in local file (AES-256-GCM encrypted)
 pub fn store_api_key(provider: &str, key: &str) -> Result<(), Box<dyn Error>> {
     log::debug!(
         "[KeyStore] Storing API Key (Provider: {}, Key Len: {})",
@@ -43,9 +209,9 @@ pub fn store_api_key(provider: &str, key: &str) -> Result<(), Box<dyn Error>> {
         key.len()
     );
 
+    let master = master_key()?;
     let path = get_secret_path(provider)?;
-    let masked = mask_data(key);
-    fs::write(&path, masked)?;
+    fs::write(&path, encrypt_secret(&master, provider, key)?)?;
     log::info!(
         "[KeyStore] API Key stored successfully for provider: {}",
         provider
@@ -54,7 +220,7 @@ pub fn store_api_key(provider: &str, key: &str) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-/// Retrieve API key from local file
+/// Retrieve API key from local file, migrating legacy XOR files in place
 pub fn retrieve_api_key(provider: &str) -> Result<String, Box<dyn Error>> {
     log::debug!("[KeyStore] Retrieving API Key (Provider: {})", provider);
 
@@ -64,12 +230,29 @@ pub fn retrieve_api_key(provider: &str) -> Result<String, Box<dyn Error>> {
     }
 
     let bytes = fs::read(&path)?;
-    let key = unmask_data(&bytes);
+    let master = master_key()?;
+    let (key, legacy) = decode_secret(&master, provider, &bytes)?;
 
     if key.is_empty() {
         return Err(format!("API key file is empty for provider: {}", provider).into());
     }
 
+    if legacy {
+        match encrypt_secret(&master, provider, &key)
+            .and_then(|blob| fs::write(&path, blob).map_err(Into::into))
+        {
+            Ok(()) => log::info!(
+                "[KeyStore] Migrated legacy API key file to AES-GCM for provider: {}",
+                provider
+            ),
+            Err(e) => log::warn!(
+                "[KeyStore] Failed to migrate legacy API key for {}: {}",
+                provider,
+                e
+            ),
+        }
+    }
+
     log::debug!(
         "[KeyStore] API Key retrieved successfully for provider: {}",
         provider
@@ -78,7 +261,6 @@ pub fn retrieve_api_key(provider: &str) -> Result<String, Box<dyn Error>> {
 }
 
 /// Delete API key file
-#[allow(dead_code)]
 pub fn delete_api_key(provider: &str) -> Result<(), Box<dyn Error>> {
     let path = get_secret_path(provider)?;
     if path.exists() {
@@ -117,4 +299,46 @@ mod tests {
         let masked2 = mask_data(original);
         assert_eq!(masked1, masked2);
     }
+
+    #[test]
+    fn test_encrypt_round_trip() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let master = load_or_create_master_key(temp.path()).unwrap();
+        assert_eq!(master, load_or_create_master_key(temp.path()).unwrap());
+
+        let blob = encrypt_secret(&master, "openai", "sk-1234567890abcdef").unwrap();
+        assert!(blob.starts_with(SECRET_MAGIC));
+        assert!(!blob
+            .windows(b"sk-1234".len())
+            .any(|w| w == b"sk-1234".as_slice()));
+        // Fresh nonce per write
+        assert_ne!(
+            blob,
+            encrypt_secret(&master, "openai", "sk-1234567890abcdef").unwrap()
+        );
+
+        let (key, legacy) = decode_secret(&master, "openai", &blob).unwrap();
+        assert_eq!(key, "sk-1234567890abcdef");
+        assert!(!legacy);
+
+        // Bound to the provider and to the master key
+        assert!(decode_secret(&master, "anthropic", &blob).is_err());
+        assert!(decode_secret(&[7u8; 32], "openai", &blob).is_err());
+    }
+
+    #[test]
+    fn test_legacy_xor_blob_is_detected_for_migration() {
+        let master = [1u8; 32];
+        let legacy_blob = mask_data("sk-legacy-key");
+
+        let (key, legacy) = decode_secret(&master, "openai", &legacy_blob).unwrap();
+        assert_eq!(key, "sk-legacy-key");
+        assert!(legacy);
+
+        let migrated = encrypt_secret(&master, "openai", &key).unwrap();
+        assert_eq!(
+            decode_secret(&master, "openai", &migrated).unwrap(),
+            ("sk-legacy-key".to_string(), false)
+        );
+    }
 }
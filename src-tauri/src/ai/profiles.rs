@@ -38,6 +38,20 @@ static PROFILES: LazyLock<Vec<AIProviderProfile>> = LazyLock::new(|| {
                 tools: true,
             },
         },
+        AIProviderProfile {
+            id: "anthropic-default".to_string(),
+            provider_id: "anthropic".to_string(),
+            label: "Anthropic".to_string(),
+            adapter_mode: "anthropic".to_string(),
+            base_url: "https://api.anthropic.com/v1".to_string(),
+            default_model: "claude-sonnet-4-5".to_string(),
+            support_level: "verified".to_string(),
+            capabilities: ProfileCapabilities {
+                chat: true,
+                stream: true,
+                tools: false,
+            },
+        },
         AIProviderProfile {
             id: "openrouter-default".to_string(),
             provider_id: "openrouter".to_string(),
@@ -245,3 +259,39 @@ pub fn default_profile_for_provider(provider_id: &str) -> Option<AIProviderProfi
     }
     fallback
 }
+
+/// Guess the provider from the shape of an API key. Several providers share
+/// the plain `sk-` prefix, so this is only a suggestion for the UI.
+pub fn detect_provider_from_key(api_key: &str) -> Option<&'static str> {
+    let key = api_key.trim();
+    if key.is_empty() {
+        return None;
+    }
+
+    // More specific prefixes must come before the generic `sk-`
+    const PREFIXES: &[(&str, &str)] = &[
+        ("sk-or-", "openrouter"),
+        ("sk-ant-", "anthropic"),
+        ("sk-proj-", "openai"),
+        ("sk-svcacct-", "openai"),
+        ("gsk_", "groq"),
+        ("sk-", "openai"),
+    ];
+    if let Some((_, provider)) = PREFIXES.iter().find(|(prefix, _)| key.starts_with(prefix)) {
+        return Some(provider);
+    }
+
+    // MiniMax issues JWT keys
+    if key.starts_with("eyJ") && key.matches('.').count() == 2 {
+        return Some("minimax");
+    }
+
+    // Zhipu keys are `<32 hex id>.<secret>`
+    if let Some((id, secret)) = key.split_once('.') {
+        if id.len() == 32 && id.chars().all(|c| c.is_ascii_hexdigit()) && !secret.is_empty() {
+            return Some("zhipu");
+        }
+    }
+
+    None
+}
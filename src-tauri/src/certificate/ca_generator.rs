@@ -1,11 +1,20 @@
+use crate::config::CaKeyAlgorithm;
 use anyhow::Result as AnyResult;
 use rcgen::{
     BasicConstraints, CertificateParams, DistinguishedName, DnType, IsCa, KeyPair, KeyUsagePurpose,
-    PKCS_ECDSA_P256_SHA256,
+    RsaKeySize, PKCS_ECDSA_P256_SHA256, PKCS_RSA_SHA256,
 };
 use time::{Duration, OffsetDateTime};
 
-pub(super) fn generate_ca() -> AnyResult<(String, String)> {
+fn generate_key_pair(algorithm: CaKeyAlgorithm) -> Result<KeyPair, rcgen::Error> {
+    match algorithm {
+        CaKeyAlgorithm::EcdsaP256 => KeyPair::generate_for(&PKCS_ECDSA_P256_SHA256),
+        CaKeyAlgorithm::Rsa2048 => KeyPair::generate_rsa_for(&PKCS_RSA_SHA256, RsaKeySize::_2048),
+        CaKeyAlgorithm::Rsa4096 => KeyPair::generate_rsa_for(&PKCS_RSA_SHA256, RsaKeySize::_4096),
+    }
+}
+
+fn ca_params(validity_days: u32, now: OffsetDateTime) -> AnyResult<CertificateParams> {
     let mut params = CertificateParams::default();
 
     // Customize certificate info
@@ -35,12 +44,20 @@ pub(super) fn generate_ca() -> AnyResult<(String, String)> {
         rcgen::ExtendedKeyUsagePurpose::EmailProtection,
     ];
 
-    let key_pair = KeyPair::generate_for(&PKCS_ECDSA_P256_SHA256)?;
-
-    // Set validity to 10 years (3650 days)
-    let now = OffsetDateTime::now_utc();
     params.not_before = now;
-    params.not_after = now + Duration::days(3650);
+    params.not_after = now
+        .checked_add(Duration::days(i64::from(validity_days.max(1))))
+        .ok_or_else(|| anyhow::anyhow!("CA validity of {} days is too long", validity_days))?;
+    Ok(params)
+}
+
+/// Generate a self-signed root CA, returning `(cert_pem, key_pem)`.
+pub(super) fn generate_ca(
+    algorithm: CaKeyAlgorithm,
+    validity_days: u32,
+) -> AnyResult<(String, String)> {
+    let params = ca_params(validity_days, OffsetDateTime::now_utc())?;
+    let key_pair = generate_key_pair(algorithm)?;
 
     let cert = params.self_signed(&key_pair)?;
 
@@ -49,3 +66,30 @@ pub(super) fn generate_ca() -> AnyResult<(String, String)> {
 
     Ok((cert_pem, key_pem))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rsa_key_when_selected() {
+        let (cert, key) = generate_ca(CaKeyAlgorithm::Rsa2048, 30).unwrap();
+        assert!(cert.contains("BEGIN CERTIFICATE"));
+        let parsed = KeyPair::from_pem(&key).unwrap();
+        assert_eq!(parsed.algorithm(), &PKCS_RSA_SHA256);
+
+        let (_, key) = generate_ca(CaKeyAlgorithm::EcdsaP256, 30).unwrap();
+        let parsed = KeyPair::from_pem(&key).unwrap();
+        assert_eq!(parsed.algorithm(), &PKCS_ECDSA_P256_SHA256);
+    }
+
+    #[test]
+    fn test_not_after_matches_validity_days() {
+        let now = OffsetDateTime::now_utc();
+        let params = ca_params(397, now).unwrap();
+        assert_eq!(params.not_before, now);
+        assert_eq!(params.not_after - params.not_before, Duration::days(397));
+
+        assert!(ca_params(u32::MAX, now).is_err());
+    }
+}
@@ -5,6 +5,278 @@ use std::process::Command;
 
 pub struct LinuxCertManager;
 
+/// Directory of `<subject hash>.N` links maintained by `update-ca-certificates`
+const HASHED_CERT_DIR: &str = "/etc/ssl/certs";
+
+const TRUST_BUNDLES: &[&str] = &[
+    "/etc/ssl/certs/ca-certificates.crt", // Debian, Ubuntu, Gentoo, Arch
+    "/etc/pki/tls/certs/ca-bundle.crt",   // RedHat, Fedora, CentOS
+    "/etc/pki/ca-trust/extracted/pem/tls-ca-bundle.pem", // Newer RHEL/Fedora
+    "/etc/ssl/ca-bundle.pem",             // OpenSUSE
+];
+
+/// File name of the installed anchor. `update-ca-certificates` only picks up `.crt`.
+const ANCHOR_FILE: &str = "relaycraft-ca-cert.crt";
+/// Anchor name used by older versions, still cleaned up on removal
+const LEGACY_ANCHOR_FILE: &str = "relaycraft-ca.crt";
+
+/// Where a distribution keeps local CA anchors and the tool that rebuilds
+/// the system bundle from them.
+#[derive(Debug, PartialEq)]
+struct TrustStore {
+    anchor_dir: &'static str,
+    update_tool: &'static str,
+}
+
+const TRUST_STORES: &[TrustStore] = &[
+    // Debian, Ubuntu, Alpine
+    TrustStore {
+        anchor_dir: "/usr/local/share/ca-certificates",
+        update_tool: "update-ca-certificates",
+    },
+    // openSUSE
+    TrustStore {
+        anchor_dir: "/etc/pki/trust/anchors",
+        update_tool: "update-ca-certificates",
+    },
+    // RHEL, Fedora, CentOS
+    TrustStore {
+        anchor_dir: "/etc/pki/ca-trust/source/anchors",
+        update_tool: "update-ca-trust",
+    },
+    // Arch
+    TrustStore {
+        anchor_dir: "/etc/ca-certificates/trust-source/anchors",
+        update_tool: "update-ca-trust",
+    },
+];
+
+/// Pick the trust store for this system: the first one whose tool is installed,
+/// preferring a store whose anchor directory already exists.
+fn detect_trust_store(
+    has_tool: &dyn Fn(&str) -> bool,
+    dir_exists: &dyn Fn(&str) -> bool,
+) -> Option<&'static TrustStore> {
+    let available: Vec<&TrustStore> = TRUST_STORES
+        .iter()
+        .filter(|store| has_tool(store.update_tool))
+        .collect();
+    available
+        .iter()
+        .find(|store| dir_exists(store.anchor_dir))
+        .or(available.first())
+        .copied()
+}
+
+/// Locate an executable on PATH, also checking the sbin directories that
+/// desktop sessions often leave out.
+fn find_tool(name: &str) -> Option<std::path::PathBuf> {
+    let path = std::env::var_os("PATH").unwrap_or_default();
+    std::env::split_paths(&path)
+        .chain(["/usr/local/sbin", "/usr/sbin", "/sbin", "/usr/bin", "/bin"].map(Into::into))
+        .map(|dir| dir.join(name))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Run a `sh -c` script as root, through pkexec's graphical prompt unless we
+/// already are root. Arguments are passed positionally, never interpolated.
+fn run_privileged(script: &str, args: &[&str]) -> Result<(), String> {
+    let is_root = unsafe { libc::geteuid() == 0 };
+    let mut command = if is_root {
+        Command::new("sh")
+    } else {
+        if find_tool("pkexec").is_none() {
+            return Err(
+                "MANUAL_STEP: pkexec is not available to request administrator rights".to_string(),
+            );
+        }
+        let mut command = Command::new("pkexec");
+        command.arg("sh");
+        command
+    };
+    let status = command
+        .args(["-c", script, "sh"])
+        .args(args)
+        .status()
+        .map_err(|e| format!("Failed to run trust store update: {}", e))?;
+
+    match status.code() {
+        Some(0) => Ok(()),
+        // pkexec: 126 = dialog dismissed, 127 = not authorized
+        Some(126) | Some(127) if !is_root => {
+            Err("User cancelled authorization or installation failed".to_string())
+        }
+        code => Err(format!(
+            "Trust store update failed (exit code {})",
+            code.map_or("unknown".to_string(), |c| c.to_string())
+        )),
+    }
+}
+
+/// DER bytes of every certificate in a PEM file or bundle
+fn pem_certificates(pem_text: &str) -> Vec<Vec<u8>> {
+    pem::parse_many(pem_text)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|block| block.tag() == "CERTIFICATE")
+        .map(pem::Pem::into_contents)
+        .collect()
+}
+
+fn sha256_fingerprint(der: &[u8]) -> Vec<u8> {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(der).to_vec()
+}
+
+/// Whether any certificate in `bundle` has the given SHA-256 fingerprint
+fn bundle_contains(bundle: &str, fingerprint: &[u8]) -> bool {
+    pem_certificates(bundle)
+        .iter()
+        .any(|der| sha256_fingerprint(der) == fingerprint)
+}
+
+/// Read one DER element at `pos`: (tag, content start, content end)
+fn der_element(der: &[u8], pos: usize) -> Option<(u8, usize, usize)> {
+    let tag = *der.get(pos)?;
+    let first = *der.get(pos + 1)? as usize;
+    let (len, start) = if first & 0x80 == 0 {
+        (first, pos + 2)
+    } else {
+        let count = first & 0x7f;
+        if count == 0 || count > 4 {
+            return None;
+        }
+        let bytes = der.get(pos + 2..pos + 2 + count)?;
+        let len = bytes.iter().fold(0usize, |acc, b| (acc << 8) | *b as usize);
+        (len, pos + 2 + count)
+    };
+    let end = start.checked_add(len)?;
+    (end <= der.len()).then_some((tag, start, end))
+}
+
+fn der_encode(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut out = vec![tag];
+    let len = content.len();
+    if len < 0x80 {
+        out.push(len as u8);
+    } else {
+        let bytes: Vec<u8> = len
+            .to_be_bytes()
+            .into_iter()
+            .skip_while(|b| *b == 0)
+            .collect();
+        out.push(0x80 | bytes.len() as u8);
+        out.extend_from_slice(&bytes);
+    }
+    out.extend_from_slice(content);
+    out
+}
+
+/// Decode an ASN.1 string value OpenSSL canonicalizes, by tag
+fn asn1_string_text(tag: u8, bytes: &[u8]) -> Option<String> {
+    match tag {
+        0x0c => Some(String::from_utf8_lossy(bytes).into_owned()), // UTF8String
+        // Printable, T61, IA5, Visible: single-byte
+        0x13 | 0x14 | 0x16 | 0x1a => Some(bytes.iter().map(|b| *b as char).collect()),
+        0x1e => Some(String::from_utf16_lossy(
+            &bytes
+                .chunks_exact(2)
+                .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                .collect::<Vec<_>>(),
+        )), // BMPString
+        0x1c => Some(
+            bytes
+                .chunks_exact(4)
+                .filter_map(|c| char::from_u32(u32::from_be_bytes([c[0], c[1], c[2], c[3]])))
+                .collect(),
+        ), // UniversalString
+        _ => None,
+    }
+}
+
+/// OpenSSL's canonical form: trimmed, inner whitespace collapsed, ASCII lowercased
+fn canonical_text(text: &str) -> String {
+    let is_space = |c: char| matches!(c, ' ' | '\t' | '\n' | '\x0b' | '\x0c' | '\r');
+    let mut out = String::with_capacity(text.len());
+    let mut in_space = false;
+    for c in text.trim_matches(is_space).chars() {
+        if is_space(c) {
+            if !in_space {
+                out.push(' ');
+            }
+            in_space = true;
+        } else {
+            out.push(c.to_ascii_lowercase());
+            in_space = false;
+        }
+    }
+    out
+}
+
+/// Subject hash as computed by `openssl x509 -subject_hash` and `c_rehash`:
+/// SHA-1 over the canonically encoded subject RDNs, first 4 bytes little-endian.
+fn subject_hash(cert_der: &[u8]) -> Option<String> {
+    use sha1::{Digest, Sha1};
+
+    let (_, cert, _) = der_element(cert_der, 0)?;
+    let (_, tbs, _) = der_element(cert_der, cert)?;
+    let mut pos = tbs;
+    let (tag, _, end) = der_element(cert_der, pos)?;
+    if tag == 0xa0 {
+        pos = end; // explicit version
+    }
+    // serial, signature algorithm, issuer, validity
+    for _ in 0..4 {
+        pos = der_element(cert_der, pos)?.2;
+    }
+    let (_, subject, subject_end) = der_element(cert_der, pos)?;
+
+    let mut canonical = Vec::new();
+    let mut rdn_pos = subject;
+    while rdn_pos < subject_end {
+        let (_, set_start, set_end) = der_element(cert_der, rdn_pos)?;
+        let mut set = Vec::new();
+        let mut attr_pos = set_start;
+        while attr_pos < set_end {
+            let (_, attr_start, attr_end) = der_element(cert_der, attr_pos)?;
+            let (_, _, oid_end) = der_element(cert_der, attr_start)?;
+            let (value_tag, value_start, value_end) = der_element(cert_der, oid_end)?;
+            let mut attr = cert_der[attr_start..oid_end].to_vec();
+            match asn1_string_text(value_tag, &cert_der[value_start..value_end]) {
+                Some(text) => attr.extend(der_encode(0x0c, canonical_text(&text).as_bytes())),
+                None => attr.extend_from_slice(&cert_der[oid_end..value_end]),
+            }
+            set.extend(der_encode(0x30, &attr));
+            attr_pos = attr_end;
+        }
+        canonical.extend(der_encode(0x31, &set));
+        rdn_pos = set_end;
+    }
+
+    let digest = Sha1::digest(&canonical);
+    Some(format!(
+        "{:08x}",
+        u32::from_le_bytes([digest[0], digest[1], digest[2], digest[3]])
+    ))
+}
+
+/// Whether a `<subject hash>.N` entry in `dir` holds exactly this certificate
+fn hashed_link_matches(dir: &Path, cert_der: &[u8]) -> bool {
+    let Some(hash) = subject_hash(cert_der) else {
+        return false;
+    };
+    let fingerprint = sha256_fingerprint(cert_der);
+    // Hash collisions get increasing suffixes
+    (0..10)
+        .map(|n| dir.join(format!("{}.{}", hash, n)))
+        .take_while(|path| path.exists())
+        .any(|path| {
+            fs::read_to_string(&path)
+                .map(|pem_text| bundle_contains(&pem_text, &fingerprint))
+                .unwrap_or(false)
+        })
+}
+
 impl CertManager for LinuxCertManager {
     fn open_cert_dir(&self, cert_dir: &Path) -> Result<(), String> {
         Command::new("xdg-open")
@@ -14,155 +286,83 @@ impl CertManager for LinuxCertManager {
         Ok(())
     }
 
-    fn is_installed(&self, _cert_path: &str) -> Result<bool, String> {
-        let local_hash = super::local_cert_hash().unwrap_or_default().to_uppercase();
-        if local_hash.is_empty() {
+    fn is_installed(&self, cert_path: &str) -> Result<bool, String> {
+        let Some(local_der) = fs::read_to_string(cert_path)
+            .ok()
+            .and_then(|pem| pem_certificates(&pem).into_iter().next())
+        else {
             return Ok(false);
-        }
+        };
 
-        let common_bundles = [
-            "/etc/ssl/certs/ca-certificates.crt", // Debian, Ubuntu, Gentoo, Arch
-            "/etc/pki/tls/certs/ca-bundle.crt",   // RedHat, Fedora, CentOS
-            "/etc/pki/ca-trust/extracted/pem/tls-ca-bundle.pem", // Newer RHEL/Fedora
-            "/etc/ssl/ca-bundle.pem",             // OpenSUSE
-        ];
-
-        for bundle_path in &common_bundles {
-            if let Ok(bundle) = fs::read_to_string(bundle_path) {
-                let cert_path = super::get_cert_path()?;
-                if let Ok(local_content) = fs::read_to_string(&cert_path) {
-                    if bundle.contains(local_content.trim()) {
-                        return Ok(true);
-                    }
-                }
-            }
+        // `update-ca-certificates` / `c_rehash` link each trusted CA as <subject hash>.N
+        if hashed_link_matches(Path::new(HASHED_CERT_DIR), &local_der) {
+            return Ok(true);
         }
 
-        // Also check hashed directory /etc/ssl/certs
-        let hashed_dir = "/etc/ssl/certs";
-        if Path::new(hashed_dir).exists() {
-            let possible_locations = [
-                "/etc/ssl/certs/RelayCraft_CA.pem",
-                "/usr/local/share/ca-certificates/RelayCraft_CA.crt",
-                "/etc/pki/ca-trust/source/anchors/RelayCraft_CA.crt",
-            ];
-            for path in &possible_locations {
-                if Path::new(path).exists() {
-                    return Ok(true);
-                }
-            }
-        }
-
-        Ok(false)
+        let fingerprint = sha256_fingerprint(&local_der);
+        Ok(TRUST_BUNDLES.iter().any(|bundle_path| {
+            fs::read_to_string(bundle_path)
+                .map(|bundle| bundle_contains(&bundle, &fingerprint))
+                .unwrap_or(false)
+        }))
     }
 
     fn install(&self, cert_path: &str) -> Result<(), String> {
-        let is_debian = Path::new("/usr/local/share/ca-certificates").exists();
-        let is_rhel = Path::new("/etc/pki/ca-trust/source/anchors").exists();
-
-        if is_debian {
-            let dest = "/usr/local/share/ca-certificates/relaycraft-ca.crt";
-            let is_root = unsafe { libc::geteuid() == 0 };
-
-            if is_root {
-                std::fs::copy(cert_path, dest).map_err(|e| e.to_string())?;
-                Command::new("update-ca-certificates")
-                    .output()
-                    .map_err(|e| e.to_string())?;
-            } else {
-                let status = Command::new("pkexec")
-                    .args([
-                        "sh",
-                        "-c",
-                        &format!("cp '{}' '{}' && update-ca-certificates", cert_path, dest),
-                    ])
-                    .status()
-                    .map_err(|e| format!("Failed to execute pkexec: {}", e))?;
-
-                if !status.success() {
-                    return Err("User cancelled authorization or installation failed".to_string());
-                }
-            }
-        } else if is_rhel {
-            let dest = "/etc/pki/ca-trust/source/anchors/relaycraft-ca.crt";
-            let is_root = unsafe { libc::geteuid() == 0 };
-
-            if is_root {
-                std::fs::copy(cert_path, dest).map_err(|e| e.to_string())?;
-                Command::new("update-ca-trust")
-                    .output()
-                    .map_err(|e| e.to_string())?;
-            } else {
-                let status = Command::new("pkexec")
-                    .args([
-                        "sh",
-                        "-c",
-                        &format!("cp '{}' '{}' && update-ca-trust", cert_path, dest),
-                    ])
-                    .status()
-                    .map_err(|e| format!("Failed to execute pkexec: {}", e))?;
-
-                if !status.success() {
-                    return Err("User cancelled authorization or installation failed".to_string());
-                }
-            }
-        } else {
-            return Err("MANUAL_STEP".to_string());
-        }
+        let Some(store) = detect_trust_store(&|tool| find_tool(tool).is_some(), &|dir| {
+            Path::new(dir).is_dir()
+        }) else {
+            let guidance = format!(
+                "MANUAL_STEP: no supported trust tool found. Copy {} into your distribution's CA anchor directory as {} and rebuild the trust store",
+                cert_path, ANCHOR_FILE
+            );
+            log::warn!("{}", guidance);
+            return Err(guidance);
+        };
+
+        let dest = format!("{}/{}", store.anchor_dir, ANCHOR_FILE);
+        run_privileged(
+            r#"mkdir -p "$1" && cp "$2" "$1/$3" && chmod 644 "$1/$3" && "$4""#,
+            &[store.anchor_dir, cert_path, ANCHOR_FILE, store.update_tool],
+        )?;
+        let _ = crate::logging::write_domain_log(
+            "audit",
+            &format!("Installed CA into {} via {}", dest, store.update_tool),
+        );
         Ok(())
     }
 
     fn remove(&self) -> Result<(), String> {
-        let is_debian = Path::new("/usr/local/share/ca-certificates").exists();
-        let is_rhel = Path::new("/etc/pki/ca-trust/source/anchors").exists();
-
-        if is_debian {
-            let target = "/usr/local/share/ca-certificates/relaycraft-ca.crt";
-            if Path::new(target).exists() {
-                let is_root = unsafe { libc::geteuid() == 0 };
-                if is_root {
-                    std::fs::remove_file(target).map_err(|e| e.to_string())?;
-                    Command::new("update-ca-certificates")
-                        .output()
-                        .map_err(|e| e.to_string())?;
-                } else {
-                    let status = Command::new("pkexec")
-                        .args([
-                            "sh",
-                            "-c",
-                            &format!("rm -f '{}' && update-ca-certificates", target),
-                        ])
-                        .status()
-                        .map_err(|e| e.to_string())?;
-                    if !status.success() {
-                        return Err("Certificate removal failed".to_string());
-                    }
-                }
-            }
-        } else if is_rhel {
-            let target = "/etc/pki/ca-trust/source/anchors/relaycraft-ca.crt";
-            if Path::new(target).exists() {
-                let is_root = unsafe { libc::geteuid() == 0 };
-                if is_root {
-                    std::fs::remove_file(target).map_err(|e| e.to_string())?;
-                    Command::new("update-ca-trust")
-                        .output()
-                        .map_err(|e| e.to_string())?;
-                } else {
-                    let status = Command::new("pkexec")
-                        .args([
-                            "sh",
-                            "-c",
-                            &format!("rm -f '{}' && update-ca-trust", target),
-                        ])
-                        .status()
-                        .map_err(|e| e.to_string())?;
-                    if !status.success() {
-                        return Err("Certificate removal failed".to_string());
-                    }
-                }
-            }
+        let Some(store) = detect_trust_store(&|tool| find_tool(tool).is_some(), &|dir| {
+            Path::new(dir).is_dir()
+        }) else {
+            return Err("MANUAL_STEP".to_string());
+        };
+        let installed: Vec<&str> = [ANCHOR_FILE, LEGACY_ANCHOR_FILE]
+            .into_iter()
+            .filter(|name| Path::new(store.anchor_dir).join(name).exists())
+            .collect();
+        if installed.is_empty() {
+            return Ok(());
         }
+
+        run_privileged(
+            r#"cd "$1" && rm -f "$2" "$3" && "$4""#,
+            &[
+                store.anchor_dir,
+                ANCHOR_FILE,
+                LEGACY_ANCHOR_FILE,
+                store.update_tool,
+            ],
+        )?;
+        let _ = crate::logging::write_domain_log(
+            "audit",
+            &format!(
+                "Removed CA ({}) from {} via {}",
+                installed.join(", "),
+                store.anchor_dir,
+                store.update_tool
+            ),
+        );
         Ok(())
     }
 
@@ -170,3 +370,97 @@ impl CertManager for LinuxCertManager {
         super::cert_info_via_openssl(cert_path)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Self-signed test CA, subject "CN=RelayCraft  Test CA, O=Relay Craft"
+    /// (double space on purpose). `openssl x509 -subject_hash` gives 4f2c5397.
+    const KNOWN_CERT: &str = "-----BEGIN CERTIFICATE-----
+MIIBvDCCAWOgAwIBAgIUCr/s8kaNYubWOj15yS64W4qgQB4wCgYIKoZIzj0EAwIw
+NDEcMBoGA1UEAwwTUmVsYXlDcmFmdCAgVGVzdCBDQTEUMBIGA1UECgwLUmVsYXkg
+Q3JhZnQwHhcNMjYxMDE2MTExMTAzWhcNMzYxMDEzMTExMTAzWjA0MRwwGgYDVQQD
+DBNSZWxheUNyYWZ0ICBUZXN0IENBMRQwEgYDVQQKDAtSZWxheSBDcmFmdDBZMBMG
+ByqGSM49AgEGCCqGSM49AwEHA0IABJBZPe5vuKfnCXyMFG21I0EB2TV9unL3DQ/f
+FvAyXu4jxSGxfVWnw0wk/vjodHJ8cEQQG3SoKZ6aUdQgjL9Lq7ujUzBRMB0GA1Ud
+DgQWBBRZtcKRbSYZCSPZKGnhGwY4BPvq+jAfBgNVHSMEGDAWgBRZtcKRbSYZCSPZ
+KGnhGwY4BPvq+jAPBgNVHRMBAf8EBTADAQH/MAoGCCqGSM49BAMCA0cAMEQCIDMg
+5XBUPYtyUomZQjby1BBj+N5Kvgj449rcGxXG1LuBAiBrDUIJxtzv5/ioPZQi7uol
+MmOziPt+pWdYfAp8MrMBKw==
+-----END CERTIFICATE-----
+";
+
+    fn known_der() -> Vec<u8> {
+        pem_certificates(KNOWN_CERT).remove(0)
+    }
+
+    #[test]
+    fn test_subject_hash_matches_openssl() {
+        assert_eq!(subject_hash(&known_der()).as_deref(), Some("4f2c5397"));
+        assert_eq!(
+            canonical_text("  RelayCraft \t Test\nCA "),
+            "relaycraft test ca"
+        );
+    }
+
+    #[test]
+    fn test_bundle_fingerprint_comparison() {
+        let der = known_der();
+        let fingerprint = sha256_fingerprint(&der);
+        let other = super::super::ca_generator::generate_ca(Default::default(), 30)
+            .unwrap()
+            .0;
+
+        // Differently wrapped PEM still matches; substring matching would not
+        let rewrapped = pem::encode_config(
+            &pem::Pem::new("CERTIFICATE", der.clone()),
+            pem::EncodeConfig::new().set_line_wrap(76),
+        );
+        assert!(bundle_contains(
+            &format!("{}\n{}", other, rewrapped),
+            &fingerprint
+        ));
+        assert!(!bundle_contains(&other, &fingerprint));
+        assert!(!bundle_contains("not a bundle", &fingerprint));
+    }
+
+    #[test]
+    fn test_detect_trust_store_by_tool() {
+        let none = detect_trust_store(&|_| false, &|_| true);
+        assert_eq!(none, None);
+
+        // Fedora: only update-ca-trust, RHEL anchor dir present
+        let fedora = detect_trust_store(&|tool| tool == "update-ca-trust", &|dir| {
+            dir == "/etc/pki/ca-trust/source/anchors"
+        })
+        .unwrap();
+        assert_eq!(fedora.anchor_dir, "/etc/pki/ca-trust/source/anchors");
+
+        // Arch: same tool, different anchor dir
+        let arch = detect_trust_store(&|tool| tool == "update-ca-trust", &|dir| {
+            dir == "/etc/ca-certificates/trust-source/anchors"
+        })
+        .unwrap();
+        assert_eq!(arch.anchor_dir, "/etc/ca-certificates/trust-source/anchors");
+
+        // Tool present but no anchor dir yet: first store for that tool
+        let fresh =
+            detect_trust_store(&|tool| tool == "update-ca-certificates", &|_| false).unwrap();
+        assert_eq!(fresh.anchor_dir, "/usr/local/share/ca-certificates");
+    }
+
+    #[test]
+    fn test_hashed_link_lookup() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let der = known_der();
+        assert!(!hashed_link_matches(temp.path(), &der));
+
+        let other = super::super::ca_generator::generate_ca(Default::default(), 30)
+            .unwrap()
+            .0;
+        fs::write(temp.path().join("4f2c5397.0"), other).unwrap();
+        fs::write(temp.path().join("4f2c5397.1"), KNOWN_CERT).unwrap();
+        assert!(hashed_link_matches(temp.path(), &der));
+    }
+}
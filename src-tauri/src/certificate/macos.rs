@@ -66,4 +66,15 @@ impl CertManager for MacOsCertManager {
     fn get_cert_info(&self, cert_path: &str) -> Result<super::DetailedCertInfo, String> {
         super::cert_info_via_openssl(cert_path)
     }
+
+    fn refresh_trust_cache(&self) -> Vec<String> {
+        // The per-user trustd is relaunched on demand by launchd and re-reads
+        // trust settings when it starts.
+        match Command::new("killall").arg("trustd").output() {
+            Ok(out) if out.status.success() => {
+                vec!["Restarted the user trust daemon (trustd)".to_string()]
+            }
+            _ => Vec::new(),
+        }
+    }
 }
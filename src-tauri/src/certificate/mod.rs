@@ -8,6 +8,9 @@ mod ca_generator;
 mod linux;
 #[cfg(target_os = "macos")]
 mod macos;
+mod pkcs12;
+mod share;
+pub mod trust_cache;
 #[cfg(target_os = "windows")]
 mod windows;
 
@@ -32,6 +35,10 @@ pub trait CertManager {
     fn install(&self, cert_path: &str) -> Result<(), String>;
     fn remove(&self) -> Result<(), String>;
     fn get_cert_info(&self, cert_path: &str) -> Result<DetailedCertInfo, String>;
+    /// Ask the OS to drop cached trust decisions. Returns the actions taken.
+    fn refresh_trust_cache(&self) -> Vec<String> {
+        Vec::new()
+    }
 }
 
 #[cfg(target_os = "macos")]
@@ -103,7 +110,7 @@ pub fn get_cert_path() -> Result<String, String> {
 }
 
 /// SHA-1 fingerprint of the local RelayCraft CA certificate.
-#[cfg(any(target_os = "windows", target_os = "linux"))]
+#[cfg(target_os = "windows")]
 fn local_cert_hash() -> Result<String, String> {
     use base64::prelude::*;
     use sha1::{Digest, Sha1};
@@ -185,7 +192,44 @@ fn cert_info_via_openssl(cert_path: &str) -> Result<DetailedCertInfo, String> {
     Ok(info)
 }
 
-pub fn ensure_ca_exists(cert_dir: &Path) -> AnyResult<()> {
+/// Files in the cert directory that contain the CA private key.
+const PRIVATE_KEY_FILES: &[&str] = &[
+    "mitmproxy-ca.pem",
+    "relaycraft-ca.pem",
+    "mitmproxy-ca.p12",
+    PKCS12_FILE,
+];
+
+/// PKCS#12 bundle written by `export_cert_pkcs12`
+const PKCS12_FILE: &str = "relaycraft-ca.p12";
+
+/// Owner-only permission bits for files holding the CA private key.
+#[cfg(unix)]
+const PRIVATE_KEY_MODE: u32 = 0o600;
+
+/// True when neither group nor others have any access.
+fn mode_is_private(mode: u32) -> bool {
+    mode & 0o077 == 0
+}
+
+#[cfg(unix)]
+fn restrict_key_permissions(cert_dir: &Path) {
+    use std::os::unix::fs::PermissionsExt;
+
+    for name in PRIVATE_KEY_FILES {
+        let path = cert_dir.join(name);
+        if !path.exists() {
+            continue;
+        }
+        if let Err(e) = fs::set_permissions(&path, fs::Permissions::from_mode(PRIVATE_KEY_MODE)) {
+            log::warn!("Failed to restrict permissions on {:?}: {}", path, e);
+        }
+    }
+}
+
+/// Create the CA files when missing. `config` supplies the key algorithm and
+/// validity of a newly generated CA; an existing CA is left untouched.
+pub fn ensure_ca_exists(cert_dir: &Path, config: &crate::config::AppConfig) -> AnyResult<()> {
     if !cert_dir.exists() {
         fs::create_dir_all(cert_dir).context("Failed to create certificate directory")?;
     }
@@ -197,7 +241,8 @@ pub fn ensure_ca_exists(cert_dir: &Path) -> AnyResult<()> {
 
     if !ca_pem_path.exists() || !branded_cert_path.exists() {
         log::info!("Generating RelayCraft CA certificate...");
-        let (ca_cert, ca_key) = generate_ca().context("Failed to generate CA certificate")?;
+        let (ca_cert, ca_key) = generate_ca(config.ca_key_algorithm, config.ca_validity_days)
+            .context("Failed to generate CA certificate")?;
 
         let combined = format!("{}\n{}", ca_key, ca_cert);
 
@@ -220,6 +265,10 @@ pub fn ensure_ca_exists(cert_dir: &Path) -> AnyResult<()> {
         let _ = fs::remove_dir_all(legacy_nested);
     }
 
+    // Also tightens files written by older versions with default permissions
+    #[cfg(unix)]
+    restrict_key_permissions(cert_dir);
+
     #[cfg(target_os = "windows")]
     {
         windows::hide_internal_files(&[
@@ -246,12 +295,136 @@ pub fn open_cert_dir() -> Result<(), String> {
     platform().open_cert_dir(&cert_dir)
 }
 
+/// Bundle the root CA and its key into a password-protected `.p12` in the
+/// cert dir, for MDM profiles and import wizards. An empty password is
+/// allowed and encoded as an empty BMPString, as PKCS#12 specifies.
+/// Returns the path of the written file.
+#[tauri::command]
+pub fn export_cert_pkcs12(password: String) -> Result<String, String> {
+    let cert_dir = get_cert_dir()?;
+    let ca_pem = fs::read_to_string(cert_dir.join("mitmproxy-ca.pem"))
+        .map_err(|e| format!("Failed to read CA: {}", e))?;
+    let der = pkcs12::build_pkcs12(&ca_pem, &password)?;
+
+    let path = cert_dir.join(PKCS12_FILE);
+    fs::write(&path, der).map_err(|e| format!("Failed to write {}: {}", PKCS12_FILE, e))?;
+    #[cfg(unix)]
+    restrict_key_permissions(&cert_dir);
+
+    let _ = logging::write_domain_log("audit", "Exported CA as PKCS#12");
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// How long a mobile cert share stays up without a download, by default
+const CERT_SHARE_DEFAULT_TIMEOUT_SECS: u64 = 300;
+const CERT_SHARE_MAX_TIMEOUT_SECS: u64 = 3600;
+
+#[derive(serde::Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CertShareInfo {
+    /// LAN URL of the certificate download
+    pub url: String,
+    /// QR code for `url`, PNG encoded as base64
+    pub qr_png_base64: String,
+    pub expires_in_secs: u64,
+}
+
+/// Serve `relaycraft-ca-cert.crt` on the LAN for phones to download. The
+/// server stops after the first download or `timeout_secs` (default 5 min).
+#[tauri::command]
+pub fn start_cert_share(timeout_secs: Option<u64>) -> Result<CertShareInfo, String> {
+    use base64::prelude::*;
+
+    let cert_der = fs::read(get_cert_dir()?.join("relaycraft-ca-cert.crt"))
+        .map_err(|e| format!("Failed to read CA certificate: {}", e))?;
+    let timeout = timeout_secs
+        .unwrap_or(CERT_SHARE_DEFAULT_TIMEOUT_SECS)
+        .clamp(1, CERT_SHARE_MAX_TIMEOUT_SECS);
+
+    let listener = std::net::TcpListener::bind("0.0.0.0:0")
+        .map_err(|e| format!("Failed to start cert share server: {}", e))?;
+    let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+    let url = format!(
+        "http://{}:{}{}",
+        crate::common::utils::get_local_ip(),
+        port,
+        share::CERT_PATH
+    );
+    let qr = share::qr_png(&url)?;
+
+    std::thread::Builder::new()
+        .name("rc-cert-share".into())
+        .spawn(move || {
+            share::serve_cert_once(listener, cert_der, std::time::Duration::from_secs(timeout))
+        })
+        .map_err(|e| format!("Failed to start cert share server: {}", e))?;
+
+    let _ = logging::write_domain_log("audit", &format!("Started mobile cert share at {}", url));
+    Ok(CertShareInfo {
+        url,
+        qr_png_base64: BASE64_STANDARD.encode(qr),
+        expires_in_secs: timeout,
+    })
+}
+
 #[tauri::command]
 pub async fn check_cert_installed() -> Result<bool, String> {
     let cert_path = get_cert_path()?;
     platform().is_installed(&cert_path)
 }
 
+/// Delay between installation checks while polling
+const CERT_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Re-check installation status until the certificate is trusted or `timeout_secs`
+/// elapses, pushing each result to `on_status`. Meant for the macOS manual trust
+/// step, where the app cannot observe when the user finishes in Keychain Access.
+/// Returns the final status.
+#[tauri::command]
+pub async fn poll_cert_installed(
+    timeout_secs: u64,
+    on_status: tauri::ipc::Channel<bool>,
+) -> Result<bool, String> {
+    let cert_path = get_cert_path()?;
+    let timeout = std::time::Duration::from_secs(timeout_secs);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let started = std::time::Instant::now();
+        loop {
+            let installed = platform().is_installed(&cert_path).unwrap_or(false);
+            if on_status.send(installed).is_err() {
+                // The UI stopped listening; report what we know
+                return installed;
+            }
+            if installed || started.elapsed() >= timeout {
+                return installed;
+            }
+            std::thread::sleep(CERT_POLL_INTERVAL);
+        }
+    })
+    .await
+    .map_err(|e| format!("Certificate polling task failed: {}", e))
+}
+
+/// Nudge the OS trust cache after installing the CA and report browsers that
+/// need a restart or a separate import before they trust it.
+#[tauri::command]
+pub async fn refresh_trust_cache() -> Result<trust_cache::TrustCacheRefresh, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let actions = platform().refresh_trust_cache();
+        let browsers =
+            trust_cache::browser_notes(std::env::consts::OS, &trust_cache::detect_browsers());
+        let restart_required = browsers.iter().any(|b| b.running);
+        trust_cache::TrustCacheRefresh {
+            actions,
+            browsers,
+            restart_required,
+        }
+    })
+    .await
+    .map_err(|e| format!("Trust cache refresh failed: {}", e))
+}
+
 #[tauri::command]
 pub async fn install_cert_automated() -> Result<(), String> {
     let _ = logging::write_domain_log("audit", "Triggered automated certificate installation");
@@ -265,9 +438,61 @@ pub async fn remove_cert_automated() -> Result<(), String> {
     platform().remove()
 }
 
+/// Outcome of regenerating the root CA.
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct CaRegenerateResult {
+    /// True if a running engine was restarted to pick up the new CA
+    pub engine_restarted: bool,
+    /// True if the engine is still serving leaf certs from the old CA and
+    /// must be restarted before clients will trust it again
+    pub restart_required: bool,
+    pub warning: Option<String>,
+}
+
+/// Decide what to tell the caller after the CA files were replaced. A failed
+/// restart is a warning rather than an error: the new CA is already on disk.
+fn regenerate_outcome(
+    engine_running: bool,
+    restart: impl FnOnce() -> Result<(), String>,
+) -> CaRegenerateResult {
+    if !engine_running {
+        return CaRegenerateResult {
+            engine_restarted: false,
+            restart_required: false,
+            warning: None,
+        };
+    }
+
+    match restart() {
+        Ok(()) => CaRegenerateResult {
+            engine_restarted: true,
+            restart_required: false,
+            warning: Some(
+                "The proxy engine was restarted to use the new CA; existing connections were reset"
+                    .to_string(),
+            ),
+        },
+        Err(e) => CaRegenerateResult {
+            engine_restarted: false,
+            restart_required: true,
+            warning: Some(format!(
+                "The proxy engine is still using the old CA and must be restarted: {}",
+                e
+            )),
+        },
+    }
+}
+
+/// Generate a fresh root CA. The engine loads its CA once at spawn, so a
+/// running engine is restarted to avoid serving leaf certs signed by the
+/// old key.
 #[tauri::command]
-pub async fn regenerate_root_ca(_app: tauri::AppHandle) -> Result<(), String> {
+pub async fn regenerate_root_ca(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, crate::proxy::ProxyState>,
+) -> Result<CaRegenerateResult, String> {
     let cert_dir = get_cert_dir()?;
+    let config = crate::config::load_config()?;
 
     if cert_dir.exists() {
         for entry in fs::read_dir(&cert_dir).map_err(|e| e.to_string())? {
@@ -279,11 +504,90 @@ pub async fn regenerate_root_ca(_app: tauri::AppHandle) -> Result<(), String> {
         }
     }
 
-    ensure_ca_exists(&cert_dir).map_err(|e| e.to_string())?;
+    ensure_ca_exists(&cert_dir, &config).map_err(|e| e.to_string())?;
+
+    let _ = logging::write_domain_log(
+        "audit",
+        &format!(
+            "Regenerated CA Root ({:?}, {} days)",
+            config.ca_key_algorithm, config.ca_validity_days
+        ),
+    );
+
+    let result = regenerate_outcome(state.engine.get_status().running, || {
+        crate::proxy::restart_engine(state.engine.as_ref(), &app)
+            .map_err(|e| format!("{:?}: {}", e.phase, e.message))
+    });
+    let _ = logging::write_domain_log(
+        "audit",
+        if result.engine_restarted {
+            "Restarted engine to load regenerated CA"
+        } else if result.restart_required {
+            "Engine restart after CA regeneration failed; restart required"
+        } else {
+            "Engine not running; regenerated CA will load on next start"
+        },
+    );
+    Ok(result)
+}
 
-    let _ = logging::write_domain_log("audit", "Regenerated CA Root");
+#[derive(serde::Serialize, Debug)]
+pub struct KeyFilePerm {
+    pub path: String,
+    /// Octal permission bits, e.g. "600"; None where the platform has no mode bits
+    pub mode: Option<String>,
+    pub protected: bool,
+}
 
-    Ok(())
+#[derive(serde::Serialize, Debug)]
+pub struct KeyPermStatus {
+    /// False on platforms where permissions cannot be inspected (Windows)
+    pub checked: bool,
+    /// True when every existing key file is readable by its owner only
+    pub protected: bool,
+    pub files: Vec<KeyFilePerm>,
+}
+
+/// Report whether the files holding the CA private key are owner-only.
+#[tauri::command]
+pub fn check_key_permissions() -> Result<KeyPermStatus, String> {
+    let cert_dir = get_cert_dir()?;
+    let mut files = Vec::new();
+
+    for name in PRIVATE_KEY_FILES {
+        let path = cert_dir.join(name);
+        let metadata = match fs::metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(_) => continue,
+        };
+
+        #[cfg(unix)]
+        let mode = {
+            use std::os::unix::fs::PermissionsExt;
+            Some(metadata.permissions().mode() & 0o777)
+        };
+        #[cfg(not(unix))]
+        let mode: Option<u32> = {
+            let _ = metadata;
+            None
+        };
+
+        files.push(KeyFilePerm {
+            path: path.to_string_lossy().to_string(),
+            mode: mode.map(|m| format!("{:o}", m)),
+            protected: mode.map(mode_is_private).unwrap_or(true),
+        });
+    }
+
+    let protected = files.iter().all(|f| f.protected);
+    if !protected {
+        log::warn!("CA private key files are readable by other users");
+    }
+    Ok(KeyPermStatus {
+        checked: cfg!(unix),
+        protected,
+        files,
+    })
 }
 
 #[tauri::command]
@@ -308,7 +612,7 @@ mod tests {
 
     #[test]
     fn test_generate_ca() {
-        let (cert, key) = generate_ca().expect("Failed to generate CA");
+        let (cert, key) = generate_ca(Default::default(), 3650).expect("Failed to generate CA");
         assert!(cert.contains("BEGIN CERTIFICATE"));
         assert!(key.contains("BEGIN PRIVATE KEY") || key.contains("BEGIN EC PRIVATE KEY"));
 
@@ -320,16 +624,56 @@ mod tests {
 
     #[test]
     fn test_generate_ca_properties() {
-        let result = generate_ca();
+        let result = generate_ca(Default::default(), 3650);
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_mode_is_private() {
+        assert!(mode_is_private(0o600));
+        assert!(mode_is_private(0o400));
+        assert!(!mode_is_private(0o644));
+        assert!(!mode_is_private(0o640));
+    }
+
+    #[test]
+    fn test_regenerate_outcome_reports_restart_state() {
+        let idle = regenerate_outcome(false, || panic!("must not restart a stopped engine"));
+        assert!(!idle.engine_restarted && !idle.restart_required);
+        assert_eq!(idle.warning, None);
+
+        let restarted = regenerate_outcome(true, || Ok(()));
+        assert!(restarted.engine_restarted && !restarted.restart_required);
+
+        let failed = regenerate_outcome(true, || Err("PortRelease: port 9090 held".to_string()));
+        assert!(!failed.engine_restarted);
+        assert!(failed.restart_required);
+        assert!(failed.warning.unwrap().contains("port 9090 held"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_ensure_ca_exists_restricts_key_files() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().expect("Failed to create temp dir");
+        ensure_ca_exists(temp.path(), &Default::default()).expect("Failed to ensure CA exists");
+
+        for name in ["mitmproxy-ca.pem", "relaycraft-ca.pem"] {
+            let mode = fs::metadata(temp.path().join(name))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600, "{} is not owner-only", name);
+        }
+    }
+
     #[test]
     fn test_ensure_ca_exists() {
         let temp = TempDir::new().expect("Failed to create temp dir");
         let cert_dir = temp.path();
 
-        ensure_ca_exists(cert_dir).expect("Failed to ensure CA exists");
+        ensure_ca_exists(cert_dir, &Default::default()).expect("Failed to ensure CA exists");
 
         let files = [
             "mitmproxy-ca.pem",
@@ -348,7 +692,7 @@ mod tests {
             .modified()
             .unwrap();
         std::thread::sleep(std::time::Duration::from_millis(10));
-        ensure_ca_exists(cert_dir).expect("Second call failed");
+        ensure_ca_exists(cert_dir, &Default::default()).expect("Second call failed");
         let mtime_new = fs::metadata(cert_dir.join("relaycraft-ca-cert.pem"))
             .unwrap()
             .modified()
@@ -0,0 +1,70 @@
+//! PKCS#12 export of the root CA.
+
+/// Friendly name shown by import wizards
+const FRIENDLY_NAME: &str = "RelayCraft Root CA";
+
+/// Build a PKCS#12 bundle from a PEM holding the CA key and certificate
+/// (the layout of `mitmproxy-ca.pem`).
+pub(super) fn build_pkcs12(ca_pem: &str, password: &str) -> Result<Vec<u8>, String> {
+    let blocks = pem::parse_many(ca_pem).map_err(|e| format!("Invalid CA PEM: {}", e))?;
+    let key = blocks
+        .iter()
+        .find(|b| b.tag() == "PRIVATE KEY")
+        .ok_or("CA PEM has no PKCS#8 private key")?;
+    let cert = blocks
+        .iter()
+        .find(|b| b.tag() == "CERTIFICATE")
+        .ok_or("CA PEM has no certificate")?;
+
+    // Refuse to bundle a key that does not parse
+    rcgen::KeyPair::try_from(key.contents())
+        .map_err(|e| format!("CA private key is invalid: {}", e))?;
+
+    let pfx = p12::PFX::new(
+        cert.contents(),
+        key.contents(),
+        None,
+        password,
+        FRIENDLY_NAME,
+    )
+    .ok_or("Failed to build PKCS#12 bundle")?;
+    Ok(pfx.to_der())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ca_pem() -> (String, String, String) {
+        let (cert, key) = super::super::ca_generator::generate_ca(Default::default(), 30).unwrap();
+        (format!("{}\n{}", key, cert), cert, key)
+    }
+
+    #[test]
+    fn test_pkcs12_round_trip() {
+        let (combined, cert, key) = ca_pem();
+        for password in ["s3cret", ""] {
+            let der = build_pkcs12(&combined, password).unwrap();
+            let pfx = p12::PFX::parse(&der).unwrap();
+            assert!(pfx.verify_mac(password));
+            assert!(!pfx.verify_mac("wrong"));
+            assert_eq!(
+                pfx.cert_x509_bags(password).unwrap(),
+                vec![pem::parse(&cert).unwrap().into_contents()]
+            );
+            assert_eq!(
+                pfx.key_bags(password).unwrap(),
+                vec![pem::parse(&key).unwrap().into_contents()]
+            );
+        }
+    }
+
+    #[test]
+    fn test_pkcs12_rejects_bad_key() {
+        let (_, cert, _) = ca_pem();
+        assert!(build_pkcs12(&cert, "x").is_err());
+
+        let bogus = pem::encode(&pem::Pem::new("PRIVATE KEY", vec![1, 2, 3]));
+        assert!(build_pkcs12(&format!("{}\n{}", bogus, cert), "x").is_err());
+    }
+}
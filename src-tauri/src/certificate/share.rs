@@ -0,0 +1,183 @@
+//! One-shot LAN server for installing the root CA on a phone.
+//!
+//! Serves `relaycraft-ca-cert.crt` (plus a tiny landing page) until the first
+//! successful download or a timeout, and renders a QR code pointing at it.
+
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::{Duration, Instant};
+
+pub(super) const CERT_PATH: &str = "/relaycraft-ca-cert.crt";
+
+/// Pixels per QR module, and the quiet zone around the code in modules
+const QR_SCALE: usize = 8;
+const QR_QUIET_ZONE: usize = 4;
+
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// Render `data` as a black-on-white QR code PNG.
+pub(super) fn qr_png(data: &str) -> Result<Vec<u8>, String> {
+    let code = qrcode::QrCode::new(data.as_bytes())
+        .map_err(|e| format!("Failed to encode QR code: {}", e))?;
+    let modules = code.width();
+    let size = (modules + 2 * QR_QUIET_ZONE) * QR_SCALE;
+    let mut pixels = vec![255u8; size * size];
+
+    for (i, color) in code.to_colors().iter().enumerate() {
+        if *color != qrcode::Color::Dark {
+            continue;
+        }
+        let x0 = (i % modules + QR_QUIET_ZONE) * QR_SCALE;
+        let y0 = (i / modules + QR_QUIET_ZONE) * QR_SCALE;
+        for y in y0..y0 + QR_SCALE {
+            pixels[y * size + x0..y * size + x0 + QR_SCALE].fill(0);
+        }
+    }
+
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, size as u32, size as u32);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&pixels))
+        .map_err(|e| format!("Failed to write QR PNG: {}", e))?;
+    Ok(out)
+}
+
+fn respond(stream: &mut TcpStream, status: &str, content_type: &str, extra: &str, body: &[u8]) {
+    let head = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len(),
+        extra
+    );
+    let _ = stream
+        .write_all(head.as_bytes())
+        .and_then(|_| stream.write_all(body))
+        .and_then(|_| stream.flush());
+}
+
+/// Answer one request. Returns true once the certificate was delivered.
+fn handle(mut stream: TcpStream, cert_der: &[u8]) -> bool {
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < MAX_REQUEST_BYTES {
+        match stream.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => request.extend_from_slice(&buf[..n]),
+        }
+    }
+    let request = String::from_utf8_lossy(&request);
+    let mut parts = request.lines().next().unwrap_or("").split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+
+    match (method, path) {
+        ("GET", CERT_PATH) => {
+            respond(
+                &mut stream,
+                "200 OK",
+                "application/x-x509-ca-cert",
+                "Content-Disposition: attachment; filename=\"relaycraft-ca-cert.crt\"\r\n",
+                cert_der,
+            );
+            true
+        }
+        ("GET", "/") => {
+            let page = format!(
+                "<!doctype html><html><head><meta name=\"viewport\" content=\"width=device-width\">\
+                 <title>RelayCraft CA</title></head><body style=\"font-family:sans-serif;padding:2em\">\
+                 <h2>RelayCraft Root CA</h2><p><a href=\"{}\">Download certificate</a></p>\
+                 <p>After installing, enable full trust for it in your device settings.</p>\
+                 </body></html>",
+                CERT_PATH
+            );
+            respond(
+                &mut stream,
+                "200 OK",
+                "text/html; charset=utf-8",
+                "",
+                page.as_bytes(),
+            );
+            false
+        }
+        _ => {
+            respond(&mut stream, "404 Not Found", "text/plain", "", b"Not found");
+            false
+        }
+    }
+}
+
+/// Serve until the certificate has been downloaded once or `timeout` elapses.
+pub(super) fn serve_cert_once(listener: TcpListener, cert_der: Vec<u8>, timeout: Duration) {
+    let deadline = Instant::now() + timeout;
+    if listener.set_nonblocking(true).is_err() {
+        return;
+    }
+    while Instant::now() < deadline {
+        match listener.accept() {
+            Ok((stream, peer)) => {
+                if handle(stream, &cert_der) {
+                    log::info!("Root CA downloaded by {}; stopping cert share", peer.ip());
+                    return;
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                std::thread::sleep(Duration::from_millis(100));
+            }
+            Err(e) => {
+                log::warn!("Cert share server stopped: {}", e);
+                return;
+            }
+        }
+    }
+    log::info!("Cert share timed out without a download");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get(port: u16, path: &str) -> String {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        write!(stream, "GET {} HTTP/1.1\r\nHost: test\r\n\r\n", path).unwrap();
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).unwrap();
+        String::from_utf8_lossy(&response).to_string()
+    }
+
+    #[test]
+    fn test_serves_page_then_cert_and_shuts_down() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            serve_cert_once(listener, b"DERBYTES".to_vec(), Duration::from_secs(10))
+        });
+
+        assert!(get(port, "/").contains(CERT_PATH));
+        assert!(get(port, "/nope").starts_with("HTTP/1.1 404"));
+
+        let cert = get(port, CERT_PATH);
+        assert!(cert.starts_with("HTTP/1.1 200"));
+        assert!(cert.contains("Content-Type: application/x-x509-ca-cert"));
+        assert!(cert.ends_with("DERBYTES"));
+
+        server.join().unwrap();
+        assert!(TcpStream::connect(("127.0.0.1", port)).is_err());
+    }
+
+    #[test]
+    fn test_qr_png_is_square_grayscale() {
+        let png_bytes = qr_png("http://192.168.1.20:50123/relaycraft-ca-cert.crt").unwrap();
+        let decoder = png::Decoder::new(std::io::Cursor::new(png_bytes));
+        let reader = decoder.read_info().unwrap();
+        let info = reader.info();
+        assert_eq!(info.width, info.height);
+        assert_eq!(info.color_type, png::ColorType::Grayscale);
+        assert_eq!(info.width as usize % QR_SCALE, 0);
+    }
+}
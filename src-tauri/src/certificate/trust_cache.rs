@@ -0,0 +1,165 @@
+//! Post-install trust refresh: nudge the OS trust cache and report browsers
+//! that keep their own certificate store or need a restart to notice the CA.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BrowserTrustNote {
+    pub browser: String,
+    pub running: bool,
+    /// The browser uses its own certificate store instead of the system one
+    pub separate_store: bool,
+    pub guidance: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustCacheRefresh {
+    /// What was done to refresh the OS trust cache
+    pub actions: Vec<String>,
+    pub browsers: Vec<BrowserTrustNote>,
+    /// True when at least one running browser must be restarted
+    pub restart_required: bool,
+}
+
+/// What is known about installed and running browsers
+#[derive(Debug, Default)]
+pub struct BrowserEnvironment {
+    pub running: Vec<String>,
+    pub firefox_profiles: bool,
+    pub chrome_nssdb: bool,
+}
+
+fn is_running(env: &BrowserEnvironment, names: &[&str]) -> bool {
+    env.running.iter().any(|process| {
+        let process = process.to_ascii_lowercase();
+        let process = process.trim_end_matches(".exe");
+        names.iter().any(|n| process == *n)
+    })
+}
+
+/// Build per-browser guidance for `os` (`std::env::consts::OS` values).
+pub fn browser_notes(os: &str, env: &BrowserEnvironment) -> Vec<BrowserTrustNote> {
+    let mut notes = Vec::new();
+
+    let firefox_running = is_running(env, &["firefox", "firefox-bin", "firefox-esr"]);
+    if env.firefox_profiles || firefox_running {
+        let guidance = if os == "linux" {
+            "Firefox uses its own certificate store. Import the CA under Settings > Privacy & Security > Certificates > View Certificates > Authorities."
+        } else {
+            "Firefox uses its own certificate store. Import the CA in its certificate settings, or enable security.enterprise_roots.enabled in about:config to trust system certificates."
+        };
+        notes.push(BrowserTrustNote {
+            browser: "Firefox".to_string(),
+            running: firefox_running,
+            separate_store: true,
+            guidance: guidance.to_string(),
+        });
+    }
+
+    let chrome_running = is_running(
+        env,
+        &["chrome", "google chrome", "chromium", "chromium-browser"],
+    );
+    let chrome_separate = os == "linux" && env.chrome_nssdb;
+    if chrome_running || chrome_separate {
+        let guidance = if chrome_separate {
+            "Chrome on Linux reads ~/.pki/nssdb. Import the CA under Settings > Privacy and security > Security > Manage certificates, then restart Chrome."
+        } else {
+            "Chrome caches certificate trust. Restart Chrome after installing the CA."
+        };
+        notes.push(BrowserTrustNote {
+            browser: "Chrome".to_string(),
+            running: chrome_running,
+            separate_store: chrome_separate,
+            guidance: guidance.to_string(),
+        });
+    }
+
+    if is_running(env, &["msedge", "microsoft edge"]) {
+        notes.push(BrowserTrustNote {
+            browser: "Edge".to_string(),
+            running: true,
+            separate_store: false,
+            guidance: "Edge caches certificate trust. Restart Edge after installing the CA."
+                .to_string(),
+        });
+    }
+
+    if os == "macos" && is_running(env, &["safari"]) {
+        notes.push(BrowserTrustNote {
+            browser: "Safari".to_string(),
+            running: true,
+            separate_store: false,
+            guidance:
+                "Safari may keep using cached trust. Restart Safari if sites still show a warning."
+                    .to_string(),
+        });
+    }
+
+    notes
+}
+
+fn firefox_profile_dirs(home: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![
+        home.join(".mozilla/firefox"),
+        home.join("snap/firefox/common/.mozilla/firefox"),
+        home.join("Library/Application Support/Firefox/Profiles"),
+    ];
+    if let Some(appdata) = dirs::config_dir() {
+        dirs.push(appdata.join("Mozilla").join("Firefox").join("Profiles"));
+    }
+    dirs
+}
+
+/// Inspect profiles on disk and running processes
+pub fn detect_browsers() -> BrowserEnvironment {
+    let home = dirs::home_dir().unwrap_or_default();
+
+    let mut sys = sysinfo::System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    let mut running: Vec<String> = sys
+        .processes()
+        .values()
+        .map(|p| p.name().to_string_lossy().to_string())
+        .collect();
+    running.sort();
+    running.dedup();
+
+    BrowserEnvironment {
+        running,
+        firefox_profiles: firefox_profile_dirs(&home).iter().any(|d| d.exists()),
+        chrome_nssdb: home.join(".pki/nssdb").exists(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_browser_notes_flag_separate_stores_and_restarts() {
+        let env = BrowserEnvironment {
+            running: vec!["chrome".into(), "bash".into()],
+            firefox_profiles: true,
+            chrome_nssdb: true,
+        };
+
+        let linux = browser_notes("linux", &env);
+        assert_eq!(linux.len(), 2);
+        assert!(linux[0].separate_store && !linux[0].running);
+        assert_eq!(linux[1].browser, "Chrome");
+        assert!(linux[1].separate_store && linux[1].running);
+
+        let windows = BrowserEnvironment {
+            running: vec!["msedge.exe".into()],
+            ..Default::default()
+        };
+        let notes = browser_notes("windows", &windows);
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].browser, "Edge");
+        assert!(!notes[0].separate_store);
+    }
+}
@@ -122,4 +122,23 @@ impl CertManager for WindowsCertManager {
             }
         }
     }
+
+    fn refresh_trust_cache(&self) -> Vec<String> {
+        use std::os::windows::process::CommandExt;
+
+        // Drop the CryptoAPI URL cache (cached chains and CRLs) so the new root
+        // is picked up by chain building without signing out.
+        let cleared = Command::new("certutil")
+            .args(["-urlcache", "*", "delete"])
+            .creation_flags(0x08000000)
+            .output()
+            .map(|out| out.status.success())
+            .unwrap_or(false);
+
+        if cleared {
+            vec!["Cleared the CryptoAPI URL cache".to_string()]
+        } else {
+            Vec::new()
+        }
+    }
 }
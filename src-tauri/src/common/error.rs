@@ -58,10 +58,12 @@ pub enum ScriptError {
     #[error("Script not found: {0}")]
     NotFound(String),
 
-    #[allow(dead_code)]
     #[error("Compilation error: {0}")]
     Compilation(String),
 
+    #[error("Validation unavailable: {0}")]
+    ValidationUnavailable(String),
+
     #[allow(dead_code)] // Added
     #[error("Runtime error: {0}")]
     Runtime(String),
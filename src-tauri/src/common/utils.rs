@@ -1,3 +1,4 @@
+use base64::Engine;
 use local_ip_address::local_ip;
 use serde::Serialize;
 
@@ -17,6 +18,71 @@ pub struct SystemInfo {
     pub build_date: String,
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JwtParts {
+    pub header: serde_json::Value,
+    pub payload: serde_json::Value,
+    /// Raw base64url signature segment (not verified)
+    pub signature: String,
+    /// `iat` as RFC 3339, if present
+    pub issued_at: Option<String>,
+    /// `exp` as RFC 3339, if present
+    pub expires_at: Option<String>,
+    /// Whether `exp` lies in the past; `None` when the token has no `exp`
+    pub expired: Option<bool>,
+}
+
+fn decode_jwt_segment(segment: &str, what: &str) -> Result<serde_json::Value, String> {
+    let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(segment.trim_end_matches('='))
+        .map_err(|e| format!("Invalid JWT {} encoding: {}", what, e))?;
+    serde_json::from_slice(&bytes).map_err(|e| format!("Invalid JWT {} JSON: {}", what, e))
+}
+
+fn claim_time(payload: &serde_json::Value, claim: &str) -> Option<i64> {
+    let value = payload.get(claim)?;
+    value.as_i64().or_else(|| value.as_f64().map(|f| f as i64))
+}
+
+fn format_timestamp(secs: i64) -> Option<String> {
+    chrono::DateTime::from_timestamp(secs, 0).map(|t| t.to_rfc3339())
+}
+
+/// Decode a JWT without verifying its signature. Accepts a bare token or an
+/// `Authorization` value with a `Bearer ` prefix.
+#[tauri::command]
+pub fn decode_jwt(token: String) -> Result<JwtParts, String> {
+    let token = token.trim();
+    let token = match token.split_once(' ') {
+        Some((scheme, rest)) if scheme.eq_ignore_ascii_case("bearer") => rest.trim(),
+        _ => token,
+    };
+
+    let segments: Vec<&str> = token.split('.').collect();
+    if segments.len() != 3 {
+        return Err(format!(
+            "Not a JWT: expected 3 segments, found {}",
+            segments.len()
+        ));
+    }
+
+    let header = decode_jwt_segment(segments[0], "header")?;
+    let payload = decode_jwt_segment(segments[1], "payload")?;
+
+    let exp = claim_time(&payload, "exp");
+    let iat = claim_time(&payload, "iat");
+
+    Ok(JwtParts {
+        issued_at: iat.and_then(format_timestamp),
+        expires_at: exp.and_then(format_timestamp),
+        expired: exp.map(|e| e <= chrono::Utc::now().timestamp()),
+        header,
+        payload,
+        signature: segments[2].to_string(),
+    })
+}
+
 #[tauri::command]
 pub fn get_local_ip() -> String {
     match local_ip() {
@@ -79,3 +145,45 @@ pub fn get_system_info() -> SystemInfo {
         build_date: env!("BUILD_DATE").to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(json: &str) -> String {
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(json)
+    }
+
+    #[test]
+    fn test_decode_jwt_reports_claims_and_expiry() {
+        let token = format!(
+            "Bearer {}.{}.c2lnbmF0dXJl",
+            encode(r#"{"alg":"HS256","typ":"JWT"}"#),
+            encode(r#"{"sub":"1234567890","name":"John Doe","iat":1516239022,"exp":1516242622}"#)
+        );
+
+        let parts = decode_jwt(token).unwrap();
+        assert_eq!(parts.header["alg"], "HS256");
+        assert_eq!(parts.payload["sub"], "1234567890");
+        assert_eq!(parts.signature, "c2lnbmF0dXJl");
+        assert_eq!(
+            parts.issued_at.as_deref(),
+            Some("2018-01-18T01:30:22+00:00")
+        );
+        assert_eq!(
+            parts.expires_at.as_deref(),
+            Some("2018-01-18T02:30:22+00:00")
+        );
+        assert_eq!(parts.expired, Some(true));
+    }
+
+    #[test]
+    fn test_decode_jwt_rejects_malformed_tokens() {
+        assert!(decode_jwt("abc.def".to_string()).is_err());
+        assert!(decode_jwt("!!!.e30.sig".to_string()).is_err());
+
+        let no_exp = format!("{}.{}.", encode(r#"{"alg":"none"}"#), encode("{}"));
+        let parts = decode_jwt(no_exp).unwrap();
+        assert_eq!(parts.expired, None);
+    }
+}
@@ -0,0 +1,289 @@
+//! Named configuration profiles.
+//!
+//! Each profile is a full `AppConfig` stored as `config/profiles/<name>.json`;
+//! `config/active_profile` holds the name `load_config`/`save_config` use.
+//! A pre-profiles `config.json` becomes the `default` profile on first use.
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::AppConfig;
+use crate::logging;
+
+pub const DEFAULT_PROFILE: &str = "default";
+const PROFILES_DIR: &str = "profiles";
+const ACTIVE_PROFILE_FILE: &str = "active_profile";
+const LEGACY_CONFIG_FILE: &str = "config.json";
+const MAX_PROFILE_NAME_LEN: usize = 64;
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileList {
+    pub active: String,
+    pub profiles: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileSwitchResult {
+    pub active: String,
+    /// The proxy is running with the previous profile's settings
+    pub restart_required: bool,
+}
+
+fn validate_profile_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_PROFILE_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid profile name '{}': use 1-{} letters, digits, '-' or '_'",
+            name, MAX_PROFILE_NAME_LEN
+        ))
+    }
+}
+
+fn profile_path(config_dir: &Path, name: &str) -> PathBuf {
+    config_dir.join(PROFILES_DIR).join(format!("{}.json", name))
+}
+
+/// Create the profiles directory, moving a legacy `config.json` into the
+/// default profile the first time.
+fn ensure_profiles_dir(config_dir: &Path) -> Result<(), String> {
+    let dir = config_dir.join(PROFILES_DIR);
+    if !dir.exists() {
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create profiles directory: {}", e))?;
+    }
+    let legacy = config_dir.join(LEGACY_CONFIG_FILE);
+    let default = profile_path(config_dir, DEFAULT_PROFILE);
+    if legacy.exists() && !default.exists() {
+        fs::rename(&legacy, &default)
+            .map_err(|e| format!("Failed to migrate config.json to a profile: {}", e))?;
+        log::info!("Migrated config.json to the '{}' profile", DEFAULT_PROFILE);
+    }
+    Ok(())
+}
+
+/// Name of the active profile, falling back to the default profile when the
+/// pointer is missing or names a profile that no longer exists.
+pub(super) fn active_profile_in(config_dir: &Path) -> String {
+    fs::read_to_string(config_dir.join(ACTIVE_PROFILE_FILE))
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|name| {
+            validate_profile_name(name).is_ok() && profile_path(config_dir, name).exists()
+        })
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+}
+
+/// Path of the active profile's config file.
+pub(super) fn active_profile_path(config_dir: &Path) -> Result<PathBuf, String> {
+    ensure_profiles_dir(config_dir)?;
+    Ok(profile_path(config_dir, &active_profile_in(config_dir)))
+}
+
+fn list_profiles_in(config_dir: &Path) -> Result<ProfileList, String> {
+    ensure_profiles_dir(config_dir)?;
+    let mut profiles: Vec<String> = fs::read_dir(config_dir.join(PROFILES_DIR))
+        .map_err(|e| format!("Failed to read profiles: {}", e))?
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().to_string_lossy().to_string();
+            let name = name.strip_suffix(".json")?.to_string();
+            validate_profile_name(&name).is_ok().then_some(name)
+        })
+        .collect();
+    // The default profile always exists, even before its first save
+    if !profiles.iter().any(|p| p == DEFAULT_PROFILE) {
+        profiles.push(DEFAULT_PROFILE.to_string());
+    }
+    profiles.sort();
+    Ok(ProfileList {
+        active: active_profile_in(config_dir),
+        profiles,
+    })
+}
+
+/// Create `name` as a copy of profile `from`, or from defaults when `from` is None.
+fn create_profile_in(config_dir: &Path, name: &str, from: Option<&str>) -> Result<(), String> {
+    validate_profile_name(name)?;
+    ensure_profiles_dir(config_dir)?;
+    let path = profile_path(config_dir, name);
+    if path.exists() {
+        return Err(format!("Profile '{}' already exists", name));
+    }
+
+    let content = match from {
+        Some(source) => {
+            validate_profile_name(source)?;
+            let source_path = profile_path(config_dir, source);
+            if source_path.exists() {
+                fs::read_to_string(&source_path)
+                    .map_err(|e| format!("Failed to read profile '{}': {}", source, e))?
+            } else if source == DEFAULT_PROFILE {
+                serde_json::to_string_pretty(&AppConfig::default()).map_err(|e| e.to_string())?
+            } else {
+                return Err(format!("Profile '{}' not found", source));
+            }
+        }
+        None => serde_json::to_string_pretty(&AppConfig::default()).map_err(|e| e.to_string())?,
+    };
+    fs::write(&path, content).map_err(|e| format!("Failed to write profile: {}", e))
+}
+
+fn switch_profile_in(config_dir: &Path, name: &str) -> Result<(), String> {
+    validate_profile_name(name)?;
+    ensure_profiles_dir(config_dir)?;
+    if name != DEFAULT_PROFILE && !profile_path(config_dir, name).exists() {
+        return Err(format!("Profile '{}' not found", name));
+    }
+    fs::write(config_dir.join(ACTIVE_PROFILE_FILE), name)
+        .map_err(|e| format!("Failed to switch profile: {}", e))
+}
+
+fn delete_profile_in(config_dir: &Path, name: &str) -> Result<(), String> {
+    validate_profile_name(name)?;
+    if name == DEFAULT_PROFILE {
+        return Err("The default profile cannot be deleted".to_string());
+    }
+    if active_profile_in(config_dir) == name {
+        return Err("Switch to another profile before deleting the active one".to_string());
+    }
+    let path = profile_path(config_dir, name);
+    if !path.exists() {
+        return Err(format!("Profile '{}' not found", name));
+    }
+    fs::remove_file(&path).map_err(|e| format!("Failed to delete profile: {}", e))
+}
+
+/// Secret-store key for the active profile's upstream proxy password. The
+/// default profile keeps the pre-profiles key so existing passwords survive.
+pub(super) fn upstream_proxy_secret() -> String {
+    let active = super::get_config_dir()
+        .map(|dir| active_profile_in(&dir))
+        .unwrap_or_else(|_| DEFAULT_PROFILE.to_string());
+    secret_for_profile(&active)
+}
+
+fn secret_for_profile(profile: &str) -> String {
+    if profile == DEFAULT_PROFILE {
+        super::UPSTREAM_PROXY_SECRET.to_string()
+    } else {
+        format!("{}.{}", super::UPSTREAM_PROXY_SECRET, profile)
+    }
+}
+
+#[tauri::command]
+pub fn list_profiles() -> Result<ProfileList, String> {
+    list_profiles_in(&super::get_config_dir()?)
+}
+
+/// Make `name` the active profile. Settings apply to the running proxy only
+/// after a restart, which the result flags.
+#[tauri::command]
+pub async fn switch_profile(
+    state: tauri::State<'_, crate::proxy::ProxyState>,
+    name: String,
+) -> Result<ProfileSwitchResult, String> {
+    let config_dir = super::get_config_dir()?;
+    let previous = active_profile_in(&config_dir);
+    switch_profile_in(&config_dir, &name)?;
+    let _ = logging::write_domain_log(
+        "audit",
+        &format!("Switched config profile: {} -> {}", previous, name),
+    );
+    Ok(ProfileSwitchResult {
+        restart_required: previous != name && state.engine.get_status().running,
+        active: name,
+    })
+}
+
+#[tauri::command]
+pub fn create_profile(name: String, from: Option<String>) -> Result<(), String> {
+    create_profile_in(&super::get_config_dir()?, &name, from.as_deref())?;
+    let _ = logging::write_domain_log(
+        "audit",
+        &format!(
+            "Created config profile '{}' from {}",
+            name,
+            from.as_deref().unwrap_or("defaults")
+        ),
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_profile(name: String) -> Result<(), String> {
+    delete_profile_in(&super::get_config_dir()?, &name)?;
+    let _ = crate::ai::crypto::delete_api_key(&secret_for_profile(&name));
+    let _ = logging::write_domain_log("audit", &format!("Deleted config profile '{}'", name));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_config_becomes_default_profile() {
+        let temp = tempfile::TempDir::new().unwrap();
+        fs::write(temp.path().join("config.json"), "{\"proxy_port\": 8888}").unwrap();
+
+        let path = active_profile_path(temp.path()).unwrap();
+        assert_eq!(path, profile_path(temp.path(), DEFAULT_PROFILE));
+        assert!(fs::read_to_string(path).unwrap().contains("8888"));
+        assert!(!temp.path().join("config.json").exists());
+    }
+
+    #[test]
+    fn test_create_switch_and_delete_profiles() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let dir = temp.path();
+        fs::create_dir_all(dir.join(PROFILES_DIR)).unwrap();
+        fs::write(profile_path(dir, DEFAULT_PROFILE), "{\"proxy_port\": 8888}").unwrap();
+
+        create_profile_in(dir, "mobile", Some(DEFAULT_PROFILE)).unwrap();
+        create_profile_in(dir, "clean", None).unwrap();
+        assert!(create_profile_in(dir, "mobile", None).is_err());
+        assert!(create_profile_in(dir, "../escape", None).is_err());
+        assert!(create_profile_in(dir, "other", Some("missing")).is_err());
+        assert!(fs::read_to_string(profile_path(dir, "mobile"))
+            .unwrap()
+            .contains("8888"));
+
+        let list = list_profiles_in(dir).unwrap();
+        assert_eq!(list.active, DEFAULT_PROFILE);
+        assert_eq!(list.profiles, vec!["clean", "default", "mobile"]);
+
+        switch_profile_in(dir, "mobile").unwrap();
+        assert_eq!(
+            active_profile_path(dir).unwrap(),
+            profile_path(dir, "mobile")
+        );
+        assert!(switch_profile_in(dir, "missing").is_err());
+
+        // The active and default profiles are protected
+        assert!(delete_profile_in(dir, "mobile").is_err());
+        assert!(delete_profile_in(dir, DEFAULT_PROFILE).is_err());
+        delete_profile_in(dir, "clean").unwrap();
+        assert_eq!(
+            list_profiles_in(dir).unwrap().profiles,
+            vec!["default", "mobile"]
+        );
+
+        // A dangling pointer falls back to the default profile
+        fs::remove_file(profile_path(dir, "mobile")).unwrap();
+        assert_eq!(active_profile_in(dir), DEFAULT_PROFILE);
+    }
+
+    #[test]
+    fn test_profile_secrets_are_separate() {
+        assert_eq!(secret_for_profile(DEFAULT_PROFILE), "upstream_proxy");
+        assert_eq!(secret_for_profile("work"), "upstream_proxy.work");
+    }
+}
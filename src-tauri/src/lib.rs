@@ -233,6 +233,8 @@ pub fn run() {
             last_rx: Mutex::new(0),
             last_tx: Mutex::new(0),
             last_update: Mutex::new(std::time::Instant::now()),
+            stats_sampler: Mutex::new(None),
+            capture_rate: Mutex::new(proxy::CaptureRateTracker::default()),
         })
         .manage(ai::AIState {
             config: Mutex::new(app_config.ai_config.clone()),
@@ -269,7 +271,7 @@ pub fn run() {
             }
             // Ensure CA certificate exists
             if let Ok(cert_dir) = certificate::get_cert_dir() {
-                let _ = certificate::ensure_ca_exists(&cert_dir);
+                let _ = certificate::ensure_ca_exists(&cert_dir, &app_config);
             }
 
             // Kill stale engine processes
@@ -279,6 +281,11 @@ pub fn run() {
                 common::jump_list::reset_jump_list();
             }
 
+            // Rule solo is a debugging aid and must not survive a restart
+            if let Ok(storage) = rules::storage::RuleStorage::from_config() {
+                let _ = storage.set_solo(None);
+            }
+
             // Allow themes directory in asset scope
             if let Ok(themes_dir) = config::get_themes_dir() {
                 let scope = app.asset_protocol_scope();
@@ -336,29 +343,64 @@ pub fn run() {
             proxy::set_proxy_active,
             proxy::prepare_update_install,
             proxy::get_process_stats,
+            proxy::get_recent_engine_output,
+            proxy::get_capture_rate,
+            proxy::find_orphaned_engines,
+            proxy::kill_orphan,
+            proxy::scan_free_ports,
+            proxy::start_stats_sampling,
+            proxy::stop_stats_sampling,
             common::utils::get_local_ip,
             certificate::get_cert_path,
             certificate::open_cert_dir,
             certificate::check_cert_installed,
+            certificate::export_cert_pkcs12,
+            certificate::start_cert_share,
+            certificate::poll_cert_installed,
+            certificate::refresh_trust_cache,
             certificate::get_detailed_cert_info,
+            certificate::check_key_permissions,
             certificate::install_cert_automated,
             certificate::remove_cert_automated,
             certificate::regenerate_root_ca,
             config::load_config,
+            config::reset_config,
             config::save_config,
+            config::list_profiles,
+            config::switch_profile,
+            config::create_profile,
+            config::delete_profile,
             config::open_config_dir,
             config::open_data_dir,
             config::open_logs_dir,
+            config::get_effective_proxy_env,
+            config::export_repro,
+            config::export_workspace,
+            config::import_workspace,
+            config::export_settings,
+            config::import_settings,
             scripts::commands::list_scripts,
             scripts::commands::get_script_content,
             scripts::commands::save_script,
+            scripts::commands::validate_script,
             scripts::commands::delete_script,
             scripts::commands::set_script_enabled,
+            scripts::commands::set_enabled_bulk,
+            scripts::commands::import_scripts_from_dir,
+            scripts::commands::export_scripts_zip,
+            scripts::commands::import_scripts_zip,
+            scripts::commands::check_preprocessor,
             scripts::commands::rename_script,
+            scripts::commands::duplicate_script,
             scripts::commands::move_script,
             ai::commands::load_ai_config,
             ai::commands::save_ai_config,
+            ai::commands::switch_ai_provider,
             ai::commands::test_ai_connection,
+            ai::commands::detect_provider,
+            ai::commands::probe_endpoint,
+            ai::commands::check_ai_config_drift,
+            ai::commands::reconcile_ai_config,
             ai::commands::list_ai_profiles,
             ai::commands::probe_ai_capabilities,
             ai::commands::ai_chat_completion,
@@ -376,27 +418,87 @@ pub fn run() {
             plugins::commands::uninstall_plugin,
             plugins::commands::uninstall_theme,
             plugins::commands::plugin_install_local_zip,
+            plugins::commands::validate_theme_zip,
             plugins::market::plugin_market_fetch,
             plugins::market::plugin_market_install,
             plugins::market::plugin_market_load_cache,
             plugins::bridge::plugin_call,
             common::utils::check_regex_match,
+            common::utils::decode_jwt,
             common::utils::get_system_info,
             traffic::replay_request,
+            traffic::replay_batch,
+            traffic::parse_curl,
+            traffic::flow_to_curl,
+            traffic::replay_assert,
+            traffic::export_test_suite,
+            traffic::run_test_suite,
             traffic::check_proxy_connectivity,
             traffic::ws_inject_frame,
+            traffic::filter_by_time_range,
+            traffic::export_har_time_range,
+            traffic::save_baseline,
+            traffic::compare_to_baseline,
+            traffic::compute_hmac_signature,
+            traffic::extract_tokens,
+            traffic::store_token,
+            traffic::list_stored_tokens,
+            traffic::remove_stored_token,
+            traffic::group_by_correlation,
+            traffic::replay_multi_env,
+            traffic::classify_body,
+            traffic::redecode_body,
+            traffic::save_request_template,
+            traffic::list_request_templates,
+            traffic::delete_request_template,
+            traffic::start_recording,
+            traffic::get_active_recording,
+            traffic::stop_recording,
+            traffic::json_body_diff,
+            traffic::diff_flows,
+            traffic::list_intercepted,
+            traffic::resume_flow,
+            traffic::flows_matching_rule,
+            traffic::analyze_caching,
             session::save_session,
             session::har::export_har,
+            session::har::export_har_filtered,
+            session::mitm::import_mitm_flows,
+            session::http_file::export_http_file,
+            session::postman::export_postman,
+            session::reference::flow_reference,
+            session::reference::resolve_reference,
+            session::split::split_session,
             rules::load_all_rules,
             rules::save_rule,
             rules::save_all_rules,
             rules::delete_rule,
+            rules::duplicate_rule,
+            rules::set_rules_enabled,
+            rules::set_group_enabled,
             rules::load_groups,
             rules::save_groups,
             rules::export_rules_bundle,
             rules::import_rules_bundle,
+            rules::export_single_rule,
+            rules::import_single_rule,
             common::window::set_window_vibrancy,
             rules::get_rules_dir_path,
+            rules::get_rule_schema,
+            rules::reload_rules,
+            rules::set_solo,
+            rules::get_solo,
+            rules::get_rule_stats,
+            rules::reset_rule_stats,
+            rules::export_compact,
+            rules::import_compact,
+            rules::load_breakpoints,
+            rules::save_breakpoints,
+            rules::clear_breakpoints,
+            rules::set_breakpoints_enabled,
+            rules::preview_jitter,
+            rules::test_rule,
+            rules::detect_rule_conflicts,
             rules::export_rules_zip,
             rules::import_rules_zip,
             gateway::load_all_gateway_routes,
@@ -410,6 +512,8 @@ pub fn run() {
             gateway::get_gateway_dir_path,
             logging::log_domain_event,
             logging::get_logs,
+            logging::set_log_streaming,
+            logging::clear_logs,
             get_startup_warnings,
             mcp::get_mcp_status,
             mcp::get_mcp_token,
@@ -427,6 +531,9 @@ pub fn run() {
                     let _ = state.engine.terminate();
                     log::info!("Child process stopped via engine abstraction");
                 }
+                if let Err(e) = rules::stats::flush_pending() {
+                    log::warn!("Failed to save rule stats: {}", e);
+                }
                 // Force kill remaining engine processes as fallback
                 #[cfg(target_os = "windows")]
                 {
@@ -456,21 +563,31 @@ pub fn run() {
 }
 
 fn apply_upstream_proxy(config: &config::AppConfig) {
-    if config.upstream_proxy.enabled && !config.upstream_proxy.url.trim().is_empty() {
-        let proxy_url = config.upstream_proxy.url.trim();
+    let upstream = &config.upstream_proxy;
+    if upstream.is_active() {
+        // Inherited by every child process, so never carries credentials; the
+        // engine gets them through RELAYCRAFT_UPSTREAM_AUTH instead
+        let redacted = upstream.redacted_url();
+        let proxy_url = redacted.as_str();
         log::info!("Applying upstream proxy to environment: {}", proxy_url);
         std::env::set_var("HTTP_PROXY", proxy_url);
         std::env::set_var("HTTPS_PROXY", proxy_url);
         std::env::set_var("ALL_PROXY", proxy_url);
+        std::env::set_var("all_proxy", proxy_url);
 
-        let bypass = if config.upstream_proxy.bypass_domains.is_empty() {
+        let bypass = if upstream.bypass_domains.is_empty() {
             "localhost,127.0.0.1,::1".to_string()
         } else {
-            config.upstream_proxy.bypass_domains.clone()
+            upstream.bypass_domains.clone()
         };
 
         std::env::set_var("NO_PROXY", bypass);
     } else {
+        if upstream.enabled {
+            if let Err(e) = upstream.validate() {
+                log::warn!("Ignoring the upstream proxy: {}", e);
+            }
+        }
         let loopback_bypass = "localhost,127.0.0.1,::1";
         std::env::set_var("NO_PROXY", loopback_bypass);
         std::env::set_var("no_proxy", loopback_bypass);
@@ -478,5 +595,6 @@ fn apply_upstream_proxy(config: &config::AppConfig) {
         std::env::remove_var("HTTP_PROXY");
         std::env::remove_var("HTTPS_PROXY");
         std::env::remove_var("ALL_PROXY");
+        std::env::remove_var("all_proxy");
     }
 }
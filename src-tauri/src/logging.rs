@@ -3,6 +3,7 @@ use std::fs::{File, OpenOptions};
 use std::io::Write;
 use std::panic;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{mpsc, Mutex};
 use std::thread;
 
@@ -13,8 +14,64 @@ struct LogEntry {
     timestamp: String,
 }
 
+/// Whether engine log lines are pushed to the UI as `proxy-log` events.
+/// Off by default so a closed logs panel doesn't cost IPC traffic.
+static LOG_STREAMING: AtomicBool = AtomicBool::new(false);
+
+/// Payload of the `proxy-log` event
+#[derive(Clone, serde::Serialize)]
+pub struct LogLineEvent {
+    pub domain: String,
+    pub line: String,
+    pub timestamp: String,
+}
+
+impl LogLineEvent {
+    pub fn new(domain: &str, line: String) -> Self {
+        Self {
+            domain: domain.to_string(),
+            line,
+            timestamp: chrono::Local::now()
+                .format("%Y-%m-%d %H:%M:%S%.3f")
+                .to_string(),
+        }
+    }
+}
+
+pub fn log_streaming_enabled() -> bool {
+    LOG_STREAMING.load(Ordering::Relaxed)
+}
+
+/// Messages consumed by the log writer thread
+enum LogMessage {
+    Entry(LogEntry),
+    /// Truncate the given files, then report back. Routed through the writer
+    /// so it never races a pending write.
+    Truncate {
+        files: Vec<&'static str>,
+        done: mpsc::Sender<Result<(), String>>,
+    },
+}
+
+/// Every log file the app writes, keyed by the names `get_logs` accepts
+const LOG_FILES: &[(&str, &str)] = &[
+    ("proxy", "engine.log"),
+    ("app", "app.log"),
+    ("audit", "audit.log"),
+    ("script", "script.log"),
+    ("plugin", "plugin.log"),
+    ("crash", "crash.log"),
+];
+
+fn log_file_name(log_name: &str) -> Option<&'static str> {
+    LOG_FILES
+        .iter()
+        .find(|(name, _)| *name == log_name)
+        .map(|(_, file)| *file)
+}
+
 lazy_static::lazy_static! {
-    static ref LOG_TX: Mutex<Option<mpsc::Sender<LogEntry>>> = Mutex::new(None);
+    static ref LOG_TX: Mutex<Option<mpsc::Sender<LogMessage>>> = Mutex::new(None);
     static ref LOG_DIR_PATH: Mutex<Option<PathBuf>> = Mutex::new(None);
 }
 
@@ -26,7 +83,7 @@ pub fn init_log_dir(path: PathBuf) {
     }
 
     // Create channel
-    let (tx, rx) = mpsc::channel::<LogEntry>();
+    let (tx, rx) = mpsc::channel::<LogMessage>();
 
     // Store sender
     if let Ok(mut global_tx) = LOG_TX.lock() {
@@ -48,7 +105,14 @@ pub fn init_log_dir(path: PathBuf) {
                 let _ = std::fs::create_dir_all(&log_dir);
             }
 
-            while let Ok(entry) = rx.recv() {
+            while let Ok(message) = rx.recv() {
+                let entry = match message {
+                    LogMessage::Entry(entry) => entry,
+                    LogMessage::Truncate { files, done } => {
+                        let _ = done.send(truncate_log_files(&log_dir, &files, &file_cache));
+                        continue;
+                    }
+                };
                 let filename = match entry.domain.as_str() {
                     "audit" => "audit.log",
                     "script" => "script.log",
@@ -210,11 +274,11 @@ pub fn write_domain_log(domain: &str, message: &str) -> std::io::Result<()> {
     if let Ok(guard) = LOG_TX.lock() {
         if let Some(tx) = &*guard {
             let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
-            let _ = tx.send(LogEntry {
+            let _ = tx.send(LogMessage::Entry(LogEntry {
                 domain: domain.to_string(),
                 message: message.to_string(),
                 timestamp,
-            });
+            }));
             return Ok(());
         }
     }
@@ -230,19 +294,18 @@ pub fn log_domain_event(domain: String, message: String) {
     let _ = write_domain_log(&domain, &message);
 }
 
+/// Turn live `proxy-log` events on while a logs view is open
+#[tauri::command]
+pub fn set_log_streaming(enabled: bool) {
+    LOG_STREAMING.store(enabled, Ordering::Relaxed);
+}
+
 #[tauri::command]
 pub async fn get_logs(log_name: String, lines: usize) -> Result<Vec<String>, String> {
     let root_dir = crate::config::get_app_root_dir()?;
     let log_dir = root_dir.join("logs");
-    let log_filename = match log_name.as_str() {
-        "proxy" => "engine.log",
-        "app" => "app.log",
-        "audit" => "audit.log",
-        "script" => "script.log",
-        "plugin" => "plugin.log",
-        "crash" => "crash.log",
-        _ => return Err(format!("Unknown log name: {}", log_name)),
-    };
+    let log_filename =
+        log_file_name(&log_name).ok_or_else(|| format!("Unknown log name: {}", log_name))?;
 
     let log_path = log_dir.join(log_filename);
 
@@ -258,6 +321,69 @@ pub async fn get_logs(log_name: String, lines: usize) -> Result<Vec<String>, Str
     result.map_err(|e| e.to_string())
 }
 
+/// Empty `files` in `log_dir`, through the writer's open handle when it has
+/// one. Handles are opened in append mode, so later writes start at 0 again.
+fn truncate_log_files(
+    log_dir: &std::path::Path,
+    files: &[&str],
+    open_files: &HashMap<String, File>,
+) -> Result<(), String> {
+    for name in files {
+        let result = match open_files.get(*name) {
+            Some(file) => file.set_len(0),
+            None => {
+                let path = log_dir.join(name);
+                if !path.exists() {
+                    continue;
+                }
+                OpenOptions::new()
+                    .write(true)
+                    .open(&path)
+                    .and_then(|f| f.set_len(0))
+            }
+        };
+        result.map_err(|e| format!("Failed to clear {}: {}", name, e))?;
+    }
+    Ok(())
+}
+
+/// Truncate one log file (`proxy`, `app`, `audit`, ...) or `"all"` of them
+#[tauri::command]
+pub async fn clear_logs(log_name: String) -> Result<(), String> {
+    let files: Vec<&'static str> = if log_name == "all" {
+        LOG_FILES.iter().map(|(_, file)| *file).collect()
+    } else {
+        vec![log_file_name(&log_name).ok_or_else(|| format!("Unknown log name: {}", log_name))?]
+    };
+
+    let (done_tx, done_rx) = mpsc::channel();
+    {
+        let guard = LOG_TX
+            .lock()
+            .map_err(|_| "Logger lock poisoned".to_string())?;
+        let tx = guard.as_ref().ok_or("Logger not initialized")?;
+        tx.send(LogMessage::Truncate {
+            files: files.clone(),
+            done: done_tx,
+        })
+        .map_err(|_| "Log writer is not running".to_string())?;
+    }
+
+    tokio::task::spawn_blocking(move || {
+        done_rx
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .map_err(|_| "Timed out waiting for the log writer".to_string())?
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let _ = write_domain_log(
+        "audit",
+        &format!("User cleared log files: {}", files.join(", ")),
+    );
+    Ok(())
+}
+
 /// Read the last `n` lines from a file by seeking backwards in chunks.
 /// Avoids reading the entire file, making it fast even for very large logs.
 fn read_last_n_lines(path: &std::path::Path, n: usize) -> std::io::Result<Vec<String>> {
@@ -297,7 +423,10 @@ fn read_last_n_lines(path: &std::path::Path, n: usize) -> std::io::Result<Vec<St
     }
 
     let text = String::from_utf8_lossy(&full);
-    let mut all_lines: Vec<String> = text.split('\n').map(|s| s.to_string()).collect();
+    let mut all_lines: Vec<String> = text
+        .split('\n')
+        .map(|s| s.strip_suffix('\r').unwrap_or(s).to_string())
+        .collect();
 
     // Remove trailing empty string from trailing newline
     if all_lines.last().map_or(false, |l| l.is_empty()) {
@@ -307,3 +436,76 @@ fn read_last_n_lines(path: &std::path::Path, n: usize) -> std::io::Result<Vec<St
     let start = all_lines.len().saturating_sub(n);
     Ok(all_lines[start..].to_vec())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_last_n_lines_of_large_file() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("engine.log");
+        let mut file = File::create(&path).unwrap();
+        // ~5 MB, so the tail read crosses many chunk boundaries
+        for i in 0..100_000 {
+            writeln!(
+                file,
+                "[2024-01-01 00:00:00] line {:06} {}",
+                i,
+                "x".repeat(20)
+            )
+            .unwrap();
+        }
+        drop(file);
+        assert!(std::fs::metadata(&path).unwrap().len() > 4 * 1024 * 1024);
+
+        let tail = read_last_n_lines(&path, 250).unwrap();
+        assert_eq!(tail.len(), 250);
+        assert!(tail[0].contains("line 099750 "));
+        assert!(tail[249].contains("line 099999 "));
+    }
+
+    #[test]
+    fn test_truncate_log_files_keeps_appending_handle_usable() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let open_path = temp.path().join("engine.log");
+        let mut open_files = HashMap::new();
+        let mut handle = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&open_path)
+            .unwrap();
+        writeln!(handle, "old line").unwrap();
+        open_files.insert("engine.log".to_string(), handle.try_clone().unwrap());
+        std::fs::write(temp.path().join("audit.log"), "old audit\n").unwrap();
+
+        truncate_log_files(
+            temp.path(),
+            &["engine.log", "audit.log", "crash.log"],
+            &open_files,
+        )
+        .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(temp.path().join("audit.log")).unwrap(),
+            ""
+        );
+        assert!(!temp.path().join("crash.log").exists());
+        writeln!(handle, "new line").unwrap();
+        assert_eq!(std::fs::read_to_string(&open_path).unwrap(), "new line\n");
+    }
+
+    #[test]
+    fn test_read_last_n_lines_more_than_file() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("app.log");
+        std::fs::write(&path, "one\r\ntwo\nthree").unwrap();
+
+        assert_eq!(
+            read_last_n_lines(&path, 100).unwrap(),
+            vec!["one", "two", "three"]
+        );
+        assert_eq!(read_last_n_lines(&path, 1).unwrap(), vec!["three"]);
+        assert!(read_last_n_lines(&path, 0).unwrap().is_empty());
+    }
+}
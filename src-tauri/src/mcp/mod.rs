@@ -647,10 +647,10 @@ async fn handle_tools_call(
     result
 }
 
-/// Read the current proxy port from config (engine listens on this port).
+/// Port the engine listens on (config, or the fallback chosen at start).
 fn get_engine_port() -> u16 {
     crate::config::load_config()
-        .map(|c| c.proxy_port)
+        .map(|c| crate::proxy::engine_port(&c))
         .unwrap_or(9090)
 }
 
@@ -1263,7 +1263,7 @@ async fn tool_replay_request(
 
     // Build headers: start from original, apply overrides
     let proxy_port = crate::config::load_config()
-        .map(|c| c.proxy_port)
+        .map(|c| crate::proxy::engine_port(&c))
         .unwrap_or(9090);
     let proxy_url = format!("http://127.0.0.1:{proxy_port}");
 
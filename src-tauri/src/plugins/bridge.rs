@@ -188,6 +188,9 @@ pub async fn plugin_call(
                 url: args.url,
                 headers: args.headers.unwrap_or_default(),
                 body: args.body,
+                // Plugins have always seen the final response
+                follow_redirects: true,
+                ..Default::default()
             };
             let response = crate::traffic::commands::replay_request_inner(req).await?;
             serde_json::to_value(response).map_err(|e| e.to_string())
@@ -442,6 +445,8 @@ pub async fn plugin_call(
                     enabled: true,
                     priority: target_priority.unwrap_or(next_priority),
                     stop_on_match: None,
+                    disable_after_hit: None,
+                    times: None,
                 },
                 match_config: crate::rules::model::RuleMatchConfig {
                     request: request_atoms,
@@ -451,6 +456,7 @@ pub async fn plugin_call(
                     crate::rules::model::MapLocalAction {
                         source: Some("manual".to_string()),
                         local_path: None,
+                        dir: None,
                         content: Some(args.response_body),
                         content_type: Some(
                             args.content_type
@@ -597,7 +603,7 @@ pub async fn plugin_call(
             let mcp_port = *mcp_state.port.lock().expect("mcp port lock poisoned");
 
             Ok(serde_json::json!({
-                "proxyPort": config.proxy_port,
+                "proxyPort": crate::proxy::engine_port(&config),
                 "proxyRunning": proxy_status.running,
                 "proxyActive": proxy_status.active,
                 "mcpEnabled": config.mcp_config.enabled,
@@ -634,7 +640,7 @@ pub async fn plugin_call(
             let limit = args.limit.min(1000);
 
             let engine_port = crate::config::load_config()
-                .map(|c| c.proxy_port)
+                .map(|c| crate::proxy::engine_port(&c))
                 .unwrap_or(9090);
 
             let mut url = format!("http://127.0.0.1:{engine_port}/_relay/poll?since=0");
@@ -760,7 +766,7 @@ pub async fn plugin_call(
                 .min(2 * 1024 * 1024); // 128 KB default, hard cap 2 MB
 
             let engine_port = crate::config::load_config()
-                .map(|c| c.proxy_port)
+                .map(|c| crate::proxy::engine_port(&c))
                 .unwrap_or(9090);
 
             let url = format!("http://127.0.0.1:{engine_port}/_relay/detail?id={id}");
@@ -127,6 +127,15 @@ pub async fn plugin_install_local_zip(path: String, _app: AppHandle) -> Result<S
     Ok(id)
 }
 
+/// Check a theme archive before installing it, so broken themes can be
+/// rejected up front instead of breaking the UI once selected.
+#[tauri::command]
+pub async fn validate_theme_zip(path: String) -> Result<crate::plugins::ThemeValidation, String> {
+    Ok(crate::plugins::validate_theme_zip(std::path::Path::new(
+        &path,
+    )))
+}
+
 #[tauri::command]
 pub async fn get_themes(
     _app: AppHandle,
@@ -216,6 +216,145 @@ pub fn get_enabled_plugin_scripts(plugins_dir: &Path, enabled_ids: &[String]) ->
     scripts
 }
 
+/// Color variables every theme must define; without them core surfaces render
+/// with no background or text color.
+pub const REQUIRED_THEME_COLORS: &[&str] = &[
+    "--color-background",
+    "--color-foreground",
+    "--color-card",
+    "--color-card-foreground",
+    "--color-muted-foreground",
+    "--color-primary",
+    "--color-border",
+];
+
+/// Outcome of inspecting a theme archive without installing it
+#[derive(Debug, Clone, Default, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThemeValidation {
+    pub valid: bool,
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Inspect a theme zip's manifest for required fields and colors.
+/// Archive-level problems (unreadable zip, missing manifest) are reported as
+/// validation errors rather than `Err` so the UI can show them uniformly.
+pub fn validate_theme_zip(zip_path: &Path) -> ThemeValidation {
+    use std::io::Read;
+
+    let mut result = ThemeValidation::default();
+
+    let archive = fs::File::open(zip_path)
+        .map_err(|e| format!("Failed to open zip: {}", e))
+        .and_then(|f| zip::ZipArchive::new(f).map_err(|e| format!("Invalid zip archive: {}", e)));
+    let mut archive = match archive {
+        Ok(a) => a,
+        Err(e) => {
+            result.errors.push(e);
+            return result;
+        }
+    };
+
+    let entry_names: Vec<String> = archive.file_names().map(|n| n.to_string()).collect();
+    let Some(manifest_name) = entry_names
+        .iter()
+        .find(|n| n.ends_with("theme.yaml") || n.ends_with("theme.yml"))
+        .cloned()
+    else {
+        result
+            .errors
+            .push("Archive does not contain theme.yaml".to_string());
+        return result;
+    };
+
+    let mut content = String::new();
+    match archive.by_name(&manifest_name) {
+        Ok(mut file) if file.size() <= 1024 * 1024 => {
+            if let Err(e) = file.read_to_string(&mut content) {
+                result
+                    .errors
+                    .push(format!("Failed to read theme manifest: {}", e));
+                return result;
+            }
+        }
+        Ok(_) => {
+            result
+                .errors
+                .push("theme manifest is too large".to_string());
+            return result;
+        }
+        Err(e) => {
+            result.errors.push(e.to_string());
+            return result;
+        }
+    }
+
+    let manifest: crate::plugins::config::ThemeManifest = match serde_yaml::from_str(&content) {
+        Ok(m) => m,
+        Err(e) => {
+            result.errors.push(format!("Invalid theme manifest: {}", e));
+            return result;
+        }
+    };
+
+    if manifest.id.trim().is_empty() {
+        result.errors.push("Theme id is empty".to_string());
+    }
+    if manifest.name.trim().is_empty() {
+        result.errors.push("Theme name is empty".to_string());
+    }
+    if manifest.r#type != "light" && manifest.r#type != "dark" {
+        result.errors.push(format!(
+            "Theme type must be 'light' or 'dark', got '{}'",
+            manifest.r#type
+        ));
+    }
+
+    for key in REQUIRED_THEME_COLORS {
+        match manifest.colors.get(*key) {
+            Some(v) if !v.trim().is_empty() => {}
+            _ => result
+                .errors
+                .push(format!("Missing required color '{}'", key)),
+        }
+    }
+
+    let mut unknown: Vec<&String> = manifest
+        .colors
+        .keys()
+        .filter(|k| !k.starts_with("--"))
+        .collect();
+    unknown.sort();
+    for key in unknown {
+        result.warnings.push(format!(
+            "Color key '{}' is not a CSS variable and will be ignored",
+            key
+        ));
+    }
+
+    if let Some(css) = manifest.css.as_deref() {
+        let base = manifest_name
+            .rsplit_once('/')
+            .map(|(dir, _)| format!("{}/", dir))
+            .unwrap_or_default();
+        let css_entry = format!("{}{}", base, css.trim_start_matches("./"));
+        if !entry_names.iter().any(|n| n == &css_entry) {
+            result.errors.push(format!(
+                "Stylesheet '{}' referenced by the theme is missing",
+                css
+            ));
+        }
+    }
+
+    result.id = Some(manifest.id);
+    result.name = Some(manifest.name);
+    result.valid = result.errors.is_empty();
+    result
+}
+
 /// Unzips a .rcplugin or .zip file and installs it as a plugin or theme.
 /// Returns the installed ID.
 pub fn install_plugin_from_zip(zip_path: &Path, app_dir: &Path) -> Result<String, String> {
@@ -472,4 +611,57 @@ mod tests {
         let path = resolve_plugin_path(plugins_dir, "correct-id").unwrap();
         assert!(path.ends_with("weird-name"));
     }
+
+    fn write_theme_zip(path: &Path, manifest: &str, extra: &[(&str, &str)]) {
+        use std::io::Write;
+        let mut zip = zip::ZipWriter::new(fs::File::create(path).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        zip.start_file("my-theme/theme.yaml", options).unwrap();
+        zip.write_all(manifest.as_bytes()).unwrap();
+        for (name, body) in extra {
+            zip.start_file(*name, options).unwrap();
+            zip.write_all(body.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn test_validate_theme_zip() {
+        let temp = TempDir::new().unwrap();
+        let colors: String = REQUIRED_THEME_COLORS
+            .iter()
+            .map(|k| format!("  \"{}\": \"#000000\"\n", k))
+            .collect();
+
+        let good = temp.path().join("good.zip");
+        write_theme_zip(
+            &good,
+            &format!(
+                "id: my-theme\nname: Mine\nversion: 1.0.0\ntype: dark\ncss: theme.css\ncolors:\n{}",
+                colors
+            ),
+            &[("my-theme/theme.css", "body {}")],
+        );
+        let validation = validate_theme_zip(&good);
+        assert!(validation.valid, "{:?}", validation.errors);
+        assert_eq!(validation.id.as_deref(), Some("my-theme"));
+
+        let bad = temp.path().join("bad.zip");
+        write_theme_zip(
+            &bad,
+            "id: broken\nname: Broken\nversion: 1.0.0\ntype: neon\ncss: missing.css\ncolors:\n  \"--color-primary\": \"#fff\"\n",
+            &[],
+        );
+        let validation = validate_theme_zip(&bad);
+        assert!(!validation.valid);
+        assert!(validation.errors.iter().any(|e| e.contains("type")));
+        assert!(validation
+            .errors
+            .iter()
+            .any(|e| e.contains("--color-background")));
+        assert!(validation.errors.iter().any(|e| e.contains("missing.css")));
+
+        let validation = validate_theme_zip(&temp.path().join("nope.zip"));
+        assert!(!validation.valid);
+    }
 }
@@ -0,0 +1,163 @@
+//! Flows-per-second tracking for the running engine.
+//!
+//! The engine keeps cumulative counters of stored and failed flows. Each call
+//! to `get_capture_rate` samples them and derives the instantaneous rate (since
+//! the previous sample) and a rolling average over `WINDOW`.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use super::ProxyState;
+
+/// Samples older than this are dropped from the rolling average
+const WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureRate {
+    /// Flows per second since the previous sample
+    pub flows_per_sec: f64,
+    /// Flows per second across the rolling window
+    pub average_flows_per_sec: f64,
+    /// Time span actually covered by the average
+    pub window_secs: f64,
+    pub total_captured: u64,
+    /// Flows the engine failed to store; a rising count means it is dropping data
+    pub failed: u64,
+}
+
+#[derive(Default)]
+pub struct CaptureRateTracker {
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl CaptureRateTracker {
+    /// Add a sample of the engine's cumulative counters taken at `now`
+    pub fn record(&mut self, now: Instant, captured: u64, failed: u64) -> CaptureRate {
+        // A lower count means the engine restarted and its counters reset
+        if self
+            .samples
+            .back()
+            .is_some_and(|(_, last)| captured < *last)
+        {
+            self.samples.clear();
+        }
+
+        let rate_since = |(at, count): (Instant, u64)| {
+            let secs = now.duration_since(at).as_secs_f64();
+            if secs > 0.0 {
+                (captured - count) as f64 / secs
+            } else {
+                0.0
+            }
+        };
+
+        let flows_per_sec = self.samples.back().copied().map(rate_since).unwrap_or(0.0);
+
+        self.samples.push_back((now, captured));
+        while self
+            .samples
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) > WINDOW)
+        {
+            self.samples.pop_front();
+        }
+
+        let oldest = self.samples.front().copied();
+        CaptureRate {
+            flows_per_sec,
+            average_flows_per_sec: oldest.map(rate_since).unwrap_or(0.0),
+            window_secs: oldest
+                .map(|(at, _)| now.duration_since(at).as_secs_f64())
+                .unwrap_or(0.0),
+            total_captured: captured,
+            failed,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.samples.clear();
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct EngineCaptureCount {
+    captured: u64,
+    failed: u64,
+}
+
+/// Sample the engine's capture counters and return the current rate.
+/// Poll this at a steady interval; the first call only establishes a baseline.
+#[tauri::command]
+pub async fn get_capture_rate(state: tauri::State<'_, ProxyState>) -> Result<CaptureRate, String> {
+    if !state.engine.get_status().running {
+        state
+            .capture_rate
+            .lock()
+            .map_err(|_| "capture rate lock poisoned".to_string())?
+            .reset();
+        return Ok(CaptureRate::default());
+    }
+
+    let config = crate::config::load_config().unwrap_or_default();
+    let url = format!(
+        "http://127.0.0.1:{}/_relay/capture_count",
+        crate::proxy::engine_port(&config)
+    );
+    let counts: EngineCaptureCount = reqwest::Client::new()
+        .get(&url)
+        .timeout(Duration::from_secs(3))
+        .send()
+        .await
+        .map_err(|e| format!("engine_error: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("engine_error: {}", e))?;
+
+    let rate = state
+        .capture_rate
+        .lock()
+        .map_err(|_| "capture rate lock poisoned".to_string())?
+        .record(Instant::now(), counts.captured, counts.failed);
+    Ok(rate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_rate_over_samples() {
+        let start = Instant::now();
+        let mut tracker = CaptureRateTracker::default();
+
+        let first = tracker.record(start, 100, 0);
+        assert_eq!(first.flows_per_sec, 0.0);
+
+        let second = tracker.record(start + Duration::from_secs(2), 120, 0);
+        assert_eq!(second.flows_per_sec, 10.0);
+
+        let third = tracker.record(start + Duration::from_secs(4), 180, 1);
+        assert_eq!(third.flows_per_sec, 30.0);
+        assert_eq!(third.average_flows_per_sec, 20.0);
+        assert_eq!(third.window_secs, 4.0);
+        assert_eq!(third.failed, 1);
+
+        // Samples older than the window no longer count toward the average
+        let late = tracker.record(start + Duration::from_secs(63), 240, 1);
+        assert_eq!(late.window_secs, 59.0);
+    }
+
+    #[test]
+    fn test_capture_rate_resets_after_engine_restart() {
+        let start = Instant::now();
+        let mut tracker = CaptureRateTracker::default();
+        tracker.record(start, 500, 0);
+
+        let after_restart = tracker.record(start + Duration::from_secs(1), 3, 0);
+        assert_eq!(after_restart.flows_per_sec, 0.0);
+        assert_eq!(after_restart.total_captured, 3);
+    }
+}
@@ -2,15 +2,37 @@ use crate::logging;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter};
 
-use super::EngineInner;
+use super::{EngineInner, MitmproxyEngine, ProxyEngine};
+
+/// Auto-restart gives up after this many consecutive failed attempts
+const MAX_AUTO_RESTARTS: u32 = 5;
+
+/// A run this long counts as stable and resets the attempt counter
+const STABLE_RUN: Duration = Duration::from_secs(60);
+
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(30);
+
+/// Exponential backoff before restart attempt `attempt` (1-based): 1s, 2s, 4s, ...
+fn restart_delay(attempt: u32) -> Duration {
+    Duration::from_secs(1u64 << attempt.saturating_sub(1).min(5)).min(MAX_RESTART_DELAY)
+}
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RestartAttempt {
+    attempt: u32,
+    max_attempts: u32,
+    delay_ms: u64,
+}
 
 pub(super) fn spawn_crash_watcher(inner: Arc<EngineInner>, app: AppHandle) {
     std::thread::Builder::new()
         .name("rc-crash-watcher".into())
         .spawn(move || {
+            let started = Instant::now();
             loop {
                 thread::sleep(Duration::from_secs(2));
                 let mut lock = match inner.child.lock() {
@@ -22,7 +44,8 @@ pub(super) fn spawn_crash_watcher(inner: Arc<EngineInner>, app: AppHandle) {
                     match child.try_wait() {
                         Ok(Some(status)) => {
                             // Process exited
-                            if !inner.is_stopping.load(Ordering::SeqCst) {
+                            let crashed = !inner.is_stopping.load(Ordering::SeqCst);
+                            if crashed {
                                 let msg = format!(
                                     "Proxy engine (PID {}) exited unexpectedly with status: {}. Check engine.log for details.",
                                     child.id(),
@@ -37,6 +60,13 @@ pub(super) fn spawn_crash_watcher(inner: Arc<EngineInner>, app: AppHandle) {
                             if let Ok(mut active) = inner.active_scripts.lock() {
                                 active.clear();
                             }
+                            drop(lock);
+                            if crashed {
+                                if started.elapsed() >= STABLE_RUN {
+                                    inner.crash_restarts.store(0, Ordering::SeqCst);
+                                }
+                                auto_restart(&inner, &app);
+                            }
                             break;
                         }
                         Ok(None) => {
@@ -57,3 +87,79 @@ pub(super) fn spawn_crash_watcher(inner: Arc<EngineInner>, app: AppHandle) {
         })
         .ok();
 }
+
+/// Restart the engine with the last-used config when `auto_restart_on_crash`
+/// is on, backing off between attempts. A successful start spawns a fresh
+/// watcher, so this returns after the first success.
+fn auto_restart(inner: &Arc<EngineInner>, app: &AppHandle) {
+    let Some(config) = inner.last_config.lock().ok().and_then(|c| c.clone()) else {
+        return;
+    };
+    if !config.auto_restart_on_crash {
+        return;
+    }
+    let was_active = inner.traffic_active.load(Ordering::SeqCst);
+    let engine = MitmproxyEngine {
+        inner: inner.clone(),
+    };
+
+    loop {
+        let attempt = inner.crash_restarts.fetch_add(1, Ordering::SeqCst) + 1;
+        if attempt > MAX_AUTO_RESTARTS {
+            let msg = format!(
+                "Proxy engine keeps crashing; gave up after {} restart attempts",
+                MAX_AUTO_RESTARTS
+            );
+            log::error!("{}", msg);
+            logging::write_domain_log("crash", &msg).ok();
+            let _ = app.emit("proxy-engine-restart-failed", &msg);
+            return;
+        }
+
+        let delay = restart_delay(attempt);
+        let _ = app.emit(
+            "proxy-engine-restarting",
+            RestartAttempt {
+                attempt,
+                max_attempts: MAX_AUTO_RESTARTS,
+                delay_ms: delay.as_millis() as u64,
+            },
+        );
+        thread::sleep(delay);
+
+        // The user stopped or restarted the engine while we were waiting
+        let child_present = inner.child.lock().map(|c| c.is_some()).unwrap_or(true);
+        if inner.is_stopping.load(Ordering::SeqCst) || child_present {
+            return;
+        }
+
+        match engine.start(app, &config) {
+            Ok(()) => {
+                let msg = format!("Proxy engine restarted after crash (attempt {})", attempt);
+                log::info!("{}", msg);
+                logging::write_domain_log("crash", &msg).ok();
+                if was_active {
+                    let _ = engine.set_active(true);
+                }
+                let _ = app.emit("proxy-engine-restarted", attempt);
+                return;
+            }
+            Err(e) => {
+                let msg = format!("Proxy engine restart attempt {} failed: {}", attempt, e);
+                log::warn!("{}", msg);
+                logging::write_domain_log("crash", &msg).ok();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restart_delay_backs_off_exponentially_with_cap() {
+        let delays: Vec<u64> = (1..=8).map(|a| restart_delay(a).as_secs()).collect();
+        assert_eq!(delays, vec![1, 2, 4, 8, 16, 30, 30, 30]);
+    }
+}
@@ -1,13 +1,33 @@
 use crate::logging;
 use std::io::BufRead;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter};
 
-pub(super) fn spawn_log_forwarder(stream: Option<impl std::io::Read + Send + 'static>) {
+use super::EngineInner;
+
+pub(super) fn spawn_log_forwarder(
+    stream: Option<impl std::io::Read + Send + 'static>,
+    inner: Arc<EngineInner>,
+    app: AppHandle,
+) {
     if let Some(s) = stream {
         let reader = std::io::BufReader::new(s);
         std::thread::Builder::new()
             .name("rc-log-forwarder".into())
             .spawn(move || {
                 for line in reader.lines().flatten() {
+                    // Rule hit markers feed the per-rule counters, not the logs
+                    if crate::rules::stats::record_marker(&line) {
+                        continue;
+                    }
+                    if let Some(id) = crate::rules::stats::parse_spent_marker(&line) {
+                        crate::rules::handle_spent_rule(&app, id);
+                        continue;
+                    }
+                    // Still logged below; the event lets the UI flag the script
+                    if let Some(event) = crate::scripts::errors::parse_script_error(&line) {
+                        let _ = app.emit("script-error", event);
+                    }
                     // Classify log domain based on content markers
                     let domain = if line.contains("[SCRIPT]")
                         || line.contains("[RELAYCRAFT][SCRIPT]")
@@ -25,7 +45,16 @@ pub(super) fn spawn_log_forwarder(stream: Option<impl std::io::Read + Send + 'st
                     } else {
                         "proxy"
                     };
+                    inner.push_output(&line);
                     logging::write_domain_log(domain, &line).ok();
+                    if logging::log_streaming_enabled() {
+                        let _ = app.emit("proxy-log", logging::LogLineEvent::new(domain, line));
+                    }
+                }
+                // The engine has stopped; save hits still waiting for the
+                // next timed flush
+                if let Err(e) = crate::rules::stats::flush_pending() {
+                    log::warn!("Failed to save rule stats: {}", e);
                 }
             })
             .ok();
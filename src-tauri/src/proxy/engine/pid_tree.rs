@@ -0,0 +1,128 @@
+use std::collections::{HashMap, VecDeque};
+
+/// Limits applied while walking the application's process tree.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct PidTreeLimits {
+    pub max_depth: usize,
+    pub max_processes: usize,
+}
+
+/// Collect `root` and its descendants breadth-first from `(pid, parent)` pairs.
+///
+/// The parent→children index is built in a single pass, so the walk is linear
+/// in the number of processes instead of rescanning the whole table for every
+/// node. Depth and breadth are capped so a pathological process table cannot
+/// make a stats poll expensive; the closest descendants are kept first.
+pub(super) fn collect_descendants(
+    root: u32,
+    processes: impl IntoIterator<Item = (u32, Option<u32>)>,
+    limits: PidTreeLimits,
+) -> Vec<u32> {
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for (pid, parent) in processes {
+        if let Some(parent) = parent {
+            if parent != pid {
+                children.entry(parent).or_default().push(pid);
+            }
+        }
+    }
+
+    let max_processes = limits.max_processes.max(1);
+    let mut result = vec![root];
+    let mut queue = VecDeque::from([(root, 0usize)]);
+
+    while let Some((pid, depth)) = queue.pop_front() {
+        if depth >= limits.max_depth {
+            continue;
+        }
+        let Some(kids) = children.remove(&pid) else {
+            continue;
+        };
+        for kid in kids {
+            if result.len() >= max_processes {
+                return result;
+            }
+            result.push(kid);
+            queue.push_back((kid, depth + 1));
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNBOUNDED: PidTreeLimits = PidTreeLimits {
+        max_depth: usize::MAX,
+        max_processes: usize::MAX,
+    };
+
+    #[test]
+    fn test_collects_only_descendants() {
+        let table = vec![
+            (1, None),
+            (10, Some(1)),
+            (11, Some(10)),
+            (12, Some(11)),
+            (20, Some(1)),
+            (30, Some(99)),
+        ];
+        let mut pids = collect_descendants(10, table, UNBOUNDED);
+        pids.sort();
+        assert_eq!(pids, vec![10, 11, 12]);
+    }
+
+    #[test]
+    fn test_respects_depth_and_breadth_limits() {
+        let table = vec![(2, Some(1)), (3, Some(2)), (4, Some(3)), (5, Some(1))];
+
+        let shallow = collect_descendants(
+            1,
+            table.clone(),
+            PidTreeLimits {
+                max_depth: 1,
+                max_processes: usize::MAX,
+            },
+        );
+        assert_eq!(shallow, vec![1, 2, 5]);
+
+        let narrow = collect_descendants(
+            1,
+            table,
+            PidTreeLimits {
+                max_depth: usize::MAX,
+                max_processes: 2,
+            },
+        );
+        assert_eq!(narrow, vec![1, 2]);
+    }
+
+    /// Benchmark-style guard: a large synthetic process table (50k entries with
+    /// a deep chain) must be walked quickly and stay within the configured caps.
+    #[test]
+    fn test_large_process_table_is_bounded() {
+        let mut table: Vec<(u32, Option<u32>)> =
+            (2..50_000).map(|pid| (pid, Some(pid - 1))).collect();
+        table.extend((50_000..60_000).map(|pid| (pid, Some(1))));
+
+        let started = std::time::Instant::now();
+        let pids = collect_descendants(
+            1,
+            table,
+            PidTreeLimits {
+                max_depth: 8,
+                max_processes: 256,
+            },
+        );
+        let elapsed = started.elapsed();
+
+        assert_eq!(pids.len(), 256);
+        assert!(
+            elapsed < std::time::Duration::from_secs(1),
+            "tree walk took {:?}",
+            elapsed
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::EngineInner;
+
+/// Engine-reported speeds older than this are ignored, so callers fall back
+/// to system-wide numbers when the engine stops answering.
+const STALE_AFTER: Duration = Duration::from_secs(5);
+
+/// Samples closer together than this are skipped to avoid noisy speeds.
+const MIN_SAMPLE_GAP: Duration = Duration::from_millis(100);
+
+/// Derives rx/tx bytes per second from the engine's cumulative byte counters.
+#[derive(Default)]
+pub(super) struct ThroughputSampler {
+    /// Last counters seen: (sampled at, rx total, tx total)
+    last: Option<(Instant, u64, u64)>,
+    /// Most recent speeds: (computed at, rx/s, tx/s)
+    speeds: Option<(Instant, u64, u64)>,
+}
+
+impl ThroughputSampler {
+    pub(super) fn record(&mut self, now: Instant, rx_total: u64, tx_total: u64) {
+        if let Some((at, rx, tx)) = self.last {
+            let elapsed = now.duration_since(at);
+            if elapsed < MIN_SAMPLE_GAP {
+                return;
+            }
+            if rx_total < rx || tx_total < tx {
+                // Counters went backwards: the engine restarted
+                self.speeds = None;
+            } else {
+                let secs = elapsed.as_secs_f64();
+                self.speeds = Some((
+                    now,
+                    ((rx_total - rx) as f64 / secs) as u64,
+                    ((tx_total - tx) as f64 / secs) as u64,
+                ));
+            }
+        }
+        self.last = Some((now, rx_total, tx_total));
+    }
+
+    /// Latest (rx, tx) bytes per second, unless stale.
+    pub(super) fn speeds(&self, now: Instant) -> Option<(u64, u64)> {
+        self.speeds
+            .filter(|(at, _, _)| now.duration_since(*at) <= STALE_AFTER)
+            .map(|(_, rx, tx)| (rx, tx))
+    }
+
+    pub(super) fn reset(&mut self) {
+        self.last = None;
+        self.speeds = None;
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct EngineThroughput {
+    rx_bytes: u64,
+    tx_bytes: u64,
+}
+
+/// Fetch the engine's byte counters in the background and feed the sampler.
+/// At most one request is in flight; failures simply leave speeds to go stale.
+pub(super) fn poll_engine_throughput(inner: Arc<EngineInner>) {
+    let Some(port) = inner.last_port.lock().ok().and_then(|p| *p) else {
+        return;
+    };
+    if inner.throughput_polling.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    tauri::async_runtime::spawn(async move {
+        let url = format!("http://127.0.0.1:{}/_relay/throughput", port);
+        let result = async {
+            reqwest::Client::new()
+                .get(&url)
+                .timeout(Duration::from_secs(2))
+                .send()
+                .await?
+                .json::<EngineThroughput>()
+                .await
+        }
+        .await;
+
+        match result {
+            Ok(counters) => {
+                if let Ok(mut sampler) = inner.throughput.lock() {
+                    sampler.record(Instant::now(), counters.rx_bytes, counters.tx_bytes);
+                }
+            }
+            Err(e) => log::debug!("Engine throughput poll failed: {}", e),
+        }
+        inner.throughput_polling.store(false, Ordering::SeqCst);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn speeds_from_counter_deltas() {
+        let t0 = Instant::now();
+        let mut sampler = ThroughputSampler::default();
+        sampler.record(t0, 1_000, 500);
+        assert_eq!(sampler.speeds(t0), None);
+
+        let t1 = t0 + Duration::from_secs(2);
+        sampler.record(t1, 5_000, 1_500);
+        assert_eq!(sampler.speeds(t1), Some((2_000, 500)));
+
+        // Too close to the previous sample: ignored
+        sampler.record(t1 + Duration::from_millis(10), 9_000, 9_000);
+        assert_eq!(sampler.speeds(t1), Some((2_000, 500)));
+    }
+
+    #[test]
+    fn restart_and_staleness_clear_speeds() {
+        let t0 = Instant::now();
+        let mut sampler = ThroughputSampler::default();
+        sampler.record(t0, 10_000, 10_000);
+        let t1 = t0 + Duration::from_secs(1);
+        sampler.record(t1, 11_000, 10_500);
+        assert!(sampler.speeds(t1).is_some());
+        assert_eq!(sampler.speeds(t1 + Duration::from_secs(10)), None);
+
+        sampler.record(t1 + Duration::from_secs(1), 100, 100);
+        assert_eq!(sampler.speeds(t1 + Duration::from_secs(1)), None);
+    }
+}
@@ -1,15 +1,23 @@
+pub mod capture_rate;
 pub mod engine;
 pub mod monitor;
+pub mod orphans;
 pub mod paths;
+pub mod ports;
 pub mod process;
 
+pub use capture_rate::*;
 pub use engine::*;
 pub use monitor::*;
+pub use orphans::*;
+pub use ports::*;
 pub use process::*;
 // pub use paths::*;
 
 use crate::common::error::ToTauriError;
 use crate::config;
+use serde::Serialize;
+use std::time::Duration;
 use tauri::AppHandle;
 
 #[tauri::command]
@@ -35,22 +43,78 @@ pub async fn stop_proxy(state: tauri::State<'_, ProxyState>) -> Result<String, S
     Ok("Proxy stopped".to_string())
 }
 
+/// How long a restart waits for the old engine's ports to close
+const RESTART_PORT_RELEASE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Phase of a restart that failed
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum RestartPhase {
+    Stop,
+    PortRelease,
+    Config,
+    Start,
+}
+
+/// Structured restart failure, so the UI can tell which half went wrong
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestartError {
+    pub phase: RestartPhase,
+    pub message: String,
+}
+
+impl RestartError {
+    fn new(phase: RestartPhase, message: impl Into<String>) -> Self {
+        Self {
+            phase,
+            message: message.into(),
+        }
+    }
+}
+
+/// Stop the engine, wait until its ports are actually released, then start it
+/// again with freshly loaded config. Returns once the new ports accept connections.
 #[tauri::command]
 pub async fn restart_proxy(
     app: AppHandle,
     state: tauri::State<'_, ProxyState>,
-) -> Result<String, String> {
-    // Load configuration
-    let config = config::load_config()?;
+) -> Result<String, RestartError> {
+    restart_engine(state.engine.as_ref(), &app)?;
+    Ok("Proxy restarted".to_string())
+}
 
-    // Stop first, then start (this reloads scripts)
-    state.engine.stop().map_err(|e| e.to_tauri_error())?;
-    state
-        .engine
-        .start(&app, &config)
-        .map_err(|e| e.to_tauri_error())?;
+/// Restart sequence shared by `restart_proxy` and callers that must make a
+/// running engine pick up changed on-disk state (e.g. a regenerated CA).
+pub(crate) fn restart_engine(
+    engine: &dyn ProxyEngine,
+    app: &AppHandle,
+) -> Result<(), RestartError> {
+    let old_ports = engine.listen_ports();
+    engine
+        .stop()
+        .map_err(|e| RestartError::new(RestartPhase::Stop, e.to_tauri_error()))?;
+
+    let held = wait_for_ports_released(&old_ports, RESTART_PORT_RELEASE_TIMEOUT);
+    if !held.is_empty() {
+        let ports: Vec<String> = held.iter().map(u16::to_string).collect();
+        return Err(RestartError::new(
+            RestartPhase::PortRelease,
+            format!(
+                "Port(s) {} still in use {}s after stopping the engine",
+                ports.join(", "),
+                RESTART_PORT_RELEASE_TIMEOUT.as_secs()
+            ),
+        ));
+    }
 
-    Ok("Proxy restarted".to_string())
+    // Load config only now so edits made while stopping are picked up
+    let config = config::load_config().map_err(|e| RestartError::new(RestartPhase::Config, e))?;
+
+    // Start blocks until every configured port is ready (this reloads scripts)
+    engine
+        .start(app, &config)
+        .map_err(|e| RestartError::new(RestartPhase::Start, e.to_tauri_error()))
 }
 
 #[tauri::command]
@@ -63,9 +127,18 @@ pub async fn get_proxy_status(
         running: status.running,
         active: status.active,
         active_scripts: status.active_scripts,
+        port: status.port,
     })
 }
 
+/// Recent engine stdout/stderr lines kept in memory, oldest first
+#[tauri::command]
+pub async fn get_recent_engine_output(
+    state: tauri::State<'_, ProxyState>,
+) -> Result<Vec<String>, String> {
+    Ok(state.engine.recent_output())
+}
+
 #[tauri::command]
 pub async fn set_proxy_active(
     state: tauri::State<'_, ProxyState>,
@@ -97,4 +170,6 @@ pub struct ProxyStatusResponse {
     pub running: bool,
     pub active: bool,
     pub active_scripts: Vec<String>,
+    /// Port actually in use; differs from config after an automatic fallback
+    pub port: Option<u16>,
 }
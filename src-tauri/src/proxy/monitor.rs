@@ -1,5 +1,13 @@
 use crate::common::error::ToTauriError;
 use crate::proxy::process::ProxyState;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{ipc::Channel, AppHandle, Manager};
+
+/// Lower bound for the sampling interval. Network speed is only recomputed when
+/// more than 100ms have elapsed, so anything faster would just repeat samples.
+const MIN_SAMPLING_INTERVAL_MS: u64 = 200;
 
 #[derive(serde::Serialize)]
 pub struct ProcessStats {
@@ -10,10 +18,8 @@ pub struct ProcessStats {
     pub tx_speed: u64,
 }
 
-#[tauri::command]
-pub async fn get_process_stats(
-    state: tauri::State<'_, ProxyState>,
-) -> Result<ProcessStats, String> {
+/// Compute a single stats sample. Shared by the point query and the sampler loop.
+pub fn collect_process_stats(state: &ProxyState) -> Result<ProcessStats, String> {
     let mut sys = state
         .system
         .lock()
@@ -63,7 +69,75 @@ pub async fn get_process_stats(
         memory_usage: stats.memory_usage,
         cpu_usage: stats.cpu_usage,
         up_time: stats.up_time,
-        rx_speed,
-        tx_speed,
+        // Prefer what the proxy itself handled over system-wide traffic
+        rx_speed: stats.rx_speed.unwrap_or(rx_speed),
+        tx_speed: stats.tx_speed.unwrap_or(tx_speed),
     })
 }
+
+#[tauri::command]
+pub async fn get_process_stats(
+    state: tauri::State<'_, ProxyState>,
+) -> Result<ProcessStats, String> {
+    collect_process_stats(&state)
+}
+
+/// Start pushing stats samples to the frontend every `interval_ms` until
+/// `stop_stats_sampling` is called or the channel is dropped.
+/// Starting a new sampler replaces any sampler that is already running.
+#[tauri::command]
+pub async fn start_stats_sampling(
+    app: AppHandle,
+    state: tauri::State<'_, ProxyState>,
+    interval_ms: u64,
+    on_sample: Channel<ProcessStats>,
+) -> Result<(), String> {
+    let interval = Duration::from_millis(interval_ms.max(MIN_SAMPLING_INTERVAL_MS));
+    let stop = Arc::new(AtomicBool::new(false));
+
+    {
+        let mut sampler = state
+            .stats_sampler
+            .lock()
+            .map_err(|_| "stats sampler lock poisoned".to_string())?;
+        if let Some(previous) = sampler.replace(stop.clone()) {
+            previous.store(true, Ordering::SeqCst);
+        }
+    }
+
+    std::thread::Builder::new()
+        .name("rc-stats-sampler".into())
+        .spawn(move || {
+            let state = app.state::<ProxyState>();
+            while !stop.load(Ordering::SeqCst) {
+                let tick = Instant::now();
+                match collect_process_stats(&state) {
+                    Ok(sample) => {
+                        if on_sample.send(sample).is_err() {
+                            log::debug!("Stats sampling channel dropped, stopping sampler");
+                            break;
+                        }
+                    }
+                    Err(e) => log::warn!("Failed to collect stats sample: {}", e),
+                }
+                // Sleep for the remainder of the interval so sample spacing stays
+                // steady regardless of how long the collection itself took.
+                std::thread::sleep(interval.saturating_sub(tick.elapsed()));
+            }
+        })
+        .map_err(|e| format!("Failed to spawn stats sampler: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn stop_stats_sampling(state: tauri::State<'_, ProxyState>) -> Result<(), String> {
+    let mut sampler = state
+        .stats_sampler
+        .lock()
+        .map_err(|_| "stats sampler lock poisoned".to_string())?;
+    if let Some(stop) = sampler.take() {
+        stop.store(true, Ordering::SeqCst);
+    }
+    Ok(())
+}
@@ -212,6 +212,29 @@ pub fn get_engine_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
     }
 }
 
+/// Python interpreter for tooling such as script syntax checks. The bundled
+/// engine is a frozen binary that cannot run arbitrary modules, so this is
+/// `RELAYCRAFT_PYTHON` when set, otherwise the first `python3`/`python` on
+/// `PATH`. `None` when neither exists.
+pub fn get_python_path() -> Option<std::path::PathBuf> {
+    if let Some(custom) = std::env::var_os("RELAYCRAFT_PYTHON") {
+        let custom = std::path::PathBuf::from(custom);
+        return custom.is_file().then_some(custom);
+    }
+
+    let names: &[&str] = if cfg!(target_os = "windows") {
+        &["python.exe", "python3.exe"]
+    } else {
+        &["python3", "python"]
+    };
+    let path_var = std::env::var_os("PATH")?;
+    names.iter().find_map(|name| {
+        std::env::split_paths(&path_var)
+            .map(|dir| dir.join(name))
+            .find(|candidate| candidate.is_file())
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
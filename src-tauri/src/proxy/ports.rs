@@ -0,0 +1,160 @@
+use crate::config;
+use crate::proxy::orphans::find_orphans;
+use crate::proxy::process::ProxyState;
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::time::{Duration, Instant};
+
+/// Upper bound on how many ports a single scan may probe
+const MAX_SCAN_COUNT: u16 = 200;
+
+/// How many ports above `proxy_port` an automatic fallback may try
+pub(crate) const PORT_FALLBACK_ATTEMPTS: u16 = 100;
+
+/// Port the running engine listens on, 0 while stopped. Differs from
+/// `config.proxy_port` after an automatic fallback.
+static RUNNING_PROXY_PORT: AtomicU16 = AtomicU16::new(0);
+
+pub(crate) fn set_running_proxy_port(port: Option<u16>) {
+    RUNNING_PROXY_PORT.store(port.unwrap_or(0), Ordering::SeqCst);
+}
+
+/// Port to reach the engine's `/_relay` API on
+pub fn engine_port(config: &config::AppConfig) -> u16 {
+    match RUNNING_PROXY_PORT.load(Ordering::SeqCst) {
+        0 => config.proxy_port,
+        port => port,
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortStatus {
+    pub port: u16,
+    pub free: bool,
+    /// What is listening, when it can be identified (RelayCraft services or
+    /// leftover engine processes). `None` for free or unidentified ports.
+    pub occupant: Option<String>,
+}
+
+/// A port is free when it can be bound on the loopback interface, which is
+/// where the engine listens by default.
+pub(crate) fn is_port_free(port: u16) -> bool {
+    TcpListener::bind(("127.0.0.1", port)).is_ok()
+}
+
+/// First free port in `start..start + attempts`, skipping `reserved`
+/// (e.g. the gateway port, which the engine is about to bind as well).
+pub(crate) fn find_free_port(start: u16, attempts: u16, reserved: &[u16]) -> Option<u16> {
+    (0..attempts)
+        .map_while(|offset| start.checked_add(offset))
+        .filter(|port| !reserved.contains(port))
+        .find(|port| is_port_free(*port))
+}
+
+/// Poll until every port can be bound again or `timeout` elapses.
+/// Returns the ports that are still held.
+pub(crate) fn wait_for_ports_released(ports: &[u16], timeout: Duration) -> Vec<u16> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let held: Vec<u16> = ports
+            .iter()
+            .copied()
+            .filter(|port| !is_port_free(*port))
+            .collect();
+        if held.is_empty() || Instant::now() >= deadline {
+            return held;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+/// Probe `count` ports starting at `start` and report which are in use
+#[tauri::command]
+pub async fn scan_free_ports(
+    state: tauri::State<'_, ProxyState>,
+    start: u16,
+    count: u16,
+) -> Result<Vec<PortStatus>, String> {
+    let count = count.clamp(1, MAX_SCAN_COUNT);
+    let app_config = config::load_config().unwrap_or_default();
+    let engine_running = state.engine.get_status().running;
+
+    let mut known: Vec<(u16, String)> = Vec::new();
+    if engine_running {
+        known.push((engine_port(&app_config), "RelayCraft proxy".to_string()));
+        if app_config.gateway.enabled {
+            known.push((app_config.gateway.port, "RelayCraft gateway".to_string()));
+        }
+    }
+    if app_config.mcp_config.enabled {
+        known.push((
+            app_config.mcp_config.port,
+            "RelayCraft MCP server".to_string(),
+        ));
+    }
+    // Orphan lookup is best effort; a failed process scan only loses labels
+    for orphan in find_orphans(&state).unwrap_or_default() {
+        for port in orphan.ports {
+            known.push((port, format!("Orphaned engine (PID {})", orphan.pid)));
+        }
+    }
+
+    let end = start.saturating_add(count - 1);
+    Ok((start..=end)
+        .map(|port| {
+            let free = is_port_free(port);
+            let occupant = if free {
+                None
+            } else {
+                known
+                    .iter()
+                    .find(|(p, _)| *p == port)
+                    .map(|(_, label)| label.clone())
+            };
+            PortStatus {
+                port,
+                free,
+                occupant,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_free_port_skips_taken_and_reserved() {
+        let taken = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = taken.local_addr().unwrap().port();
+
+        let found = find_free_port(port, 20, &[]).unwrap();
+        assert_ne!(found, port);
+        assert!(found > port);
+
+        let after_reserved = find_free_port(port, 20, &[found]).unwrap();
+        assert_ne!(after_reserved, port);
+        assert_ne!(after_reserved, found);
+
+        assert_eq!(find_free_port(port, 1, &[]), None);
+        assert_eq!(find_free_port(u16::MAX, 5, &[u16::MAX]), None);
+    }
+
+    #[test]
+    fn test_wait_for_ports_released() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let held = wait_for_ports_released(&[port], Duration::from_millis(200));
+        assert_eq!(held, vec![port]);
+
+        let releaser = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(150));
+            drop(listener);
+        });
+        assert!(wait_for_ports_released(&[port], Duration::from_secs(5)).is_empty());
+        releaser.join().unwrap();
+    }
+}
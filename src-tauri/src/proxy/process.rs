@@ -1,4 +1,5 @@
 use crate::proxy::engine::ProxyEngine;
+use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, Mutex};
 
 pub struct ProxyState {
@@ -8,6 +9,10 @@ pub struct ProxyState {
     pub last_rx: Mutex<u64>,
     pub last_tx: Mutex<u64>,
     pub last_update: Mutex<std::time::Instant>,
+    /// Stop flag of the running stats sampler, if any
+    pub stats_sampler: Mutex<Option<Arc<AtomicBool>>>,
+    /// Recent engine capture counter samples
+    pub capture_rate: Mutex<super::CaptureRateTracker>,
 }
 
 impl Drop for ProxyState {
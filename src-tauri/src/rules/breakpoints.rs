@@ -0,0 +1,165 @@
+//! Breakpoint storage, kept in `data/breakpoints.yaml` apart from the rules
+//! directory so debugging setup never mixes with traffic-shaping rules.
+
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::error::RuleError;
+
+/// A single breakpoint, in the shape the engine's debug manager accepts
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Breakpoint {
+    pub id: String,
+    pub pattern: String,
+    /// "contains", "exact" or "regex"
+    #[serde(default = "default_match_type")]
+    pub match_type: String,
+    #[serde(default = "default_true")]
+    pub break_on_request: bool,
+    #[serde(default)]
+    pub break_on_response: bool,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_match_type() -> String {
+    "contains".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// The persisted breakpoint set with a master switch for the whole set
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BreakpointSet {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub breakpoints: Vec<Breakpoint>,
+}
+
+impl Default for BreakpointSet {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            breakpoints: Vec::new(),
+        }
+    }
+}
+
+impl BreakpointSet {
+    /// Breakpoints that should be pushed to the engine right now
+    pub fn active(&self) -> Vec<Breakpoint> {
+        if !self.enabled {
+            return Vec::new();
+        }
+        self.breakpoints
+            .iter()
+            .filter(|b| b.enabled)
+            .cloned()
+            .collect()
+    }
+}
+
+pub struct BreakpointStorage {
+    path: PathBuf,
+}
+
+impl BreakpointStorage {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Create storage from app config
+    pub fn from_config() -> Result<Self, RuleError> {
+        let data_dir = crate::config::get_data_dir().map_err(RuleError::Invalid)?;
+        Ok(Self::new(data_dir.join("breakpoints.yaml")))
+    }
+
+    /// Load the saved set; a missing file is an empty, enabled set
+    pub fn load(&self) -> Result<BreakpointSet, RuleError> {
+        if !self.path.exists() {
+            return Ok(BreakpointSet::default());
+        }
+        let content = fs::read_to_string(&self.path)?;
+        if content.trim().is_empty() {
+            return Ok(BreakpointSet::default());
+        }
+        serde_yaml::from_str(&content).map_err(|e| RuleError::Parse(e.to_string()))
+    }
+
+    pub fn save(&self, set: &BreakpointSet) -> Result<(), RuleError> {
+        let content =
+            serde_yaml::to_string(set).map_err(|e| RuleError::Serialization(e.to_string()))?;
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, content)?;
+        Ok(())
+    }
+
+    pub fn clear(&self) -> Result<(), RuleError> {
+        if self.path.exists() {
+            fs::remove_file(&self.path)?;
+        }
+        Ok(())
+    }
+
+    /// Flip the master switch without touching individual breakpoints
+    pub fn set_enabled(&self, enabled: bool) -> Result<BreakpointSet, RuleError> {
+        let mut set = self.load()?;
+        set.enabled = enabled;
+        self.save(&set)?;
+        Ok(set)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_breakpoints_round_trip_and_toggle() {
+        let temp = TempDir::new().unwrap();
+        let storage = BreakpointStorage::new(temp.path().join("breakpoints.yaml"));
+        assert_eq!(storage.load().unwrap(), BreakpointSet::default());
+
+        let set = BreakpointSet {
+            enabled: true,
+            breakpoints: vec![
+                Breakpoint {
+                    id: "bp-1".into(),
+                    pattern: "/api/login".into(),
+                    match_type: "contains".into(),
+                    break_on_request: true,
+                    break_on_response: false,
+                    enabled: true,
+                },
+                Breakpoint {
+                    id: "bp-2".into(),
+                    pattern: "^https://cdn".into(),
+                    match_type: "regex".into(),
+                    break_on_request: false,
+                    break_on_response: true,
+                    enabled: false,
+                },
+            ],
+        };
+        storage.save(&set).unwrap();
+        assert_eq!(storage.load().unwrap(), set);
+        assert_eq!(set.active().len(), 1);
+
+        let disabled = storage.set_enabled(false).unwrap();
+        assert!(disabled.active().is_empty());
+        assert_eq!(storage.load().unwrap().breakpoints.len(), 2);
+
+        storage.clear().unwrap();
+        assert_eq!(storage.load().unwrap(), BreakpointSet::default());
+    }
+}
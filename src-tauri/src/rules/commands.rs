@@ -1,7 +1,72 @@
-use super::model::{Rule, RuleGroup};
-use super::storage::{ImportResult, RuleStorage};
+use super::breakpoints::{BreakpointSet, BreakpointStorage};
+use super::conflicts::{find_conflicts, RuleConflict};
+use super::matcher::{test_rule_against, RuleTestResult};
+use super::model::{Rule, RuleGroup, ThrottleAction};
+use super::stats::{self, RuleStats, RuleStatsStore};
+use super::storage::{ImportResult, RuleEntry, RuleStorage};
 use crate::common::error::ToTauriError;
+use crate::proxy::ProxyState;
+use crate::session::model::Flow;
 use std::path::Path;
+use tauri::{AppHandle, Manager};
+
+/// Outcome of asking the engine to reload rules
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReloadRulesResult {
+    /// False when the engine is not running and nothing was reloaded
+    pub reloaded: bool,
+    /// Number of rules active after the reload
+    pub rule_count: Option<usize>,
+}
+
+/// Ask the running engine to re-read the rules directory immediately instead
+/// of waiting for its change detection to notice edited files.
+async fn request_engine_reload(state: &ProxyState) -> Result<ReloadRulesResult, String> {
+    if !state.engine.get_status().running {
+        return Ok(ReloadRulesResult {
+            reloaded: false,
+            rule_count: None,
+        });
+    }
+
+    let config = crate::config::load_config().unwrap_or_default();
+    let url = format!(
+        "http://127.0.0.1:{}/_relay/reload_rules",
+        crate::proxy::engine_port(&config)
+    );
+
+    let response = reqwest::Client::new()
+        .post(&url)
+        .timeout(std::time::Duration::from_secs(5))
+        .send()
+        .await
+        .map_err(|e| format!("engine_error: {}", e))?;
+
+    let status = response.status();
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+    if !status.is_success() {
+        let message = body["message"].as_str().unwrap_or("unknown error");
+        return Err(format!(
+            "engine_error: HTTP {} {}",
+            status.as_u16(),
+            message
+        ));
+    }
+
+    Ok(ReloadRulesResult {
+        reloaded: true,
+        rule_count: body["count"].as_u64().map(|c| c as usize),
+    })
+}
+
+/// Reload rules into the running engine without restarting it
+#[tauri::command]
+pub async fn reload_rules(
+    state: tauri::State<'_, ProxyState>,
+) -> Result<ReloadRulesResult, String> {
+    request_engine_reload(&state).await
+}
 
 /// Get rules directory path
 #[tauri::command]
@@ -21,9 +86,14 @@ pub fn load_all_rules() -> Result<String, String> {
     serde_json::to_string(&response).map_err(|e| format!("Failed to serialize response: {}", e))
 }
 
-/// Save rule
+/// Save rule, optionally pushing the change to the running engine right away
 #[tauri::command]
-pub fn save_rule(rule_json: String, group_id: Option<String>) -> Result<(), String> {
+pub async fn save_rule(
+    state: tauri::State<'_, ProxyState>,
+    rule_json: String,
+    group_id: Option<String>,
+    reload: Option<bool>,
+) -> Result<(), String> {
     let storage = RuleStorage::from_config().map_err(|e| e.to_tauri_error())?;
 
     let rule: Rule =
@@ -31,15 +101,106 @@ pub fn save_rule(rule_json: String, group_id: Option<String>) -> Result<(), Stri
 
     storage
         .save(&rule, group_id.as_deref())
+        .map_err(|e| e.to_tauri_error())?;
+
+    if reload.unwrap_or(false) {
+        request_engine_reload(&state).await?;
+    }
+    Ok(())
+}
+
+/// Delete rule, optionally pushing the change to the running engine right away
+#[tauri::command]
+pub async fn delete_rule(
+    state: tauri::State<'_, ProxyState>,
+    rule_id: String,
+    reload: Option<bool>,
+) -> Result<(), String> {
+    let storage = RuleStorage::from_config().map_err(|e| e.to_tauri_error())?;
+
+    storage.delete(&rule_id).map_err(|e| e.to_tauri_error())?;
+
+    if reload.unwrap_or(false) {
+        request_engine_reload(&state).await?;
+    }
+    Ok(())
+}
+
+/// Copy a rule under a new ID into the same group, disabled
+#[tauri::command]
+pub fn duplicate_rule(rule_id: String, new_name: Option<String>) -> Result<Rule, String> {
+    let storage = RuleStorage::from_config().map_err(|e| e.to_tauri_error())?;
+
+    storage
+        .duplicate(&rule_id, new_name.as_deref())
         .map_err(|e| e.to_tauri_error())
 }
 
-/// Delete rule
+/// Enable or disable several rules at once, returning how many changed
 #[tauri::command]
-pub fn delete_rule(rule_id: String) -> Result<(), String> {
+pub async fn set_rules_enabled(
+    state: tauri::State<'_, ProxyState>,
+    ids: Vec<String>,
+    enabled: bool,
+) -> Result<usize, String> {
     let storage = RuleStorage::from_config().map_err(|e| e.to_tauri_error())?;
 
-    storage.delete(&rule_id).map_err(|e| e.to_tauri_error())
+    let changed = storage
+        .set_rules_enabled(&ids, enabled)
+        .map_err(|e| e.to_tauri_error())?;
+    if changed > 0 {
+        request_engine_reload(&state).await?;
+    }
+    Ok(changed)
+}
+
+/// Enable or disable a group and all of its rules, returning how many rules changed
+#[tauri::command]
+pub async fn set_group_enabled(
+    state: tauri::State<'_, ProxyState>,
+    group_id: String,
+    enabled: bool,
+) -> Result<usize, String> {
+    let storage = RuleStorage::from_config().map_err(|e| e.to_tauri_error())?;
+
+    let changed = storage
+        .set_group_enabled(&group_id, enabled)
+        .map_err(|e| e.to_tauri_error())?;
+    if changed > 0 {
+        request_engine_reload(&state).await?;
+    }
+    Ok(changed)
+}
+
+/// JSON Schema for a single rule, for editor completion and validation
+#[tauri::command]
+pub fn get_rule_schema() -> Result<String, String> {
+    serde_json::to_string_pretty(&super::schema::rule_schema())
+        .map_err(|e| format!("Failed to serialize schema: {}", e))
+}
+
+/// Pairs of enabled rules that can match the same request but disagree on
+/// the outcome. Advisory only: nothing is blocked.
+#[tauri::command]
+pub fn detect_rule_conflicts() -> Result<Vec<RuleConflict>, String> {
+    let storage = RuleStorage::from_config().map_err(|e| e.to_tauri_error())?;
+
+    let disabled_groups: Vec<String> = storage
+        .load_groups()
+        .map_err(|e| e.to_tauri_error())?
+        .into_iter()
+        .filter(|g| !g.enabled)
+        .map(|g| g.id)
+        .collect();
+    let entries = storage.load_all().map_err(|e| e.to_tauri_error())?.rules;
+    let disabled_ids: Vec<String> = entries
+        .iter()
+        .filter(|e| disabled_groups.contains(&e.group_id))
+        .map(|e| e.rule.id.clone())
+        .collect();
+    let rules: Vec<Rule> = entries.into_iter().map(|e| e.rule).collect();
+
+    Ok(find_conflicts(&rules, &disabled_ids))
 }
 
 /// Load groups
@@ -80,6 +241,42 @@ pub fn import_rules_bundle(yaml_content: String) -> Result<ImportResult, String>
         .map_err(|e| e.to_tauri_error())
 }
 
+/// Export a single rule (with its group) as YAML for sharing
+#[tauri::command]
+pub fn export_single_rule(rule_id: String) -> Result<String, String> {
+    let storage = RuleStorage::from_config().map_err(|e| e.to_tauri_error())?;
+
+    storage
+        .export_rule(&rule_id)
+        .map_err(|e| e.to_tauri_error())
+}
+
+/// Import a rule shared with `export_single_rule`
+#[tauri::command]
+pub fn import_single_rule(yaml: String) -> Result<RuleEntry, String> {
+    let storage = RuleStorage::from_config().map_err(|e| e.to_tauri_error())?;
+
+    storage.import_rule(&yaml).map_err(|e| e.to_tauri_error())
+}
+
+/// Export all rules as a compact string for pasting into chat
+#[tauri::command]
+pub fn export_compact() -> Result<String, String> {
+    let storage = RuleStorage::from_config().map_err(|e| e.to_tauri_error())?;
+
+    storage.export_compact().map_err(|e| e.to_tauri_error())
+}
+
+/// Import rules from a string produced by `export_compact`
+#[tauri::command]
+pub fn import_compact(encoded: String) -> Result<ImportResult, String> {
+    let storage = RuleStorage::from_config().map_err(|e| e.to_tauri_error())?;
+
+    storage
+        .import_compact(&encoded)
+        .map_err(|e| e.to_tauri_error())
+}
+
 /// Export rules to a ZIP file
 #[tauri::command]
 pub async fn export_rules_zip(save_path: String) -> Result<String, String> {
@@ -102,6 +299,134 @@ pub async fn import_rules_zip(zip_path: String) -> Result<ImportResult, String>
         .map_err(|e| e.to_tauri_error())
 }
 
+/// Solo a single rule so the engine applies only that one, or clear solo with `None`.
+/// Solo state is transient and is reset on the next app launch.
+#[tauri::command]
+pub fn set_solo(rule_id: Option<String>) -> Result<(), String> {
+    let storage = RuleStorage::from_config().map_err(|e| e.to_tauri_error())?;
+
+    storage
+        .set_solo(rule_id.as_deref())
+        .map_err(|e| e.to_tauri_error())
+}
+
+/// Get the currently soloed rule ID, if any
+#[tauri::command]
+pub fn get_solo() -> Result<Option<String>, String> {
+    let storage = RuleStorage::from_config().map_err(|e| e.to_tauri_error())?;
+
+    Ok(storage.get_solo())
+}
+
+/// Persist the disabled state of a rule the engine reported as spent, either
+/// a one-shot rule or one that used up `execution.times`, then reload the
+/// engine so its in-memory copy matches the file.
+pub fn handle_spent_rule(app: &AppHandle, rule_id: String) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let disabled = match RuleStorage::from_config()
+            .and_then(|storage| storage.disable_spent_rules(&[rule_id.clone()]))
+        {
+            Ok(disabled) => disabled,
+            Err(e) => {
+                log::warn!("Failed to disable spent rule {}: {}", rule_id, e);
+                return;
+            }
+        };
+        if disabled.is_empty() {
+            return;
+        }
+
+        for rule in &disabled {
+            let reason = if rule.execution.disable_after_hit == Some(true) {
+                "after its first hit"
+            } else {
+                "after reaching its hit limit"
+            };
+            let _ = crate::logging::write_domain_log(
+                "audit",
+                &format!("Rule {} disabled automatically {}", rule.id, reason),
+            );
+        }
+
+        let state = app.state::<ProxyState>();
+        if let Err(e) = request_engine_reload(&state).await {
+            log::warn!("Failed to reload rules after disabling {}: {}", rule_id, e);
+        }
+    });
+}
+
+/// Hit count and last hit time per rule, kept across sessions
+#[tauri::command]
+pub fn get_rule_stats() -> Result<RuleStats, String> {
+    stats::flush_pending().map_err(|e| e.to_tauri_error())?;
+    let store = RuleStatsStore::from_config().map_err(|e| e.to_tauri_error())?;
+
+    store.load().map_err(|e| e.to_tauri_error())
+}
+
+/// Forget all rule hit counters
+#[tauri::command]
+pub fn reset_rule_stats() -> Result<(), String> {
+    stats::clear_pending();
+    let store = RuleStatsStore::from_config().map_err(|e| e.to_tauri_error())?;
+
+    store.reset().map_err(|e| e.to_tauri_error())
+}
+
+/// Load the saved breakpoint set (empty when nothing has been saved)
+#[tauri::command]
+pub fn load_breakpoints() -> Result<BreakpointSet, String> {
+    let storage = BreakpointStorage::from_config().map_err(|e| e.to_tauri_error())?;
+
+    storage.load().map_err(|e| e.to_tauri_error())
+}
+
+/// Persist the breakpoint set, replacing what was saved before
+#[tauri::command]
+pub fn save_breakpoints(set: BreakpointSet) -> Result<(), String> {
+    let storage = BreakpointStorage::from_config().map_err(|e| e.to_tauri_error())?;
+
+    storage.save(&set).map_err(|e| e.to_tauri_error())
+}
+
+/// Remove all saved breakpoints
+#[tauri::command]
+pub fn clear_breakpoints() -> Result<(), String> {
+    let storage = BreakpointStorage::from_config().map_err(|e| e.to_tauri_error())?;
+
+    storage.clear().map_err(|e| e.to_tauri_error())
+}
+
+/// Enable or disable the whole breakpoint set at once
+#[tauri::command]
+pub fn set_breakpoints_enabled(enabled: bool) -> Result<BreakpointSet, String> {
+    let storage = BreakpointStorage::from_config().map_err(|e| e.to_tauri_error())?;
+
+    storage.set_enabled(enabled).map_err(|e| e.to_tauri_error())
+}
+
+/// Dry-run a rule's request conditions against a sample flow, without the engine.
+#[tauri::command]
+pub fn test_rule(rule_json: String, flow: Flow) -> Result<RuleTestResult, String> {
+    let rule: Rule =
+        serde_json::from_str(&rule_json).map_err(|e| format!("Failed to parse rule: {}", e))?;
+    Ok(test_rule_against(&rule, &flow))
+}
+
+/// Upper bound on delays returned by a jitter preview.
+const MAX_JITTER_PREVIEW_SAMPLES: usize = 1000;
+
+/// Sample the delays a throttle action would apply to successive requests.
+/// With `jitterSeed` set, this is the exact sequence the engine will use.
+#[tauri::command]
+pub fn preview_jitter(action: ThrottleAction, samples: usize) -> Result<Vec<u32>, String> {
+    Ok(super::jitter::sample_delays(
+        &action,
+        samples.min(MAX_JITTER_PREVIEW_SAMPLES),
+    ))
+}
+
 /// Save all rules and groups in a single batch operation
 /// This is more efficient than calling save_rule multiple times
 #[tauri::command]
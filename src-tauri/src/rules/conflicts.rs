@@ -0,0 +1,317 @@
+//! Advisory detection of enabled rules that can match the same request but
+//! disagree on what happens to it.
+//!
+//! Overlap is judged per matcher kind (`host`, `url`, `method`): when
+//! both rules constrain the same kind, some request must be able to satisfy
+//! both. Anything we cannot decide (two regexes, two `contains`) counts as a
+//! plausible overlap. The outcome mirrors the engine pipeline: Block beats
+//! Redirect beats Map Local / Map Remote, ties go to the rule evaluated first,
+//! and `stopOnMatch` hides every later rule.
+
+use serde::Serialize;
+
+use super::matcher::{match_text, value_string};
+use super::model::{MatchAtom, Rule, RuleAction};
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleConflict {
+    /// The rule the engine evaluates first
+    pub first_id: String,
+    pub second_id: String,
+    pub reason: String,
+}
+
+/// Matchers of `kind` that narrow a rule (inverted atoms only widen it)
+fn atoms_of<'a>(rule: &'a Rule, kind: &str) -> Vec<&'a MatchAtom> {
+    rule.match_config
+        .request
+        .iter()
+        .filter(|a| a.atom_type == kind && !a.invert.unwrap_or(false))
+        .collect()
+}
+
+fn methods(atom: &MatchAtom) -> Vec<String> {
+    match &atom.value {
+        Some(serde_json::Value::Array(list)) => list
+            .iter()
+            .filter_map(|m| m.as_str())
+            .map(|m| m.to_ascii_uppercase())
+            .collect(),
+        _ => vec![value_string(&atom.value).to_ascii_uppercase()],
+    }
+}
+
+/// Could one string satisfy both atoms?
+fn values_may_overlap(a: &MatchAtom, b: &MatchAtom) -> bool {
+    let (va, vb) = (value_string(&a.value), value_string(&b.value));
+    match (a.match_type.as_str(), b.match_type.as_str()) {
+        ("exact", "exact") => va == vb,
+        ("exact", other) => match_text(&va, &vb, other),
+        (other, "exact") => match_text(&vb, &va, other),
+        _ => true,
+    }
+}
+
+/// Whether some request can match both rules
+pub fn rules_may_overlap(a: &Rule, b: &Rule) -> bool {
+    for kind in ["host", "url"] {
+        let (atoms_a, atoms_b) = (atoms_of(a, kind), atoms_of(b, kind));
+        // Every atom is ANDed, so each pair across the two rules must agree
+        for x in &atoms_a {
+            if atoms_b.iter().any(|y| !values_may_overlap(x, y)) {
+                return false;
+            }
+        }
+    }
+
+    let (methods_a, methods_b) = (atoms_of(a, "method"), atoms_of(b, "method"));
+    for x in &methods_a {
+        let allowed = methods(x);
+        if methods_b
+            .iter()
+            .any(|y| !methods(y).iter().any(|m| allowed.contains(m)))
+        {
+            return false;
+        }
+    }
+    true
+}
+
+/// What a rule does with the request itself, ranked by engine precedence
+fn disposition(rule: &Rule) -> Option<(u8, &'static str)> {
+    rule.actions
+        .iter()
+        .filter_map(|action| match action {
+            RuleAction::BlockRequest => Some((0, "blocks")),
+            RuleAction::Redirect(_) => Some((1, "redirects")),
+            RuleAction::MapLocal(_) => Some((2, "maps to a local file")),
+            RuleAction::MapRemote(_) => Some((2, "maps to a remote URL")),
+            _ => None,
+        })
+        .min_by_key(|(rank, _)| *rank)
+}
+
+/// Targets whose whole body the rule replaces
+fn body_sets(rule: &Rule) -> Vec<&str> {
+    rule.actions
+        .iter()
+        .filter_map(|action| match action {
+            RuleAction::RewriteBody(body) if body.set.is_some() => Some(body.target.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Why `first` and `second` (in evaluation order) disagree, if they do
+fn conflict_reason(first: &Rule, second: &Rule) -> Option<String> {
+    if first.execution.stop_on_match.unwrap_or(false) {
+        return (!second.actions.is_empty()).then(|| {
+            format!(
+                "'{}' stops matching, so '{}' never applies to the same requests",
+                first.name, second.name
+            )
+        });
+    }
+
+    if let (Some((rank_a, what_a)), Some((rank_b, what_b))) =
+        (disposition(first), disposition(second))
+    {
+        let (winner, what_winner, loser, what_loser) = if rank_b < rank_a {
+            (second, what_b, first, what_a)
+        } else {
+            (first, what_a, second, what_b)
+        };
+        return Some(format!(
+            "'{}' {} and '{}' {}; only '{}' takes effect",
+            winner.name, what_winner, loser.name, what_loser, winner.name
+        ));
+    }
+
+    let second_sets = body_sets(second);
+    if let Some(target) = body_sets(first)
+        .into_iter()
+        .find(|t| second_sets.contains(t))
+    {
+        return Some(format!(
+            "both replace the {} body; '{}' runs last and wins",
+            target, second.name
+        ));
+    }
+    None
+}
+
+/// Conflicting pairs among `rules`. Disabled rules and rules whose ID is in
+/// `disabled_ids` (e.g. from a disabled group) are skipped.
+pub fn find_conflicts(rules: &[Rule], disabled_ids: &[String]) -> Vec<RuleConflict> {
+    let mut active: Vec<&Rule> = rules
+        .iter()
+        .filter(|r| r.execution.enabled && !disabled_ids.contains(&r.id))
+        .collect();
+    active.sort_by(|a, b| {
+        a.execution
+            .priority
+            .cmp(&b.execution.priority)
+            .then_with(|| a.name.cmp(&b.name))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+
+    let mut conflicts = Vec::new();
+    for (i, first) in active.iter().enumerate() {
+        for second in &active[i + 1..] {
+            if !rules_may_overlap(first, second) {
+                continue;
+            }
+            if let Some(reason) = conflict_reason(first, second) {
+                conflicts.push(RuleConflict {
+                    first_id: first.id.clone(),
+                    second_id: second.id.clone(),
+                    reason,
+                });
+            }
+        }
+    }
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::model::*;
+
+    fn atom(kind: &str, match_type: &str, value: &str) -> MatchAtom {
+        MatchAtom {
+            atom_type: kind.into(),
+            match_type: match_type.into(),
+            key: None,
+            value: Some(value.into()),
+            invert: None,
+        }
+    }
+
+    fn rule(id: &str, priority: i32, atoms: Vec<MatchAtom>, action: RuleAction) -> Rule {
+        Rule {
+            id: id.into(),
+            name: id.into(),
+            r#type: RuleType::BlockRequest,
+            execution: RuleExecution {
+                enabled: true,
+                priority,
+                stop_on_match: None,
+                disable_after_hit: None,
+                times: None,
+            },
+            match_config: RuleMatchConfig {
+                request: atoms,
+                response: vec![],
+            },
+            actions: vec![action],
+            tags: None,
+            metadata: None,
+        }
+    }
+
+    fn map_remote(target: &str) -> RuleAction {
+        RuleAction::MapRemote(MapRemoteAction {
+            target_url: target.into(),
+            preserve_path: None,
+            headers: None,
+        })
+    }
+
+    #[test]
+    fn test_block_and_map_remote_conflict() {
+        let rules = vec![
+            rule(
+                "mirror",
+                1,
+                vec![atom("host", "exact", "api.example.com")],
+                map_remote("http://localhost:3000"),
+            ),
+            rule(
+                "block",
+                2,
+                vec![
+                    atom("host", "wildcard", "*.example.com"),
+                    atom("url", "contains", "/v1"),
+                ],
+                RuleAction::BlockRequest,
+            ),
+        ];
+
+        let conflicts = find_conflicts(&rules, &[]);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].first_id, "mirror");
+        assert_eq!(conflicts[0].second_id, "block");
+        // Block wins in the engine even though it is evaluated second
+        assert!(conflicts[0].reason.starts_with("'block' blocks"));
+
+        assert!(find_conflicts(&rules, &["block".to_string()]).is_empty());
+    }
+
+    #[test]
+    fn test_disjoint_rules_do_not_conflict() {
+        let rules = vec![
+            rule(
+                "a",
+                1,
+                vec![atom("host", "exact", "a.example.com")],
+                RuleAction::BlockRequest,
+            ),
+            rule(
+                "b",
+                1,
+                vec![atom("host", "exact", "b.example.com")],
+                map_remote("http://localhost:3000"),
+            ),
+            rule(
+                "c",
+                1,
+                vec![
+                    atom("host", "exact", "a.example.com"),
+                    atom("method", "exact", "POST"),
+                ],
+                map_remote("http://localhost:4000"),
+            ),
+            rule(
+                "d",
+                1,
+                vec![
+                    atom("host", "exact", "a.example.com"),
+                    atom("method", "exact", "GET"),
+                ],
+                map_remote("http://localhost:5000"),
+            ),
+        ];
+
+        let pairs: Vec<(String, String)> = find_conflicts(&rules, &[])
+            .into_iter()
+            .map(|c| (c.first_id, c.second_id))
+            .collect();
+        // Only the shared host with compatible methods overlaps
+        assert_eq!(
+            pairs,
+            vec![
+                ("a".to_string(), "c".to_string()),
+                ("a".to_string(), "d".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stop_on_match_hides_later_rules() {
+        let mut first = rule("first", 1, vec![], RuleAction::BlockRequest);
+        first.actions = vec![];
+        first.execution.stop_on_match = Some(true);
+        let second = rule(
+            "second",
+            2,
+            vec![atom("url", "regex", "^https://")],
+            map_remote("http://localhost"),
+        );
+
+        let conflicts = find_conflicts(&[second, first], &[]);
+        assert_eq!(conflicts.len(), 1);
+        assert!(conflicts[0].reason.contains("never applies"));
+    }
+}
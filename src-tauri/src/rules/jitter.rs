@@ -0,0 +1,90 @@
+//! Latency jitter for throttle rules.
+//!
+//! The engine draws delays from the same SplitMix64 sequence
+//! (`rules/actions.py`), so a preview with a given `jitterSeed` shows exactly
+//! the delays the engine will apply, in order.
+
+use super::model::ThrottleAction;
+
+/// SplitMix64: tiny, seedable and easy to mirror in Python.
+pub struct JitterRng {
+    state: u64,
+}
+
+impl JitterRng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Delay in `[delay - jitter, delay + jitter]`, clamped at zero.
+    pub fn next_delay(&mut self, delay_ms: u32, jitter_ms: u32) -> u32 {
+        if jitter_ms == 0 {
+            return delay_ms;
+        }
+        let span = 2 * jitter_ms as u64 + 1;
+        let offset = (self.next_u64() % span) as i64 - jitter_ms as i64;
+        (delay_ms as i64 + offset).max(0) as u32
+    }
+}
+
+/// First `samples` delays a throttle action would produce. Without a seed
+/// the sequence is seeded from the clock, like the engine does.
+pub fn sample_delays(action: &ThrottleAction, samples: usize) -> Vec<u32> {
+    let seed = action
+        .jitter_seed
+        .unwrap_or_else(|| chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0) as u64);
+    let mut rng = JitterRng::new(seed);
+    let delay = action.delay_ms.unwrap_or(0);
+    let jitter = action.jitter_ms.unwrap_or(0);
+    (0..samples)
+        .map(|_| rng.next_delay(delay, jitter))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn throttle(delay: u32, jitter: u32, seed: Option<u64>) -> ThrottleAction {
+        ThrottleAction {
+            subtype: Some("delay".to_string()),
+            delay_ms: Some(delay),
+            jitter_ms: Some(jitter),
+            jitter_seed: seed,
+            packet_loss: None,
+            bandwidth_kbps: None,
+        }
+    }
+
+    #[test]
+    fn test_splitmix_reference_values() {
+        // Reference output of SplitMix64 seeded with 0
+        let mut rng = JitterRng::new(0);
+        assert_eq!(rng.next_u64(), 0xE220_A839_7B1D_CDAF);
+        assert_eq!(rng.next_u64(), 0x6E78_9E6A_A1B9_65F4);
+    }
+
+    #[test]
+    fn test_seeded_delays_are_reproducible_and_bounded() {
+        let action = throttle(200, 50, Some(42));
+        let first = sample_delays(&action, 100);
+        assert_eq!(first, sample_delays(&action, 100));
+        assert!(first.iter().all(|d| (150..=250).contains(d)));
+        assert!(first.iter().any(|d| *d != first[0]));
+    }
+
+    #[test]
+    fn test_jitter_never_goes_negative() {
+        let delays = sample_delays(&throttle(10, 100, Some(7)), 200);
+        assert!(delays.iter().all(|d| *d <= 110));
+        assert_eq!(sample_delays(&throttle(80, 0, None), 3), vec![80, 80, 80]);
+    }
+}
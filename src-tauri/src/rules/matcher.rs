@@ -0,0 +1,334 @@
+//! Rust port of the engine's request matcher (`rules/matcher.py`).
+//!
+//! Used to evaluate rules against already captured flows. Semantics follow
+//! the engine: all request atoms must match (AND), response atoms are not
+//! considered, and an invalid pattern simply does not match.
+
+use regex::Regex;
+use serde::Serialize;
+
+use super::model::{MatchAtom, Rule, RuleMatchConfig};
+use crate::session::model::Flow;
+
+/// Same translation as the engine: only `.`, `*` and `?` are special.
+fn wildcard_to_regex(pattern: &str) -> String {
+    format!(
+        "^{}$",
+        pattern
+            .replace('.', r"\.")
+            .replace('*', ".*")
+            .replace('?', ".")
+    )
+}
+
+/// Match `text` against `pattern` using one of the engine's match types.
+pub fn match_text(text: &str, pattern: &str, match_type: &str) -> bool {
+    match match_type {
+        "contains" => text.contains(pattern),
+        "exact" => text == pattern,
+        "regex" => Regex::new(pattern)
+            .map(|re| re.is_match(text))
+            .unwrap_or(false),
+        "wildcard" => Regex::new(&wildcard_to_regex(pattern))
+            .map(|re| re.is_match(text))
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+pub(super) fn value_string(value: &Option<serde_json::Value>) -> String {
+    match value {
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(serde_json::Value::Null) | None => "None".to_string(),
+        Some(other) => other.to_string(),
+    }
+}
+
+/// Match a keyed value (header / query) that may be absent.
+fn match_keyed(actual: Option<&str>, atom: &MatchAtom) -> bool {
+    match atom.match_type.as_str() {
+        "exists" => actual.is_some(),
+        "not_exists" => actual.is_none(),
+        other => actual
+            .map(|v| match_text(v, &value_string(&atom.value), other))
+            .unwrap_or(false),
+    }
+}
+
+/// Evaluate a single atom against the request side of `flow`.
+pub fn match_atom(flow: &Flow, atom: &MatchAtom) -> bool {
+    let url = url::Url::parse(&flow.request.url).ok();
+    let pattern = value_string(&atom.value);
+
+    let result = match atom.atom_type.as_str() {
+        "url" => match_text(&flow.request.url, &pattern, &atom.match_type),
+        "host" => {
+            let host = url.as_ref().and_then(|u| u.host_str()).unwrap_or("");
+            match_text(host, &pattern, &atom.match_type)
+        }
+        "method" => match &atom.value {
+            Some(serde_json::Value::Array(methods)) => methods
+                .iter()
+                .any(|m| m.as_str() == Some(flow.request.method.as_str())),
+            _ => flow.request.method == pattern,
+        },
+        "header" => match atom.key.as_deref() {
+            Some(key) => {
+                let actual = flow
+                    .request
+                    .headers
+                    .iter()
+                    .find(|h| h.name.eq_ignore_ascii_case(key))
+                    .map(|h| h.value.as_str());
+                match_keyed(actual, atom)
+            }
+            None => return false,
+        },
+        "query" => match atom.key.as_deref() {
+            Some(key) => {
+                let actual = flow
+                    .request
+                    .query_string
+                    .iter()
+                    .find(|q| q.name == key)
+                    .map(|q| q.value.as_str());
+                match_keyed(actual, atom)
+            }
+            None => return false,
+        },
+        "port" => url
+            .as_ref()
+            .and_then(|u| u.port_or_known_default())
+            .map(|p| p.to_string() == pattern)
+            .unwrap_or(false),
+        "ip" => flow
+            .rc
+            .client_ip
+            .as_deref()
+            .map(|ip| match_text(ip, &pattern, &atom.match_type))
+            .unwrap_or(false),
+        _ => false,
+    };
+
+    if atom.invert.unwrap_or(false) {
+        !result
+    } else {
+        result
+    }
+}
+
+/// True when every request atom of `config` matches `flow`.
+/// A rule without request atoms matches everything.
+pub fn matches_request(flow: &Flow, config: &RuleMatchConfig) -> bool {
+    config.request.iter().all(|atom| match_atom(flow, atom))
+}
+
+/// Outcome of one request atom in a dry run.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AtomTestResult {
+    pub index: usize,
+    pub atom_type: String,
+    pub match_type: String,
+    pub key: Option<String>,
+    /// Result after `invert` was applied
+    pub matched: bool,
+    /// Why the atom can never match as configured, e.g. an invalid regex
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleTestResult {
+    pub matched: bool,
+    pub atoms: Vec<AtomTestResult>,
+    /// Response atoms are ignored by the engine, so they are not evaluated
+    pub skipped_response_atoms: usize,
+}
+
+/// Configuration problems that make the engine treat an atom as a non-match.
+fn atom_error(atom: &MatchAtom) -> Option<String> {
+    match atom.atom_type.as_str() {
+        "url" | "host" | "method" | "port" | "ip" => {}
+        "header" | "query" if atom.key.is_none() => {
+            return Some(format!("A {} condition needs a key", atom.atom_type));
+        }
+        "header" | "query" => {}
+        other => return Some(format!("Unsupported match target '{}'", other)),
+    }
+    let pattern = value_string(&atom.value);
+    let compiled = match atom.match_type.as_str() {
+        "regex" => Regex::new(&pattern),
+        "wildcard" => Regex::new(&wildcard_to_regex(&pattern)),
+        _ => return None,
+    };
+    compiled
+        .err()
+        .map(|e| format!("Invalid {} pattern: {}", atom.match_type, e))
+}
+
+/// Evaluate a rule's request atoms against `flow` the way the engine would,
+/// reporting every atom rather than stopping at the first miss.
+pub fn test_rule_against(rule: &Rule, flow: &Flow) -> RuleTestResult {
+    let atoms: Vec<AtomTestResult> = rule
+        .match_config
+        .request
+        .iter()
+        .enumerate()
+        .map(|(index, atom)| AtomTestResult {
+            index,
+            atom_type: atom.atom_type.clone(),
+            match_type: atom.match_type.clone(),
+            key: atom.key.clone(),
+            matched: match_atom(flow, atom),
+            error: atom_error(atom),
+        })
+        .collect();
+
+    RuleTestResult {
+        matched: atoms.iter().all(|a| a.matched),
+        atoms,
+        skipped_response_atoms: rule.match_config.response.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::model::HarHeader;
+
+    fn atom(atom_type: &str, match_type: &str, value: serde_json::Value) -> MatchAtom {
+        MatchAtom {
+            atom_type: atom_type.to_string(),
+            match_type: match_type.to_string(),
+            key: None,
+            value: Some(value),
+            invert: None,
+        }
+    }
+
+    fn flow(method: &str, url: &str) -> Flow {
+        let mut flow = Flow::default();
+        flow.request.method = method.to_string();
+        flow.request.url = url.to_string();
+        flow.request.headers.push(HarHeader {
+            name: "Authorization".to_string(),
+            value: "Bearer abc".to_string(),
+            comment: None,
+        });
+        flow
+    }
+
+    #[test]
+    fn test_match_text_types() {
+        assert!(match_text("https://api.test/v1/users", "/v1/", "contains"));
+        assert!(match_text("api.test", "api.test", "exact"));
+        assert!(match_text(
+            "https://api.test/v1/users/42",
+            r"/users/\d+$",
+            "regex"
+        ));
+        assert!(match_text(
+            "https://api.test/v1/users",
+            "https://*.test/*",
+            "wildcard"
+        ));
+        assert!(!match_text(
+            "https://apixtest/",
+            "https://api.test/",
+            "wildcard"
+        ));
+        assert!(!match_text("anything", "(", "regex"));
+    }
+
+    fn rule_with(request: Vec<MatchAtom>) -> Rule {
+        serde_json::from_value(serde_json::json!({
+            "id": "r1",
+            "name": "dry run",
+            "type": "block_request",
+            "execution": { "enabled": false, "priority": 0 },
+            "match": { "request": request, "response": [] },
+            "actions": [],
+            "tags": null
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rule_dry_run_contains_and_exact() {
+        let f = flow("GET", "https://api.test/v1/users?page=2");
+        let mut header = atom("header", "exact", serde_json::json!("Bearer abc"));
+        header.key = Some("Authorization".to_string());
+        let rule = rule_with(vec![
+            atom("url", "contains", serde_json::json!("/v1/users")),
+            atom("host", "exact", serde_json::json!("api.test")),
+            header,
+        ]);
+
+        let result = test_rule_against(&rule, &f);
+        assert!(result.matched);
+        assert!(result.atoms.iter().all(|a| a.matched && a.error.is_none()));
+
+        let rule = rule_with(vec![
+            atom("url", "contains", serde_json::json!("/v1/users")),
+            atom("host", "exact", serde_json::json!("api.test.example")),
+        ]);
+        let result = test_rule_against(&rule, &f);
+        assert!(!result.matched);
+        assert!(result.atoms[0].matched);
+        assert!(!result.atoms[1].matched);
+    }
+
+    #[test]
+    fn test_rule_dry_run_regex_and_invert() {
+        let f = flow("DELETE", "https://api.test/v1/users/42");
+        let mut not_get = atom("method", "exact", serde_json::json!(["GET"]));
+        not_get.invert = Some(true);
+        let rule = rule_with(vec![
+            atom("url", "regex", serde_json::json!(r"/users/\d+$")),
+            not_get,
+        ]);
+        let result = test_rule_against(&rule, &f);
+        assert!(result.matched);
+        assert_eq!(result.atoms.len(), 2);
+
+        let rule = rule_with(vec![
+            atom("url", "regex", serde_json::json!("users/(")),
+            atom("body", "contains", serde_json::json!("x")),
+        ]);
+        let result = test_rule_against(&rule, &f);
+        assert!(!result.matched);
+        assert!(result.atoms[0]
+            .error
+            .as_deref()
+            .unwrap()
+            .starts_with("Invalid regex pattern"));
+        assert!(result.atoms[1].error.as_deref().unwrap().contains("'body'"));
+    }
+
+    #[test]
+    fn test_match_request_atoms_are_anded() {
+        let f = flow("POST", "https://api.test:8443/login?next=home");
+        let mut header = atom("header", "contains", serde_json::json!("Bearer"));
+        header.key = Some("authorization".to_string());
+
+        let config = RuleMatchConfig {
+            request: vec![
+                atom("host", "exact", serde_json::json!("api.test")),
+                atom("method", "exact", serde_json::json!(["GET", "POST"])),
+                atom("port", "exact", serde_json::json!(8443)),
+                header,
+            ],
+            response: vec![],
+        };
+        assert!(matches_request(&f, &config));
+
+        let mut inverted = atom("url", "contains", serde_json::json!("/login"));
+        inverted.invert = Some(true);
+        let config = RuleMatchConfig {
+            request: vec![inverted],
+            response: vec![],
+        };
+        assert!(!matches_request(&f, &config));
+    }
+}
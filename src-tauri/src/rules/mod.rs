@@ -1,5 +1,11 @@
+pub mod breakpoints;
 pub mod commands;
+pub mod conflicts;
+pub mod jitter;
+pub mod matcher;
 pub mod model;
+pub mod schema;
+pub mod stats;
 pub mod storage;
 
 pub use commands::*;
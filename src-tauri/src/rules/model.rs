@@ -5,6 +5,12 @@ use serde::{Deserialize, Serialize};
 pub struct MapLocalAction {
     pub source: Option<String>, // file, manual
     pub local_path: Option<String>,
+    /// Serve a whole folder: the request path (no query) is joined to `dir`,
+    /// a directory resolves to its `index.html`, a missing file answers 404
+    /// and a path that would leave `dir` answers 403. Takes precedence over
+    /// `local_path`. The engine resolves paths in `core/rules/actions.py`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dir: Option<String>,
     pub content: Option<String>,
     pub content_type: Option<String>,
     pub status_code: Option<u32>,
@@ -19,6 +25,38 @@ pub struct MapRemoteAction {
     pub headers: Option<HeaderConfig>,
 }
 
+/// Answer the client with a 3xx pointing at `target_url`, so it follows the
+/// redirect itself (unlike Map Remote, which forwards transparently).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RedirectAction {
+    pub target_url: String,
+    /// 301, 302, 307 or 308
+    pub status_code: u16,
+    /// Append the original query string to `target_url`
+    #[serde(default)]
+    pub preserve_query: bool,
+}
+
+/// Which side of the exchange a breakpoint rule pauses on
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BreakpointPhase {
+    #[default]
+    Request,
+    Response,
+    Both,
+}
+
+/// Pause matching flows in the engine until the UI resumes them
+/// (see `traffic::intercept`).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BreakpointAction {
+    #[serde(default)]
+    pub phase: BreakpointPhase,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RewriteHeaderAction {
@@ -89,6 +127,12 @@ pub struct JsonModification {
 pub struct ThrottleAction {
     pub subtype: Option<String>,
     pub delay_ms: Option<u32>,
+    /// Randomize the delay within `delay_ms ± jitter_ms`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jitter_ms: Option<u32>,
+    /// Seed for the jitter sequence, for reproducible runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jitter_seed: Option<u64>,
     pub packet_loss: Option<f32>,
     pub bandwidth_kbps: Option<u32>,
 }
@@ -98,9 +142,11 @@ pub struct ThrottleAction {
 pub enum RuleAction {
     MapLocal(MapLocalAction),
     MapRemote(MapRemoteAction),
+    Redirect(RedirectAction),
     RewriteHeader(RewriteHeaderAction),
     RewriteBody(RewriteBodyAction),
     Throttle(ThrottleAction),
+    Breakpoint(BreakpointAction),
     BlockRequest,
 }
 
@@ -121,7 +167,13 @@ pub struct RuleExecution {
     pub enabled: bool,
     pub priority: i32,
     pub stop_on_match: Option<bool>,
-    // times: Option<i32>, // Reserved for future
+    /// Disable the rule after its first hit (one-shot mocks)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_after_hit: Option<bool>,
+    /// Apply the rule this many times, then disable it. The engine counts the
+    /// hits and reports the last one so the disabled state is saved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub times: Option<u32>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -138,9 +190,11 @@ pub struct RuleMatchConfig {
 pub enum RuleType {
     MapLocal,
     MapRemote,
+    Redirect,
     RewriteHeader,
     RewriteBody,
     Throttle,
+    Breakpoint,
     BlockRequest,
 }
 
@@ -0,0 +1,288 @@
+//! Hand-written JSON Schema (draft 2020-12) for a single rule, used by the
+//! rule editor for completion and validation. It follows the serde shape of
+//! `model::Rule`; the tests pin every `RuleAction` and `RuleType` variant so a
+//! new variant cannot be added without updating the schema.
+
+use serde_json::{json, Value};
+
+fn header_config() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "request": { "type": "array", "items": { "$ref": "#/$defs/headerOperation" } },
+            "response": { "type": "array", "items": { "$ref": "#/$defs/headerOperation" } }
+        }
+    })
+}
+
+fn replace_mode() -> Value {
+    json!({
+        "type": "object",
+        "required": ["pattern", "replacement"],
+        "properties": {
+            "pattern": { "type": "string" },
+            "replacement": { "type": "string" }
+        }
+    })
+}
+
+/// One `oneOf` branch per action, discriminated by `type`
+fn action_variants() -> Vec<Value> {
+    let variant = |tag: &str, required: &[&str], properties: Value| {
+        let mut properties = properties;
+        properties["type"] = json!({ "const": tag });
+        let mut required: Vec<&str> = required.to_vec();
+        required.insert(0, "type");
+        json!({
+            "type": "object",
+            "required": required,
+            "properties": properties
+        })
+    };
+
+    vec![
+        variant(
+            "map_local",
+            &[],
+            json!({
+                "source": { "enum": ["file", "manual"] },
+                "localPath": { "type": "string" },
+                "dir": { "type": "string", "description": "Serve a whole folder; request paths resolve inside it" },
+                "content": { "type": "string" },
+                "contentType": { "type": "string" },
+                "statusCode": { "type": "integer" },
+                "headers": { "$ref": "#/$defs/headerConfig" }
+            }),
+        ),
+        variant(
+            "map_remote",
+            &["targetUrl"],
+            json!({
+                "targetUrl": { "type": "string" },
+                "preservePath": { "type": "boolean" },
+                "headers": { "$ref": "#/$defs/headerConfig" }
+            }),
+        ),
+        variant(
+            "redirect",
+            &["targetUrl", "statusCode"],
+            json!({
+                "targetUrl": { "type": "string" },
+                "statusCode": { "enum": [301, 302, 307, 308] },
+                "preserveQuery": { "type": "boolean" }
+            }),
+        ),
+        variant(
+            "rewrite_header",
+            &["headers"],
+            json!({ "headers": { "$ref": "#/$defs/headerConfig" } }),
+        ),
+        variant(
+            "rewrite_body",
+            &["target"],
+            json!({
+                "target": { "enum": ["request", "response"] },
+                "statusCode": { "type": "integer" },
+                "contentType": { "type": "string" },
+                "set": {
+                    "type": "object",
+                    "required": ["content"],
+                    "properties": {
+                        "content": { "type": "string" },
+                        "statusCode": { "type": "integer" },
+                        "contentType": { "type": "string" }
+                    }
+                },
+                "replace": { "$ref": "#/$defs/replaceMode" },
+                "regexReplace": { "$ref": "#/$defs/replaceMode" },
+                "json": {
+                    "type": "object",
+                    "required": ["modifications"],
+                    "properties": {
+                        "modifications": {
+                            "type": "array",
+                            "items": {
+                                "type": "object",
+                                "required": ["path", "value", "operation"],
+                                "properties": {
+                                    "path": { "type": "string" },
+                                    "value": {},
+                                    "operation": { "enum": ["set", "delete", "append"] },
+                                    "enabled": { "type": "boolean" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }),
+        ),
+        variant(
+            "throttle",
+            &[],
+            json!({
+                "subtype": { "enum": ["delay", "drop", "timeout", "reset"] },
+                "delayMs": { "type": "integer", "minimum": 0 },
+                "jitterMs": { "type": "integer", "minimum": 0 },
+                "jitterSeed": { "type": "integer", "minimum": 0 },
+                "packetLoss": { "type": "number", "minimum": 0, "maximum": 100 },
+                "bandwidthKbps": { "type": "integer", "minimum": 0 }
+            }),
+        ),
+        variant(
+            "breakpoint",
+            &[],
+            json!({ "phase": { "enum": ["request", "response", "both"] } }),
+        ),
+        variant("block_request", &[], json!({})),
+    ]
+}
+
+/// Schema for one rule as it appears under `rule:` in a rule file
+pub fn rule_schema() -> Value {
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "RelayCraft rule",
+        "type": "object",
+        "required": ["id", "name", "type", "execution", "match", "actions"],
+        "properties": {
+            "id": { "type": "string" },
+            "name": { "type": "string" },
+            "type": {
+                "enum": [
+                    "map_local", "map_remote", "redirect", "rewrite_header",
+                    "rewrite_body", "throttle", "breakpoint", "block_request"
+                ]
+            },
+            "execution": {
+                "type": "object",
+                "required": ["enabled", "priority"],
+                "properties": {
+                    "enabled": { "type": "boolean" },
+                    "priority": { "type": "integer" },
+                    "stopOnMatch": { "type": "boolean" },
+                    "disableAfterHit": { "type": "boolean" },
+                    "times": { "type": "integer", "minimum": 1 }
+                }
+            },
+            "match": {
+                "type": "object",
+                "properties": {
+                    "request": { "type": "array", "items": { "$ref": "#/$defs/matchAtom" } },
+                    "response": { "type": "array", "items": { "$ref": "#/$defs/matchAtom" } }
+                }
+            },
+            "actions": { "type": "array", "items": { "$ref": "#/$defs/action" } },
+            "tags": { "type": ["array", "null"], "items": { "type": "string" } },
+            "metadata": {
+                "type": "object",
+                "properties": {
+                    "source": { "enum": ["user", "ai_assistant", "ai_mcp"] },
+                    "aiIntent": { "type": "string" }
+                }
+            }
+        },
+        "$defs": {
+            "matchAtom": {
+                "type": "object",
+                "required": ["type", "matchType"],
+                "properties": {
+                    "type": { "enum": ["url", "host", "method", "header", "query", "port", "ip"] },
+                    "matchType": {
+                        "enum": ["contains", "exact", "regex", "wildcard", "exists", "not_exists"]
+                    },
+                    "key": { "type": "string" },
+                    "value": { "type": ["string", "array", "number"], "items": { "type": "string" } },
+                    "invert": { "type": "boolean" }
+                }
+            },
+            "headerOperation": {
+                "type": "object",
+                "required": ["operation", "key"],
+                "properties": {
+                    "operation": { "enum": ["add", "set", "remove"] },
+                    "key": { "type": "string" },
+                    "value": { "type": "string" }
+                }
+            },
+            "headerConfig": header_config(),
+            "replaceMode": replace_mode(),
+            "action": { "oneOf": action_variants() }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::model::*;
+
+    /// Exhaustive on purpose: a new variant fails to compile here until the
+    /// schema and this list are updated.
+    fn action_tag(action: &RuleAction) -> &'static str {
+        match action {
+            RuleAction::MapLocal(_) => "map_local",
+            RuleAction::MapRemote(_) => "map_remote",
+            RuleAction::Redirect(_) => "redirect",
+            RuleAction::RewriteHeader(_) => "rewrite_header",
+            RuleAction::RewriteBody(_) => "rewrite_body",
+            RuleAction::Throttle(_) => "throttle",
+            RuleAction::Breakpoint(_) => "breakpoint",
+            RuleAction::BlockRequest => "block_request",
+        }
+    }
+
+    fn type_tag(rule_type: &RuleType) -> &'static str {
+        match rule_type {
+            RuleType::MapLocal => "map_local",
+            RuleType::MapRemote => "map_remote",
+            RuleType::Redirect => "redirect",
+            RuleType::RewriteHeader => "rewrite_header",
+            RuleType::RewriteBody => "rewrite_body",
+            RuleType::Throttle => "throttle",
+            RuleType::Breakpoint => "breakpoint",
+            RuleType::BlockRequest => "block_request",
+        }
+    }
+
+    #[test]
+    fn test_schema_covers_every_action() {
+        let schema = rule_schema();
+        let discriminators: Vec<&str> = schema["$defs"]["action"]["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v["properties"]["type"]["const"].as_str().unwrap())
+            .collect();
+
+        let actions = [
+            RuleAction::BlockRequest,
+            RuleAction::Breakpoint(BreakpointAction {
+                phase: BreakpointPhase::Both,
+            }),
+        ];
+        for action in &actions {
+            // The schema tag must be the serde tag
+            let tag = serde_json::to_value(action).unwrap()["type"].clone();
+            assert_eq!(tag, action_tag(action));
+        }
+        for tag in [
+            "map_local",
+            "map_remote",
+            "redirect",
+            "rewrite_header",
+            "rewrite_body",
+            "throttle",
+            "breakpoint",
+            "block_request",
+        ] {
+            assert!(discriminators.contains(&tag), "missing action {}", tag);
+            let rule_type: RuleType = serde_json::from_value(tag.into()).unwrap();
+            assert_eq!(type_tag(&rule_type), tag);
+            assert!(schema["properties"]["type"]["enum"]
+                .as_array()
+                .unwrap()
+                .contains(&tag.into()));
+        }
+        assert_eq!(discriminators.len(), 8);
+    }
+}
@@ -0,0 +1,228 @@
+//! Aggregate hit counters per rule, kept in `rule_stats.json` in the rules
+//! directory so they survive sessions and restarts.
+//!
+//! The engine prints a `[RULE_HIT] <rule_id> <epoch_ms>` marker line for every
+//! rule hit; the engine log forwarder hands those lines to [`record_marker`].
+//! Hits are buffered in memory and written at most every couple of seconds.
+//!
+//! One-shot rules and rules limited by `execution.times` are counted down by
+//! the engine itself. Their last allowed hit is followed by
+//! `[RULE_SPENT] <rule_id>`, which the forwarder hands to
+//! [`super::commands::handle_spent_rule`] so the rule is disabled on disk.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::storage::RuleStorage;
+use crate::common::error::RuleError;
+
+pub const RULE_HIT_MARKER: &str = "[RULE_HIT]";
+pub const RULE_SPENT_MARKER: &str = "[RULE_SPENT]";
+
+/// Minimum time between writes of the stats file
+const FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleStat {
+    pub hit_count: u64,
+    /// Epoch milliseconds of the most recent hit
+    pub last_hit_ms: u64,
+}
+
+impl RuleStat {
+    fn merge(&mut self, other: &RuleStat) {
+        self.hit_count += other.hit_count;
+        self.last_hit_ms = self.last_hit_ms.max(other.last_hit_ms);
+    }
+}
+
+pub type RuleStats = HashMap<String, RuleStat>;
+
+pub struct RuleStatsStore {
+    path: PathBuf,
+}
+
+impl RuleStatsStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Create storage from app config
+    pub fn from_config() -> Result<Self, RuleError> {
+        let rules = RuleStorage::from_config()?;
+        Ok(Self::new(rules.base_dir.join("rule_stats.json")))
+    }
+
+    /// Load saved counters; a missing file means no hits yet
+    pub fn load(&self) -> Result<RuleStats, RuleError> {
+        if !self.path.exists() {
+            return Ok(RuleStats::new());
+        }
+        let content = fs::read_to_string(&self.path)?;
+        if content.trim().is_empty() {
+            return Ok(RuleStats::new());
+        }
+        serde_json::from_str(&content).map_err(|e| RuleError::Parse(e.to_string()))
+    }
+
+    /// Add `hits` to the saved counters
+    pub fn add(&self, hits: &RuleStats) -> Result<(), RuleError> {
+        if hits.is_empty() {
+            return Ok(());
+        }
+        let mut stats = self.load()?;
+        for (id, stat) in hits {
+            stats.entry(id.clone()).or_default().merge(stat);
+        }
+        let content = serde_json::to_string_pretty(&stats)
+            .map_err(|e| RuleError::Serialization(e.to_string()))?;
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, content)?;
+        Ok(())
+    }
+
+    pub fn reset(&self) -> Result<(), RuleError> {
+        if self.path.exists() {
+            fs::remove_file(&self.path)?;
+        }
+        Ok(())
+    }
+}
+
+struct PendingHits {
+    hits: RuleStats,
+    last_flush: Instant,
+}
+
+lazy_static::lazy_static! {
+    static ref PENDING: Mutex<PendingHits> = Mutex::new(PendingHits {
+        hits: RuleStats::new(),
+        last_flush: Instant::now(),
+    });
+}
+
+/// Parse a `[RULE_HIT] <rule_id> <epoch_ms>` marker out of an engine log line
+pub fn parse_marker(line: &str) -> Option<(String, u64)> {
+    let rest = &line[line.find(RULE_HIT_MARKER)? + RULE_HIT_MARKER.len()..];
+    let mut parts = rest.split_whitespace();
+    let id = parts.next()?.to_string();
+    let at_ms = parts.next().and_then(|t| t.parse().ok()).unwrap_or(0);
+    Some((id, at_ms))
+}
+
+/// Parse a `[RULE_SPENT] <rule_id>` marker out of an engine log line
+pub fn parse_spent_marker(line: &str) -> Option<String> {
+    let rest = &line[line.find(RULE_SPENT_MARKER)? + RULE_SPENT_MARKER.len()..];
+    rest.split_whitespace().next().map(str::to_string)
+}
+
+fn flush(pending: &mut PendingHits) -> Result<(), RuleError> {
+    pending.last_flush = Instant::now();
+    let hits = std::mem::take(&mut pending.hits);
+    RuleStatsStore::from_config()?.add(&hits)
+}
+
+/// Count the hit carried by an engine log line.
+/// Returns false when the line is not a hit marker.
+pub fn record_marker(line: &str) -> bool {
+    let Some((id, at_ms)) = parse_marker(line) else {
+        return false;
+    };
+    let Ok(mut pending) = PENDING.lock() else {
+        return true;
+    };
+    pending.hits.entry(id).or_default().merge(&RuleStat {
+        hit_count: 1,
+        last_hit_ms: at_ms,
+    });
+    if pending.last_flush.elapsed() >= FLUSH_INTERVAL {
+        if let Err(e) = flush(&mut pending) {
+            log::warn!("Failed to save rule stats: {}", e);
+        }
+    }
+    true
+}
+
+/// Write buffered hits to disk now
+pub fn flush_pending() -> Result<(), RuleError> {
+    match PENDING.lock() {
+        Ok(mut pending) => flush(&mut pending),
+        Err(_) => Ok(()),
+    }
+}
+
+/// Drop buffered hits without saving them
+pub fn clear_pending() {
+    if let Ok(mut pending) = PENDING.lock() {
+        pending.hits.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_marker() {
+        assert_eq!(
+            parse_marker("[RELAYCRAFT] [RULE_HIT] rule-1 1700000000000"),
+            Some(("rule-1".to_string(), 1_700_000_000_000))
+        );
+        assert_eq!(
+            parse_marker("[RULE_HIT] rule-2"),
+            Some(("rule-2".to_string(), 0))
+        );
+        assert_eq!(parse_marker("[RULE_HIT]"), None);
+        assert_eq!(parse_marker("GET https://example.com 200"), None);
+
+        assert_eq!(
+            parse_spent_marker("[RELAYCRAFT] [RULE_SPENT] rule-3"),
+            Some("rule-3".to_string())
+        );
+        assert_eq!(parse_spent_marker("[RULE_SPENT]"), None);
+        assert_eq!(parse_spent_marker("[RULE_HIT] rule-3 1"), None);
+        assert_eq!(parse_marker("[RULE_SPENT] rule-3"), None);
+    }
+
+    #[test]
+    fn test_stats_increment_and_reset() {
+        let temp = TempDir::new().unwrap();
+        let store = RuleStatsStore::new(temp.path().join("rule_stats.json"));
+        assert!(store.load().unwrap().is_empty());
+
+        let hit = |count, at| RuleStat {
+            hit_count: count,
+            last_hit_ms: at,
+        };
+        store
+            .add(&RuleStats::from([
+                ("a".to_string(), hit(2, 200)),
+                ("b".to_string(), hit(1, 100)),
+            ]))
+            .unwrap();
+        store
+            .add(&RuleStats::from([("a".to_string(), hit(1, 150))]))
+            .unwrap();
+
+        let stats = store.load().unwrap();
+        assert_eq!(stats["a"], hit(3, 200));
+        assert_eq!(stats["b"], hit(1, 100));
+
+        let json = serde_json::to_value(&stats["a"]).unwrap();
+        assert_eq!(json["hitCount"], 3);
+        assert_eq!(json["lastHitMs"], 200);
+
+        store.reset().unwrap();
+        assert!(store.load().unwrap().is_empty());
+        store.reset().unwrap();
+    }
+}
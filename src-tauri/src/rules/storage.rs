@@ -1,14 +1,20 @@
-use super::model::{Rule, RuleGroup};
+use super::model::{Rule, RuleAction, RuleGroup};
 use crate::common::error::RuleError;
 // use crate::config;
+use base64::Engine;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
 use serde::{Deserialize, Serialize};
 use std::fs::{self, File};
 use std::io::{Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 use zip::write::SimpleFileOptions;
 use zip::{ZipArchive, ZipWriter};
 
+/// Marks a compact rules string so foreign text is rejected before decoding
+pub const COMPACT_PREFIX: &str = "RCRULES1:";
+
 /// A single rule that failed to import
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -27,9 +33,152 @@ pub struct ImportResult {
     pub skipped_count: usize,
     #[serde(default)]
     pub failed_rules: Vec<FailedRule>,
+    /// Map Local file paths referenced by imported rules that do not exist on this machine
+    #[serde(default)]
+    pub missing_files: Vec<String>,
     pub error: Option<String>,
 }
 
+/// Collect Map Local files and folders referenced by `rules` that are missing
+/// on disk. Pure inspection: the result is informational and never blocks an
+/// import.
+pub fn find_missing_local_files<'a>(rules: impl IntoIterator<Item = &'a Rule>) -> Vec<String> {
+    let mut missing: Vec<String> = Vec::new();
+    for rule in rules {
+        for action in &rule.actions {
+            let RuleAction::MapLocal(map_local) = action else {
+                continue;
+            };
+            if map_local.source.as_deref() == Some("manual") {
+                continue;
+            }
+            if let Some(dir) = map_local.dir.as_deref() {
+                if !Path::new(dir).is_dir() && !missing.iter().any(|p| p == dir) {
+                    missing.push(dir.to_string());
+                }
+                continue;
+            }
+            let Some(local_path) = map_local.local_path.as_deref() else {
+                continue;
+            };
+            let local_path = local_path.trim();
+            if local_path.is_empty() || Path::new(local_path).exists() {
+                continue;
+            }
+            if !missing.iter().any(|p| p == local_path) {
+                missing.push(local_path.to_string());
+            }
+        }
+    }
+    missing
+}
+
+/// Status codes a Redirect action may answer with
+const REDIRECT_STATUS_CODES: &[u16] = &[301, 302, 307, 308];
+
+/// Reject rules the engine could not apply: every regex-typed match atom and
+/// every `regexReplace` pattern must compile, and redirects need a 3xx code.
+pub fn validate_rule(rule: &Rule) -> Result<(), RuleError> {
+    if rule.execution.times == Some(0) {
+        return Err(RuleError::Invalid(
+            "execution.times: must be at least 1".to_string(),
+        ));
+    }
+
+    let atoms = [
+        ("request", &rule.match_config.request),
+        ("response", &rule.match_config.response),
+    ];
+    for (side, atoms) in atoms {
+        for (i, atom) in atoms.iter().enumerate() {
+            if atom.match_type != "regex" {
+                continue;
+            }
+            let pattern = match &atom.value {
+                Some(serde_json::Value::String(s)) => s.clone(),
+                Some(other) => other.to_string(),
+                None => String::new(),
+            };
+            regex::Regex::new(&pattern)
+                .map_err(|e| RuleError::Invalid(format!("match.{}[{}].value: {}", side, i, e)))?;
+        }
+    }
+    for (i, action) in rule.actions.iter().enumerate() {
+        match action {
+            RuleAction::MapLocal(map_local) => {
+                if let Some(dir) = map_local.dir.as_deref() {
+                    let dir = Path::new(dir);
+                    if dir
+                        .components()
+                        .any(|c| c == std::path::Component::ParentDir)
+                    {
+                        return Err(RuleError::Invalid(format!(
+                            "actions[{}].dir: must not contain '..'",
+                            i
+                        )));
+                    }
+                }
+            }
+            RuleAction::RewriteBody(body) => {
+                if let Some(replace) = &body.regex_replace {
+                    regex::Regex::new(&replace.pattern).map_err(|e| {
+                        RuleError::Invalid(format!("actions[{}].regexReplace.pattern: {}", i, e))
+                    })?;
+                }
+            }
+            RuleAction::Redirect(redirect) => {
+                if !REDIRECT_STATUS_CODES.contains(&redirect.status_code) {
+                    return Err(RuleError::Invalid(format!(
+                        "actions[{}].statusCode: {} is not a redirect status (301, 302, 307, 308)",
+                        i, redirect.status_code
+                    )));
+                }
+                if redirect.target_url.trim().is_empty() {
+                    return Err(RuleError::Invalid(format!(
+                        "actions[{}].targetUrl: must not be empty",
+                        i
+                    )));
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Map Local folders a rule serves from
+fn local_dirs(rule: &Rule) -> Vec<&str> {
+    rule.actions
+        .iter()
+        .filter_map(|action| match action {
+            RuleAction::MapLocal(map_local) => map_local.dir.as_deref(),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Require every Map Local `dir` that `rule` adds or changes relative to
+/// `previous` to exist. Folders the saved rule already had are not checked
+/// again, so a rule whose folder went missing can still be toggled or moved.
+fn check_new_local_dirs(rule: &Rule, previous: Option<&Rule>) -> Result<(), RuleError> {
+    let known = previous.map(local_dirs).unwrap_or_default();
+    for (i, action) in rule.actions.iter().enumerate() {
+        let RuleAction::MapLocal(map_local) = action else {
+            continue;
+        };
+        let Some(dir) = map_local.dir.as_deref() else {
+            continue;
+        };
+        if !known.contains(&dir) && !Path::new(dir).is_dir() {
+            return Err(RuleError::Invalid(format!(
+                "actions[{}].dir: {} is not a directory",
+                i, dir
+            )));
+        }
+    }
+    Ok(())
+}
+
 /// Rule storage with dependency injection support
 pub struct RuleStorage {
     pub base_dir: PathBuf,
@@ -113,8 +262,29 @@ impl RuleStorage {
             .unwrap_or_else(|| "Default".to_string())
     }
 
-    /// Save rule to storage
+    /// Save rule to storage. A Map Local folder that is new or changed
+    /// must exist.
     pub fn save(&self, rule: &Rule, group_id: Option<&str>) -> Result<(), RuleError> {
+        check_new_local_dirs(rule, self.find_stored(&rule.id).as_ref())?;
+        self.write_rule(rule, group_id)
+    }
+
+    /// The saved version of a rule, if there is one
+    fn find_stored(&self, rule_id: &str) -> Option<Rule> {
+        let file_name = format!("{}.yaml", rule_id);
+        WalkDir::new(&self.base_dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .find(|e| e.path().is_file() && e.file_name().to_str() == Some(file_name.as_str()))
+            .and_then(|e| self.load_rule_from_path(e.path()).ok())
+            .map(|entry| entry.rule)
+    }
+
+    /// Write a rule without checking its Map Local folders, for edits that
+    /// do not come from the rule editor (toggles, copies, imports)
+    fn write_rule(&self, rule: &Rule, group_id: Option<&str>) -> Result<(), RuleError> {
+        validate_rule(rule)?;
+
         let group_id = group_id.unwrap_or("Default");
         let safe_path = group_id.replace("..", "").replace(":", "");
         let target_dir = self.base_dir.join(safe_path);
@@ -157,6 +327,30 @@ impl RuleStorage {
         Ok(())
     }
 
+    /// Copy a rule into its own group under a fresh ID. The copy starts
+    /// disabled so it never fires alongside the original by accident.
+    pub fn duplicate(&self, rule_id: &str, new_name: Option<&str>) -> Result<Rule, RuleError> {
+        let entry = self
+            .load_all()?
+            .rules
+            .into_iter()
+            .find(|e| e.rule.id == rule_id)
+            .ok_or_else(|| RuleError::NotFound(rule_id.to_string()))?;
+
+        let mut rule = entry.rule;
+        rule.id = uuid::Uuid::new_v4().to_string();
+        rule.name = match new_name.map(str::trim).filter(|n| !n.is_empty()) {
+            Some(name) => name.to_string(),
+            None => format!("{} (copy)", rule.name),
+        };
+        rule.execution.enabled = false;
+
+        // The new ID gives the copy its own file name, so the move-on-save
+        // cleanup in `write_rule` cannot touch the original.
+        self.write_rule(&rule, Some(&entry.group_id))?;
+        Ok(rule)
+    }
+
     /// Delete rule by ID
     pub fn delete(&self, rule_id: &str) -> Result<(), RuleError> {
         let file_name = format!("{}.yaml", rule_id);
@@ -212,6 +406,127 @@ impl RuleStorage {
         Ok(())
     }
 
+    /// Write a batch of edited rules. Every rule is validated and serialized
+    /// before the first file is touched, so a bad rule aborts the whole batch.
+    fn save_batch(&self, edits: &[(Rule, String)]) -> Result<(), RuleError> {
+        for (rule, _) in edits {
+            validate_rule(rule)?;
+            serde_yaml::to_string(&RuleFile { rule: rule.clone() })
+                .map_err(|e| RuleError::Serialization(e.to_string()))?;
+        }
+        for (rule, group_id) in edits {
+            self.write_rule(rule, Some(group_id))?;
+        }
+        Ok(())
+    }
+
+    /// Enable or disable the given rules. Returns how many rules changed.
+    pub fn set_rules_enabled(&self, ids: &[String], enabled: bool) -> Result<usize, RuleError> {
+        let edits: Vec<(Rule, String)> = self
+            .load_all()?
+            .rules
+            .into_iter()
+            .filter(|e| ids.contains(&e.rule.id) && e.rule.execution.enabled != enabled)
+            .map(|e| {
+                let mut rule = e.rule;
+                rule.execution.enabled = enabled;
+                (rule, e.group_id)
+            })
+            .collect();
+
+        self.save_batch(&edits)?;
+        Ok(edits.len())
+    }
+
+    /// Enable or disable a group and every rule in it. Returns how many rules changed.
+    pub fn set_group_enabled(&self, group_id: &str, enabled: bool) -> Result<usize, RuleError> {
+        let mut groups = self.load_groups()?;
+        let group = groups.iter_mut().find(|g| g.id == group_id);
+
+        let entries: Vec<RuleEntry> = self
+            .load_all()?
+            .rules
+            .into_iter()
+            .filter(|e| e.group_id == group_id)
+            .collect();
+        if group.is_none() && entries.is_empty() {
+            return Err(RuleError::NotFound(group_id.to_string()));
+        }
+
+        let edits: Vec<(Rule, String)> = entries
+            .into_iter()
+            .filter(|e| e.rule.execution.enabled != enabled)
+            .map(|e| {
+                let mut rule = e.rule;
+                rule.execution.enabled = enabled;
+                (rule, e.group_id)
+            })
+            .collect();
+
+        self.save_batch(&edits)?;
+        if let Some(group) = group {
+            if group.enabled != enabled {
+                group.enabled = enabled;
+                self.save_groups(&groups)?;
+            }
+        }
+        Ok(edits.len())
+    }
+
+    /// Marker file holding the soloed rule ID. The engine's rule loader checks
+    /// it on every reload; it is cleared on app startup so solo never persists.
+    fn solo_file(&self) -> PathBuf {
+        self.base_dir.join(".solo")
+    }
+
+    /// Get the currently soloed rule ID, if any
+    pub fn get_solo(&self) -> Option<String> {
+        fs::read_to_string(self.solo_file())
+            .ok()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    }
+
+    /// Solo a single rule, or clear solo mode with `None`
+    pub fn set_solo(&self, rule_id: Option<&str>) -> Result<(), RuleError> {
+        match rule_id {
+            Some(id) => {
+                let exists = self.load_all()?.rules.iter().any(|e| e.rule.id == id);
+                if !exists {
+                    return Err(RuleError::NotFound(id.to_string()));
+                }
+                fs::write(self.solo_file(), id)?;
+                log::info!("Soloed rule {}", id);
+            }
+            None => {
+                if self.solo_file().exists() {
+                    fs::remove_file(self.solo_file())?;
+                    log::info!("Cleared rule solo");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Rules in the order the engine evaluates them: priority, then name, then ID.
+    /// When a rule is soloed only that rule is returned.
+    pub fn load_effective_order(&self) -> Result<Vec<Rule>, RuleError> {
+        let mut rules: Vec<Rule> = self.load_all()?.rules.into_iter().map(|e| e.rule).collect();
+
+        if let Some(solo_id) = self.get_solo() {
+            rules.retain(|r| r.id == solo_id);
+        }
+
+        rules.sort_by(|a, b| {
+            a.execution
+                .priority
+                .cmp(&b.execution.priority)
+                .then_with(|| a.name.cmp(&b.name))
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        Ok(rules)
+    }
+
     /// Export all rules as bundle
     pub fn export_bundle(&self) -> Result<String, RuleError> {
         let response = self.load_all()?;
@@ -226,6 +541,33 @@ impl RuleStorage {
         serde_yaml::to_string(&bundle).map_err(|e| RuleError::Serialization(e.to_string()))
     }
 
+    /// Export one rule with its group ID. The YAML is a `RuleFile` plus a
+    /// `groupId` key, so it can also be dropped straight into a rules folder.
+    pub fn export_rule(&self, rule_id: &str) -> Result<String, RuleError> {
+        let entry = self
+            .load_all()?
+            .rules
+            .into_iter()
+            .find(|e| e.rule.id == rule_id)
+            .ok_or_else(|| RuleError::NotFound(rule_id.to_string()))?;
+
+        serde_yaml::to_string(&entry).map_err(|e| RuleError::Serialization(e.to_string()))
+    }
+
+    /// Import a rule written by `export_rule` (or a plain rule file, which
+    /// lands in the default group). An existing rule with the same ID is replaced.
+    pub fn import_rule(&self, yaml_content: &str) -> Result<RuleEntry, RuleError> {
+        let shared: SharedRuleFile =
+            serde_yaml::from_str(yaml_content).map_err(|e| RuleError::Parse(e.to_string()))?;
+        let group_id = shared.group_id.unwrap_or_else(|| "Default".to_string());
+
+        self.save(&shared.rule, Some(&group_id))?;
+        Ok(RuleEntry {
+            group_id,
+            rule: shared.rule,
+        })
+    }
+
     /// Import rules from bundle, collecting per-rule errors instead of failing fast
     pub fn import_bundle(&self, yaml_content: &str) -> Result<ImportResult, RuleError> {
         let bundle: RuleBundle =
@@ -234,10 +576,10 @@ impl RuleStorage {
         let mut imported_count = 0;
         let mut failed_rules: Vec<FailedRule> = Vec::new();
 
-        for entry in bundle.rules {
+        for entry in &bundle.rules {
             let rule_id = entry.rule.id.clone();
             let rule_name = entry.rule.name.clone();
-            match self.save(&entry.rule, Some(&entry.group_id)) {
+            match self.write_rule(&entry.rule, Some(&entry.group_id)) {
                 Ok(()) => imported_count += 1,
                 Err(e) => failed_rules.push(FailedRule {
                     id: rule_id,
@@ -251,15 +593,73 @@ impl RuleStorage {
             self.save_groups(&bundle.groups)?;
         }
 
+        let missing_files = find_missing_local_files(bundle.rules.iter().map(|e| &e.rule));
+
         Ok(ImportResult {
             success: failed_rules.is_empty(),
             imported_count,
             skipped_count: failed_rules.len(),
             failed_rules,
+            missing_files,
             error: None,
         })
     }
 
+    /// Disable every rule among `spent_ids` that is still enabled and is
+    /// either one-shot or limited by `execution.times`.
+    /// Returns the rules that were disabled.
+    pub fn disable_spent_rules(&self, spent_ids: &[String]) -> Result<Vec<Rule>, RuleError> {
+        let mut disabled = Vec::new();
+        for entry in self.load_all()?.rules {
+            let mut rule = entry.rule;
+            let limited =
+                rule.execution.disable_after_hit == Some(true) || rule.execution.times.is_some();
+            if !spent_ids.contains(&rule.id) || !rule.execution.enabled || !limited {
+                continue;
+            }
+            rule.execution.enabled = false;
+            self.write_rule(&rule, Some(&entry.group_id))?;
+            disabled.push(rule);
+        }
+        Ok(disabled)
+    }
+
+    /// Export the bundle as a single pasteable string: prefix + base64(gzip(YAML))
+    pub fn export_compact(&self) -> Result<String, RuleError> {
+        let yaml = self.export_bundle()?;
+
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::best());
+        encoder.write_all(yaml.as_bytes())?;
+        let compressed = encoder.finish()?;
+
+        Ok(format!(
+            "{}{}",
+            COMPACT_PREFIX,
+            base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(compressed)
+        ))
+    }
+
+    /// Import a string produced by [`Self::export_compact`]
+    pub fn import_compact(&self, encoded: &str) -> Result<ImportResult, RuleError> {
+        let payload = encoded
+            .trim()
+            .strip_prefix(COMPACT_PREFIX)
+            .ok_or_else(|| RuleError::Parse("Not a RelayCraft rules string".to_string()))?;
+
+        // Chat clients may wrap long strings, so ignore embedded whitespace
+        let payload: String = payload.chars().filter(|c| !c.is_whitespace()).collect();
+        let compressed = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(payload.trim_end_matches('='))
+            .map_err(|e| RuleError::Parse(format!("Corrupted rules string: {}", e)))?;
+
+        let mut yaml = String::new();
+        GzDecoder::new(compressed.as_slice())
+            .read_to_string(&mut yaml)
+            .map_err(|e| RuleError::Parse(format!("Corrupted rules string: {}", e)))?;
+
+        self.import_bundle(&yaml)
+    }
+
     /// Export rules to a ZIP file
     pub fn export_zip(&self, save_path: &std::path::Path) -> Result<(), RuleError> {
         // Create ZIP file
@@ -310,6 +710,7 @@ impl RuleStorage {
 
         let mut imported_count = 0;
         let mut skipped_count = 0;
+        let mut imported_rules: Vec<Rule> = Vec::new();
 
         for i in 0..archive.len() {
             let mut file = archive
@@ -361,6 +762,10 @@ impl RuleStorage {
                 }
             }
             imported_count += 1;
+
+            if let Ok(entry) = self.load_rule_from_path(&outpath) {
+                imported_rules.push(entry.rule);
+            }
         }
 
         Ok(ImportResult {
@@ -368,6 +773,7 @@ impl RuleStorage {
             imported_count,
             skipped_count,
             failed_rules: vec![],
+            missing_files: find_missing_local_files(&imported_rules),
             error: None,
         })
     }
@@ -380,6 +786,15 @@ pub struct RuleFile {
     pub rule: Rule,
 }
 
+/// A single shared rule: `RuleEntry` with the group made optional
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SharedRuleFile {
+    #[serde(default)]
+    group_id: Option<String>,
+    rule: Rule,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct GroupsFile {
@@ -434,6 +849,8 @@ mod tests {
                 enabled: true,
                 priority: 10,
                 stop_on_match: Some(true),
+                disable_after_hit: None,
+                times: None,
             },
             match_config: RuleMatchConfig {
                 request: vec![],
@@ -475,6 +892,75 @@ mod tests {
         assert_eq!(groups.len(), 0);
     }
 
+    #[test]
+    fn test_toggle_group_and_rules() {
+        let temp = TempDir::new().unwrap();
+        let storage = RuleStorage::new(temp.path().to_path_buf()).unwrap();
+
+        let rule = |id: &str| Rule {
+            id: id.into(),
+            name: id.into(),
+            r#type: RuleType::BlockRequest,
+            execution: RuleExecution {
+                enabled: true,
+                priority: 1,
+                stop_on_match: None,
+                disable_after_hit: None,
+                times: None,
+            },
+            match_config: RuleMatchConfig {
+                request: vec![],
+                response: vec![],
+            },
+            actions: vec![RuleAction::BlockRequest],
+            tags: None,
+            metadata: None,
+        };
+        for id in ["a", "b", "c"] {
+            storage.save(&rule(id), Some("ads")).unwrap();
+        }
+        storage.save(&rule("other"), Some("api")).unwrap();
+        storage
+            .save_groups(&[RuleGroup {
+                id: "ads".into(),
+                name: "Ads".into(),
+                enabled: true,
+                priority: 1,
+                description: None,
+            }])
+            .unwrap();
+
+        let enabled_ids = |storage: &RuleStorage| {
+            let mut ids: Vec<String> = storage
+                .load_all()
+                .unwrap()
+                .rules
+                .into_iter()
+                .filter(|e| e.rule.execution.enabled)
+                .map(|e| e.rule.id)
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(storage.set_group_enabled("ads", false).unwrap(), 3);
+        assert_eq!(enabled_ids(&storage), vec!["other"]);
+        assert!(!storage.load_groups().unwrap()[0].enabled);
+        // Already disabled: nothing left to change
+        assert_eq!(storage.set_group_enabled("ads", false).unwrap(), 0);
+
+        let ids = vec!["a".to_string(), "other".to_string(), "missing".to_string()];
+        assert_eq!(storage.set_rules_enabled(&ids, true).unwrap(), 1);
+        assert_eq!(enabled_ids(&storage), vec!["a", "other"]);
+
+        assert_eq!(storage.set_group_enabled("ads", true).unwrap(), 2);
+        assert!(storage.load_groups().unwrap()[0].enabled);
+        assert!(matches!(
+            storage.set_group_enabled("nope", true),
+            Err(RuleError::NotFound(_))
+        ));
+    }
+
     #[test]
     fn test_bundle_export_import() {
         let temp = TempDir::new().unwrap();
@@ -489,6 +975,8 @@ mod tests {
                 enabled: true,
                 priority: 1,
                 stop_on_match: None,
+                disable_after_hit: None,
+                times: None,
             },
             match_config: RuleMatchConfig {
                 request: vec![],
@@ -544,6 +1032,8 @@ mod tests {
                 enabled: true,
                 priority: 1,
                 stop_on_match: None,
+                disable_after_hit: None,
+                times: None,
             },
             match_config: RuleMatchConfig {
                 request: vec![],
@@ -570,4 +1060,594 @@ mod tests {
             panic!("Expected MapRemote action");
         }
     }
+
+    #[test]
+    fn test_save_rejects_invalid_regex() {
+        let temp = TempDir::new().unwrap();
+        let storage = RuleStorage::new(temp.path().to_path_buf()).unwrap();
+
+        let rewrite = |pattern: &str| {
+            RuleAction::RewriteBody(RewriteBodyAction {
+                target: "response".into(),
+                status_code: None,
+                content_type: None,
+                set: None,
+                replace: None,
+                regex_replace: Some(BodyReplaceMode {
+                    pattern: pattern.into(),
+                    replacement: "x".into(),
+                }),
+                json: None,
+            })
+        };
+        let mut rule = Rule {
+            id: "regex-rule".into(),
+            name: "Regex Rule".into(),
+            r#type: RuleType::RewriteBody,
+            execution: RuleExecution {
+                enabled: true,
+                priority: 1,
+                stop_on_match: None,
+                disable_after_hit: None,
+                times: None,
+            },
+            match_config: RuleMatchConfig {
+                request: vec![MatchAtom {
+                    atom_type: "url".into(),
+                    match_type: "regex".into(),
+                    key: None,
+                    value: Some(serde_json::json!(r"/api/v\d+/")),
+                    invert: None,
+                }],
+                response: vec![],
+            },
+            actions: vec![rewrite(r"token=\w+")],
+            tags: None,
+            metadata: None,
+        };
+        storage.save(&rule, None).unwrap();
+
+        rule.match_config.request[0].value = Some(serde_json::json!("/api/(v1"));
+        match storage.save(&rule, None) {
+            Err(RuleError::Invalid(msg)) => assert!(msg.starts_with("match.request[0].value")),
+            other => panic!("expected invalid rule, got {:?}", other),
+        }
+
+        rule.match_config.request[0].value = Some(serde_json::json!("/api/"));
+        rule.actions = vec![RuleAction::BlockRequest, rewrite("[a-")];
+        match storage.save(&rule, None) {
+            Err(RuleError::Invalid(msg)) => {
+                assert!(msg.starts_with("actions[1].regexReplace.pattern"))
+            }
+            other => panic!("expected invalid rule, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_redirect_serialization() {
+        let temp = TempDir::new().unwrap();
+        let storage = RuleStorage::new(temp.path().to_path_buf()).unwrap();
+
+        let mut rule = Rule {
+            id: "redirect".into(),
+            name: "Redirect".into(),
+            r#type: RuleType::Redirect,
+            execution: RuleExecution {
+                enabled: true,
+                priority: 1,
+                stop_on_match: None,
+                disable_after_hit: None,
+                times: None,
+            },
+            match_config: RuleMatchConfig {
+                request: vec![],
+                response: vec![],
+            },
+            actions: vec![RuleAction::Redirect(RedirectAction {
+                target_url: "https://new.example.com/login".into(),
+                status_code: 308,
+                preserve_query: true,
+            })],
+            tags: None,
+            metadata: None,
+        };
+
+        storage.save(&rule, None).unwrap();
+
+        let response = storage.load_all().unwrap();
+        let loaded_rule = &response.rules[0].rule;
+        assert_eq!(loaded_rule.r#type, RuleType::Redirect);
+        if let RuleAction::Redirect(redirect) = &loaded_rule.actions[0] {
+            assert_eq!(redirect.target_url, "https://new.example.com/login");
+            assert_eq!(redirect.status_code, 308);
+            assert!(redirect.preserve_query);
+        } else {
+            panic!("Expected Redirect action");
+        }
+
+        rule.actions = vec![RuleAction::Redirect(RedirectAction {
+            target_url: "https://new.example.com/".into(),
+            status_code: 200,
+            preserve_query: false,
+        })];
+        assert!(matches!(
+            storage.save(&rule, None),
+            Err(RuleError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_times_serialization() {
+        let execution: RuleExecution = serde_json::from_value(serde_json::json!({
+            "enabled": true,
+            "priority": 1,
+            "stopOnMatch": null,
+            "times": 3
+        }))
+        .unwrap();
+        assert_eq!(execution.times, Some(3));
+        assert_eq!(serde_json::to_value(&execution).unwrap()["times"], 3);
+
+        // Unlimited rules keep the field out of the file
+        let execution = RuleExecution {
+            times: None,
+            ..execution
+        };
+        let yaml = serde_yaml::to_string(&execution).unwrap();
+        assert!(!yaml.contains("times"));
+        let parsed: RuleExecution = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed.times, None);
+
+        let mut rule = Rule {
+            id: "limited".into(),
+            name: "Limited".into(),
+            r#type: RuleType::BlockRequest,
+            execution: RuleExecution {
+                times: Some(0),
+                ..parsed
+            },
+            match_config: RuleMatchConfig {
+                request: vec![],
+                response: vec![],
+            },
+            actions: vec![RuleAction::BlockRequest],
+            tags: None,
+            metadata: None,
+        };
+        assert!(matches!(validate_rule(&rule), Err(RuleError::Invalid(_))));
+        rule.execution.times = Some(1);
+        assert!(validate_rule(&rule).is_ok());
+    }
+
+    #[test]
+    fn test_breakpoint_serialization() {
+        let action: RuleAction =
+            serde_json::from_value(serde_json::json!({ "type": "breakpoint", "phase": "both" }))
+                .unwrap();
+        let RuleAction::Breakpoint(breakpoint) = &action else {
+            panic!("Expected Breakpoint action");
+        };
+        assert_eq!(breakpoint.phase, BreakpointPhase::Both);
+        assert_eq!(
+            serde_json::to_value(&action).unwrap(),
+            serde_json::json!({ "type": "breakpoint", "phase": "both" })
+        );
+
+        // Phase defaults to the request side
+        let action: RuleAction =
+            serde_json::from_value(serde_json::json!({ "type": "breakpoint" })).unwrap();
+        assert!(matches!(
+            action,
+            RuleAction::Breakpoint(BreakpointAction {
+                phase: BreakpointPhase::Request
+            })
+        ));
+
+        let yaml = serde_yaml::to_string(&RuleAction::Breakpoint(BreakpointAction {
+            phase: BreakpointPhase::Response,
+        }))
+        .unwrap();
+        assert!(yaml.contains("type: breakpoint"));
+        assert!(yaml.contains("phase: response"));
+        assert_eq!(
+            serde_json::to_value(RuleType::Breakpoint).unwrap(),
+            "breakpoint"
+        );
+    }
+
+    #[test]
+    fn test_duplicate_rule() {
+        let temp = TempDir::new().unwrap();
+        let storage = RuleStorage::new(temp.path().to_path_buf()).unwrap();
+
+        let rule = Rule {
+            id: "original".into(),
+            name: "Block ads".into(),
+            r#type: RuleType::BlockRequest,
+            execution: RuleExecution {
+                enabled: true,
+                priority: 1,
+                stop_on_match: None,
+                disable_after_hit: None,
+                times: None,
+            },
+            match_config: RuleMatchConfig {
+                request: vec![],
+                response: vec![],
+            },
+            actions: vec![RuleAction::BlockRequest],
+            tags: None,
+            metadata: None,
+        };
+        storage.save(&rule, Some("ads")).unwrap();
+
+        let copy = storage.duplicate("original", None).unwrap();
+        assert_ne!(copy.id, rule.id);
+        assert_eq!(copy.name, "Block ads (copy)");
+        assert!(!copy.execution.enabled);
+
+        let group_dir = temp.path().join("ads");
+        assert!(group_dir.join("original.yaml").exists());
+        assert!(group_dir.join(format!("{}.yaml", copy.id)).exists());
+
+        let loaded = storage.load_all().unwrap().rules;
+        assert_eq!(loaded.len(), 2);
+        let original = loaded.iter().find(|e| e.rule.id == "original").unwrap();
+        let duplicate = loaded.iter().find(|e| e.rule.id == copy.id).unwrap();
+        assert!(original.rule.execution.enabled);
+        assert_eq!(duplicate.group_id, "ads");
+        assert_eq!(
+            serde_json::to_value(&original.rule.actions).unwrap(),
+            serde_json::to_value(&duplicate.rule.actions).unwrap()
+        );
+
+        let renamed = storage
+            .duplicate("original", Some("Block trackers"))
+            .unwrap();
+        assert_eq!(renamed.name, "Block trackers");
+        assert!(matches!(
+            storage.duplicate("missing", None),
+            Err(RuleError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_map_local_dir() {
+        let temp = TempDir::new().unwrap();
+        let site = temp.path().join("site");
+        fs::create_dir_all(site.join("docs")).unwrap();
+        fs::write(site.join("index.html"), "home").unwrap();
+        fs::write(site.join("docs").join("index.html"), "docs").unwrap();
+
+        let action = |dir: String| {
+            RuleAction::MapLocal(MapLocalAction {
+                source: Some("file".into()),
+                local_path: None,
+                dir: Some(dir),
+                content: None,
+                content_type: None,
+                status_code: None,
+                headers: None,
+            })
+        };
+        let json = serde_json::to_value(action(site.to_string_lossy().into_owned())).unwrap();
+        assert_eq!(json["type"], "map_local");
+        assert_eq!(json["dir"], site.to_string_lossy().as_ref());
+        let parsed: MapLocalAction =
+            serde_json::from_value(serde_json::json!({ "localPath": "/tmp/a.json" })).unwrap();
+        assert!(parsed.dir.is_none());
+
+        let storage = RuleStorage::new(temp.path().join("rules")).unwrap();
+        let mut rule = Rule {
+            id: "site".into(),
+            name: "Site".into(),
+            r#type: RuleType::MapLocal,
+            execution: RuleExecution {
+                enabled: true,
+                priority: 1,
+                stop_on_match: None,
+                disable_after_hit: None,
+                times: None,
+            },
+            match_config: RuleMatchConfig {
+                request: vec![],
+                response: vec![],
+            },
+            actions: vec![action(site.to_string_lossy().into_owned())],
+            tags: None,
+            metadata: None,
+        };
+        storage.save(&rule, None).unwrap();
+
+        rule.actions = vec![action(format!("{}/../site", site.display()))];
+        assert!(matches!(
+            storage.save(&rule, None),
+            Err(RuleError::Invalid(_))
+        ));
+        rule.actions = vec![action(
+            temp.path().join("missing").to_string_lossy().into_owned(),
+        )];
+        assert!(matches!(
+            storage.save(&rule, None),
+            Err(RuleError::Invalid(_))
+        ));
+
+        // A saved folder that later disappears does not block other edits
+        fs::remove_dir_all(&site).unwrap();
+        assert_eq!(
+            storage
+                .set_rules_enabled(&["site".to_string()], false)
+                .unwrap(),
+            1
+        );
+        let copy = storage.duplicate("site", None).unwrap();
+        let mut saved = storage.find_stored("site").unwrap();
+        saved.name = "Renamed".into();
+        storage.save(&saved, None).unwrap();
+        let bundle = storage.export_bundle().unwrap();
+        let other = RuleStorage::new(temp.path().join("other")).unwrap();
+        let result = other.import_bundle(&bundle).unwrap();
+        assert_eq!(result.imported_count, 2);
+        assert_eq!(
+            result.missing_files,
+            vec![site.to_string_lossy().into_owned()]
+        );
+        assert!(storage.find_stored(&copy.id).is_some());
+    }
+
+    #[test]
+    fn test_single_rule_round_trip() {
+        let source = TempDir::new().unwrap();
+        let storage = RuleStorage::new(source.path().to_path_buf()).unwrap();
+        let rule = Rule {
+            id: "share-me".into(),
+            name: "Shared".into(),
+            r#type: RuleType::Redirect,
+            execution: RuleExecution {
+                enabled: true,
+                priority: 3,
+                stop_on_match: Some(true),
+                disable_after_hit: None,
+                times: None,
+            },
+            match_config: RuleMatchConfig {
+                request: vec![],
+                response: vec![],
+            },
+            actions: vec![RuleAction::Redirect(RedirectAction {
+                target_url: "https://example.com/".into(),
+                status_code: 302,
+                preserve_query: false,
+            })],
+            tags: Some(vec!["demo".into()]),
+            metadata: None,
+        };
+        storage.save(&rule, Some("shared")).unwrap();
+        storage
+            .save(
+                &Rule {
+                    id: "other".into(),
+                    ..rule.clone()
+                },
+                None,
+            )
+            .unwrap();
+
+        let yaml = storage.export_rule("share-me").unwrap();
+        assert!(!yaml.contains("other"));
+        // Still readable as a plain rule file
+        let as_file: RuleFile = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(as_file.rule.id, "share-me");
+
+        let target = TempDir::new().unwrap();
+        let fresh = RuleStorage::new(target.path().to_path_buf()).unwrap();
+        let imported = fresh.import_rule(&yaml).unwrap();
+        assert_eq!(imported.group_id, "shared");
+
+        let loaded = fresh.load_all().unwrap().rules;
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].group_id, "shared");
+        assert_eq!(
+            serde_json::to_value(&loaded[0].rule).unwrap(),
+            serde_json::to_value(&rule).unwrap()
+        );
+
+        assert!(matches!(
+            storage.export_rule("missing"),
+            Err(RuleError::NotFound(_))
+        ));
+        assert!(matches!(
+            fresh.import_rule("not: [a rule"),
+            Err(RuleError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_import_bundle_reports_missing_local_files() {
+        let temp = TempDir::new().unwrap();
+        let storage = RuleStorage::new(temp.path().to_path_buf()).unwrap();
+
+        let existing = temp.path().join("mock.json");
+        fs::write(&existing, "{}").unwrap();
+
+        let map_local = |path: &str| {
+            RuleAction::MapLocal(MapLocalAction {
+                source: Some("file".into()),
+                local_path: Some(path.into()),
+                dir: None,
+                content: None,
+                content_type: None,
+                status_code: None,
+                headers: None,
+            })
+        };
+
+        let rule = Rule {
+            id: "ml".into(),
+            name: "Map Local".into(),
+            r#type: RuleType::MapLocal,
+            execution: RuleExecution {
+                enabled: true,
+                priority: 1,
+                stop_on_match: None,
+                disable_after_hit: None,
+                times: None,
+            },
+            match_config: RuleMatchConfig {
+                request: vec![],
+                response: vec![],
+            },
+            actions: vec![
+                map_local(&existing.to_string_lossy()),
+                map_local("/definitely/not/here/mock.json"),
+            ],
+            tags: None,
+            metadata: None,
+        };
+        storage.save(&rule, None).unwrap();
+        let bundle = storage.export_bundle().unwrap();
+
+        let temp2 = TempDir::new().unwrap();
+        let storage2 = RuleStorage::new(temp2.path().to_path_buf()).unwrap();
+        let result = storage2.import_bundle(&bundle).unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.imported_count, 1);
+        assert_eq!(
+            result.missing_files,
+            vec!["/definitely/not/here/mock.json".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_solo_limits_effective_order() {
+        let temp = TempDir::new().unwrap();
+        let storage = RuleStorage::new(temp.path().to_path_buf()).unwrap();
+
+        let block = |id: &str, priority: i32| Rule {
+            id: id.into(),
+            name: id.into(),
+            r#type: RuleType::BlockRequest,
+            execution: RuleExecution {
+                enabled: true,
+                priority,
+                stop_on_match: None,
+                disable_after_hit: None,
+                times: None,
+            },
+            match_config: RuleMatchConfig {
+                request: vec![],
+                response: vec![],
+            },
+            actions: vec![RuleAction::BlockRequest],
+            tags: None,
+            metadata: None,
+        };
+        storage.save(&block("b", 2), None).unwrap();
+        storage.save(&block("a", 1), None).unwrap();
+
+        let ids = |rules: Vec<Rule>| rules.into_iter().map(|r| r.id).collect::<Vec<_>>();
+        assert_eq!(ids(storage.load_effective_order().unwrap()), vec!["a", "b"]);
+
+        storage.set_solo(Some("b")).unwrap();
+        assert_eq!(storage.get_solo().as_deref(), Some("b"));
+        assert_eq!(ids(storage.load_effective_order().unwrap()), vec!["b"]);
+
+        assert!(storage.set_solo(Some("missing")).is_err());
+
+        storage.set_solo(None).unwrap();
+        assert_eq!(storage.get_solo(), None);
+        assert_eq!(ids(storage.load_effective_order().unwrap()), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_disable_spent_rules_only_touches_one_shot_rules() {
+        let temp = TempDir::new().unwrap();
+        let storage = RuleStorage::new(temp.path().to_path_buf()).unwrap();
+
+        let block = |id: &str, one_shot: Option<bool>| Rule {
+            id: id.into(),
+            name: id.into(),
+            r#type: RuleType::BlockRequest,
+            execution: RuleExecution {
+                enabled: true,
+                priority: 1,
+                stop_on_match: None,
+                disable_after_hit: one_shot,
+                times: None,
+            },
+            match_config: RuleMatchConfig {
+                request: vec![],
+                response: vec![],
+            },
+            actions: vec![RuleAction::BlockRequest],
+            tags: None,
+            metadata: None,
+        };
+        storage.save(&block("once", Some(true)), None).unwrap();
+        storage.save(&block("always", None), None).unwrap();
+
+        let hits = vec!["once".to_string(), "always".to_string()];
+        let disabled = storage.disable_spent_rules(&hits).unwrap();
+        assert_eq!(disabled.len(), 1);
+        assert_eq!(disabled[0].id, "once");
+        // Already disabled, so a repeated report is a no-op
+        assert!(storage.disable_spent_rules(&hits).unwrap().is_empty());
+
+        let rules = storage.load_all().unwrap().rules;
+        let enabled = |id: &str| {
+            rules
+                .iter()
+                .find(|e| e.rule.id == id)
+                .unwrap()
+                .rule
+                .execution
+                .enabled
+        };
+        assert!(!enabled("once"));
+        assert!(enabled("always"));
+    }
+
+    #[test]
+    fn test_compact_string_round_trip() {
+        let source_dir = TempDir::new().unwrap();
+        let source = RuleStorage::new(source_dir.path().to_path_buf()).unwrap();
+        let rule = Rule {
+            id: "compact-rule".into(),
+            name: "Compact".into(),
+            r#type: RuleType::BlockRequest,
+            execution: RuleExecution {
+                enabled: true,
+                priority: 1,
+                stop_on_match: None,
+                disable_after_hit: None,
+                times: None,
+            },
+            match_config: RuleMatchConfig {
+                request: vec![],
+                response: vec![],
+            },
+            actions: vec![RuleAction::BlockRequest],
+            tags: None,
+            metadata: None,
+        };
+        source.save(&rule, None).unwrap();
+
+        let encoded = source.export_compact().unwrap();
+        assert!(encoded.starts_with(COMPACT_PREFIX));
+
+        let target_dir = TempDir::new().unwrap();
+        let target = RuleStorage::new(target_dir.path().to_path_buf()).unwrap();
+        let result = target.import_compact(&format!("  {}\n", encoded)).unwrap();
+        assert_eq!(result.imported_count, 1);
+        assert_eq!(target.load_all().unwrap().rules[0].rule.id, "compact-rule");
+
+        assert!(matches!(
+            target.import_compact("H4sIAAAAAAAA"),
+            Err(RuleError::Parse(_))
+        ));
+        assert!(target
+            .import_compact(&format!("{}not-base64!", COMPACT_PREFIX))
+            .is_err());
+    }
 }
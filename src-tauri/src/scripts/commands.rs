@@ -1,7 +1,7 @@
-use crate::common::error::ToTauriError;
+use crate::common::error::{ScriptError, ToTauriError};
 use crate::logging;
-use crate::scripts::model::ScriptInfo;
-use crate::scripts::storage::ScriptStorage;
+use crate::scripts::model::{ScriptImportResult, ScriptInfo};
+use crate::scripts::storage::{check_syntax, ScriptStorage};
 
 #[tauri::command]
 pub fn list_scripts() -> Result<Vec<ScriptInfo>, String> {
@@ -17,8 +17,19 @@ pub fn get_script_content(name: String) -> Result<String, String> {
     storage.get_content(&name).map_err(|e| e.to_tauri_error())
 }
 
+/// Save a script. With `validate`, content that does not compile is rejected
+/// before anything is written; the check is skipped when no interpreter is
+/// available.
 #[tauri::command]
-pub fn save_script(name: String, content: String) -> Result<(), String> {
+pub fn save_script(name: String, content: String, validate: Option<bool>) -> Result<(), String> {
+    if validate.unwrap_or(false) {
+        match check_syntax(&content) {
+            Err(ScriptError::ValidationUnavailable(reason)) => {
+                log::warn!("Skipping syntax check for {}: {}", name, reason);
+            }
+            result => result.map_err(|e| e.to_tauri_error())?,
+        }
+    }
     let storage = ScriptStorage::from_config().map_err(|e| e.to_tauri_error())?;
 
     storage
@@ -29,6 +40,12 @@ pub fn save_script(name: String, content: String) -> Result<(), String> {
     Ok(())
 }
 
+/// Syntax-check script content without saving it
+#[tauri::command]
+pub fn validate_script(content: String) -> Result<(), String> {
+    check_syntax(&content).map_err(|e| e.to_tauri_error())
+}
+
 #[tauri::command]
 pub fn delete_script(name: String) -> Result<(), String> {
     let storage = ScriptStorage::from_config().map_err(|e| e.to_tauri_error())?;
@@ -53,6 +70,31 @@ pub fn set_script_enabled(name: String, enabled: bool) -> Result<(), String> {
     Ok(())
 }
 
+/// Enable or disable every script matching `pattern` (glob or substring)
+#[tauri::command]
+pub fn set_enabled_bulk(pattern: String, enabled: bool) -> Result<Vec<ScriptInfo>, String> {
+    let storage = ScriptStorage::from_config().map_err(|e| e.to_tauri_error())?;
+
+    let changed = storage
+        .set_enabled_bulk(&pattern, enabled)
+        .map_err(|e| e.to_tauri_error())?;
+
+    if !changed.is_empty() {
+        let _ = logging::write_domain_log(
+            "audit",
+            &format!(
+                "Set {} script(s) matching '{}' active: {} ({})",
+                changed.len(),
+                pattern,
+                enabled,
+                changed.join(", ")
+            ),
+        );
+    }
+
+    storage.list_scripts().map_err(|e| e.to_tauri_error())
+}
+
 #[tauri::command]
 pub fn rename_script(old_name: String, new_name: String) -> Result<(), String> {
     let storage = ScriptStorage::from_config().map_err(|e| e.to_tauri_error())?;
@@ -68,6 +110,22 @@ pub fn rename_script(old_name: String, new_name: String) -> Result<(), String> {
     Ok(())
 }
 
+/// Copy a script next to the original; the copy starts disabled
+#[tauri::command]
+pub fn duplicate_script(name: String) -> Result<Vec<ScriptInfo>, String> {
+    let storage = ScriptStorage::from_config().map_err(|e| e.to_tauri_error())?;
+
+    let new_name = storage
+        .duplicate_script(&name)
+        .map_err(|e| e.to_tauri_error())?;
+
+    let _ = logging::write_domain_log(
+        "audit",
+        &format!("Duplicated script {} as {}", name, new_name),
+    );
+    storage.list_scripts().map_err(|e| e.to_tauri_error())
+}
+
 #[tauri::command]
 pub fn move_script(name: String, direction: String) -> Result<Vec<ScriptInfo>, String> {
     let storage = ScriptStorage::from_config().map_err(|e| e.to_tauri_error())?;
@@ -76,3 +134,91 @@ pub fn move_script(name: String, direction: String) -> Result<Vec<ScriptInfo>, S
         .move_script(&name, &direction)
         .map_err(|e| e.to_tauri_error())
 }
+
+/// Copy `.py` files from an existing mitmproxy script directory into script
+/// storage. Imported scripts start disabled; existing names are skipped.
+#[tauri::command]
+pub fn import_scripts_from_dir(dir: String) -> Result<ScriptImportResult, String> {
+    let storage = ScriptStorage::from_config().map_err(|e| e.to_tauri_error())?;
+
+    let result = storage
+        .import_from_dir(std::path::Path::new(&dir))
+        .map_err(|e| e.to_tauri_error())?;
+
+    let _ = logging::write_domain_log(
+        "audit",
+        &format!(
+            "Imported {} script(s) from {} ({} skipped)",
+            result.imported.len(),
+            dir,
+            result.skipped.len()
+        ),
+    );
+    Ok(result)
+}
+
+/// Export all scripts, with their enabled state, to a ZIP file
+#[tauri::command]
+pub async fn export_scripts_zip(save_path: String) -> Result<String, String> {
+    let storage = ScriptStorage::from_config().map_err(|e| e.to_tauri_error())?;
+
+    storage
+        .export_zip(std::path::Path::new(&save_path))
+        .map_err(|e| e.to_tauri_error())?;
+
+    Ok(format!("Successfully exported scripts to {}", save_path))
+}
+
+/// Import scripts from a ZIP made by `export_scripts_zip`
+#[tauri::command]
+pub async fn import_scripts_zip(zip_path: String) -> Result<ScriptImportResult, String> {
+    let storage = ScriptStorage::from_config().map_err(|e| e.to_tauri_error())?;
+
+    let result = storage
+        .import_zip(std::path::Path::new(&zip_path))
+        .map_err(|e| e.to_tauri_error())?;
+
+    let _ = logging::write_domain_log(
+        "audit",
+        &format!(
+            "Imported {} script(s) from {} ({} skipped)",
+            result.imported.len(),
+            zip_path,
+            result.skipped.len()
+        ),
+    );
+    Ok(result)
+}
+
+/// Ask the running engine whether tracking injection works in its bundled
+/// interpreter. When it does not, scripts still load but their hits are not
+/// recorded.
+#[tauri::command]
+pub async fn check_preprocessor(
+    state: tauri::State<'_, crate::proxy::ProxyState>,
+) -> Result<bool, String> {
+    if !state.engine.get_status().running {
+        return Err("Proxy engine is not running".to_string());
+    }
+
+    let config = crate::config::load_config().unwrap_or_default();
+    let url = format!(
+        "http://127.0.0.1:{}/_relay/scripts/load_status",
+        crate::proxy::engine_port(&config)
+    );
+    let report: serde_json::Value = reqwest::Client::new()
+        .get(&url)
+        .timeout(std::time::Duration::from_secs(5))
+        .send()
+        .await
+        .map_err(|e| format!("engine_error: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse script load status: {}", e))?;
+
+    let preprocessor = &report["preprocessor"];
+    if let Some(error) = preprocessor["error"].as_str() {
+        log::warn!("Script preprocessor self-test failed: {}", error);
+    }
+    Ok(preprocessor["ok"].as_bool().unwrap_or(false))
+}
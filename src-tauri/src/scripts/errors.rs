@@ -0,0 +1,60 @@
+//! Runtime errors raised by user scripts.
+//!
+//! The engine wraps every script hook and, when one raises, prints a single
+//! `[RELAYCRAFT][SCRIPT][ERROR] {"script_name", "message", "traceback"}` line
+//! before mitmproxy handles the exception. The log forwarder turns those lines
+//! into `script-error` events.
+
+use serde::{Deserialize, Serialize};
+
+pub const SCRIPT_ERROR_MARKER: &str = "[SCRIPT][ERROR]";
+
+/// Payload of the `script-error` event
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScriptErrorEvent {
+    /// Manifest name (path relative to the scripts directory, e.g.
+    /// `auth/login.py`) of the script whose hook raised
+    pub script_name: String,
+    pub message: String,
+    #[serde(default)]
+    pub traceback: String,
+}
+
+/// Parse a script error line. Plain `[SCRIPT][ERROR]` log lines written by
+/// scripts themselves carry no JSON payload and yield `None`.
+pub fn parse_script_error(line: &str) -> Option<ScriptErrorEvent> {
+    let rest = &line[line.find(SCRIPT_ERROR_MARKER)? + SCRIPT_ERROR_MARKER.len()..];
+    let payload = rest.trim();
+    if !payload.starts_with('{') {
+        return None;
+    }
+    serde_json::from_str(payload).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_script_error() {
+        let line = r#"[RELAYCRAFT][SCRIPT][ERROR] {"script_name": "auth.py", "message": "KeyError: 'token'", "traceback": "Traceback (most recent call last):\n  File \"auth.py\", line 3\nKeyError: 'token'\n"}"#;
+        let event = parse_script_error(line).unwrap();
+        assert_eq!(event.script_name, "auth.py");
+        assert_eq!(event.message, "KeyError: 'token'");
+        assert!(event.traceback.ends_with("KeyError: 'token'\n"));
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["script_name"], "auth.py");
+
+        // Messages logged by a script through the fallback logger
+        assert_eq!(
+            parse_script_error("[RELAYCRAFT][SCRIPT][ERROR] token missing"),
+            None
+        );
+        assert_eq!(
+            parse_script_error("[RELAYCRAFT][SCRIPT][ERROR] {not json"),
+            None
+        );
+        assert_eq!(parse_script_error("[SCRIPT] hello"), None);
+    }
+}
@@ -1,3 +1,4 @@
 pub mod commands;
+pub mod errors;
 pub mod model;
 pub mod storage;
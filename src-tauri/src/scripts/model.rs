@@ -17,3 +17,11 @@ pub struct ScriptEntry {
 pub struct Manifest {
     pub scripts: Vec<ScriptEntry>,
 }
+
+/// Outcome of importing scripts from an external directory
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ScriptImportResult {
+    pub imported: Vec<String>,
+    /// Names that already exist in script storage and were left untouched
+    pub skipped: Vec<String>,
+}
@@ -1,8 +1,12 @@
-use super::model::{Manifest, ScriptEntry, ScriptInfo};
+use super::model::{Manifest, ScriptEntry, ScriptImportResult, ScriptInfo};
 use crate::common::error::ScriptError;
 // use serde::{Deserialize, Serialize};
-use std::fs;
-use std::path::PathBuf;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
 
 /// Script storage with dependency injection support
 pub struct ScriptStorage {
@@ -60,17 +64,27 @@ impl ScriptStorage {
         let mut manifest = self.load_manifest()?;
         let mut scripts_on_disk = Vec::new();
 
-        if let Ok(entries) = fs::read_dir(&self.base_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("py") {
-                    let name = path
-                        .file_name()
-                        .and_then(|s| s.to_str())
-                        .unwrap_or("unknown")
-                        .to_string();
-                    scripts_on_disk.push(name);
-                }
+        // Subfolders are part of the name ("auth/login.py"); caches and hidden
+        // folders are not scripts
+        let walker = WalkDir::new(&self.base_dir)
+            .min_depth(1)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|e| {
+                let name = e.file_name().to_string_lossy();
+                !(e.file_type().is_dir() && (name.starts_with('.') || name == "__pycache__"))
+            });
+        for entry in walker.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if !path.is_file() || path.extension().and_then(|s| s.to_str()) != Some("py") {
+                continue;
+            }
+            if let Ok(relative) = path.strip_prefix(&self.base_dir) {
+                let parts: Vec<String> = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy().to_string())
+                    .collect();
+                scripts_on_disk.push(parts.join("/"));
             }
         }
 
@@ -119,30 +133,39 @@ impl ScriptStorage {
         Ok(result)
     }
 
-    /// Validate that a script name contains no path traversal characters.
-    fn validate_name(name: &str) -> Result<(), ScriptError> {
-        if name.contains("..") || name.contains('/') || name.contains('\\') {
+    /// Normalize a script name to a `/`-separated path inside the scripts
+    /// directory. Subfolders are allowed; `..`, `.`, empty segments, absolute
+    /// paths and drive prefixes are rejected.
+    pub(crate) fn clean_name(name: &str) -> Result<String, ScriptError> {
+        let normalized = name.replace('\\', "/");
+        let valid = !normalized.starts_with('/')
+            && normalized
+                .split('/')
+                .all(|part| !part.is_empty() && part != "." && part != ".." && !part.contains(':'));
+        if !valid {
             log::warn!("[Security] Blocked path traversal in script name: {}", name);
             return Err(ScriptError::NotFound(format!(
                 "Invalid script name: {}",
                 name
             )));
         }
-        Ok(())
+        Ok(normalized)
     }
 
     /// Get script content
     pub fn get_content(&self, name: &str) -> Result<String, ScriptError> {
-        Self::validate_name(name)?;
-        let path = self.base_dir.join(name);
+        let path = self.base_dir.join(Self::clean_name(name)?);
         fs::read_to_string(path).map_err(|e| ScriptError::Io(e))
     }
 
     /// Save script content
     pub fn save_script(&self, name: &str, content: &str) -> Result<(), ScriptError> {
-        let safe_name = name.replace("..", "").replace("/", "").replace("\\", "");
+        let safe_name = Self::clean_name(name)?;
         let path = self.base_dir.join(&safe_name);
 
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
         fs::write(&path, content)?;
 
         let mut manifest = self.load_manifest()?;
@@ -158,8 +181,8 @@ impl ScriptStorage {
 
     /// Delete script
     pub fn delete_script(&self, name: &str) -> Result<(), ScriptError> {
-        Self::validate_name(name)?;
-        let path = self.base_dir.join(name);
+        let name = Self::clean_name(name)?;
+        let path = self.base_dir.join(&name);
         if path.exists() {
             fs::remove_file(path)?;
         }
@@ -175,27 +198,88 @@ impl ScriptStorage {
 
     /// Set script enabled
     pub fn set_enabled(&self, name: &str, enabled: bool) -> Result<(), ScriptError> {
+        let name = Self::clean_name(name)?;
         let mut manifest = self.load_manifest()?;
         if let Some(entry) = manifest.scripts.iter_mut().find(|s| s.name == name) {
             entry.enabled = enabled;
             self.save_manifest(&manifest)?;
             Ok(())
         } else {
-            Err(ScriptError::NotFound(name.to_string()))
+            Err(ScriptError::NotFound(name))
         }
     }
 
+    /// Set enabled state for every script whose name matches `pattern`, saving the
+    /// manifest once. Returns the names whose state actually changed.
+    pub fn set_enabled_bulk(
+        &self,
+        pattern: &str,
+        enabled: bool,
+    ) -> Result<Vec<String>, ScriptError> {
+        // Sync first so scripts dropped into the folder but not yet listed are included
+        self.list_scripts()?;
+
+        let mut manifest = self.load_manifest()?;
+        let mut changed = Vec::new();
+        for entry in manifest.scripts.iter_mut() {
+            if entry.enabled != enabled && matches_pattern(pattern, &entry.name) {
+                entry.enabled = enabled;
+                changed.push(entry.name.clone());
+            }
+        }
+
+        if !changed.is_empty() {
+            self.save_manifest(&manifest)?;
+        }
+        Ok(changed)
+    }
+
+    /// Copy every `.py` file in `dir` (non-recursive) into storage as a
+    /// disabled script. Names that already exist are skipped, not overwritten.
+    pub fn import_from_dir(&self, dir: &Path) -> Result<ScriptImportResult, ScriptError> {
+        if !dir.is_dir() {
+            return Err(ScriptError::NotFound(format!(
+                "Not a directory: {}",
+                dir.display()
+            )));
+        }
+
+        let existing: Vec<String> = self.list_scripts()?.into_iter().map(|s| s.name).collect();
+
+        let mut files: Vec<PathBuf> = fs::read_dir(dir)?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("py")
+            })
+            .collect();
+        files.sort();
+
+        let mut result = ScriptImportResult::default();
+        for path in files {
+            let Some(name) = path.file_name().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            if existing.iter().any(|n| n == name) {
+                result.skipped.push(name.to_string());
+                continue;
+            }
+            let content = fs::read_to_string(&path)?;
+            self.save_script(name, &content)?;
+            result.imported.push(name.to_string());
+        }
+        Ok(result)
+    }
+
     /// Rename script
     pub fn rename_script(&self, old_name: &str, new_name: &str) -> Result<(), ScriptError> {
-        let safe_new_name = new_name
-            .replace("..", "")
-            .replace("/", "")
-            .replace("\\", "");
-        let old_path = self.base_dir.join(old_name);
+        let old_name = Self::clean_name(old_name)?;
+        let safe_new_name = Self::clean_name(new_name)?;
+        let old_path = self.base_dir.join(&old_name);
         let new_path = self.base_dir.join(&safe_new_name);
 
         if !old_path.exists() {
-            return Err(ScriptError::NotFound(old_name.to_string()));
+            return Err(ScriptError::NotFound(old_name));
         }
         if new_path.exists() {
             return Err(ScriptError::Runtime(
@@ -203,6 +287,9 @@ impl ScriptStorage {
             ));
         }
 
+        if let Some(parent) = new_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
         fs::rename(&old_path, &new_path)?;
 
         let mut manifest = self.load_manifest()?;
@@ -213,8 +300,32 @@ impl ScriptStorage {
         Ok(())
     }
 
+    /// Copy a script to `<name> (copy).py` (or `(copy 2)`, ... when taken) in
+    /// the same folder. The copy starts disabled. Returns the new name.
+    pub fn duplicate_script(&self, name: &str) -> Result<String, ScriptError> {
+        let name = Self::clean_name(name)?;
+        let path = self.base_dir.join(&name);
+        if !path.is_file() {
+            return Err(ScriptError::NotFound(name));
+        }
+        let content = fs::read_to_string(&path)?;
+
+        let stem = name.strip_suffix(".py").unwrap_or(&name);
+        let new_name = (1..)
+            .map(|n| match n {
+                1 => format!("{} (copy).py", stem),
+                n => format!("{} (copy {}).py", stem, n),
+            })
+            .find(|candidate| !self.base_dir.join(candidate).exists())
+            .unwrap_or_default();
+
+        self.save_script(&new_name, &content)?;
+        Ok(new_name)
+    }
+
     /// Move script in manifest
     pub fn move_script(&self, name: &str, direction: &str) -> Result<Vec<ScriptInfo>, ScriptError> {
+        let name = Self::clean_name(name)?;
         let mut manifest = self.load_manifest()?;
         if let Some(pos) = manifest.scripts.iter().position(|s| s.name == name) {
             if direction == "up" && pos > 0 {
@@ -228,6 +339,122 @@ impl ScriptStorage {
         self.list_scripts()
     }
 
+    /// Export every script and the manifest (order and enabled flags) to a ZIP file
+    pub fn export_zip(&self, save_path: &Path) -> Result<(), ScriptError> {
+        // Sync first so scripts not yet listed are included
+        self.list_scripts()?;
+        let manifest = self.load_manifest()?;
+
+        let file = File::create(save_path)?;
+        let mut zip = ZipWriter::new(file);
+        let options = SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .unix_permissions(0o644);
+        let zip_error = |e: zip::result::ZipError| {
+            ScriptError::Serialization(format!("Failed to write ZIP entry: {}", e))
+        };
+
+        for entry in &manifest.scripts {
+            let content = fs::read(self.base_dir.join(&entry.name))?;
+            zip.start_file(entry.name.as_str(), options)
+                .map_err(zip_error)?;
+            zip.write_all(&content)?;
+        }
+
+        let manifest_json = serde_json::to_string_pretty(&manifest)
+            .map_err(|e| ScriptError::Serialization(e.to_string()))?;
+        zip.start_file("manifest.json", options)
+            .map_err(zip_error)?;
+        zip.write_all(manifest_json.as_bytes())?;
+
+        zip.finish()
+            .map_err(|e| ScriptError::Serialization(format!("Failed to finalize ZIP: {}", e)))?;
+        Ok(())
+    }
+
+    /// Restore scripts from a ZIP made by [`Self::export_zip`]. Enabled flags
+    /// come from the bundled manifest; existing names are skipped, not
+    /// overwritten, and anything that is not a `.py` file is ignored.
+    pub fn import_zip(&self, zip_path: &Path) -> Result<ScriptImportResult, ScriptError> {
+        let file = File::open(zip_path)?;
+        let mut archive = ZipArchive::new(file).map_err(|e| {
+            ScriptError::Serialization(format!("Failed to read ZIP archive: {}", e))
+        })?;
+
+        let existing: Vec<String> = self.list_scripts()?.into_iter().map(|s| s.name).collect();
+        let mut bundled = Manifest::default();
+        let mut files: Vec<(String, Vec<u8>)> = Vec::new();
+
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i).map_err(|e| {
+                ScriptError::Serialization(format!("Failed to access ZIP entry: {}", e))
+            })?;
+            if file.is_dir() {
+                continue;
+            }
+
+            let name = file.name().to_string();
+            if name.contains("__MACOSX")
+                || Path::new(&name)
+                    .file_name()
+                    .map(|s| s.to_string_lossy().starts_with('.'))
+                    .unwrap_or(false)
+            {
+                continue;
+            }
+
+            let mut content = Vec::new();
+            file.read_to_end(&mut content)?;
+            if name == "manifest.json" {
+                bundled = serde_json::from_slice(&content).unwrap_or_else(|e| {
+                    log::warn!("Ignoring unreadable manifest in script bundle: {}", e);
+                    Manifest::default()
+                });
+            } else if name.ends_with(".py") {
+                match Self::clean_name(&name) {
+                    Ok(name) => files.push((name, content)),
+                    Err(_) => continue,
+                }
+            }
+        }
+
+        // Follow the bundled order; scripts missing from its manifest go last
+        let position = |name: &str| {
+            bundled
+                .scripts
+                .iter()
+                .position(|s| s.name == name)
+                .unwrap_or(usize::MAX)
+        };
+        files.sort_by_key(|(name, _)| position(name));
+
+        let mut manifest = self.load_manifest()?;
+        let mut result = ScriptImportResult::default();
+        for (name, content) in files {
+            if existing.contains(&name) {
+                result.skipped.push(name);
+                continue;
+            }
+            let path = self.base_dir.join(&name);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, content)?;
+
+            let enabled = bundled.scripts.iter().any(|s| s.name == name && s.enabled);
+            manifest.scripts.push(ScriptEntry {
+                name: name.clone(),
+                enabled,
+            });
+            result.imported.push(name);
+        }
+
+        if !result.imported.is_empty() {
+            self.save_manifest(&manifest)?;
+        }
+        Ok(result)
+    }
+
     /// Get enabled script paths for proxy
     pub fn get_enabled_script_paths(&self) -> Result<Vec<PathBuf>, ScriptError> {
         let manifest = self.load_manifest()?;
@@ -241,11 +468,107 @@ impl ScriptStorage {
     }
 }
 
+/// Compile `content` with Python's `py_compile` and report the first syntax
+/// error as `line N: message`. Fails with `ValidationUnavailable` when no
+/// interpreter is available.
+pub fn check_syntax(content: &str) -> Result<(), ScriptError> {
+    let Some(python) = crate::proxy::paths::get_python_path() else {
+        return Err(ScriptError::ValidationUnavailable(
+            "no Python interpreter found".to_string(),
+        ));
+    };
+
+    // py_compile writes a .pyc beside its input, so work in a throwaway dir
+    let work_dir = std::env::temp_dir().join(format!("relaycraft-lint-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&work_dir)?;
+    let source = work_dir.join("script.py");
+    let result = fs::write(&source, content).and_then(|_| {
+        std::process::Command::new(&python)
+            .arg("-c")
+            .arg(PY_COMPILE_CHECK)
+            .arg(&source)
+            .env("PYTHONUTF8", "1")
+            .output()
+    });
+    let _ = fs::remove_dir_all(&work_dir);
+
+    let output = result?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    match (lines.next(), lines.next()) {
+        (Some(line), Some(message)) => Err(ScriptError::Compilation(format!(
+            "line {}: {}",
+            line, message
+        ))),
+        _ => Err(ScriptError::Compilation(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        )),
+    }
+}
+
+/// Prints the error line and message on separate lines and exits 1 when the
+/// file given as the first argument does not compile
+const PY_COMPILE_CHECK: &str = "\
+import py_compile, sys
+try:
+    py_compile.compile(sys.argv[1], doraise=True)
+except py_compile.PyCompileError as e:
+    err = e.exc_value
+    print(getattr(err, 'lineno', None) or 0)
+    print(getattr(err, 'msg', None) or e.msg)
+    sys.exit(1)
+";
+
+/// Case-insensitive match: glob semantics (`*`, `?`) when the pattern contains a
+/// wildcard, plain substring otherwise.
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.trim().to_lowercase();
+    let name = name.to_lowercase();
+
+    if !pattern.contains('*') && !pattern.contains('?') {
+        return name.contains(&pattern);
+    }
+
+    fn glob(p: &[char], n: &[char]) -> bool {
+        match (p.first(), n.first()) {
+            (None, None) => true,
+            (Some('*'), _) => glob(&p[1..], n) || (!n.is_empty() && glob(p, &n[1..])),
+            (Some('?'), Some(_)) => glob(&p[1..], &n[1..]),
+            (Some(a), Some(b)) if a == b => glob(&p[1..], &n[1..]),
+            _ => false,
+        }
+    }
+
+    let p: Vec<char> = pattern.chars().collect();
+    let n: Vec<char> = name.chars().collect();
+    glob(&p, &n)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_check_syntax() {
+        if crate::proxy::paths::get_python_path().is_none() {
+            assert!(matches!(
+                check_syntax("pass\n"),
+                Err(ScriptError::ValidationUnavailable(_))
+            ));
+            return;
+        }
+
+        assert!(check_syntax("def request(flow):\n    pass\n").is_ok());
+        match check_syntax("def request(flow):\n    pass\n\nif True\n    pass\n") {
+            Err(ScriptError::Compilation(msg)) => assert!(msg.starts_with("line 4:"), "{}", msg),
+            other => panic!("Expected compilation error, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_script_registration() {
         let temp = TempDir::new().unwrap();
@@ -288,4 +611,168 @@ mod tests {
         assert_eq!(scripts.len(), 0);
         assert!(!temp.path().join("test.py").exists());
     }
+
+    #[test]
+    fn test_nested_scripts() {
+        let temp = TempDir::new().unwrap();
+        let storage = ScriptStorage::new(temp.path().to_path_buf()).unwrap();
+
+        // Dropped into a subfolder by hand, then discovered on listing
+        fs::create_dir_all(temp.path().join("auth/__pycache__")).unwrap();
+        fs::write(temp.path().join("auth/login.py"), "# login").unwrap();
+        fs::write(temp.path().join("auth/__pycache__/cached.py"), "").unwrap();
+        storage.save_script("mocks\\users.py", "# users").unwrap();
+
+        let names: Vec<String> = storage
+            .list_scripts()
+            .unwrap()
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(names, vec!["mocks/users.py", "auth/login.py"]);
+
+        // Windows-style separators name the same manifest entry
+        storage.set_enabled("auth\\login.py", true).unwrap();
+        let order: Vec<String> = storage
+            .move_script("auth\\login.py", "up")
+            .unwrap()
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(order, vec!["auth/login.py", "mocks/users.py"]);
+        let paths = storage.get_enabled_script_paths().unwrap();
+        assert_eq!(paths, vec![temp.path().join("auth/login.py")]);
+        assert!(paths[0].is_file());
+
+        storage
+            .rename_script("mocks/users.py", "mocks/v2/users.py")
+            .unwrap();
+        assert_eq!(storage.get_content("mocks/v2/users.py").unwrap(), "# users");
+
+        for bad in [
+            "../evil.py",
+            "auth/../../evil.py",
+            "/etc/evil.py",
+            "C:/evil.py",
+        ] {
+            assert!(storage.save_script(bad, "").is_err(), "{}", bad);
+            assert!(storage.set_enabled(bad, true).is_err(), "{}", bad);
+            assert!(
+                storage.rename_script("auth/login.py", bad).is_err(),
+                "{}",
+                bad
+            );
+        }
+        assert!(storage.get_content("../manifest.json").is_err());
+        assert!(!temp.path().parent().unwrap().join("evil.py").exists());
+    }
+
+    #[test]
+    fn test_zip_round_trip_keeps_enabled_flags() {
+        let source_dir = TempDir::new().unwrap();
+        let source = ScriptStorage::new(source_dir.path().to_path_buf()).unwrap();
+        source.save_script("mocks/users.py", "# users").unwrap();
+        source.save_script("auth.py", "# auth").unwrap();
+        source.save_script("debug.py", "# debug").unwrap();
+        source.set_enabled("auth.py", true).unwrap();
+        source.set_enabled("mocks/users.py", true).unwrap();
+
+        let zip_path = source_dir.path().join("scripts.zip");
+        source.export_zip(&zip_path).unwrap();
+
+        let target_dir = TempDir::new().unwrap();
+        let target = ScriptStorage::new(target_dir.path().to_path_buf()).unwrap();
+        target.save_script("debug.py", "# local").unwrap();
+
+        let result = target.import_zip(&zip_path).unwrap();
+        assert_eq!(result.imported, vec!["mocks/users.py", "auth.py"]);
+        assert_eq!(result.skipped, vec!["debug.py"]);
+
+        let scripts = target.list_scripts().unwrap();
+        let state: Vec<(&str, bool)> = scripts
+            .iter()
+            .map(|s| (s.name.as_str(), s.enabled))
+            .collect();
+        assert_eq!(
+            state,
+            vec![
+                ("debug.py", false),
+                ("mocks/users.py", true),
+                ("auth.py", true)
+            ]
+        );
+        assert_eq!(target.get_content("debug.py").unwrap(), "# local");
+        assert_eq!(target.get_content("mocks/users.py").unwrap(), "# users");
+    }
+
+    #[test]
+    fn test_duplicate_script() {
+        let temp = TempDir::new().unwrap();
+        let storage = ScriptStorage::new(temp.path().to_path_buf()).unwrap();
+        storage.save_script("auth/login.py", "# login").unwrap();
+        storage.set_enabled("auth/login.py", true).unwrap();
+
+        let copy = storage.duplicate_script("auth/login.py").unwrap();
+        assert_eq!(copy, "auth/login (copy).py");
+        assert_eq!(
+            fs::read_to_string(temp.path().join("auth/login (copy).py")).unwrap(),
+            "# login"
+        );
+        let second = storage.duplicate_script("auth/login.py").unwrap();
+        assert_eq!(second, "auth/login (copy 2).py");
+
+        let manifest = storage.load_manifest().unwrap();
+        let entry = |name: &str| manifest.scripts.iter().find(|s| s.name == name).unwrap();
+        assert!(entry("auth/login.py").enabled);
+        assert!(!entry("auth/login (copy).py").enabled);
+        assert!(!entry("auth/login (copy 2).py").enabled);
+
+        assert!(storage.duplicate_script("missing.py").is_err());
+        assert!(storage.duplicate_script("../login.py").is_err());
+    }
+
+    #[test]
+    fn test_set_enabled_bulk() {
+        let temp = TempDir::new().unwrap();
+        let storage = ScriptStorage::new(temp.path().to_path_buf()).unwrap();
+
+        storage.save_script("debug_auth.py", "").unwrap();
+        storage.save_script("debug_cache.py", "").unwrap();
+        storage.save_script("mock_users.py", "").unwrap();
+
+        let changed = storage.set_enabled_bulk("debug_*", true).unwrap();
+        assert_eq!(changed, vec!["debug_auth.py", "debug_cache.py"]);
+
+        // Already enabled scripts are not reported again
+        let changed = storage.set_enabled_bulk("DEBUG", true).unwrap();
+        assert!(changed.is_empty());
+
+        let changed = storage.set_enabled_bulk("users", true).unwrap();
+        assert_eq!(changed, vec!["mock_users.py"]);
+        assert_eq!(storage.get_enabled_script_paths().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_import_from_dir_skips_existing_names() {
+        let temp = TempDir::new().unwrap();
+        let storage = ScriptStorage::new(temp.path().join("scripts")).unwrap();
+        storage.save_script("auth.py", "# managed").unwrap();
+
+        let source = temp.path().join("mitmproxy");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("auth.py"), "# external").unwrap();
+        fs::write(source.join("cache.py"), "print('cache')").unwrap();
+        fs::write(source.join("notes.txt"), "ignored").unwrap();
+
+        let result = storage.import_from_dir(&source).unwrap();
+        assert_eq!(result.imported, vec!["cache.py"]);
+        assert_eq!(result.skipped, vec!["auth.py"]);
+
+        assert_eq!(storage.get_content("auth.py").unwrap(), "# managed");
+        let scripts = storage.list_scripts().unwrap();
+        assert!(scripts.iter().all(|s| !s.enabled));
+        assert!(storage
+            .import_from_dir(&temp.path().join("missing"))
+            .is_err());
+    }
 }
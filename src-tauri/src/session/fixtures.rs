@@ -0,0 +1,62 @@
+//! Flows and headers shared by the session and traffic tests.
+
+use crate::session::model::*;
+
+/// A `name: value` header without a comment.
+pub fn header(name: &str, value: &str) -> HarHeader {
+    HarHeader {
+        name: name.to_string(),
+        value: value.to_string(),
+        comment: None,
+    }
+}
+
+/// A plain `GET https://example.com/api` answered with `200 hello`.
+pub fn sample_flow() -> Flow {
+    Flow {
+        id: "test-id".to_string(),
+        started_date_time: "2024-01-01T00:00:00Z".to_string(),
+        time: 150.0,
+        request: FlowRequest {
+            method: "GET".to_string(),
+            url: "https://example.com/api".to_string(),
+            http_version: "HTTP/1.1".to_string(),
+            headers: vec![HarHeader {
+                name: "Host".to_string(),
+                value: "example.com".to_string(),
+                comment: None,
+            }],
+            cookies: vec![],
+            query_string: vec![],
+            post_data: None,
+            body_size: 0,
+            headers_size: -1,
+            parsed_url: None,
+        },
+        response: FlowResponse {
+            status: 200,
+            status_text: "OK".to_string(),
+            http_version: "HTTP/1.1".to_string(),
+            headers: vec![],
+            cookies: vec![],
+            content: HarContent {
+                size: 5,
+                mime_type: "text/plain".to_string(),
+                text: Some("hello".to_string()),
+                encoding: None,
+                compression: None,
+                comment: None,
+            },
+            headers_size: -1,
+            body_size: 5,
+            redirect_url: "".to_string(),
+        },
+        timings: HarTimings {
+            wait: Some(100.0),
+            receive: Some(50.0),
+            ..Default::default()
+        },
+        cache: serde_json::Value::Null,
+        rc: RcExtension::default(),
+    }
+}
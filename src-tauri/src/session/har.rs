@@ -6,9 +6,10 @@
 use crate::logging;
 use crate::session::har_model::{
     HarContent, HarCreator, HarEntry, HarHeader, HarLog, HarLogContent, HarRequest, HarResponse,
-    HarTimings,
+    HarTimings, HarWebSocketMessage,
 };
-use crate::session::model::{Flow, FlowRequest, FlowResponse};
+use crate::session::model::{Flow, FlowRequest, FlowResponse, RcWebSocketFrame};
+use serde::Deserialize;
 use std::fs::File;
 
 // ==================== Flow to HAR ====================
@@ -24,9 +25,40 @@ pub fn flow_to_har_entry(flow: &Flow) -> HarEntry {
         response: flow_response_to_har(&flow.response),
         cache: flow.cache.clone(),
         timings: har_timings_from_flow(&flow.timings),
+        resource_type: flow.rc.is_websocket.then(|| "websocket".to_string()),
+        websocket_messages: flow
+            .rc
+            .is_websocket
+            .then(|| har_websocket_messages(&flow.rc.websocket_frames)),
     }
 }
 
+/// WebSocket opcodes (RFC 6455) by RelayCraft frame type
+const WS_OPCODES: &[(&str, u8)] = &[
+    ("text", 1),
+    ("binary", 2),
+    ("close", 8),
+    ("ping", 9),
+    ("pong", 10),
+];
+
+fn har_websocket_messages(frames: &[RcWebSocketFrame]) -> Vec<HarWebSocketMessage> {
+    let mut frames: Vec<&RcWebSocketFrame> = frames.iter().collect();
+    frames.sort_by_key(|f| f.seq);
+    frames
+        .into_iter()
+        .map(|frame| HarWebSocketMessage {
+            direction: if frame.from_client { "send" } else { "receive" }.to_string(),
+            time: frame.timestamp / 1000.0,
+            opcode: WS_OPCODES
+                .iter()
+                .find(|(name, _)| *name == frame.frame_type)
+                .map_or(1, |(_, code)| *code),
+            data: frame.content.clone(),
+        })
+        .collect()
+}
+
 fn flow_request_to_har(req: &FlowRequest) -> HarRequest {
     HarRequest {
         method: req.method.clone(),
@@ -113,12 +145,65 @@ fn har_timings_from_flow(timings: &crate::session::model::HarTimings) -> HarTimi
 // HAR imports are now handled by the Python engine via /_relay/import_har_file
 // which uses ijson streaming to avoid memory issues with large files.
 
-// ==================== Tauri Commands ====================
+// ==================== Export Filter ====================
 
-#[tauri::command]
-pub async fn export_har(path: String, flows: Vec<Flow>) -> Result<(), String> {
-    let entries: Vec<HarEntry> = flows.iter().map(flow_to_har_entry).collect();
+/// Criteria for `export_har_filtered`; unset fields match every flow.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct HarExportFilter {
+    /// Case-insensitive substring of the request host
+    pub host_contains: Option<String>,
+    pub status_min: Option<i32>,
+    pub status_max: Option<i32>,
+    /// Request methods to keep; empty keeps all
+    pub methods: Vec<String>,
+    /// Case-insensitive substring of the response MIME type
+    pub mime_contains: Option<String>,
+}
+
+impl HarExportFilter {
+    pub fn matches(&self, flow: &Flow) -> bool {
+        if let Some(needle) = &self.host_contains {
+            let host = url::Url::parse(&flow.request.url)
+                .ok()
+                .and_then(|u| u.host_str().map(|h| h.to_ascii_lowercase()))
+                .unwrap_or_default();
+            if !host.contains(&needle.to_ascii_lowercase()) {
+                return false;
+            }
+        }
+        let status = flow.response.status;
+        if self.status_min.is_some_and(|min| status < min)
+            || self.status_max.is_some_and(|max| status > max)
+        {
+            return false;
+        }
+        if !self.methods.is_empty()
+            && !self
+                .methods
+                .iter()
+                .any(|m| m.eq_ignore_ascii_case(&flow.request.method))
+        {
+            return false;
+        }
+        if let Some(needle) = &self.mime_contains {
+            if !flow
+                .response
+                .content
+                .mime_type
+                .to_ascii_lowercase()
+                .contains(&needle.to_ascii_lowercase())
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+// ==================== Tauri Commands ====================
 
+fn write_har(path: &str, entries: Vec<HarEntry>) -> Result<(), String> {
     let har_log = HarLog {
         log: HarLogContent {
             version: "1.2".to_string(),
@@ -132,12 +217,42 @@ pub async fn export_har(path: String, flows: Vec<Flow>) -> Result<(), String> {
 
     let file = File::create(&path).map_err(|e| format!("Failed to create file: {}", e))?;
     let writer = std::io::BufWriter::new(file);
-    serde_json::to_writer(writer, &har_log)
-        .map_err(|e| format!("Failed to serialize HAR: {}", e))?;
+    serde_json::to_writer(writer, &har_log).map_err(|e| format!("Failed to serialize HAR: {}", e))
+}
+
+#[tauri::command]
+pub async fn export_har(path: String, flows: Vec<Flow>) -> Result<(), String> {
+    write_har(&path, flows.iter().map(flow_to_har_entry).collect())?;
     let _ = logging::write_domain_log("audit", &format!("Exported HAR to {}", path));
     Ok(())
 }
 
+/// Export only the flows matching `filter`. Returns the number of entries written.
+#[tauri::command]
+pub async fn export_har_filtered(
+    path: String,
+    flows: Vec<Flow>,
+    filter: HarExportFilter,
+) -> Result<usize, String> {
+    let entries: Vec<HarEntry> = flows
+        .iter()
+        .filter(|flow| filter.matches(flow))
+        .map(flow_to_har_entry)
+        .collect();
+    let count = entries.len();
+    write_har(&path, entries)?;
+    let _ = logging::write_domain_log(
+        "audit",
+        &format!(
+            "Exported HAR to {} ({} of {} flows)",
+            path,
+            count,
+            flows.len()
+        ),
+    );
+    Ok(count)
+}
+
 // Note: import_har has been removed - HAR imports are now handled by the Python engine
 // via /_relay/import_har_file which uses ijson streaming to avoid memory issues with large files.
 
@@ -146,57 +261,12 @@ pub async fn export_har(path: String, flows: Vec<Flow>) -> Result<(), String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::session::fixtures::sample_flow;
     use crate::session::model::{HarHeader, HarTimings, RcExtension};
 
     #[test]
     fn test_flow_to_har_conversion() {
-        let flow = Flow {
-            id: "test-id".to_string(),
-            started_date_time: "2024-01-01T00:00:00Z".to_string(),
-            time: 150.0,
-            request: FlowRequest {
-                method: "GET".to_string(),
-                url: "https://example.com/api".to_string(),
-                http_version: "HTTP/1.1".to_string(),
-                headers: vec![HarHeader {
-                    name: "Host".to_string(),
-                    value: "example.com".to_string(),
-                    comment: None,
-                }],
-                cookies: vec![],
-                query_string: vec![],
-                post_data: None,
-                body_size: 0,
-                headers_size: -1,
-                parsed_url: None,
-            },
-            response: FlowResponse {
-                status: 200,
-                status_text: "OK".to_string(),
-                http_version: "HTTP/1.1".to_string(),
-                headers: vec![],
-                cookies: vec![],
-                content: crate::session::model::HarContent {
-                    size: 5,
-                    mime_type: "text/plain".to_string(),
-                    text: Some("hello".to_string()),
-                    encoding: None,
-                    compression: None,
-                    comment: None,
-                },
-                headers_size: -1,
-                body_size: 5,
-                redirect_url: "".to_string(),
-            },
-            timings: HarTimings {
-                wait: Some(100.0),
-                receive: Some(50.0),
-                ..Default::default()
-            },
-            cache: serde_json::Value::Null,
-            rc: RcExtension::default(),
-        };
-
+        let flow = sample_flow();
         let entry = flow_to_har_entry(&flow);
         assert_eq!(entry.request.method, "GET");
         assert_eq!(entry.response.status, 200);
@@ -253,4 +323,126 @@ mod tests {
         assert_eq!(set_cookies[0].value, "a=1");
         assert_eq!(set_cookies[1].value, "b=2");
     }
+
+    fn filter_flow(method: &str, url: &str, status: i32, mime: &str) -> Flow {
+        let mut flow = Flow::default();
+        flow.request.method = method.to_string();
+        flow.request.url = url.to_string();
+        flow.response.status = status;
+        flow.response.content.mime_type = mime.to_string();
+        flow
+    }
+
+    fn filter_flows() -> Vec<Flow> {
+        vec![
+            filter_flow(
+                "GET",
+                "https://api.example.com/users",
+                200,
+                "application/json",
+            ),
+            filter_flow(
+                "POST",
+                "https://api.example.com/users",
+                500,
+                "application/json",
+            ),
+            filter_flow("GET", "https://cdn.example.com/logo.png", 404, "image/png"),
+            filter_flow("delete", "http://API.example.com/users/1", 403, "text/html"),
+        ]
+    }
+
+    fn matching(filter: &HarExportFilter) -> usize {
+        filter_flows().iter().filter(|f| filter.matches(f)).count()
+    }
+
+    #[test]
+    fn test_export_filter_host() {
+        let filter = HarExportFilter {
+            host_contains: Some("Api.".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(matching(&filter), 3);
+        assert_eq!(matching(&HarExportFilter::default()), 4);
+    }
+
+    #[test]
+    fn test_export_filter_status_range() {
+        let failing = HarExportFilter {
+            status_min: Some(400),
+            ..Default::default()
+        };
+        assert_eq!(matching(&failing), 3);
+        let client_errors = HarExportFilter {
+            status_min: Some(400),
+            status_max: Some(499),
+            ..Default::default()
+        };
+        assert_eq!(matching(&client_errors), 2);
+    }
+
+    #[test]
+    fn test_export_filter_methods() {
+        let filter = HarExportFilter {
+            methods: vec!["POST".to_string(), "DELETE".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(matching(&filter), 2);
+    }
+
+    #[test]
+    fn test_export_filter_mime() {
+        let filter = HarExportFilter {
+            mime_contains: Some("JSON".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(matching(&filter), 2);
+    }
+
+    #[test]
+    fn test_websocket_frames_exported_as_har_messages() {
+        let frame =
+            |seq: i32, frame_type: &str, from_client: bool, content: &str, encoding: &str| {
+                RcWebSocketFrame {
+                    id: format!("ws-{}", seq),
+                    flow_id: "ws".to_string(),
+                    seq,
+                    frame_type: frame_type.to_string(),
+                    from_client,
+                    content: content.to_string(),
+                    encoding: Some(encoding.to_string()),
+                    timestamp: 1_700_000_000_000.0 + seq as f64,
+                    length: 0,
+                }
+            };
+        let mut flow = Flow::default();
+        flow.request.url = "wss://example.com/socket".to_string();
+        flow.rc.is_websocket = true;
+        flow.rc.websocket_frame_count = 2;
+        flow.rc.websocket_frames = vec![
+            frame(1, "binary", false, "AAEC", "base64"),
+            frame(0, "text", true, "{\"op\":\"subscribe\"}", "text"),
+        ];
+
+        let json = serde_json::to_string(&flow_to_har_entry(&flow)).unwrap();
+        assert!(json.contains("\"_webSocketMessages\""));
+        let entry: HarEntry = serde_json::from_str(&json).unwrap();
+        assert_eq!(entry.resource_type.as_deref(), Some("websocket"));
+
+        let messages = entry.websocket_messages.unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].direction, "send");
+        assert_eq!(messages[0].opcode, 1);
+        assert_eq!(messages[0].data, "{\"op\":\"subscribe\"}");
+        assert_eq!(messages[0].time, 1_700_000_000.0);
+        assert_eq!(messages[1].direction, "receive");
+        assert_eq!(messages[1].opcode, 2);
+        assert_eq!(messages[1].data, "AAEC");
+    }
+
+    #[test]
+    fn test_plain_flow_has_no_websocket_messages() {
+        let json = serde_json::to_string(&flow_to_har_entry(&Flow::default())).unwrap();
+        assert!(!json.contains("_webSocketMessages"));
+    }
 }
@@ -28,6 +28,31 @@ pub struct HarEntry {
     pub response: HarResponse,
     pub cache: serde_json::Value,
     pub timings: HarTimings,
+    #[serde(
+        rename = "_resourceType",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub resource_type: Option<String>,
+    /// WebSocket messages, in the format Chrome DevTools uses
+    #[serde(
+        rename = "_webSocketMessages",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub websocket_messages: Option<Vec<HarWebSocketMessage>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct HarWebSocketMessage {
+    /// "send" (client to server) or "receive"
+    #[serde(rename = "type")]
+    pub direction: String,
+    /// Seconds since the epoch
+    pub time: f64,
+    pub opcode: u8,
+    /// Text payload, or base64 for binary frames
+    pub data: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -0,0 +1,173 @@
+//! Export of captured flows as a `.http` file, runnable with VS Code's
+//! REST Client or JetBrains' HTTP client.
+
+use std::fs;
+
+use crate::logging;
+use crate::session::model::Flow;
+
+/// Headers the HTTP client computes itself
+const SKIPPED_HEADERS: &[&str] = &["content-length", "transfer-encoding", "connection"];
+
+/// Body of a request as text: the raw body, or form parameters re-encoded.
+pub(crate) fn request_body_text(flow: &Flow) -> Option<String> {
+    let post_data = flow.request.post_data.as_ref()?;
+    if let Some(text) = post_data.text.as_ref().filter(|t| !t.is_empty()) {
+        return Some(text.clone());
+    }
+    let params = post_data.params.as_ref().filter(|p| !p.is_empty())?;
+    let mut form = url::form_urlencoded::Serializer::new(String::new());
+    for param in params {
+        form.append_pair(&param.name, param.value.as_deref().unwrap_or(""));
+    }
+    Some(form.finish())
+}
+
+/// Request headers worth replaying: no HTTP/2 pseudo-headers, no
+/// framing headers, and a single `Host`.
+pub(crate) fn replayable_headers(flow: &Flow) -> Vec<(&str, &str)> {
+    let mut seen_host = false;
+    flow.request
+        .headers
+        .iter()
+        .filter(|h| !h.name.starts_with(':'))
+        .filter(|h| {
+            let lower = h.name.to_ascii_lowercase();
+            if lower == "host" {
+                return !std::mem::replace(&mut seen_host, true);
+            }
+            !SKIPPED_HEADERS.contains(&lower.as_str())
+        })
+        .map(|h| (h.name.as_str(), h.value.as_str()))
+        .collect()
+}
+
+fn render_request(flow: &Flow) -> String {
+    let mut out = format!("### {} {}\n", flow.request.method, flow.request.url);
+    out.push_str(&format!("{} {}\n", flow.request.method, flow.request.url));
+    for (name, value) in replayable_headers(flow) {
+        out.push_str(&format!("{}: {}\n", name, value));
+    }
+    if let Some(body) = request_body_text(flow) {
+        out.push('\n');
+        out.push_str(&body);
+        if !body.ends_with('\n') {
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Render flows as `###`-separated requests.
+pub fn flows_to_http_file(flows: &[Flow]) -> String {
+    flows
+        .iter()
+        .map(render_request)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[tauri::command]
+pub async fn export_http_file(path: String, flows: Vec<Flow>) -> Result<usize, String> {
+    fs::write(&path, flows_to_http_file(&flows))
+        .map_err(|e| format!("Failed to write .http file: {}", e))?;
+    let _ = logging::write_domain_log(
+        "audit",
+        &format!("Exported {} requests as .http to {}", flows.len(), path),
+    );
+    Ok(flows.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::fixtures::header;
+    use crate::session::model::{HarPostData, HarPostParam};
+
+    fn sample_flow() -> Flow {
+        let mut flow = Flow::default();
+        flow.request.method = "POST".to_string();
+        flow.request.url = "https://api.example.com/v1/items?draft=1".to_string();
+        flow.request.headers = vec![
+            header(":authority", "api.example.com"),
+            header("Host", "api.example.com"),
+            header("host", "api.example.com"),
+            header("Content-Type", "application/json"),
+            header("Content-Length", "13"),
+        ];
+        flow.request.post_data = Some(HarPostData {
+            mime_type: "application/json".to_string(),
+            text: Some("{\"name\":\"a\"}".to_string()),
+            ..Default::default()
+        });
+        flow
+    }
+
+    /// Minimal reader for the blocks we write: (method, url, headers, body)
+    fn parse_blocks(text: &str) -> Vec<(String, String, Vec<String>, String)> {
+        text.split("### ")
+            .filter(|block| !block.trim().is_empty())
+            .map(|block| {
+                let mut lines = block.lines().skip(1);
+                let request_line = lines.next().unwrap();
+                let (method, url) = request_line.split_once(' ').unwrap();
+                let mut headers = Vec::new();
+                for line in lines.by_ref() {
+                    if line.is_empty() {
+                        break;
+                    }
+                    headers.push(line.to_string());
+                }
+                let body = lines.collect::<Vec<_>>().join("\n");
+                (
+                    method.to_string(),
+                    url.to_string(),
+                    headers,
+                    body.trim().to_string(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_http_file_round_trip() {
+        let mut get = Flow::default();
+        get.request.method = "GET".to_string();
+        get.request.url = "https://example.com/".to_string();
+
+        let text = flows_to_http_file(&[sample_flow(), get]);
+        let blocks = parse_blocks(&text);
+        assert_eq!(blocks.len(), 2);
+
+        let (method, url, headers, body) = &blocks[0];
+        assert_eq!(method, "POST");
+        assert_eq!(url, "https://api.example.com/v1/items?draft=1");
+        assert_eq!(
+            headers,
+            &vec![
+                "Host: api.example.com".to_string(),
+                "Content-Type: application/json".to_string()
+            ]
+        );
+        assert_eq!(body, "{\"name\":\"a\"}");
+
+        assert_eq!(blocks[1].0, "GET");
+        assert_eq!(blocks[1].1, "https://example.com/");
+        assert!(blocks[1].3.is_empty());
+    }
+
+    #[test]
+    fn test_form_params_become_body() {
+        let mut flow = Flow::default();
+        flow.request.post_data = Some(HarPostData {
+            mime_type: "application/x-www-form-urlencoded".to_string(),
+            params: Some(vec![HarPostParam {
+                name: "q".to_string(),
+                value: Some("a b".to_string()),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        });
+        assert_eq!(request_body_text(&flow).as_deref(), Some("q=a+b"));
+    }
+}
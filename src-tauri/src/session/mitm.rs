@@ -0,0 +1,430 @@
+//! Import of mitmproxy's native `.flow` dumps (`mitmdump -w`).
+//!
+//! A dump is a sequence of tnetstring-encoded flow states. HTTP flows are
+//! mapped onto `Flow`; WebSocket flows are flagged and counted, but their
+//! messages are not converted into frames yet. TCP/UDP/DNS flows are skipped.
+
+use base64::Engine;
+
+use crate::logging;
+use crate::session::model::{
+    Flow, FlowRequest, FlowResponse, HarContent, HarHeader, HarPostData, HarQueryString,
+    HarTimings, RcError, RcExtension,
+};
+
+/// Nesting limit, so a corrupt file cannot overflow the stack
+const MAX_DEPTH: usize = 64;
+
+/// A decoded tnetstring value
+#[derive(Debug, Clone, PartialEq)]
+enum TValue {
+    Bytes(Vec<u8>),
+    Str(String),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Null,
+    List(Vec<TValue>),
+    Dict(Vec<(TValue, TValue)>),
+}
+
+impl TValue {
+    fn get(&self, key: &str) -> Option<&TValue> {
+        match self {
+            TValue::Dict(items) => items
+                .iter()
+                .find(|(k, _)| k.as_text().as_deref() == Some(key))
+                .map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Text of a str or bytes value; mitmproxy stores most HTTP fields as bytes.
+    fn as_text(&self) -> Option<String> {
+        match self {
+            TValue::Str(s) => Some(s.clone()),
+            TValue::Bytes(b) => Some(String::from_utf8_lossy(b).to_string()),
+            _ => None,
+        }
+    }
+
+    fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            TValue::Bytes(b) => Some(b),
+            TValue::Str(s) => Some(s.as_bytes()),
+            _ => None,
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            TValue::Float(f) => Some(*f),
+            TValue::Int(i) => Some(*i as f64),
+            _ => None,
+        }
+    }
+
+    fn as_i64(&self) -> Option<i64> {
+        match self {
+            TValue::Int(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    fn as_list(&self) -> &[TValue] {
+        match self {
+            TValue::List(items) => items,
+            _ => &[],
+        }
+    }
+}
+
+/// Parse one tnetstring starting at `pos`, returning it and the offset after it.
+fn parse_tnetstring(data: &[u8], pos: usize, depth: usize) -> Result<(TValue, usize), String> {
+    if depth > MAX_DEPTH {
+        return Err("Flow file is nested too deeply".to_string());
+    }
+    let colon = data[pos..]
+        .iter()
+        .take(12)
+        .position(|&b| b == b':')
+        .ok_or_else(|| format!("Malformed tnetstring length at byte {}", pos))?;
+    let len: usize = std::str::from_utf8(&data[pos..pos + colon])
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| format!("Malformed tnetstring length at byte {}", pos))?;
+    let start = pos + colon + 1;
+    let end = start
+        .checked_add(len)
+        .filter(|&end| end < data.len())
+        .ok_or_else(|| format!("Truncated tnetstring at byte {}", pos))?;
+    let payload = &data[start..end];
+    let text =
+        || std::str::from_utf8(payload).map_err(|_| format!("Invalid tnetstring at byte {}", pos));
+
+    let value = match data[end] {
+        b',' => TValue::Bytes(payload.to_vec()),
+        b';' => TValue::Str(text()?.to_string()),
+        b'#' => TValue::Int(
+            text()?
+                .parse()
+                .map_err(|_| format!("Invalid integer at byte {}", pos))?,
+        ),
+        b'^' => TValue::Float(
+            text()?
+                .parse()
+                .map_err(|_| format!("Invalid float at byte {}", pos))?,
+        ),
+        b'!' => TValue::Bool(payload == b"true"),
+        b'~' => TValue::Null,
+        b']' => {
+            let mut items = Vec::new();
+            let mut cursor = start;
+            while cursor < end {
+                let (item, next) = parse_tnetstring(&data[..end], cursor, depth + 1)?;
+                items.push(item);
+                cursor = next;
+            }
+            TValue::List(items)
+        }
+        b'}' => {
+            let mut items = Vec::new();
+            let mut cursor = start;
+            while cursor < end {
+                let (key, next) = parse_tnetstring(&data[..end], cursor, depth + 1)?;
+                if next >= end {
+                    return Err(format!("Dictionary key without value at byte {}", cursor));
+                }
+                let (value, next) = parse_tnetstring(&data[..end], next, depth + 1)?;
+                items.push((key, value));
+                cursor = next;
+            }
+            TValue::Dict(items)
+        }
+        other => {
+            return Err(format!(
+                "Unknown tnetstring type '{}' at byte {}",
+                other as char, end
+            ))
+        }
+    };
+    Ok((value, end + 1))
+}
+
+fn headers(state: &TValue) -> Vec<HarHeader> {
+    state
+        .get("headers")
+        .map(TValue::as_list)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|pair| match pair.as_list() {
+            [name, value] => Some(HarHeader {
+                name: name.as_text()?,
+                value: value.as_text()?,
+                comment: None,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+fn header_value(headers: &[HarHeader], name: &str) -> Option<String> {
+    headers
+        .iter()
+        .find(|h| h.name.eq_ignore_ascii_case(name))
+        .map(|h| h.value.clone())
+}
+
+fn text_field(state: &TValue, key: &str) -> String {
+    state.get(key).and_then(TValue::as_text).unwrap_or_default()
+}
+
+fn time_field(state: &TValue, key: &str) -> Option<f64> {
+    state.get(key).and_then(TValue::as_f64)
+}
+
+fn request_url(request: &TValue) -> String {
+    let scheme = text_field(request, "scheme");
+    let authority = text_field(request, "authority");
+    let host = if authority.is_empty() {
+        let host = text_field(request, "host");
+        let port = request.get("port").and_then(TValue::as_i64);
+        match (scheme.as_str(), port) {
+            ("http", Some(80)) | ("https", Some(443)) | (_, None) => host,
+            (_, Some(port)) => format!("{}:{}", host, port),
+        }
+    } else {
+        authority
+    };
+    format!("{}://{}{}", scheme, host, text_field(request, "path"))
+}
+
+fn convert_request(request: &TValue) -> FlowRequest {
+    let url = request_url(request);
+    let headers = headers(request);
+    let content = request
+        .get("content")
+        .and_then(TValue::as_bytes)
+        .unwrap_or_default();
+    let post_data = (!content.is_empty()).then(|| HarPostData {
+        mime_type: header_value(&headers, "content-type").unwrap_or_default(),
+        text: Some(String::from_utf8_lossy(content).to_string()),
+        ..Default::default()
+    });
+    let query_string = url::Url::parse(&url)
+        .map(|u| {
+            u.query_pairs()
+                .map(|(name, value)| HarQueryString {
+                    name: name.into_owned(),
+                    value: value.into_owned(),
+                    comment: None,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    FlowRequest {
+        method: text_field(request, "method"),
+        url,
+        http_version: text_field(request, "http_version"),
+        headers,
+        cookies: vec![],
+        query_string,
+        post_data,
+        body_size: content.len() as i64,
+        headers_size: -1,
+        parsed_url: None,
+    }
+}
+
+fn convert_response(response: &TValue) -> FlowResponse {
+    let headers = headers(response);
+    let content = response
+        .get("content")
+        .and_then(TValue::as_bytes)
+        .unwrap_or_default();
+    let (text, encoding) = match std::str::from_utf8(content) {
+        Ok(text) => (text.to_string(), None),
+        Err(_) => (
+            base64::engine::general_purpose::STANDARD.encode(content),
+            Some("base64".to_string()),
+        ),
+    };
+
+    FlowResponse {
+        status: response
+            .get("status_code")
+            .and_then(TValue::as_i64)
+            .unwrap_or(0) as i32,
+        status_text: text_field(response, "reason"),
+        http_version: text_field(response, "http_version"),
+        redirect_url: header_value(&headers, "location").unwrap_or_default(),
+        content: HarContent {
+            size: content.len() as i64,
+            mime_type: header_value(&headers, "content-type").unwrap_or_default(),
+            text: (!content.is_empty()).then_some(text),
+            encoding,
+            ..Default::default()
+        },
+        headers,
+        cookies: vec![],
+        headers_size: -1,
+        body_size: content.len() as i64,
+    }
+}
+
+fn peer_ip(conn: Option<&TValue>) -> Option<String> {
+    conn?.get("peername")?.as_list().first()?.as_text()
+}
+
+/// Convert one flow state; None for flows that are not HTTP.
+fn convert_flow(state: &TValue) -> Option<Flow> {
+    if state.get("type").and_then(TValue::as_text).as_deref() != Some("http") {
+        return None;
+    }
+    let request = state.get("request")?;
+    let response = state.get("response").filter(|r| **r != TValue::Null);
+
+    let req_start = time_field(request, "timestamp_start").unwrap_or(0.0);
+    let req_end = time_field(request, "timestamp_end").unwrap_or(req_start);
+    let res_start = response.and_then(|r| time_field(r, "timestamp_start"));
+    let res_end = response.and_then(|r| time_field(r, "timestamp_end"));
+    let ms = |seconds: f64| (seconds * 1000.0).max(0.0);
+
+    let websocket = state.get("websocket").filter(|w| **w != TValue::Null);
+    let error = state
+        .get("error")
+        .filter(|e| **e != TValue::Null)
+        .map(|e| RcError {
+            message: text_field(e, "msg"),
+            error_type: "connection".to_string(),
+            code: None,
+            stack: None,
+        });
+
+    Some(Flow {
+        id: state
+            .get("id")
+            .and_then(TValue::as_text)
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        started_date_time: chrono::DateTime::from_timestamp_millis((req_start * 1000.0) as i64)
+            .map(|t| t.to_rfc3339())
+            .unwrap_or_default(),
+        time: ms(res_end.unwrap_or(req_end) - req_start),
+        request: convert_request(request),
+        response: response.map(convert_response).unwrap_or_default(),
+        timings: HarTimings {
+            send: Some(ms(req_end - req_start)),
+            wait: res_start.map(|start| ms(start - req_end)),
+            receive: res_start.zip(res_end).map(|(start, end)| ms(end - start)),
+            ..Default::default()
+        },
+        cache: serde_json::json!({}),
+        rc: RcExtension {
+            client_ip: peer_ip(state.get("client_conn")),
+            server_ip: peer_ip(state.get("server_conn")),
+            error,
+            is_websocket: websocket.is_some(),
+            websocket_frame_count: websocket
+                .and_then(|w| w.get("messages"))
+                .map_or(0, |m| m.as_list().len() as i32),
+            ..Default::default()
+        },
+    })
+}
+
+/// Parse a whole `.flow` dump into flows, skipping non-HTTP flows.
+pub fn parse_mitm_flows(data: &[u8]) -> Result<Vec<Flow>, String> {
+    let mut flows = Vec::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        if data[pos].is_ascii_whitespace() {
+            pos += 1;
+            continue;
+        }
+        let (state, next) = parse_tnetstring(data, pos, 0)?;
+        flows.extend(convert_flow(&state));
+        pos = next;
+    }
+    Ok(flows)
+}
+
+#[tauri::command]
+pub async fn import_mitm_flows(path: String) -> Result<Vec<Flow>, String> {
+    let data = std::fs::read(&path).map_err(|e| format!("Failed to read flow file: {}", e))?;
+    let flows = parse_mitm_flows(&data)?;
+    let _ = logging::write_domain_log(
+        "audit",
+        &format!(
+            "Imported {} flows from mitmproxy dump {}",
+            flows.len(),
+            path
+        ),
+    );
+    Ok(flows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &[u8] = include_bytes!("testdata/sample.flow");
+
+    #[test]
+    fn test_parse_tnetstring_types() {
+        let (value, next) =
+            parse_tnetstring(b"35:1:a;1:1#1:b;19:3:1.5^0:,4:true!0:~]}", 0, 0).unwrap();
+        assert_eq!(next, 39);
+        assert_eq!(value.get("a").unwrap().as_i64(), Some(1));
+        let list = value.get("b").unwrap().as_list();
+        assert_eq!(list[0].as_f64(), Some(1.5));
+        assert_eq!(list[1], TValue::Bytes(vec![]));
+        assert_eq!(list[2], TValue::Bool(true));
+        assert_eq!(list[3], TValue::Null);
+        assert!(parse_tnetstring(b"99:abc,", 0, 0).is_err());
+    }
+
+    #[test]
+    fn test_import_http_flow_fixture() {
+        let flows = parse_mitm_flows(SAMPLE).unwrap();
+        assert_eq!(flows.len(), 2);
+
+        let flow = &flows[0];
+        assert_eq!(flow.id, "5f0b6c2e-1a3d-4f7e-9b2a-0c8d1e2f3a4b");
+        assert_eq!(flow.request.method, "POST");
+        assert_eq!(flow.request.url, "https://api.example.com/v1/items?draft=1");
+        assert_eq!(flow.request.query_string[0].name, "draft");
+        assert_eq!(
+            flow.request.post_data.as_ref().unwrap().text.as_deref(),
+            Some("{\"name\":\"widget\"}")
+        );
+        assert_eq!(flow.response.status, 201);
+        assert_eq!(flow.response.status_text, "Created");
+        assert_eq!(flow.response.content.text.as_deref(), Some("{\"id\":7}"));
+        assert_eq!(flow.response.content.mime_type, "application/json");
+        // Repeated headers are kept
+        assert_eq!(
+            flow.response
+                .headers
+                .iter()
+                .filter(|h| h.name == "Set-Cookie")
+                .count(),
+            2
+        );
+        assert_eq!(flow.started_date_time, "2023-11-14T22:13:20+00:00");
+        assert!((flow.time - 125.0).abs() < 0.01);
+        assert_eq!(flow.rc.client_ip.as_deref(), Some("192.168.1.50"));
+        assert_eq!(flow.rc.server_ip.as_deref(), Some("93.184.216.34"));
+        assert!(!flow.rc.is_websocket);
+    }
+
+    #[test]
+    fn test_import_marks_websocket_flows() {
+        let flows = parse_mitm_flows(SAMPLE).unwrap();
+        let ws = &flows[1];
+        assert_eq!(ws.request.url, "https://ws.example.com/socket");
+        assert_eq!(ws.response.status, 101);
+        assert!(ws.rc.is_websocket);
+        assert_eq!(ws.rc.websocket_frame_count, 2);
+    }
+}
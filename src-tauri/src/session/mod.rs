@@ -3,9 +3,16 @@ use crate::session::model::Session;
 use std::fs::File;
 use std::io::BufWriter;
 
+#[cfg(test)]
+pub(crate) mod fixtures;
 pub mod har;
 pub mod har_model;
+pub mod http_file;
+pub mod mitm;
 pub mod model;
+pub mod postman;
+pub mod reference;
+pub mod split;
 
 #[tauri::command]
 pub async fn save_session(path: String, session: Session) -> Result<(), String> {
@@ -131,7 +131,6 @@ pub struct RcMatchedHit {
 /// RelayCraft extension - WebSocket frame
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
-#[allow(dead_code)]
 pub struct RcWebSocketFrame {
     pub id: String,
     pub flow_id: String,
@@ -196,6 +195,8 @@ pub struct RcExtension {
     pub error: Option<RcError>,
     pub is_websocket: bool,
     pub websocket_frame_count: i32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub websocket_frames: Vec<RcWebSocketFrame>,
     #[serde(default)]
     pub hits: Vec<RcMatchedHit>,
     pub intercept: RcIntercept,
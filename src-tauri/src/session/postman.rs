@@ -0,0 +1,286 @@
+//! Export of captured flows as a Postman collection (v2.1), one folder per host.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufWriter;
+
+use serde::{Deserialize, Serialize};
+
+use crate::logging;
+use crate::session::http_file::{replayable_headers, request_body_text};
+use crate::session::model::Flow;
+
+const SCHEMA_V21: &str = "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
+/// Body types that cannot be carried as raw text
+const BINARY_MIME_PREFIXES: &[&str] = &[
+    "image/",
+    "audio/",
+    "video/",
+    "application/octet-stream",
+    "application/protobuf",
+    "application/x-protobuf",
+    "application/grpc",
+    "application/zip",
+    "application/pdf",
+];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PostmanCollection {
+    pub info: PostmanInfo,
+    pub item: Vec<PostmanFolder>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PostmanInfo {
+    #[serde(rename = "_postman_id")]
+    pub postman_id: String,
+    pub name: String,
+    pub schema: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PostmanFolder {
+    pub name: String,
+    pub item: Vec<PostmanItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PostmanItem {
+    pub name: String,
+    pub request: PostmanRequest,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PostmanRequest {
+    pub method: String,
+    pub header: Vec<PostmanKeyValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<PostmanBody>,
+    pub url: PostmanUrl,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PostmanKeyValue {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PostmanBody {
+    pub mode: String,
+    pub raw: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PostmanUrl {
+    pub raw: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
+    #[serde(default)]
+    pub host: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<String>,
+    #[serde(default)]
+    pub path: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub query: Vec<PostmanKeyValue>,
+}
+
+fn postman_url(raw: &str) -> PostmanUrl {
+    let Ok(parsed) = url::Url::parse(raw) else {
+        return PostmanUrl {
+            raw: raw.to_string(),
+            protocol: None,
+            host: Vec::new(),
+            port: None,
+            path: Vec::new(),
+            query: Vec::new(),
+        };
+    };
+    PostmanUrl {
+        raw: raw.to_string(),
+        protocol: Some(parsed.scheme().to_string()),
+        host: parsed
+            .host_str()
+            .map(|h| h.split('.').map(str::to_string).collect())
+            .unwrap_or_default(),
+        port: parsed.port().map(|p| p.to_string()),
+        path: parsed
+            .path_segments()
+            .map(|segments| {
+                segments
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        query: parsed
+            .query_pairs()
+            .map(|(key, value)| PostmanKeyValue {
+                key: key.into_owned(),
+                value: value.into_owned(),
+            })
+            .collect(),
+    }
+}
+
+fn postman_item(flow: &Flow) -> PostmanItem {
+    let url = postman_url(&flow.request.url);
+    let path = url::Url::parse(&flow.request.url)
+        .map(|u| u.path().to_string())
+        .unwrap_or_else(|_| flow.request.url.clone());
+
+    let mime = flow
+        .request
+        .post_data
+        .as_ref()
+        .map(|p| p.mime_type.to_ascii_lowercase())
+        .unwrap_or_default();
+    let (body, description) = match request_body_text(flow) {
+        Some(_) if BINARY_MIME_PREFIXES.iter().any(|p| mime.starts_with(p)) => (
+            None,
+            Some(format!("Binary request body ({}) omitted", mime)),
+        ),
+        Some(raw) => {
+            let options = mime
+                .contains("json")
+                .then(|| serde_json::json!({ "raw": { "language": "json" } }));
+            (
+                Some(PostmanBody {
+                    mode: "raw".to_string(),
+                    raw,
+                    options,
+                }),
+                None,
+            )
+        }
+        None => (None, None),
+    };
+
+    PostmanItem {
+        name: format!("{} {}", flow.request.method, path),
+        request: PostmanRequest {
+            method: flow.request.method.clone(),
+            header: replayable_headers(flow)
+                .into_iter()
+                .map(|(key, value)| PostmanKeyValue {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                })
+                .collect(),
+            body,
+            url,
+            description,
+        },
+    }
+}
+
+/// Build a collection with one folder per request host, in host order.
+pub fn flows_to_postman(name: &str, flows: &[Flow]) -> PostmanCollection {
+    let mut folders: BTreeMap<String, Vec<PostmanItem>> = BTreeMap::new();
+    for flow in flows {
+        let host = url::Url::parse(&flow.request.url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string))
+            .unwrap_or_else(|| "unknown".to_string());
+        folders.entry(host).or_default().push(postman_item(flow));
+    }
+
+    PostmanCollection {
+        info: PostmanInfo {
+            postman_id: uuid::Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            schema: SCHEMA_V21.to_string(),
+        },
+        item: folders
+            .into_iter()
+            .map(|(name, item)| PostmanFolder { name, item })
+            .collect(),
+    }
+}
+
+#[tauri::command]
+pub async fn export_postman(path: String, flows: Vec<Flow>) -> Result<usize, String> {
+    let name = std::path::Path::new(&path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "RelayCraft".to_string());
+    let collection = flows_to_postman(&name, &flows);
+
+    let file = File::create(&path).map_err(|e| format!("Failed to create file: {}", e))?;
+    serde_json::to_writer_pretty(BufWriter::new(file), &collection)
+        .map_err(|e| format!("Failed to serialize collection: {}", e))?;
+    let _ = logging::write_domain_log(
+        "audit",
+        &format!(
+            "Exported {} requests as Postman collection to {}",
+            flows.len(),
+            path
+        ),
+    );
+    Ok(flows.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::model::HarPostData;
+
+    fn flow(method: &str, url: &str, body: Option<(&str, &str)>) -> Flow {
+        let mut flow = Flow::default();
+        flow.request.method = method.to_string();
+        flow.request.url = url.to_string();
+        flow.request.post_data = body.map(|(mime, text)| HarPostData {
+            mime_type: mime.to_string(),
+            text: Some(text.to_string()),
+            ..Default::default()
+        });
+        flow
+    }
+
+    #[test]
+    fn test_postman_collection_round_trip() {
+        let flows = vec![
+            flow(
+                "GET",
+                "https://api.example.com/v1/users?page=2&q=a%20b",
+                None,
+            ),
+            flow(
+                "POST",
+                "https://api.example.com:8443/v1/users",
+                Some(("application/json", "{\"name\":\"a\"}")),
+            ),
+            flow(
+                "PUT",
+                "https://cdn.example.com/upload",
+                Some(("image/png", "\u{fffd}PNG")),
+            ),
+        ];
+
+        let json = serde_json::to_string(&flows_to_postman("demo", &flows)).unwrap();
+        let collection: PostmanCollection = serde_json::from_str(&json).unwrap();
+        assert_eq!(collection.info.schema, SCHEMA_V21);
+        assert_eq!(collection.item.len(), 2);
+        let total: usize = collection.item.iter().map(|f| f.item.len()).sum();
+        assert_eq!(total, 3);
+
+        let api = &collection.item[0];
+        assert_eq!(api.name, "api.example.com");
+        let get = &api.item[0].request;
+        assert_eq!(get.url.path, vec!["v1", "users"]);
+        assert_eq!(get.url.query[1].value, "a b");
+        let post = &api.item[1].request;
+        assert_eq!(post.url.port.as_deref(), Some("8443"));
+        assert_eq!(post.body.as_ref().unwrap().raw, "{\"name\":\"a\"}");
+
+        let upload = &collection.item[1].item[0].request;
+        assert!(upload.body.is_none());
+        assert!(upload.description.as_ref().unwrap().contains("image/png"));
+    }
+}
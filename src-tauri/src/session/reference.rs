@@ -0,0 +1,108 @@
+//! Stable, shareable references to flows inside a session file.
+//!
+//! A reference has the form `<session_id>:<hash>`, where `hash` is a short
+//! SHA-1 prefix of the flow id. Flow ids are preserved through save/load,
+//! so a reference stays valid no matter how the session is re-serialized or
+//! how its flows are ordered.
+
+use sha1::{Digest, Sha1};
+
+use crate::session::model::{Flow, Session};
+
+/// Number of hex characters of the flow id hash kept in a reference
+const REFERENCE_HASH_LEN: usize = 10;
+
+fn flow_hash(flow_id: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(flow_id.as_bytes());
+    let mut hash = hex::encode(hasher.finalize());
+    hash.truncate(REFERENCE_HASH_LEN);
+    hash
+}
+
+/// Build the reference for `flow_id` within `session`.
+pub fn make_reference(session: &Session, flow_id: &str) -> Result<String, String> {
+    if !session.flows.iter().any(|f| f.id == flow_id) {
+        return Err(format!("Flow '{}' is not part of this session", flow_id));
+    }
+    Ok(format!("{}:{}", session.id, flow_hash(flow_id)))
+}
+
+/// Find the flow a reference points at.
+pub fn find_by_reference<'a>(session: &'a Session, reference: &str) -> Result<&'a Flow, String> {
+    let (session_id, hash) = reference
+        .trim()
+        .rsplit_once(':')
+        .ok_or_else(|| format!("Malformed flow reference '{}'", reference))?;
+
+    if session_id != session.id {
+        return Err(format!(
+            "Reference belongs to session '{}', not '{}'",
+            session_id, session.id
+        ));
+    }
+
+    let hash = hash.to_ascii_lowercase();
+    session
+        .flows
+        .iter()
+        .find(|f| flow_hash(&f.id) == hash)
+        .ok_or_else(|| format!("No flow in this session matches reference '{}'", reference))
+}
+
+/// Generate a shareable reference for a flow within a session.
+#[tauri::command]
+pub fn flow_reference(session: Session, flow_id: String) -> Result<String, String> {
+    make_reference(&session, &flow_id)
+}
+
+/// Resolve a reference produced by `flow_reference` back to its flow.
+#[tauri::command]
+pub fn resolve_reference(session: Session, reference: String) -> Result<Flow, String> {
+    find_by_reference(&session, &reference).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session_with(ids: &[&str]) -> Session {
+        Session {
+            id: "session-1".to_string(),
+            name: "Test".to_string(),
+            flows: ids
+                .iter()
+                .map(|id| Flow {
+                    id: id.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_reference_survives_reserialization_and_reordering() {
+        let session = session_with(&["a1b2", "c3d4", "e5f6"]);
+        let reference = make_reference(&session, "c3d4").unwrap();
+
+        let json = serde_json::to_string(&session).unwrap();
+        let mut reloaded: Session = serde_json::from_str(&json).unwrap();
+        reloaded.flows.reverse();
+
+        let flow = find_by_reference(&reloaded, &reference).unwrap();
+        assert_eq!(flow.id, "c3d4");
+    }
+
+    #[test]
+    fn test_reference_rejects_other_sessions_and_unknown_flows() {
+        let session = session_with(&["a1b2"]);
+        assert!(make_reference(&session, "missing").is_err());
+
+        let reference = make_reference(&session, "a1b2").unwrap();
+        let mut other = session_with(&["a1b2"]);
+        other.id = "session-2".to_string();
+        assert!(find_by_reference(&other, &reference).is_err());
+        assert!(find_by_reference(&session, "garbage").is_err());
+    }
+}
@@ -0,0 +1,218 @@
+//! Splitting a large session into several smaller session files.
+//!
+//! Each part is a complete, standalone session: it gets a fresh id, a name
+//! derived from the original, and metadata recomputed from its own flows.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::logging;
+use crate::session::model::{Flow, Session, SessionMetadata};
+
+/// How `split_session` partitions the flows of a session.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SplitStrategy {
+    /// One file per request host.
+    ByHost,
+    /// Consecutive chunks of at most `count` flows.
+    ByCount { count: usize },
+}
+
+/// Host of a flow's request URL, or "unknown" if it cannot be parsed.
+fn flow_host(flow: &Flow) -> String {
+    url::Url::parse(&flow.request.url)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.to_string()))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn started_ms(flow: &Flow) -> Option<i64> {
+    chrono::DateTime::parse_from_rfc3339(&flow.started_date_time)
+        .ok()
+        .map(|dt| dt.timestamp_millis())
+}
+
+/// Recompute metadata for a subset of flows, keeping the descriptive
+/// fields of the original session.
+fn metadata_for(flows: &[Flow], base: &SessionMetadata) -> SessionMetadata {
+    let mut first: Option<i64> = None;
+    let mut last_end: Option<f64> = None;
+    for flow in flows {
+        if let Some(start) = started_ms(flow) {
+            first = Some(first.map_or(start, |f| f.min(start)));
+            let end = start as f64 + flow.time.max(0.0);
+            last_end = Some(last_end.map_or(end, |l| l.max(end)));
+        }
+    }
+    let duration = match (first, last_end) {
+        (Some(first), Some(end)) => (end - first as f64).max(0.0),
+        _ => 0.0,
+    };
+
+    SessionMetadata {
+        duration,
+        flow_count: flows.len(),
+        size_bytes: flows
+            .iter()
+            .map(|f| f.response.content.size.max(0) as usize)
+            .sum(),
+        ..base.clone()
+    }
+}
+
+/// Partition `session` into labelled parts according to `strategy`.
+/// Flow order is preserved inside each part.
+pub fn split_flows(
+    session: &Session,
+    strategy: &SplitStrategy,
+) -> Result<Vec<(String, Session)>, String> {
+    let groups: Vec<(String, Vec<Flow>)> = match strategy {
+        SplitStrategy::ByHost => {
+            let mut by_host: BTreeMap<String, Vec<Flow>> = BTreeMap::new();
+            for flow in &session.flows {
+                by_host
+                    .entry(flow_host(flow))
+                    .or_default()
+                    .push(flow.clone());
+            }
+            by_host.into_iter().collect()
+        }
+        SplitStrategy::ByCount { count } => {
+            if *count == 0 {
+                return Err("Split count must be greater than zero".to_string());
+            }
+            let total = session.flows.len().div_ceil(*count);
+            session
+                .flows
+                .chunks(*count)
+                .enumerate()
+                .map(|(i, chunk)| (format!("{}/{}", i + 1, total), chunk.to_vec()))
+                .collect()
+        }
+    };
+
+    Ok(groups
+        .into_iter()
+        .map(|(label, flows)| {
+            let name = match strategy {
+                SplitStrategy::ByHost => format!("{} - {}", session.name, label),
+                SplitStrategy::ByCount { .. } => format!("{} ({})", session.name, label),
+            };
+            let part = Session {
+                id: uuid::Uuid::new_v4().to_string(),
+                name,
+                description: session.description.clone(),
+                metadata: metadata_for(&flows, &session.metadata),
+                flows,
+            };
+            (label, part)
+        })
+        .collect())
+}
+
+/// Turn a part label into something safe to use in a file name.
+fn sanitize_label(label: &str) -> String {
+    label
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Split a session into several `.relay` files written to `output_dir`.
+/// Returns the paths of the files that were written.
+#[tauri::command]
+pub async fn split_session(
+    session: Session,
+    strategy: SplitStrategy,
+    output_dir: String,
+) -> Result<Vec<String>, String> {
+    let dir = Path::new(&output_dir);
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create directory: {}", e))?;
+
+    let stem = sanitize_label(&session.name);
+    let mut paths = Vec::new();
+    for (label, part) in split_flows(&session, &strategy)? {
+        let path = dir.join(format!("{}-{}.relay", stem, sanitize_label(&label)));
+        let file = File::create(&path).map_err(|e| format!("Failed to create file: {}", e))?;
+        serde_json::to_writer(BufWriter::new(file), &part)
+            .map_err(|e| format!("Failed to serialize session: {}", e))?;
+        paths.push(path.to_string_lossy().to_string());
+    }
+
+    let _ = logging::write_domain_log(
+        "audit",
+        &format!(
+            "Split Session '{}' into {} files in {}",
+            session.name,
+            paths.len(),
+            output_dir
+        ),
+    );
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flow(id: &str, url: &str, start: &str, time: f64, size: i64) -> Flow {
+        let mut flow = Flow {
+            id: id.to_string(),
+            started_date_time: start.to_string(),
+            time,
+            ..Default::default()
+        };
+        flow.request.url = url.to_string();
+        flow.response.content.size = size;
+        flow
+    }
+
+    fn sample() -> Session {
+        Session {
+            id: "s".to_string(),
+            name: "Capture".to_string(),
+            flows: vec![
+                flow("1", "https://a.com/x", "2024-01-01T00:00:00Z", 100.0, 10),
+                flow("2", "https://b.com/y", "2024-01-01T00:00:01Z", 50.0, 20),
+                flow("3", "https://a.com/z", "2024-01-01T00:00:02Z", 500.0, 30),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_split_by_host_recomputes_metadata() {
+        let parts = split_flows(&sample(), &SplitStrategy::ByHost).unwrap();
+        assert_eq!(parts.len(), 2);
+
+        let (label, a) = &parts[0];
+        assert_eq!(label, "a.com");
+        assert_eq!(a.name, "Capture - a.com");
+        assert_eq!(a.metadata.flow_count, 2);
+        assert_eq!(a.metadata.size_bytes, 40);
+        assert_eq!(a.metadata.duration, 2500.0);
+        assert_ne!(a.id, "s");
+    }
+
+    #[test]
+    fn test_split_by_count() {
+        let parts = split_flows(&sample(), &SplitStrategy::ByCount { count: 2 }).unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].1.name, "Capture (1/2)");
+        assert_eq!(parts[0].1.flows.len(), 2);
+        assert_eq!(parts[1].1.flows[0].id, "3");
+
+        assert!(split_flows(&sample(), &SplitStrategy::ByCount { count: 0 }).is_err());
+    }
+}
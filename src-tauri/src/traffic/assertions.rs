@@ -0,0 +1,511 @@
+//! Replay with response assertions, for quick smoke tests.
+//!
+//! Assertions are evaluated against the replayed response and reported one
+//! by one, so a failing run still shows every check and the response itself.
+
+use base64::Engine;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::commands::{
+    replay_client, replay_with_client, ReplayRequest, ReplayResponse, MAX_REPLAY_CONCURRENCY,
+};
+use crate::logging;
+
+/// Version written into test suite files.
+const TEST_SUITE_VERSION: u32 = 1;
+
+/// A condition the replayed response must satisfy.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Assertion {
+    StatusEquals {
+        value: u16,
+    },
+    /// Inclusive range, e.g. 200..=299
+    StatusInRange {
+        min: u16,
+        max: u16,
+    },
+    HeaderPresent {
+        name: String,
+    },
+    HeaderEquals {
+        name: String,
+        value: String,
+    },
+    BodyContains {
+        value: String,
+    },
+    /// `path` uses the `$.items[0].id` / `$["a-b"]` notation of body diffs
+    JsonPathEquals {
+        path: String,
+        value: Value,
+    },
+    ResponseTimeUnder {
+        ms: u64,
+    },
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AssertionOutcome {
+    pub assertion: Assertion,
+    pub passed: bool,
+    /// What was actually observed, for display next to the expectation
+    pub actual: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AssertResult {
+    /// True when the request succeeded and every assertion passed
+    pub passed: bool,
+    pub outcomes: Vec<AssertionOutcome>,
+    pub response: Option<ReplayResponse>,
+    pub duration_ms: f64,
+    /// Transport error; assertions are all failed when set
+    pub error: Option<String>,
+}
+
+/// Split `$.a[0]["b-c"]` into object keys and array indices.
+fn parse_json_path(path: &str) -> Result<Vec<Value>, String> {
+    let rest = path
+        .strip_prefix('$')
+        .ok_or_else(|| format!("JSON path must start with '$': {}", path))?;
+    let mut segments = Vec::new();
+    let mut chars = rest.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => {
+                let mut key = String::new();
+                while let Some(&next) = chars.peek() {
+                    if next == '.' || next == '[' {
+                        break;
+                    }
+                    key.push(next);
+                    chars.next();
+                }
+                if key.is_empty() {
+                    return Err(format!("Empty key in JSON path: {}", path));
+                }
+                segments.push(Value::String(key));
+            }
+            '[' => {
+                let mut inner = String::new();
+                for next in chars.by_ref() {
+                    if next == ']' && !(inner.starts_with('"') && !inner.ends_with('"')) {
+                        break;
+                    }
+                    inner.push(next);
+                }
+                if inner.starts_with('"') {
+                    let key: String = serde_json::from_str(&inner)
+                        .map_err(|_| format!("Invalid quoted key in JSON path: {}", path))?;
+                    segments.push(Value::String(key));
+                } else {
+                    let index: u64 = inner
+                        .parse()
+                        .map_err(|_| format!("Invalid index in JSON path: {}", path))?;
+                    segments.push(Value::from(index));
+                }
+            }
+            _ => return Err(format!("Unexpected '{}' in JSON path: {}", c, path)),
+        }
+    }
+    Ok(segments)
+}
+
+/// Resolve a JSON path against `root`.
+pub fn json_path_lookup<'a>(root: &'a Value, path: &str) -> Result<Option<&'a Value>, String> {
+    Ok(parse_json_path(path)?
+        .iter()
+        .try_fold(root, |value, segment| match segment {
+            Value::String(key) => value.get(key),
+            Value::Number(index) => value.get(index.as_u64()? as usize),
+            _ => None,
+        }))
+}
+
+fn header_value<'a>(response: &'a ReplayResponse, name: &str) -> Option<&'a str> {
+    response
+        .headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(name))
+        .map(|(_, v)| v.as_str())
+}
+
+fn body_text(response: &ReplayResponse) -> String {
+    if response.encoding == "base64" {
+        base64::engine::general_purpose::STANDARD
+            .decode(&response.body)
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+            .unwrap_or_default()
+    } else {
+        response.body.clone()
+    }
+}
+
+/// Evaluate one assertion; returns (passed, observed value).
+fn check(
+    assertion: &Assertion,
+    response: &ReplayResponse,
+    duration_ms: f64,
+) -> (bool, Option<String>) {
+    match assertion {
+        Assertion::StatusEquals { value } => {
+            (response.status == *value, Some(response.status.to_string()))
+        }
+        Assertion::StatusInRange { min, max } => (
+            (*min..=*max).contains(&response.status),
+            Some(response.status.to_string()),
+        ),
+        Assertion::HeaderPresent { name } => {
+            let actual = header_value(response, name);
+            (actual.is_some(), actual.map(str::to_string))
+        }
+        Assertion::HeaderEquals { name, value } => {
+            let actual = header_value(response, name);
+            (actual == Some(value.as_str()), actual.map(str::to_string))
+        }
+        Assertion::BodyContains { value } => (body_text(response).contains(value.as_str()), None),
+        Assertion::JsonPathEquals { path, value } => {
+            let body: Value = match serde_json::from_str(&body_text(response)) {
+                Ok(body) => body,
+                Err(_) => return (false, Some("<body is not JSON>".to_string())),
+            };
+            match json_path_lookup(&body, path) {
+                Ok(Some(actual)) => (actual == value, Some(actual.to_string())),
+                Ok(None) => (false, None),
+                Err(e) => (false, Some(e)),
+            }
+        }
+        Assertion::ResponseTimeUnder { ms } => (
+            duration_ms < *ms as f64,
+            Some(format!("{:.0}ms", duration_ms)),
+        ),
+    }
+}
+
+/// Evaluate every assertion against a response.
+pub fn evaluate_assertions(
+    assertions: &[Assertion],
+    response: &ReplayResponse,
+    duration_ms: f64,
+) -> Vec<AssertionOutcome> {
+    assertions
+        .iter()
+        .map(|assertion| {
+            let (passed, actual) = check(assertion, response, duration_ms);
+            AssertionOutcome {
+                assertion: assertion.clone(),
+                passed,
+                actual,
+            }
+        })
+        .collect()
+}
+
+/// Replay `req` with `client` and check the response against `assertions`.
+pub async fn replay_assert_with(
+    client: &reqwest::Client,
+    req: ReplayRequest,
+    assertions: Vec<Assertion>,
+) -> AssertResult {
+    let started = std::time::Instant::now();
+    let result = replay_with_client(client, req).await;
+    let duration_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+    match result {
+        Ok(response) => {
+            let outcomes = evaluate_assertions(&assertions, &response, duration_ms);
+            AssertResult {
+                passed: outcomes.iter().all(|o| o.passed),
+                outcomes,
+                response: Some(response),
+                duration_ms,
+                error: None,
+            }
+        }
+        Err(e) => AssertResult {
+            passed: false,
+            outcomes: assertions
+                .into_iter()
+                .map(|assertion| AssertionOutcome {
+                    assertion,
+                    passed: false,
+                    actual: None,
+                })
+                .collect(),
+            response: None,
+            duration_ms,
+            error: Some(e),
+        },
+    }
+}
+
+/// Replay a request through the engine and assert on the response.
+#[tauri::command]
+pub async fn replay_assert(
+    req: ReplayRequest,
+    assertions: Vec<Assertion>,
+) -> Result<AssertResult, String> {
+    let client = replay_client()?;
+    Ok(replay_assert_with(&client, req, assertions).await)
+}
+
+/// One named replay with the assertions it must satisfy.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayAssertCase {
+    pub name: String,
+    pub request: ReplayRequest,
+    pub assertions: Vec<Assertion>,
+}
+
+/// On-disk test suite: plain JSON so it can live in version control.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TestSuiteFile {
+    version: u32,
+    cases: Vec<ReplayAssertCase>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CaseResult {
+    pub name: String,
+    #[serde(flatten)]
+    pub result: AssertResult,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiteResult {
+    pub passed: bool,
+    pub total: usize,
+    pub passed_count: usize,
+    pub failed_count: usize,
+    pub duration_ms: f64,
+    pub cases: Vec<CaseResult>,
+}
+
+fn read_test_suite(path: &std::path::Path) -> Result<Vec<ReplayAssertCase>, String> {
+    let content =
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to read test suite: {}", e))?;
+    let suite: TestSuiteFile =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid test suite: {}", e))?;
+    if suite.version > TEST_SUITE_VERSION {
+        return Err(format!(
+            "Test suite version {} is newer than supported ({})",
+            suite.version, TEST_SUITE_VERSION
+        ));
+    }
+    Ok(suite.cases)
+}
+
+/// Run cases with at most `concurrency` in flight; results keep case order.
+pub async fn run_cases(
+    client: &reqwest::Client,
+    cases: Vec<ReplayAssertCase>,
+    concurrency: usize,
+) -> SuiteResult {
+    let started = std::time::Instant::now();
+    let concurrency = concurrency.clamp(1, MAX_REPLAY_CONCURRENCY);
+    let cases: Vec<CaseResult> = futures_util::stream::iter(cases)
+        .map(|case| async move {
+            CaseResult {
+                name: case.name,
+                result: replay_assert_with(client, case.request, case.assertions).await,
+            }
+        })
+        .buffered(concurrency)
+        .collect()
+        .await;
+
+    let passed_count = cases.iter().filter(|c| c.result.passed).count();
+    SuiteResult {
+        passed: passed_count == cases.len(),
+        total: cases.len(),
+        passed_count,
+        failed_count: cases.len() - passed_count,
+        duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+        cases,
+    }
+}
+
+/// Save replay+assertion cases as a JSON test suite.
+#[tauri::command]
+pub fn export_test_suite(path: String, cases: Vec<ReplayAssertCase>) -> Result<(), String> {
+    let suite = TestSuiteFile {
+        version: TEST_SUITE_VERSION,
+        cases,
+    };
+    let content = serde_json::to_string_pretty(&suite).map_err(|e| e.to_string())?;
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write test suite: {}", e))?;
+    let _ = logging::write_domain_log(
+        "audit",
+        &format!(
+            "Exported test suite with {} cases to {}",
+            suite.cases.len(),
+            path
+        ),
+    );
+    Ok(())
+}
+
+/// Run a saved test suite through the engine. Cases run one after another
+/// unless `concurrency` is above 1.
+#[tauri::command]
+pub async fn run_test_suite(
+    path: String,
+    concurrency: Option<usize>,
+) -> Result<SuiteResult, String> {
+    let cases = read_test_suite(std::path::Path::new(&path))?;
+    let client = replay_client()?;
+    Ok(run_cases(&client, cases, concurrency.unwrap_or(1)).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traffic::test_server::{response, serve};
+
+    /// Serve one canned HTTP response on a loopback port and return its URL.
+    fn mock_server(response_headers: &str, body: &'static str) -> String {
+        let canned = response("200 OK", response_headers, body.as_bytes());
+        serve(1, move |_| canned.clone()).url("/api/items")
+    }
+
+    fn get(url: &str) -> ReplayRequest {
+        ReplayRequest {
+            url: url.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn run(req: ReplayRequest, assertions: Vec<Assertion>) -> AssertResult {
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(replay_assert_with(&client, req, assertions))
+    }
+
+    #[test]
+    fn test_json_path_lookup() {
+        let value = serde_json::json!({"items": [{"id": 7}], "a-b": {"c": true}});
+        assert_eq!(
+            json_path_lookup(&value, "$.items[0].id").unwrap(),
+            Some(&serde_json::json!(7))
+        );
+        assert_eq!(
+            json_path_lookup(&value, "$[\"a-b\"].c").unwrap(),
+            Some(&Value::Bool(true))
+        );
+        assert_eq!(json_path_lookup(&value, "$.items[3]").unwrap(), None);
+        assert!(json_path_lookup(&value, "items").is_err());
+    }
+
+    #[test]
+    fn test_replay_assert_against_mock_server() {
+        let url = mock_server(
+            "Content-Type: application/json\r\nETag: \"v1\"\r\n",
+            r#"{"items": [{"id": 7, "name": "widget"}]}"#,
+        );
+        let result = run(
+            get(&url),
+            vec![
+                Assertion::StatusEquals { value: 200 },
+                Assertion::StatusInRange { min: 200, max: 299 },
+                Assertion::HeaderPresent {
+                    name: "etag".to_string(),
+                },
+                Assertion::HeaderEquals {
+                    name: "Content-Type".to_string(),
+                    value: "application/json".to_string(),
+                },
+                Assertion::BodyContains {
+                    value: "widget".to_string(),
+                },
+                Assertion::JsonPathEquals {
+                    path: "$.items[0].id".to_string(),
+                    value: serde_json::json!(7),
+                },
+                Assertion::ResponseTimeUnder { ms: 10_000 },
+            ],
+        );
+        assert!(result.error.is_none());
+        assert!(result.outcomes.iter().all(|o| o.passed));
+        assert!(result.passed);
+        assert_eq!(result.response.unwrap().status, 200);
+    }
+
+    #[test]
+    fn test_failed_assertions_report_actual_values() {
+        let url = mock_server("Content-Type: text/plain\r\n", "hello");
+        let result = run(
+            get(&url),
+            vec![
+                Assertion::StatusEquals { value: 201 },
+                Assertion::HeaderPresent {
+                    name: "etag".to_string(),
+                },
+                Assertion::JsonPathEquals {
+                    path: "$.id".to_string(),
+                    value: serde_json::json!(1),
+                },
+            ],
+        );
+        assert!(!result.passed);
+        assert!(result.outcomes.iter().all(|o| !o.passed));
+        assert_eq!(result.outcomes[0].actual.as_deref(), Some("200"));
+        assert_eq!(
+            result.outcomes[2].actual.as_deref(),
+            Some("<body is not JSON>")
+        );
+    }
+
+    #[test]
+    fn test_suite_round_trip_and_run() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("smoke.json");
+        let ok_url = mock_server("Content-Type: application/json\r\n", r#"{"ok": true}"#);
+        let cases = vec![
+            ReplayAssertCase {
+                name: "health".to_string(),
+                request: get(&ok_url),
+                assertions: vec![Assertion::JsonPathEquals {
+                    path: "$.ok".to_string(),
+                    value: Value::Bool(true),
+                }],
+            },
+            ReplayAssertCase {
+                name: "unreachable".to_string(),
+                request: get("http://127.0.0.1:9/down"),
+                assertions: vec![Assertion::StatusEquals { value: 200 }],
+            },
+        ];
+        export_test_suite(path.to_string_lossy().to_string(), cases).unwrap();
+
+        let cases = read_test_suite(&path).unwrap();
+        assert_eq!(cases.len(), 2);
+
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+        let suite = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(run_cases(&client, cases, 2));
+        assert!(!suite.passed);
+        assert_eq!(
+            (suite.total, suite.passed_count, suite.failed_count),
+            (2, 1, 1)
+        );
+        assert_eq!(suite.cases[0].name, "health");
+        assert!(suite.cases[0].result.passed);
+        assert!(suite.cases[1].result.error.is_some());
+    }
+}
@@ -0,0 +1,241 @@
+//! Golden-file comparison of captured responses.
+//!
+//! A baseline is a captured [`Flow`] saved as JSON. Comparing against it reports
+//! differences in status, headers and body while ignoring headers that change
+//! on every response.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use super::commands::fetch_flow;
+use crate::logging;
+use crate::session::model::{Flow, HarHeader};
+
+/// Headers ignored by default because they differ on every response.
+pub const DEFAULT_IGNORED_HEADERS: &[&str] = &[
+    "date",
+    "age",
+    "expires",
+    "last-modified",
+    "etag",
+    "set-cookie",
+    "request-id",
+    "x-request-id",
+    "x-correlation-id",
+    "x-amzn-requestid",
+    "x-amz-request-id",
+    "cf-ray",
+    "server-timing",
+    "x-runtime",
+    "content-length",
+];
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HeaderChange {
+    pub name: String,
+    pub baseline: Option<String>,
+    pub current: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BaselineDiff {
+    /// True when no difference was found outside the ignored headers
+    pub matches: bool,
+    pub baseline_status: i32,
+    pub current_status: i32,
+    pub status_changed: bool,
+    pub header_changes: Vec<HeaderChange>,
+    pub body_changed: bool,
+    pub baseline_body_size: usize,
+    pub current_body_size: usize,
+}
+
+/// Collapse headers into lowercase name -> joined value, skipping ignored names.
+pub(super) fn header_map(headers: &[HarHeader], ignored: &[String]) -> BTreeMap<String, String> {
+    let mut map: BTreeMap<String, String> = BTreeMap::new();
+    for header in headers {
+        let name = header.name.to_ascii_lowercase();
+        if ignored.iter().any(|i| i == &name) {
+            continue;
+        }
+        map.entry(name)
+            .and_modify(|v| {
+                v.push_str(", ");
+                v.push_str(&header.value);
+            })
+            .or_insert_with(|| header.value.clone());
+    }
+    map
+}
+
+/// Bodies are equal when their text matches, or when both parse as JSON
+/// values that are equal (so formatting and key order do not matter).
+fn bodies_equal(baseline: &str, current: &str) -> bool {
+    if baseline == current {
+        return true;
+    }
+    match (
+        serde_json::from_str::<serde_json::Value>(baseline),
+        serde_json::from_str::<serde_json::Value>(current),
+    ) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Diff the response of `current` against `baseline`.
+pub fn diff_against_baseline(baseline: &Flow, current: &Flow, ignored: &[String]) -> BaselineDiff {
+    let ignored: Vec<String> = ignored.iter().map(|h| h.to_ascii_lowercase()).collect();
+
+    let base_headers = header_map(&baseline.response.headers, &ignored);
+    let cur_headers = header_map(&current.response.headers, &ignored);
+
+    let mut names: Vec<&String> = base_headers.keys().chain(cur_headers.keys()).collect();
+    names.sort();
+    names.dedup();
+
+    let header_changes: Vec<HeaderChange> = names
+        .into_iter()
+        .filter(|name| base_headers.get(*name) != cur_headers.get(*name))
+        .map(|name| HeaderChange {
+            name: name.clone(),
+            baseline: base_headers.get(name).cloned(),
+            current: cur_headers.get(name).cloned(),
+        })
+        .collect();
+
+    let base_body = baseline.response.content.text.as_deref().unwrap_or("");
+    let cur_body = current.response.content.text.as_deref().unwrap_or("");
+    let body_changed = !bodies_equal(base_body, cur_body);
+
+    let status_changed = baseline.response.status != current.response.status;
+
+    BaselineDiff {
+        matches: !status_changed && header_changes.is_empty() && !body_changed,
+        baseline_status: baseline.response.status,
+        current_status: current.response.status,
+        status_changed,
+        header_changes,
+        body_changed,
+        baseline_body_size: base_body.len(),
+        current_body_size: cur_body.len(),
+    }
+}
+
+/// Save a captured flow as a baseline file for later comparison.
+#[tauri::command]
+pub async fn save_baseline(flow_id: String, baseline_path: String) -> Result<(), String> {
+    let flow = fetch_flow(&flow_id).await?;
+    let json = serde_json::to_string_pretty(&flow)
+        .map_err(|e| format!("Failed to serialize baseline: {}", e))?;
+    std::fs::write(&baseline_path, json).map_err(|e| format!("Failed to write baseline: {}", e))?;
+    let _ = logging::write_domain_log(
+        "audit",
+        &format!("Saved baseline for flow {} to {}", flow_id, baseline_path),
+    );
+    Ok(())
+}
+
+/// Compare a captured flow's response against a saved baseline.
+/// `ignore_headers` replaces the default list of volatile headers when given.
+#[tauri::command]
+pub async fn compare_to_baseline(
+    flow_id: String,
+    baseline_path: String,
+    ignore_headers: Option<Vec<String>>,
+) -> Result<BaselineDiff, String> {
+    let content = std::fs::read_to_string(&baseline_path)
+        .map_err(|e| format!("Failed to read baseline: {}", e))?;
+    let baseline: Flow =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid baseline file: {}", e))?;
+
+    let current = fetch_flow(&flow_id).await?;
+
+    let ignored = ignore_headers.unwrap_or_else(|| {
+        DEFAULT_IGNORED_HEADERS
+            .iter()
+            .map(|h| h.to_string())
+            .collect()
+    });
+
+    Ok(diff_against_baseline(&baseline, &current, &ignored))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response_flow(status: i32, headers: &[(&str, &str)], body: &str) -> Flow {
+        let mut flow = Flow::default();
+        flow.response.status = status;
+        flow.response.headers = headers
+            .iter()
+            .map(|(name, value)| HarHeader {
+                name: name.to_string(),
+                value: value.to_string(),
+                comment: None,
+            })
+            .collect();
+        flow.response.content.text = Some(body.to_string());
+        flow
+    }
+
+    fn defaults() -> Vec<String> {
+        DEFAULT_IGNORED_HEADERS
+            .iter()
+            .map(|h| h.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_volatile_headers_and_json_formatting_are_ignored() {
+        let baseline = response_flow(
+            200,
+            &[
+                ("Date", "Mon, 01 Jan 2024"),
+                ("Content-Type", "application/json"),
+            ],
+            r#"{"a":1,"b":[1,2]}"#,
+        );
+        let current = response_flow(
+            200,
+            &[
+                ("date", "Tue, 02 Jan 2024"),
+                ("content-type", "application/json"),
+            ],
+            "{ \"b\": [1, 2], \"a\": 1 }",
+        );
+
+        let diff = diff_against_baseline(&baseline, &current, &defaults());
+        assert!(diff.matches);
+    }
+
+    #[test]
+    fn test_reports_status_header_and_body_changes() {
+        let baseline = response_flow(200, &[("X-Version", "1")], "ok");
+        let current = response_flow(500, &[("X-Version", "2"), ("X-New", "y")], "error");
+
+        let diff = diff_against_baseline(&baseline, &current, &defaults());
+        assert!(!diff.matches);
+        assert!(diff.status_changed);
+        assert!(diff.body_changed);
+        assert_eq!(
+            diff.header_changes,
+            vec![
+                HeaderChange {
+                    name: "x-new".into(),
+                    baseline: None,
+                    current: Some("y".into()),
+                },
+                HeaderChange {
+                    name: "x-version".into(),
+                    baseline: Some("1".into()),
+                    current: Some("2".into()),
+                },
+            ]
+        );
+    }
+}
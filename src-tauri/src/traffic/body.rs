@@ -0,0 +1,377 @@
+//! Content sniffing for captured bodies.
+//!
+//! Mime types are often wrong, so the decision between a text view and a
+//! hex/download view is made from the bytes: byte order marks, magic numbers,
+//! NUL bytes and UTF-8 validity.
+
+use base64::Engine;
+use serde::Serialize;
+
+use super::commands::fetch_flow;
+use crate::session::model::{Flow, HarHeader};
+
+/// Only the head of a body is inspected
+const SNIFF_LIMIT: usize = 8 * 1024;
+
+/// Share of control bytes above which a body is treated as binary
+const MAX_CONTROL_RATIO: f64 = 0.05;
+
+const MAGIC_NUMBERS: &[(&[u8], &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "png"),
+    (b"\xff\xd8\xff", "jpeg"),
+    (b"GIF87a", "gif"),
+    (b"GIF89a", "gif"),
+    (b"%PDF-", "pdf"),
+    (b"PK\x03\x04", "zip"),
+    (b"\x1f\x8b", "gzip"),
+    (b"\x00asm", "wasm"),
+    (b"wOFF", "woff"),
+    (b"wOF2", "woff2"),
+];
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BodyClassification {
+    /// "text", "binary" or "empty"
+    pub kind: String,
+    /// 0.0 - 1.0
+    pub confidence: f32,
+    /// Best guess at the text encoding; `None` for binary bodies
+    pub charset: Option<String>,
+    pub has_bom: bool,
+    /// File format recognised from a magic number, e.g. "png"
+    pub format: Option<String>,
+    pub size: usize,
+    pub mime_type: String,
+}
+
+impl BodyClassification {
+    fn new(kind: &str, confidence: f32, charset: Option<&str>) -> Self {
+        Self {
+            kind: kind.to_string(),
+            confidence,
+            charset: charset.map(str::to_string),
+            has_bom: false,
+            format: None,
+            size: 0,
+            mime_type: String::new(),
+        }
+    }
+}
+
+/// Raw bytes and mime type of the request or response body of `flow`.
+/// `which` is "request" or "response".
+pub(crate) fn body_bytes(flow: &Flow, which: &str) -> Result<(Vec<u8>, String), String> {
+    let b64 = base64::engine::general_purpose::STANDARD;
+    match which {
+        "response" => {
+            let content = &flow.response.content;
+            let text = content.text.as_deref().unwrap_or("");
+            let bytes = if content.encoding.as_deref() == Some("base64") {
+                b64.decode(text)
+                    .map_err(|e| format!("Invalid base64 body: {}", e))?
+            } else {
+                text.as_bytes().to_vec()
+            };
+            Ok((bytes, content.mime_type.clone()))
+        }
+        "request" => {
+            // The engine stores binary request bodies as base64 without an
+            // encoding marker, so only text is available here.
+            let post = flow.request.post_data.as_ref();
+            let text = post.and_then(|p| p.text.as_deref()).unwrap_or("");
+            let mime = post.map(|p| p.mime_type.clone()).unwrap_or_default();
+            Ok((text.as_bytes().to_vec(), mime))
+        }
+        other => Err(format!(
+            "Unknown body '{}', expected 'request' or 'response'",
+            other
+        )),
+    }
+}
+
+fn detect_bom(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\xef\xbb\xbf") {
+        Some("utf-8")
+    } else if bytes.starts_with(b"\xff\xfe\x00\x00") {
+        Some("utf-32le")
+    } else if bytes.starts_with(b"\x00\x00\xfe\xff") {
+        Some("utf-32be")
+    } else if bytes.starts_with(b"\xff\xfe") {
+        Some("utf-16le")
+    } else if bytes.starts_with(b"\xfe\xff") {
+        Some("utf-16be")
+    } else {
+        None
+    }
+}
+
+/// UTF-16 without a BOM: mostly ASCII with a NUL in every other byte
+fn detect_utf16_without_bom(sample: &[u8]) -> Option<&'static str> {
+    if sample.len() < 4 {
+        return None;
+    }
+    let pairs = sample.len() / 2;
+    let even_nuls = sample.iter().step_by(2).filter(|b| **b == 0).count();
+    let odd_nuls = sample
+        .iter()
+        .skip(1)
+        .step_by(2)
+        .filter(|b| **b == 0)
+        .count();
+    if odd_nuls * 10 >= pairs * 9 && even_nuls == 0 {
+        Some("utf-16le")
+    } else if even_nuls * 10 >= pairs * 9 && odd_nuls == 0 {
+        Some("utf-16be")
+    } else {
+        None
+    }
+}
+
+fn control_ratio(sample: &[u8]) -> f64 {
+    if sample.is_empty() {
+        return 0.0;
+    }
+    let controls = sample
+        .iter()
+        .filter(|b| **b < 0x20 && !matches!(**b, b'\t' | b'\n' | b'\r' | 0x0c | 0x1b))
+        .count();
+    controls as f64 / sample.len() as f64
+}
+
+/// Valid UTF-8, allowing a multi-byte sequence cut off by the sniff limit
+fn is_utf8_prefix(sample: &[u8], truncated: bool) -> bool {
+    match std::str::from_utf8(sample) {
+        Ok(_) => true,
+        Err(e) => truncated && e.error_len().is_none(),
+    }
+}
+
+/// Classify raw body bytes as text or binary
+pub fn classify_bytes(bytes: &[u8]) -> BodyClassification {
+    let mut result = if bytes.is_empty() {
+        BodyClassification::new("empty", 1.0, None)
+    } else if let Some(charset) = detect_bom(bytes) {
+        let mut r = BodyClassification::new("text", 1.0, Some(charset));
+        r.has_bom = true;
+        r
+    } else if let Some((_, format)) = MAGIC_NUMBERS.iter().find(|(m, _)| bytes.starts_with(m)) {
+        let mut r = BodyClassification::new("binary", 1.0, None);
+        r.format = Some(format.to_string());
+        r
+    } else {
+        let truncated = bytes.len() > SNIFF_LIMIT;
+        let sample = &bytes[..bytes.len().min(SNIFF_LIMIT)];
+
+        if sample.contains(&0) {
+            match detect_utf16_without_bom(sample) {
+                Some(charset) => BodyClassification::new("text", 0.7, Some(charset)),
+                None => BodyClassification::new("binary", 0.95, None),
+            }
+        } else if control_ratio(sample) > MAX_CONTROL_RATIO {
+            BodyClassification::new("binary", 0.85, None)
+        } else if is_utf8_prefix(sample, truncated) {
+            if sample.is_ascii() {
+                BodyClassification::new("text", 0.9, Some("us-ascii"))
+            } else {
+                // Non-ASCII that still validates is very unlikely to be accidental
+                BodyClassification::new("text", 0.98, Some("utf-8"))
+            }
+        } else {
+            // Printable but not UTF-8: most likely a legacy single/double-byte charset
+            BodyClassification::new("text", 0.5, None)
+        }
+    };
+    result.size = bytes.len();
+    result
+}
+
+/// Legacy encodings tried in order when nothing declares a charset.
+/// windows-1252 maps every byte, so it is the last resort.
+const FALLBACK_CHARSETS: &[&str] = &["gbk", "shift_jis", "euc-kr", "big5", "windows-1252"];
+
+/// `charset` parameter of a Content-Type value
+fn content_type_charset(content_type: &str) -> Option<String> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (key, value) = param.split_once('=')?;
+        key.trim()
+            .eq_ignore_ascii_case("charset")
+            .then(|| value.trim().trim_matches('"').to_string())
+    })
+}
+
+/// `<meta charset>` or `http-equiv` declaration near the top of an HTML body
+fn html_meta_charset(bytes: &[u8]) -> Option<String> {
+    let head = String::from_utf8_lossy(&bytes[..bytes.len().min(1024)]).to_ascii_lowercase();
+    let start = head.find("charset=")? + "charset=".len();
+    let value: String = head[start..]
+        .trim_start_matches(['"', '\''])
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.'))
+        .collect();
+    (!value.is_empty()).then_some(value)
+}
+
+/// Pick a charset for `bytes`: BOM, then the declared Content-Type charset,
+/// then an HTML meta tag, then UTF-8 if valid, then the first legacy
+/// encoding that decodes without errors.
+pub fn detect_charset(bytes: &[u8], headers: &[HarHeader]) -> &'static encoding_rs::Encoding {
+    if let Some((encoding, _)) = encoding_rs::Encoding::for_bom(bytes) {
+        return encoding;
+    }
+
+    let declared = headers
+        .iter()
+        .find(|h| h.name.eq_ignore_ascii_case("content-type"))
+        .and_then(|h| content_type_charset(&h.value))
+        .or_else(|| html_meta_charset(bytes));
+    if let Some(encoding) =
+        declared.and_then(|label| encoding_rs::Encoding::for_label(label.as_bytes()))
+    {
+        return encoding;
+    }
+
+    if std::str::from_utf8(bytes).is_ok() {
+        return encoding_rs::UTF_8;
+    }
+
+    FALLBACK_CHARSETS
+        .iter()
+        .filter_map(|label| encoding_rs::Encoding::for_label(label.as_bytes()))
+        .find(|encoding| !encoding.decode_without_bom_handling(bytes).1)
+        .unwrap_or(encoding_rs::WINDOWS_1252)
+}
+
+/// Decode `bytes` as `charset`, or detect the charset when it is "auto"
+pub fn redecode_bytes(
+    bytes: &[u8],
+    headers: &[HarHeader],
+    charset: &str,
+) -> Result<String, String> {
+    let encoding = if charset.eq_ignore_ascii_case("auto") {
+        detect_charset(bytes, headers)
+    } else {
+        encoding_rs::Encoding::for_label(charset.trim().as_bytes())
+            .ok_or_else(|| format!("Unknown charset: {}", charset))?
+    };
+    let (text, _, _) = encoding.decode(bytes);
+    Ok(text.into_owned())
+}
+
+/// Re-decode a captured body with `charset` ("auto" to detect it), fixing
+/// mojibake from bodies that are not UTF-8
+#[tauri::command]
+pub async fn redecode_body(
+    flow_id: String,
+    which: String,
+    charset: String,
+) -> Result<String, String> {
+    let flow = fetch_flow(&flow_id).await?;
+    let (bytes, _) = body_bytes(&flow, &which)?;
+    let headers = if which == "request" {
+        &flow.request.headers
+    } else {
+        &flow.response.headers
+    };
+    redecode_bytes(&bytes, headers, &charset)
+}
+
+/// Classify the request or response body of a captured flow
+#[tauri::command]
+pub async fn classify_body(flow_id: String, which: String) -> Result<BodyClassification, String> {
+    let flow = fetch_flow(&flow_id).await?;
+    let (bytes, mime_type) = body_bytes(&flow, &which)?;
+    let mut classification = classify_bytes(&bytes);
+    classification.mime_type = mime_type;
+    Ok(classification)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kind(bytes: &[u8]) -> (String, Option<String>) {
+        let c = classify_bytes(bytes);
+        (c.kind, c.charset)
+    }
+
+    #[test]
+    fn test_classify_text_bodies() {
+        assert_eq!(kind(b""), ("empty".into(), None));
+        assert_eq!(
+            kind(br#"{"ok":true}"#),
+            ("text".into(), Some("us-ascii".into()))
+        );
+        assert_eq!(
+            kind("héllo wörld".as_bytes()),
+            ("text".into(), Some("utf-8".into()))
+        );
+        assert_eq!(
+            kind(b"\xef\xbb\xbfhello"),
+            ("text".into(), Some("utf-8".into()))
+        );
+        assert!(classify_bytes(b"\xff\xfeh\x00i\x00").has_bom);
+        assert_eq!(
+            kind(b"h\x00e\x00l\x00l\x00o\x00"),
+            ("text".into(), Some("utf-16le".into()))
+        );
+        // GBK "中文" is not UTF-8 but is still printable text
+        assert_eq!(kind(b"\xd6\xd0\xce\xc4"), ("text".into(), None));
+    }
+
+    #[test]
+    fn test_classify_binary_bodies() {
+        let png = classify_bytes(b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR");
+        assert_eq!(png.kind, "binary");
+        assert_eq!(png.format.as_deref(), Some("png"));
+
+        assert_eq!(kind(b"\x01\x02\x03\x00\xfe\x10\x11").0, "binary");
+        assert_eq!(kind(&[0x08u8; 64]).0, "binary");
+    }
+
+    fn content_type(value: &str) -> Vec<HarHeader> {
+        vec![HarHeader {
+            name: "Content-Type".to_string(),
+            value: value.to_string(),
+            comment: None,
+        }]
+    }
+
+    // "中文编码" in GBK
+    const GBK_BYTES: &[u8] = b"\xd6\xd0\xce\xc4\xb1\xe0\xc2\xeb";
+
+    #[test]
+    fn test_redecode_gbk_with_explicit_and_declared_charset() {
+        assert_eq!(redecode_bytes(GBK_BYTES, &[], "gbk").unwrap(), "中文编码");
+        assert_eq!(
+            redecode_bytes(
+                GBK_BYTES,
+                &content_type("text/html; charset=\"GB2312\""),
+                "auto"
+            )
+            .unwrap(),
+            "中文编码"
+        );
+        assert!(redecode_bytes(GBK_BYTES, &[], "no-such-charset").is_err());
+    }
+
+    #[test]
+    fn test_detect_charset_without_declaration() {
+        assert_eq!(detect_charset(GBK_BYTES, &[]), encoding_rs::GBK);
+        assert_eq!(detect_charset("中文".as_bytes(), &[]), encoding_rs::UTF_8);
+
+        let mut html = b"<html><head><meta charset=\"shift_jis\">".to_vec();
+        html.extend_from_slice(b"\x93\xfa\x96\x7b");
+        assert!(redecode_bytes(&html, &[], "auto")
+            .unwrap()
+            .ends_with("日本"));
+    }
+
+    #[test]
+    fn test_utf8_cut_at_sniff_limit_is_still_text() {
+        let mut body = "a".repeat(SNIFF_LIMIT - 1).into_bytes();
+        body.extend_from_slice("é".as_bytes());
+        body.extend_from_slice(b"tail");
+        assert_eq!(kind(&body).1.as_deref(), Some("utf-8"));
+    }
+}
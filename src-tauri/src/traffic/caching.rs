@@ -0,0 +1,305 @@
+//! Response caching effectiveness.
+//!
+//! Inspects `Cache-Control`, `ETag`, `Expires` and `Last-Modified` on captured
+//! responses and flags headers that defeat browser or CDN caching.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::session::model::{Flow, HarHeader};
+
+/// Fingerprinted or `immutable` content should be cached for at least this long.
+const MIN_IMMUTABLE_MAX_AGE_SECS: i64 = 30 * 24 * 3600;
+
+/// Path extensions treated as static assets.
+const STATIC_EXTENSIONS: &[&str] = &[
+    "js", "mjs", "css", "png", "jpg", "jpeg", "gif", "svg", "webp", "avif", "ico", "woff", "woff2",
+    "ttf", "otf", "mp4", "webm",
+];
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum CachingSeverity {
+    Info,
+    Warning,
+}
+
+/// One caching issue found on a response.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CachingFinding {
+    pub flow_id: String,
+    pub url: String,
+    /// Stable identifier, e.g. "no_cache_static"
+    pub code: String,
+    pub severity: CachingSeverity,
+    pub message: String,
+    /// Whether a shared or browser cache may store the response at all
+    pub cacheable: bool,
+    /// Freshness lifetime from `max-age` or `Expires - Date`, in seconds
+    pub max_age_secs: Option<i64>,
+}
+
+/// Parsed `Cache-Control` directives, lowercased; valueless directives map to "".
+fn cache_control(headers: &[HarHeader]) -> Option<HashMap<String, String>> {
+    let values: Vec<&str> = headers
+        .iter()
+        .filter(|h| h.name.eq_ignore_ascii_case("cache-control"))
+        .map(|h| h.value.as_str())
+        .collect();
+    if values.is_empty() {
+        return None;
+    }
+    Some(
+        values
+            .iter()
+            .flat_map(|v| v.split(','))
+            .filter_map(|directive| {
+                let directive = directive.trim();
+                if directive.is_empty() {
+                    return None;
+                }
+                let (name, value) = directive.split_once('=').unwrap_or((directive, ""));
+                Some((
+                    name.trim().to_ascii_lowercase(),
+                    value.trim().trim_matches('"').to_string(),
+                ))
+            })
+            .collect(),
+    )
+}
+
+fn header<'a>(headers: &'a [HarHeader], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|h| h.name.eq_ignore_ascii_case(name))
+        .map(|h| h.value.as_str())
+}
+
+/// Freshness lifetime of `Expires` relative to `Date` (or now, without `Date`).
+fn expires_lifetime(headers: &[HarHeader]) -> Option<i64> {
+    let expires = chrono::DateTime::parse_from_rfc2822(header(headers, "expires")?).ok()?;
+    let date = header(headers, "date")
+        .and_then(|d| chrono::DateTime::parse_from_rfc2822(d).ok())
+        .map(|d| d.timestamp())
+        .unwrap_or_else(|| chrono::Utc::now().timestamp());
+    Some(expires.timestamp() - date)
+}
+
+fn is_static_asset(flow: &Flow) -> bool {
+    let path = url::Url::parse(&flow.request.url)
+        .map(|u| u.path().to_ascii_lowercase())
+        .unwrap_or_default();
+    let by_extension = path
+        .rsplit_once('.')
+        .map(|(_, ext)| STATIC_EXTENSIONS.contains(&ext))
+        .unwrap_or(false);
+    let mime = flow.response.content.mime_type.to_ascii_lowercase();
+    by_extension
+        || mime.starts_with("image/")
+        || mime.starts_with("font/")
+        || mime.starts_with("text/css")
+        || mime.contains("javascript")
+}
+
+/// File names carrying a content hash, e.g. `app.3f9a1c2b.js` or `main-8d2e41ab9c.css`.
+fn is_fingerprinted(url: &str) -> bool {
+    let file = url::Url::parse(url)
+        .ok()
+        .and_then(|u| u.path_segments()?.last().map(str::to_string))
+        .unwrap_or_default();
+    file.split(['.', '-', '_']).any(|part| {
+        part.len() >= 8
+            && part.chars().all(|c| c.is_ascii_hexdigit())
+            && part.chars().any(|c| c.is_ascii_digit())
+    })
+}
+
+fn finding(
+    flow: &Flow,
+    code: &str,
+    severity: CachingSeverity,
+    message: String,
+    cacheable: bool,
+    max_age_secs: Option<i64>,
+) -> CachingFinding {
+    CachingFinding {
+        flow_id: flow.id.clone(),
+        url: flow.request.url.clone(),
+        code: code.to_string(),
+        severity,
+        message,
+        cacheable,
+        max_age_secs,
+    }
+}
+
+/// Caching issues of a single flow. Only successful GET responses are judged.
+pub fn caching_findings(flow: &Flow) -> Vec<CachingFinding> {
+    let status = flow.response.status;
+    if !flow.request.method.eq_ignore_ascii_case("GET") || !(200..300).contains(&status) {
+        return Vec::new();
+    }
+
+    let headers = &flow.response.headers;
+    let directives = cache_control(headers);
+    let has = |name: &str| directives.as_ref().is_some_and(|d| d.contains_key(name));
+    let max_age = directives
+        .as_ref()
+        .and_then(|d| d.get("max-age"))
+        .and_then(|v| v.parse::<i64>().ok())
+        .or_else(|| expires_lifetime(headers));
+
+    let cacheable = !has("no-store");
+    let revalidates_always = has("no-cache") || max_age.is_some_and(|age| age <= 0);
+    let is_static = is_static_asset(flow);
+    let immutable = has("immutable") || (is_static && is_fingerprinted(&flow.request.url));
+    let mut out = Vec::new();
+
+    if is_static && (!cacheable || revalidates_always) {
+        out.push(finding(
+            flow,
+            "no_cache_static",
+            CachingSeverity::Warning,
+            "Static asset is not cached; every load goes back to the server".to_string(),
+            cacheable,
+            max_age,
+        ));
+    } else if is_static && directives.is_none() && header(headers, "expires").is_none() {
+        out.push(finding(
+            flow,
+            "no_cache_headers",
+            CachingSeverity::Warning,
+            "Static asset has no Cache-Control or Expires; browsers fall back to heuristic caching"
+                .to_string(),
+            cacheable,
+            max_age,
+        ));
+    }
+
+    if cacheable && header(headers, "etag").is_none() && header(headers, "last-modified").is_none()
+    {
+        out.push(finding(
+            flow,
+            "missing_validator",
+            CachingSeverity::Info,
+            "No ETag or Last-Modified; stale copies cannot be revalidated with a 304".to_string(),
+            cacheable,
+            max_age,
+        ));
+    }
+
+    if immutable && cacheable && !revalidates_always {
+        if let Some(age) = max_age.filter(|age| *age < MIN_IMMUTABLE_MAX_AGE_SECS) {
+            out.push(finding(
+                flow,
+                "short_max_age_immutable",
+                CachingSeverity::Warning,
+                format!(
+                    "Immutable content expires after {}s; use max-age=31536000, immutable",
+                    age
+                ),
+                cacheable,
+                max_age,
+            ));
+        }
+    }
+
+    out
+}
+
+/// Inspect caching headers of every flow and report issues.
+#[tauri::command]
+pub fn analyze_caching(flows: Vec<Flow>) -> Vec<CachingFinding> {
+    flows.iter().flat_map(caching_findings).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flow(url: &str, mime: &str, headers: &[(&str, &str)]) -> Flow {
+        let mut flow = Flow::default();
+        flow.id = "f1".to_string();
+        flow.request.method = "GET".to_string();
+        flow.request.url = url.to_string();
+        flow.response.status = 200;
+        flow.response.content.mime_type = mime.to_string();
+        flow.response.headers = headers
+            .iter()
+            .map(|(name, value)| HarHeader {
+                name: name.to_string(),
+                value: value.to_string(),
+                comment: None,
+            })
+            .collect();
+        flow
+    }
+
+    fn codes(flow: &Flow) -> Vec<String> {
+        caching_findings(flow).into_iter().map(|f| f.code).collect()
+    }
+
+    #[test]
+    fn test_flags_uncached_static_asset_and_missing_validator() {
+        let f = flow(
+            "https://cdn.test/logo.png",
+            "image/png",
+            &[("Cache-Control", "no-cache")],
+        );
+        assert_eq!(codes(&f), vec!["no_cache_static", "missing_validator"]);
+
+        let bare = flow(
+            "https://cdn.test/site.css",
+            "text/css",
+            &[("ETag", "\"a\"")],
+        );
+        assert_eq!(codes(&bare), vec!["no_cache_headers"]);
+    }
+
+    #[test]
+    fn test_short_max_age_on_fingerprinted_asset() {
+        let f = flow(
+            "https://cdn.test/app.3f9a1c2b.js",
+            "application/javascript",
+            &[("Cache-Control", "public, max-age=600"), ("ETag", "\"x\"")],
+        );
+        let findings = caching_findings(&f);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].code, "short_max_age_immutable");
+        assert_eq!(findings[0].max_age_secs, Some(600));
+
+        let good = flow(
+            "https://cdn.test/app.3f9a1c2b.js",
+            "application/javascript",
+            &[
+                ("Cache-Control", "public, max-age=31536000, immutable"),
+                ("ETag", "\"x\""),
+            ],
+        );
+        assert!(caching_findings(&good).is_empty());
+    }
+
+    #[test]
+    fn test_expires_lifetime_and_no_store() {
+        let f = flow(
+            "https://api.test/v1/items",
+            "application/json",
+            &[
+                ("Date", "Wed, 21 Oct 2015 07:28:00 GMT"),
+                ("Expires", "Wed, 21 Oct 2015 08:28:00 GMT"),
+                ("Last-Modified", "Tue, 20 Oct 2015 07:28:00 GMT"),
+            ],
+        );
+        assert!(caching_findings(&f).is_empty());
+        assert_eq!(expires_lifetime(&f.response.headers), Some(3600));
+
+        let private = flow(
+            "https://api.test/v1/me",
+            "application/json",
+            &[("Cache-Control", "no-store")],
+        );
+        assert!(caching_findings(&private).is_empty());
+    }
+}
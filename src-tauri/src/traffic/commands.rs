@@ -1,21 +1,72 @@
 use std::collections::HashMap;
 
 use base64::Engine;
+use chrono::{DateTime, FixedOffset};
 use futures_util::StreamExt;
 
+use super::csrf::{refresh_csrf_token, CsrfRefresh};
+use super::signing::{apply_replay_signing, ReplaySigning};
+use super::tokens::{apply_token_injection, TokenInjection};
+use crate::rules::matcher::matches_request;
+use crate::rules::model::RuleMatchConfig;
+use crate::session::model::Flow;
+
 /// Maximum response body size transferred over IPC (5 MB).
 /// Prevents large responses from serializing over the IPC bridge and freezing the UI.
 const MAX_BODY_BYTES: usize = 5 * 1024 * 1024;
 
-#[derive(serde::Deserialize)]
+/// Upper bound on replays in flight at once when sending several requests.
+pub const MAX_REPLAY_CONCURRENCY: usize = 8;
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 pub struct ReplayRequest {
     pub method: String,
     pub url: String,
     pub headers: HashMap<String, String>,
     pub body: Option<String>,
+    /// `body` is base64 and is decoded to raw bytes before sending
+    /// (protobuf, multipart uploads, images)
+    #[serde(default)]
+    pub body_is_base64: bool,
+    /// Recompute an HMAC signature header before sending
+    #[serde(default)]
+    pub signing: Option<ReplaySigning>,
+    /// Set a header from the token store before sending
+    #[serde(default)]
+    pub token: Option<TokenInjection>,
+    /// Fetch a fresh CSRF token and substitute it before sending
+    #[serde(default)]
+    pub csrf: Option<CsrfRefresh>,
+    /// Follow 3xx responses. Off by default so each hop can be inspected.
+    #[serde(default)]
+    pub follow_redirects: bool,
+    /// Redirects followed before giving up, when `follow_redirects` is set
+    #[serde(default = "default_max_redirects")]
+    pub max_redirects: usize,
 }
 
-#[derive(serde::Serialize)]
+fn default_max_redirects() -> usize {
+    10
+}
+
+impl Default for ReplayRequest {
+    fn default() -> Self {
+        Self {
+            method: "GET".to_string(),
+            url: String::new(),
+            headers: HashMap::new(),
+            body: None,
+            body_is_base64: false,
+            signing: None,
+            token: None,
+            csrf: None,
+            follow_redirects: false,
+            max_redirects: default_max_redirects(),
+        }
+    }
+}
+
+#[derive(serde::Serialize, Clone)]
 pub struct ReplayResponse {
     pub status: u16,
     pub headers: HashMap<String, String>,
@@ -23,13 +74,85 @@ pub struct ReplayResponse {
     pub encoding: String,   // "text" or "base64"
     pub truncated: bool,    // true if body was cut off at MAX_BODY_BYTES
     pub total_bytes: usize, // actual content-length or bytes read
+    /// Send until the last body byte was read
+    pub elapsed_ms: f64,
+    /// Send until response headers arrived. reqwest does not expose DNS or
+    /// connect timings, and replays go through the engine anyway.
+    pub ttfb_ms: f64,
+    /// URL of the final response when redirects were followed
+    pub final_url: Option<String>,
+}
+
+/// Whether a response with this content type should be returned as text.
+/// Without a content type, the body itself decides.
+fn is_text_response(content_type: &str, body: &[u8]) -> bool {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+    if mime.is_empty() {
+        return std::str::from_utf8(body).is_ok();
+    }
+    mime.starts_with("text/")
+        || mime.ends_with("+json")
+        || mime.ends_with("+xml")
+        || [
+            "json",
+            "xml",
+            "javascript",
+            "ecmascript",
+            "x-www-form-urlencoded",
+            "graphql",
+            "yaml",
+            "csv",
+        ]
+        .iter()
+        .any(|kind| mime.contains(kind))
+}
+
+/// Fetch a captured flow (with bodies) from the engine by ID.
+pub async fn fetch_flow(flow_id: &str) -> Result<Flow, String> {
+    let config = crate::config::load_config().unwrap_or_default();
+    let id: String = url::form_urlencoded::byte_serialize(flow_id.as_bytes()).collect();
+    let url = format!(
+        "http://127.0.0.1:{}/_relay/detail?id={}",
+        crate::proxy::engine_port(&config),
+        id
+    );
+
+    let response = reqwest::Client::new()
+        .get(&url)
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("engine_error: {}", e))?;
+
+    if response.status().as_u16() == 404 {
+        return Err(format!("Flow '{}' not found", flow_id));
+    }
+    if !response.status().is_success() {
+        return Err(format!("engine_error: HTTP {}", response.status().as_u16()));
+    }
+
+    response
+        .json::<Flow>()
+        .await
+        .map_err(|e| format!("Failed to parse flow: {}", e))
 }
 
 /// Core implementation, usable by both the Tauri command and the plugin bridge.
 pub async fn replay_request_inner(req: ReplayRequest) -> Result<ReplayResponse, String> {
+    let client = replay_client()?;
+    replay_with_client(&client, req).await
+}
+
+/// HTTP client that routes replays through the local engine.
+pub fn replay_client() -> Result<reqwest::Client, String> {
     // Load config to get the current proxy port
     let config = crate::config::load_config().unwrap_or_default();
-    let proxy_url = format!("http://127.0.0.1:{}", config.proxy_port);
+    let proxy_url = format!("http://127.0.0.1:{}", crate::proxy::engine_port(&config));
 
     let client_builder = reqwest::Client::builder()
         // TLS verification must be disabled here by design: all requests are routed through the
@@ -37,6 +160,8 @@ pub async fn replay_request_inner(req: ReplayRequest) -> Result<ReplayResponse,
         // Platform TLS verifiers reject these generated certs regardless of CA trust due to
         // additional compliance checks. This is safe because the connection target is always loopback.
         .danger_accept_invalid_certs(true)
+        // Redirects are followed per request in `replay_with_client`
+        .redirect(reqwest::redirect::Policy::none())
         .timeout(std::time::Duration::from_secs(30))
         .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
         .gzip(true)
@@ -44,32 +169,125 @@ pub async fn replay_request_inner(req: ReplayRequest) -> Result<ReplayResponse,
         .deflate(true);
 
     // Add proxy configuration
-    let client = match reqwest::Proxy::all(&proxy_url) {
-        Ok(proxy) => client_builder
-            .proxy(proxy)
-            .build()
-            .map_err(|e| e.to_string())?,
-        Err(_) => client_builder.build().map_err(|e| e.to_string())?,
-    };
+    match reqwest::Proxy::all(&proxy_url) {
+        Ok(proxy) => client_builder.proxy(proxy).build(),
+        Err(_) => client_builder.build(),
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// Next hop for a redirect response, or `None` when `response` is not one.
+/// Follows browser semantics: 303, and 301/302 after a POST, switch to a
+/// bodyless GET; credentials are dropped when the origin changes.
+fn redirect_target(
+    response: &reqwest::Response,
+    method: &mut reqwest::Method,
+    headers: &mut HashMap<String, String>,
+    body: &mut Option<Vec<u8>>,
+) -> Option<url::Url> {
+    let status = response.status().as_u16();
+    if !matches!(status, 301 | 302 | 303 | 307 | 308) {
+        return None;
+    }
+    let location = response.headers().get(reqwest::header::LOCATION)?;
+    let next = response.url().join(location.to_str().ok()?).ok()?;
 
+    if status == 303 || (matches!(status, 301 | 302) && *method == reqwest::Method::POST) {
+        if *method != reqwest::Method::HEAD {
+            *method = reqwest::Method::GET;
+        }
+        *body = None;
+        headers.retain(|k, _| !k.eq_ignore_ascii_case("content-type"));
+    }
+    if next.origin() != response.url().origin() {
+        headers.retain(|k, _| {
+            !k.eq_ignore_ascii_case("authorization") && !k.eq_ignore_ascii_case("cookie")
+        });
+    }
+    // Host must follow the new target
+    headers.retain(|k, _| !k.eq_ignore_ascii_case("host"));
+    Some(next)
+}
+
+/// Send a replay with the given client. The client must not follow
+/// redirects itself (see [`replay_client`]); that is decided per request.
+pub async fn replay_with_client(
+    client: &reqwest::Client,
+    req: ReplayRequest,
+) -> Result<ReplayResponse, String> {
     let method = reqwest::Method::from_bytes(req.method.as_bytes())
         .map_err(|_| "Invalid HTTP method".to_string())?;
 
-    let mut request_builder = client.request(method, &req.url);
-
-    for (key, value) in req.headers {
-        // Skip certain headers that might interfere
-        if key.to_lowercase() == "content-length" {
-            continue;
+    let mut headers = req.headers;
+    let mut body = req.body;
+    // Refresh CSRF and inject tokens first so a signature covers the fresh values
+    if let Some(csrf) = &req.csrf {
+        refresh_csrf_token(client, csrf, &mut headers, &mut body).await?;
+    }
+    if let Some(token) = &req.token {
+        apply_token_injection(token, &mut headers)?;
+    }
+    if let Some(signing) = &req.signing {
+        if req.body_is_base64 {
+            return Err("Request signing is not supported for base64 bodies".to_string());
         }
-        request_builder = request_builder.header(key, value);
+        apply_replay_signing(
+            signing,
+            &req.method,
+            &req.url,
+            &mut headers,
+            body.as_deref(),
+        )?;
     }
 
-    if let Some(body_content) = req.body {
-        request_builder = request_builder.body(body_content);
-    }
+    let mut body: Option<Vec<u8>> = match body {
+        Some(encoded) if req.body_is_base64 => Some(
+            base64::engine::general_purpose::STANDARD
+                .decode(encoded.trim())
+                .map_err(|e| format!("Invalid base64 body: {}", e))?,
+        ),
+        other => other.map(String::into_bytes),
+    };
+
+    let started = std::time::Instant::now();
+    let mut method = method;
+    let mut url = req.url.clone();
+    let mut redirects = 0;
+    let response = loop {
+        let mut request_builder = client.request(method.clone(), &url);
+
+        for (key, value) in &headers {
+            // Skip certain headers that might interfere
+            if key.to_lowercase() == "content-length" {
+                continue;
+            }
+            request_builder = request_builder.header(key, value);
+        }
 
-    let response = request_builder.send().await.map_err(|e| e.to_string())?;
+        if let Some(body_content) = &body {
+            request_builder = request_builder.body(body_content.clone());
+        }
+
+        let response = request_builder.send().await.map_err(|e| e.to_string())?;
+        if !req.follow_redirects {
+            break response;
+        }
+        match redirect_target(&response, &mut method, &mut headers, &mut body) {
+            Some(_) if redirects >= req.max_redirects => {
+                return Err(format!(
+                    "Too many redirects (stopped after {})",
+                    req.max_redirects
+                ));
+            }
+            Some(next) => {
+                redirects += 1;
+                url = next.to_string();
+            }
+            None => break response,
+        }
+    };
+    let ttfb_ms = started.elapsed().as_secs_f64() * 1000.0;
+    let final_url = req.follow_redirects.then(|| response.url().to_string());
 
     let status = response.status().as_u16();
     let mut headers = HashMap::new();
@@ -89,10 +307,7 @@ pub async fn replay_request_inner(req: ReplayRequest) -> Result<ReplayResponse,
         return Err("SSE stream responses are not supported by replay_request".to_string());
     }
 
-    let is_binary = content_type.starts_with("image/")
-        || content_type.starts_with("application/octet-stream")
-        || content_type.starts_with("video/")
-        || content_type.starts_with("audio/");
+    let content_length = response.content_length();
 
     // Stream body up to MAX_BODY_BYTES to avoid freezing the IPC bridge with huge payloads.
     let mut stream = response.bytes_stream();
@@ -110,9 +325,13 @@ pub async fn replay_request_inner(req: ReplayRequest) -> Result<ReplayResponse,
         buffer.extend_from_slice(&chunk);
     }
 
-    let total_bytes = buffer.len();
+    let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+    let total_bytes = match content_length {
+        Some(len) if truncated => len as usize,
+        _ => buffer.len(),
+    };
 
-    let (body, encoding) = if is_binary {
+    let (body, encoding) = if !is_text_response(&content_type, &buffer) {
         let encoded = base64::engine::general_purpose::STANDARD.encode(&buffer);
         (encoded, "base64".to_string())
     } else {
@@ -127,9 +346,113 @@ pub async fn replay_request_inner(req: ReplayRequest) -> Result<ReplayResponse,
         encoding,
         truncated,
         total_bytes,
+        elapsed_ms,
+        ttfb_ms,
+        final_url,
     })
 }
 
+/// Outcome of one replay sent through [`replay_concurrently`].
+pub struct TimedReplay {
+    pub result: Result<ReplayResponse, String>,
+    pub duration_ms: f64,
+}
+
+/// Replay several requests with at most `concurrency` in flight.
+/// Results are returned in the same order as `requests`.
+pub async fn replay_concurrently(
+    requests: Vec<ReplayRequest>,
+    concurrency: usize,
+) -> Vec<TimedReplay> {
+    match replay_client() {
+        Ok(client) => replay_concurrently_with(&client, requests, concurrency).await,
+        Err(e) => requests
+            .iter()
+            .map(|_| TimedReplay {
+                result: Err(e.clone()),
+                duration_ms: 0.0,
+            })
+            .collect(),
+    }
+}
+
+/// [`replay_concurrently`] over a shared client.
+pub async fn replay_concurrently_with(
+    client: &reqwest::Client,
+    requests: Vec<ReplayRequest>,
+    concurrency: usize,
+) -> Vec<TimedReplay> {
+    let concurrency = concurrency.clamp(1, MAX_REPLAY_CONCURRENCY);
+    futures_util::stream::iter(requests)
+        .map(|req| async move {
+            let started = std::time::Instant::now();
+            let result = replay_with_client(client, req).await;
+            TimedReplay {
+                result,
+                duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+            }
+        })
+        .buffered(concurrency)
+        .collect()
+        .await
+}
+
+/// One entry of a batch replay, at the same index as its request.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchReplayItem {
+    pub response: Option<ReplayResponse>,
+    pub error: Option<String>,
+    pub duration_ms: f64,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchReplayResult {
+    pub results: Vec<BatchReplayItem>,
+    /// Wall-clock time for the whole batch
+    pub total_ms: f64,
+}
+
+async fn replay_batch_with(
+    client: &reqwest::Client,
+    requests: Vec<ReplayRequest>,
+    concurrency: usize,
+) -> BatchReplayResult {
+    let started = std::time::Instant::now();
+    let results = replay_concurrently_with(client, requests, concurrency)
+        .await
+        .into_iter()
+        .map(|reply| {
+            let (response, error) = match reply.result {
+                Ok(r) => (Some(r), None),
+                Err(e) => (None, Some(e)),
+            };
+            BatchReplayItem {
+                response,
+                error,
+                duration_ms: reply.duration_ms,
+            }
+        })
+        .collect();
+    BatchReplayResult {
+        results,
+        total_ms: started.elapsed().as_secs_f64() * 1000.0,
+    }
+}
+
+/// Resend several requests through the engine. `concurrency` 1 sends them in
+/// order; higher values run up to that many at once (capped at
+/// [`MAX_REPLAY_CONCURRENCY`]). A failing request does not abort the batch.
+#[tauri::command]
+pub async fn replay_batch(
+    requests: Vec<ReplayRequest>,
+    concurrency: usize,
+) -> Result<BatchReplayResult, String> {
+    let client = replay_client()?;
+    Ok(replay_batch_with(&client, requests, concurrency).await)
+}
+
 /// Tauri command wrapper — delegates to the shared inner implementation.
 #[tauri::command]
 pub async fn replay_request(req: ReplayRequest) -> Result<ReplayResponse, String> {
@@ -153,7 +476,10 @@ pub struct WsResendRequest {
 #[tauri::command]
 pub async fn ws_inject_frame(req: WsResendRequest) -> Result<(), String> {
     let config = crate::config::load_config().unwrap_or_default();
-    let target = format!("http://127.0.0.1:{}/_relay/ws/inject", config.proxy_port);
+    let target = format!(
+        "http://127.0.0.1:{}/_relay/ws/inject",
+        crate::proxy::engine_port(&config)
+    );
 
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(10))
@@ -235,3 +561,294 @@ pub async fn check_proxy_connectivity(proxy_url: String) -> Result<String, Strin
         }
     }
 }
+
+fn parse_rfc3339(value: &str) -> Result<DateTime<FixedOffset>, String> {
+    DateTime::parse_from_rfc3339(value.trim())
+        .map_err(|e| format!("Invalid RFC3339 timestamp '{}': {}", value, e))
+}
+
+/// Keep flows whose `started_date_time` falls within `[start, end]` (inclusive).
+/// Flows with a missing or unparseable timestamp are dropped.
+pub fn filter_flows_by_time_range(
+    flows: Vec<Flow>,
+    start_iso: &str,
+    end_iso: &str,
+) -> Result<Vec<Flow>, String> {
+    let start = parse_rfc3339(start_iso)?;
+    let end = parse_rfc3339(end_iso)?;
+    if start > end {
+        return Err("Start of time range is after its end".to_string());
+    }
+
+    Ok(flows
+        .into_iter()
+        .filter(|flow| {
+            DateTime::parse_from_rfc3339(&flow.started_date_time)
+                .map(|ts| ts >= start && ts <= end)
+                .unwrap_or(false)
+        })
+        .collect())
+}
+
+/// Return only the flows that started within the given time window.
+#[tauri::command]
+pub fn filter_by_time_range(
+    flows: Vec<Flow>,
+    start_iso: String,
+    end_iso: String,
+) -> Result<Vec<Flow>, String> {
+    filter_flows_by_time_range(flows, &start_iso, &end_iso)
+}
+
+/// IDs of the flows whose request matches the `match` block of a rule.
+/// `rule_json` may be a full rule or a draft from the editor.
+#[tauri::command]
+pub fn flows_matching_rule(rule_json: String, flows: Vec<Flow>) -> Result<Vec<String>, String> {
+    let rule: serde_json::Value =
+        serde_json::from_str(&rule_json).map_err(|e| format!("Invalid rule JSON: {}", e))?;
+    let match_config: RuleMatchConfig = match rule.get("match") {
+        Some(m) => serde_json::from_value(m.clone())
+            .map_err(|e| format!("Invalid rule match config: {}", e))?,
+        None => return Err("Rule has no 'match' section".to_string()),
+    };
+
+    Ok(flows
+        .iter()
+        .filter(|flow| matches_request(flow, &match_config))
+        .map(|flow| flow.id.clone())
+        .collect())
+}
+
+/// Export only the flows within the given time window as a HAR file.
+#[tauri::command]
+pub async fn export_har_time_range(
+    path: String,
+    flows: Vec<Flow>,
+    start_iso: String,
+    end_iso: String,
+) -> Result<usize, String> {
+    let flows = filter_flows_by_time_range(flows, &start_iso, &end_iso)?;
+    let count = flows.len();
+    crate::session::har::export_har(path, flows).await?;
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traffic::test_server::{response, serve};
+
+    fn get(url: &str) -> ReplayRequest {
+        ReplayRequest {
+            url: url.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    #[test]
+    fn test_is_text_response() {
+        assert!(is_text_response("text/html; charset=utf-8", b""));
+        assert!(is_text_response("application/problem+json", b""));
+        assert!(is_text_response("application/x-www-form-urlencoded", b""));
+        assert!(!is_text_response("application/pdf", b"%PDF"));
+        assert!(!is_text_response("application/protobuf", b"\x08\x01"));
+        assert!(is_text_response("", b"plain"));
+        assert!(!is_text_response("", &[0xff, 0xfe, 0x00]));
+    }
+
+    #[test]
+    fn test_replay_reports_timing_and_round_trips_binary() {
+        let body: Vec<u8> = vec![0x89, b'P', b'N', b'G', 0x00, 0xff, 0xfe, 0x10];
+        let served = body.clone();
+        let server = serve(1, move |_| {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            response(
+                "200 OK",
+                "Content-Type: application/x-custom-binary\r\n",
+                &served,
+            )
+        });
+
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+        let response = block_on(replay_with_client(&client, get(&server.url("/logo")))).unwrap();
+        assert_eq!(response.encoding, "base64");
+        assert_eq!(
+            base64::engine::general_purpose::STANDARD
+                .decode(&response.body)
+                .unwrap(),
+            body
+        );
+        assert_eq!(response.total_bytes, body.len());
+        assert!(response.elapsed_ms >= 20.0);
+        assert!(response.ttfb_ms > 0.0 && response.ttfb_ms <= response.elapsed_ms);
+    }
+
+    /// Serve one redirect from `/start` to `/end`, then the final page.
+    fn redirect_server(connections: usize) -> String {
+        serve(connections, |req| {
+            if req.method == "GET" && req.path == "/start" {
+                response("302 Found", "Location: /end\r\n", b"")
+            } else {
+                response("200 OK", "Content-Type: text/plain\r\n", b"done")
+            }
+        })
+        .base
+    }
+
+    #[test]
+    fn test_replay_redirect_modes() {
+        let client = reqwest::Client::builder()
+            .no_proxy()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap();
+
+        // Default: the 3xx itself comes back
+        let base = redirect_server(1);
+        let hop = block_on(replay_with_client(&client, get(&format!("{}/start", base)))).unwrap();
+        assert_eq!(hop.status, 302);
+        assert_eq!(
+            hop.headers.get("location").map(String::as_str),
+            Some("/end")
+        );
+        assert_eq!(hop.final_url, None);
+
+        let base = redirect_server(2);
+        let mut req = get(&format!("{}/start", base));
+        req.follow_redirects = true;
+        let followed = block_on(replay_with_client(&client, req)).unwrap();
+        assert_eq!(followed.status, 200);
+        assert_eq!(followed.body, "done");
+        assert_eq!(followed.final_url, Some(format!("{}/end", base)));
+
+        let base = redirect_server(1);
+        let mut req = get(&format!("{}/start", base));
+        req.follow_redirects = true;
+        req.max_redirects = 0;
+        assert!(block_on(replay_with_client(&client, req)).is_err());
+    }
+
+    #[test]
+    fn test_replay_sends_base64_body_byte_exact() {
+        let server = serve(1, |_| response("204 No Content", "", b""));
+
+        let payload: Vec<u8> = vec![0x0a, 0x03, 0xff, 0x00, 0xfe, 0x12, 0x80];
+        let mut req = get(&server.url("/upload"));
+        req.method = "POST".to_string();
+        req.headers
+            .insert("Content-Type".into(), "application/x-protobuf".into());
+        req.headers.insert("Content-Length".into(), "999".into());
+        req.body = Some(base64::engine::general_purpose::STANDARD.encode(&payload));
+        req.body_is_base64 = true;
+
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+        let response = block_on(replay_with_client(&client, req)).unwrap();
+        assert_eq!(response.status, 204);
+        let received = server.requests();
+        assert_eq!(received[0].method, "POST");
+        assert!(received[0].head.contains("content-length: 7\r\n"));
+        assert_eq!(received[0].body, payload);
+
+        let mut bad = get("http://127.0.0.1:1/");
+        bad.body = Some("not base64!".into());
+        bad.body_is_base64 = true;
+        assert!(block_on(replay_with_client(&client, bad))
+            .unwrap_err()
+            .contains("Invalid base64"));
+    }
+
+    #[test]
+    fn test_replay_batch_keeps_order_and_isolates_failures() {
+        let base = serve(3, |req| {
+            response(
+                "200 OK",
+                "Content-Type: text/plain\r\n",
+                req.path.as_bytes(),
+            )
+        })
+        .base;
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+        let requests = vec![
+            get(&format!("{}/one", base)),
+            get("not a url"),
+            get(&format!("{}/two", base)),
+            get(&format!("{}/three", base)),
+        ];
+
+        let batch = block_on(replay_batch_with(&client, requests, 2));
+        assert_eq!(batch.results.len(), 4);
+        let bodies: Vec<Option<&str>> = batch
+            .results
+            .iter()
+            .map(|item| item.response.as_ref().map(|r| r.body.as_str()))
+            .collect();
+        assert_eq!(
+            bodies,
+            vec![Some("/one"), None, Some("/two"), Some("/three")]
+        );
+        assert!(batch.results[1].error.is_some());
+        assert!(batch.total_ms >= batch.results[0].duration_ms);
+    }
+
+    fn flow_at(id: &str, started: &str) -> Flow {
+        Flow {
+            id: id.to_string(),
+            started_date_time: started.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_filter_by_time_range_is_inclusive() {
+        let flows = vec![
+            flow_at("before", "2024-01-01T09:59:59Z"),
+            flow_at("start", "2024-01-01T10:00:00Z"),
+            flow_at("offset", "2024-01-01T12:30:00+02:00"),
+            flow_at("end", "2024-01-01T11:00:00.000Z"),
+            flow_at("after", "2024-01-01T11:00:01Z"),
+            flow_at("broken", ""),
+        ];
+
+        let ids: Vec<String> =
+            filter_flows_by_time_range(flows, "2024-01-01T10:00:00Z", "2024-01-01T11:00:00Z")
+                .unwrap()
+                .into_iter()
+                .map(|f| f.id)
+                .collect();
+
+        assert_eq!(ids, vec!["start", "offset", "end"]);
+    }
+
+    #[test]
+    fn test_flows_matching_rule_uses_match_section() {
+        let mut api = flow_at("api", "");
+        api.request.url = "https://api.test/v1/users".to_string();
+        let mut cdn = flow_at("cdn", "");
+        cdn.request.url = "https://cdn.test/app.js".to_string();
+
+        let rule = r#"{"name": "draft", "match": {"request": [
+            {"type": "host", "matchType": "wildcard", "value": "api.*"}
+        ]}}"#;
+        let ids = flows_matching_rule(rule.to_string(), vec![api, cdn]).unwrap();
+        assert_eq!(ids, vec!["api"]);
+
+        assert!(flows_matching_rule("{}".to_string(), vec![]).is_err());
+    }
+
+    #[test]
+    fn test_filter_by_time_range_rejects_bad_bounds() {
+        assert!(filter_flows_by_time_range(vec![], "yesterday", "2024-01-01T11:00:00Z").is_err());
+        assert!(
+            filter_flows_by_time_range(vec![], "2024-01-02T00:00:00Z", "2024-01-01T00:00:00Z")
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//! Grouping of captured flows into logical transactions by correlation id.
+//!
+//! Distributed systems propagate a request or trace id across every hop of one
+//! operation. Flows carrying the same id are grouped and ordered by start time.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::session::model::{Flow, HarHeader};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionStep {
+    pub flow_id: String,
+    pub started_date_time: String,
+    pub method: String,
+    pub url: String,
+    pub status: i32,
+    pub time: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transaction {
+    pub correlation_id: String,
+    /// Header the id was taken from, as given by the caller
+    pub header: String,
+    pub steps: Vec<TransactionStep>,
+    /// Milliseconds from the first request start to the last response end
+    pub duration_ms: f64,
+}
+
+/// Extract the trace id from a W3C `traceparent` value
+/// (`version-traceid-parentid-flags`).
+fn traceparent_trace_id(value: &str) -> Option<&str> {
+    let mut parts = value.trim().split('-');
+    let _version = parts.next()?;
+    let trace_id = parts.next()?;
+    if trace_id.len() == 32 && trace_id.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(trace_id)
+    } else {
+        None
+    }
+}
+
+fn correlation_value(headers: &[HarHeader], name: &str) -> Option<String> {
+    let value = headers
+        .iter()
+        .find(|h| h.name.eq_ignore_ascii_case(name))
+        .map(|h| h.value.trim())
+        .filter(|v| !v.is_empty())?;
+
+    if name.eq_ignore_ascii_case("traceparent") {
+        traceparent_trace_id(value).map(|id| id.to_ascii_lowercase())
+    } else {
+        Some(value.to_string())
+    }
+}
+
+fn start_millis(flow: &Flow) -> Option<i64> {
+    chrono::DateTime::parse_from_rfc3339(&flow.started_date_time)
+        .ok()
+        .map(|t| t.timestamp_millis())
+}
+
+/// Group flows sharing a correlation id. For each flow the first header in
+/// `header_names` that is present on the request decides its group.
+pub fn group_flows_by_correlation(flows: &[Flow], header_names: &[String]) -> Vec<Transaction> {
+    let mut groups: HashMap<String, (String, Vec<&Flow>)> = HashMap::new();
+
+    for flow in flows {
+        let found = header_names.iter().find_map(|name| {
+            correlation_value(&flow.request.headers, name).map(|value| (name, value))
+        });
+        if let Some((name, value)) = found {
+            groups
+                .entry(value)
+                .or_insert_with(|| (name.clone(), Vec::new()))
+                .1
+                .push(flow);
+        }
+    }
+
+    let mut transactions: Vec<(Option<i64>, Transaction)> = groups
+        .into_iter()
+        .map(|(correlation_id, (header, mut members))| {
+            members.sort_by_key(|f| (start_millis(f), f.started_date_time.clone()));
+
+            let first_start = members.iter().filter_map(|f| start_millis(f)).min();
+            let last_end = members
+                .iter()
+                .filter_map(|f| start_millis(f).map(|s| s as f64 + f.time.max(0.0)))
+                .fold(None, |acc: Option<f64>, end| {
+                    Some(acc.map_or(end, |a| a.max(end)))
+                });
+            let duration_ms = match (first_start, last_end) {
+                (Some(start), Some(end)) => (end - start as f64).max(0.0),
+                _ => 0.0,
+            };
+
+            let steps = members
+                .iter()
+                .map(|f| TransactionStep {
+                    flow_id: f.id.clone(),
+                    started_date_time: f.started_date_time.clone(),
+                    method: f.request.method.clone(),
+                    url: f.request.url.clone(),
+                    status: f.response.status,
+                    time: f.time,
+                })
+                .collect();
+
+            (
+                first_start,
+                Transaction {
+                    correlation_id,
+                    header,
+                    steps,
+                    duration_ms,
+                },
+            )
+        })
+        .collect();
+
+    transactions.sort_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then_with(|| a.1.correlation_id.cmp(&b.1.correlation_id))
+    });
+    transactions.into_iter().map(|(_, t)| t).collect()
+}
+
+/// Group flows into transactions by the given correlation headers
+#[tauri::command]
+pub fn group_by_correlation(flows: Vec<Flow>, header_names: Vec<String>) -> Vec<Transaction> {
+    group_flows_by_correlation(&flows, &header_names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flow(id: &str, started: &str, header: (&str, &str)) -> Flow {
+        let mut flow = Flow {
+            id: id.to_string(),
+            started_date_time: started.to_string(),
+            time: 10.0,
+            ..Default::default()
+        };
+        flow.request.headers = vec![HarHeader {
+            name: header.0.to_string(),
+            value: header.1.to_string(),
+            comment: None,
+        }];
+        flow
+    }
+
+    #[test]
+    fn test_group_by_correlation_orders_by_time() {
+        let trace = "4bf92f3577b34da6a3ce929d0e0e4736";
+        let flows = vec![
+            flow("b", "2024-01-01T00:00:01.000Z", ("x-request-id", "req-1")),
+            flow("a", "2024-01-01T00:00:00.000Z", ("X-Request-Id", "req-1")),
+            flow(
+                "c",
+                "2024-01-01T00:00:05.000Z",
+                ("traceparent", &format!("00-{}-00f067aa0ba902b7-01", trace)),
+            ),
+            flow(
+                "d",
+                "2024-01-01T00:00:06.000Z",
+                ("Traceparent", &format!("00-{}-b7ad6b7169203331-01", trace)),
+            ),
+            flow("e", "2024-01-01T00:00:07.000Z", ("x-other", "ignored")),
+        ];
+
+        let groups = group_flows_by_correlation(
+            &flows,
+            &["X-Request-Id".to_string(), "traceparent".to_string()],
+        );
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].correlation_id, "req-1");
+        let ids: Vec<&str> = groups[0].steps.iter().map(|s| s.flow_id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b"]);
+        assert_eq!(groups[0].duration_ms, 1010.0);
+
+        assert_eq!(groups[1].correlation_id, trace);
+        assert_eq!(groups[1].header, "traceparent");
+        assert_eq!(groups[1].steps.len(), 2);
+    }
+}
@@ -0,0 +1,340 @@
+//! Fresh CSRF tokens for replayed requests.
+//!
+//! Before a replay is sent, a "token source" URL is fetched with the same
+//! session cookies, a token is extracted from its response and substituted
+//! into the replayed request's header and/or body.
+
+use std::collections::HashMap;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Where to find the token in the source response
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "from", rename_all = "camelCase")]
+pub enum CsrfSelector {
+    /// Value of a response header
+    Header { name: String },
+    /// Value of a cookie set by the response
+    Cookie { name: String },
+    /// Dotted path into a JSON body, e.g. `data.csrfToken`
+    Json { path: String },
+    /// First capture group of a regex over the body (HTML meta tags, hidden inputs)
+    Pattern { regex: String },
+}
+
+/// Fetch a fresh CSRF token before replaying
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CsrfRefresh {
+    /// URL whose response carries a fresh token (usually the form page)
+    pub source_url: String,
+    pub selector: CsrfSelector,
+    /// Header to set on the replayed request, e.g. `X-CSRF-Token`
+    #[serde(default)]
+    pub target_header: Option<String>,
+    /// Form or top-level JSON field of the replayed body to overwrite
+    #[serde(default)]
+    pub body_field: Option<String>,
+    /// Merge cookies set by the source response into the replay's Cookie
+    /// header, for frameworks that pair the token with a cookie
+    #[serde(default = "default_true")]
+    pub sync_cookies: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Response of the token source, reduced to what extraction needs
+pub struct CsrfSourceResponse {
+    /// Lowercased header names; repeated headers keep every value
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl CsrfSourceResponse {
+    fn set_cookies(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.headers
+            .iter()
+            .filter(|(name, _)| name == "set-cookie")
+            .filter_map(|(_, value)| {
+                let pair = value.split(';').next()?;
+                let (name, value) = pair.split_once('=')?;
+                Some((name.trim(), value.trim()))
+            })
+    }
+}
+
+/// Pull the token out of the source response
+pub fn extract_csrf_token(
+    selector: &CsrfSelector,
+    response: &CsrfSourceResponse,
+) -> Result<String, String> {
+    let token = match selector {
+        CsrfSelector::Header { name } => {
+            let name = name.to_ascii_lowercase();
+            response
+                .headers
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, v)| v.clone())
+        }
+        CsrfSelector::Cookie { name } => response
+            .set_cookies()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.to_string()),
+        CsrfSelector::Json { path } => {
+            let json: serde_json::Value = serde_json::from_str(&response.body)
+                .map_err(|e| format!("Token source did not return JSON: {}", e))?;
+            path.split('.')
+                .filter(|p| !p.is_empty())
+                .try_fold(&json, |value, key| value.get(key))
+                .and_then(|value| match value {
+                    serde_json::Value::String(s) => Some(s.clone()),
+                    serde_json::Value::Null => None,
+                    other => Some(other.to_string()),
+                })
+        }
+        CsrfSelector::Pattern { regex } => {
+            let re = Regex::new(regex).map_err(|e| format!("Invalid token pattern: {}", e))?;
+            re.captures(&response.body).and_then(|caps| {
+                caps.get(1)
+                    .or_else(|| caps.get(0))
+                    .map(|m| m.as_str().to_string())
+            })
+        }
+    };
+
+    token
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| "No CSRF token found in the token source response".to_string())
+}
+
+/// Overwrite `field` in a JSON object or form-encoded body
+fn replace_body_field(body: &str, field: &str, token: &str) -> Result<String, String> {
+    if let Ok(serde_json::Value::Object(mut map)) = serde_json::from_str(body) {
+        map.insert(
+            field.to_string(),
+            serde_json::Value::String(token.to_string()),
+        );
+        return serde_json::to_string(&map).map_err(|e| e.to_string());
+    }
+
+    let mut replaced = false;
+    let pairs: Vec<(String, String)> = url::form_urlencoded::parse(body.as_bytes())
+        .map(|(k, v)| {
+            if k == field {
+                replaced = true;
+                (k.into_owned(), token.to_string())
+            } else {
+                (k.into_owned(), v.into_owned())
+            }
+        })
+        .collect();
+    if !replaced {
+        return Err(format!("Field '{}' not found in request body", field));
+    }
+    Ok(url::form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(pairs)
+        .finish())
+}
+
+/// Merge `name=value` pairs into a Cookie header value, replacing same-named ones
+fn merge_cookies<'a>(existing: &str, updates: impl Iterator<Item = (&'a str, &'a str)>) -> String {
+    let mut cookies: Vec<(String, String)> = existing
+        .split(';')
+        .filter_map(|pair| {
+            let (name, value) = pair.split_once('=')?;
+            Some((name.trim().to_string(), value.trim().to_string()))
+        })
+        .collect();
+    for (name, value) in updates {
+        match cookies.iter_mut().find(|(n, _)| n == name) {
+            Some(cookie) => cookie.1 = value.to_string(),
+            None => cookies.push((name.to_string(), value.to_string())),
+        }
+    }
+    cookies
+        .iter()
+        .map(|(n, v)| format!("{}={}", n, v))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Substitute the token (and optionally cookies) into the replayed request
+pub fn apply_csrf_token(
+    refresh: &CsrfRefresh,
+    token: &str,
+    source: &CsrfSourceResponse,
+    headers: &mut HashMap<String, String>,
+    body: &mut Option<String>,
+) -> Result<(), String> {
+    if refresh.target_header.is_none() && refresh.body_field.is_none() {
+        return Err("CSRF refresh needs a target header or body field".to_string());
+    }
+
+    if let Some(header) = &refresh.target_header {
+        headers.retain(|k, _| !k.eq_ignore_ascii_case(header));
+        headers.insert(header.clone(), token.to_string());
+    }
+
+    if let Some(field) = &refresh.body_field {
+        let current = body.as_deref().unwrap_or("");
+        *body = Some(replace_body_field(current, field, token)?);
+    }
+
+    if refresh.sync_cookies && source.set_cookies().next().is_some() {
+        let existing_key = headers
+            .keys()
+            .find(|k| k.eq_ignore_ascii_case("cookie"))
+            .cloned();
+        let existing = existing_key
+            .as_ref()
+            .and_then(|k| headers.remove(k))
+            .unwrap_or_default();
+        headers.insert(
+            existing_key.unwrap_or_else(|| "Cookie".to_string()),
+            merge_cookies(&existing, source.set_cookies()),
+        );
+    }
+    Ok(())
+}
+
+/// Fetch the token source with the replay's session headers, then apply the token
+pub async fn refresh_csrf_token(
+    client: &reqwest::Client,
+    refresh: &CsrfRefresh,
+    headers: &mut HashMap<String, String>,
+    body: &mut Option<String>,
+) -> Result<(), String> {
+    let mut request = client.get(&refresh.source_url);
+    for (key, value) in headers.iter() {
+        if key.eq_ignore_ascii_case("cookie") || key.eq_ignore_ascii_case("authorization") {
+            request = request.header(key, value);
+        }
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch CSRF token source: {}", e))?;
+    let source = CsrfSourceResponse {
+        headers: response
+            .headers()
+            .iter()
+            .filter_map(|(k, v)| Some((k.as_str().to_string(), v.to_str().ok()?.to_string())))
+            .collect(),
+        body: response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read CSRF token source: {}", e))?,
+    };
+
+    let token = extract_csrf_token(&refresh.selector, &source)?;
+    apply_csrf_token(refresh, &token, &source, headers, body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(headers: &[(&str, &str)], body: &str) -> CsrfSourceResponse {
+        CsrfSourceResponse {
+            headers: headers
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            body: body.to_string(),
+        }
+    }
+
+    fn refresh(selector: CsrfSelector) -> CsrfRefresh {
+        CsrfRefresh {
+            source_url: "https://app.test/form".to_string(),
+            selector,
+            target_header: Some("X-CSRF-Token".to_string()),
+            body_field: None,
+            sync_cookies: true,
+        }
+    }
+
+    #[test]
+    fn test_extract_from_each_selector() {
+        let resp = source(
+            &[
+                ("x-csrf-token", "hdr"),
+                ("set-cookie", "csrftoken=ck; Path=/; HttpOnly"),
+            ],
+            r#"<meta name="csrf-token" content="meta-tok">"#,
+        );
+        let header = CsrfSelector::Header {
+            name: "X-CSRF-Token".to_string(),
+        };
+        let cookie = CsrfSelector::Cookie {
+            name: "csrftoken".to_string(),
+        };
+        let pattern = CsrfSelector::Pattern {
+            regex: r#"name="csrf-token" content="([^"]+)""#.to_string(),
+        };
+        assert_eq!(extract_csrf_token(&header, &resp).unwrap(), "hdr");
+        assert_eq!(extract_csrf_token(&cookie, &resp).unwrap(), "ck");
+        assert_eq!(extract_csrf_token(&pattern, &resp).unwrap(), "meta-tok");
+
+        let json = source(&[], r#"{"data": {"csrf": "json-tok"}}"#);
+        let path = CsrfSelector::Json {
+            path: "data.csrf".to_string(),
+        };
+        assert_eq!(extract_csrf_token(&path, &json).unwrap(), "json-tok");
+
+        let missing = CsrfSelector::Json {
+            path: "data.nope".to_string(),
+        };
+        assert!(extract_csrf_token(&missing, &json).is_err());
+    }
+
+    #[test]
+    fn test_apply_to_header_form_body_and_cookies() {
+        let mut cfg = refresh(CsrfSelector::Cookie {
+            name: "csrftoken".to_string(),
+        });
+        cfg.body_field = Some("csrfmiddlewaretoken".to_string());
+        let resp = source(&[("set-cookie", "csrftoken=new; Path=/")], "");
+
+        let mut headers = HashMap::from([
+            ("x-csrf-token".to_string(), "stale".to_string()),
+            (
+                "cookie".to_string(),
+                "sessionid=s1; csrftoken=old".to_string(),
+            ),
+        ]);
+        let mut body = Some("csrfmiddlewaretoken=old&name=a+b".to_string());
+
+        apply_csrf_token(&cfg, "new", &resp, &mut headers, &mut body).unwrap();
+
+        assert_eq!(headers.get("X-CSRF-Token").map(String::as_str), Some("new"));
+        assert!(!headers.contains_key("x-csrf-token"));
+        assert_eq!(
+            headers.get("cookie").map(String::as_str),
+            Some("sessionid=s1; csrftoken=new")
+        );
+        assert_eq!(body.as_deref(), Some("csrfmiddlewaretoken=new&name=a+b"));
+    }
+
+    #[test]
+    fn test_apply_to_json_body() {
+        let mut cfg = refresh(CsrfSelector::Header {
+            name: "x-token".to_string(),
+        });
+        cfg.target_header = None;
+        cfg.body_field = Some("_token".to_string());
+        let mut headers = HashMap::new();
+        let mut body = Some(r#"{"_token": "old", "qty": 2}"#.to_string());
+
+        apply_csrf_token(&cfg, "fresh", &source(&[], ""), &mut headers, &mut body).unwrap();
+
+        let value: serde_json::Value = serde_json::from_str(body.as_deref().unwrap()).unwrap();
+        assert_eq!(value["_token"], "fresh");
+        assert_eq!(value["qty"], 2);
+    }
+}
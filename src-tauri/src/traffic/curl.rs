@@ -0,0 +1,587 @@
+//! Parsing `curl` command lines (as copied from browser devtools) into
+//! replay requests, and rendering captured flows back into `curl` commands.
+
+use std::collections::{HashMap, VecDeque};
+
+use base64::Engine;
+
+use super::commands::ReplayRequest;
+use crate::session::http_file::{replayable_headers, request_body_text};
+use crate::session::model::Flow;
+
+/// Options that take a value we do not use
+const IGNORED_VALUE_OPTIONS: &[&str] = &[
+    "-o",
+    "--output",
+    "--output-dir",
+    "-m",
+    "--max-time",
+    "--connect-timeout",
+    "--expect100-timeout",
+    "--keepalive-time",
+    "--happy-eyeballs-timeout-ms",
+    "--speed-limit",
+    "-y",
+    "--speed-time",
+    "-Y",
+    "--max-filesize",
+    "--limit-rate",
+    "--retry",
+    "--retry-delay",
+    "--retry-max-time",
+    "-x",
+    "--proxy",
+    "-U",
+    "--proxy-user",
+    "--proxy-header",
+    "--preproxy",
+    "--noproxy",
+    "--proxy-cacert",
+    "--cacert",
+    "--capath",
+    "-E",
+    "--cert",
+    "--cert-type",
+    "--key",
+    "--key-type",
+    "--pass",
+    "--ciphers",
+    "--tls-max",
+    "--pinnedpubkey",
+    "--resolve",
+    "--connect-to",
+    "--dns-servers",
+    "--doh-url",
+    "--interface",
+    "--local-port",
+    "--unix-socket",
+    "--abstract-unix-socket",
+    "-c",
+    "--cookie-jar",
+    "-D",
+    "--dump-header",
+    "-w",
+    "--write-out",
+    "--trace",
+    "--trace-ascii",
+    "--stderr",
+    "-K",
+    "--config",
+    "--netrc-file",
+    "-r",
+    "--range",
+    "-C",
+    "--continue-at",
+    "-z",
+    "--time-cond",
+    "--alt-svc",
+    "--hsts",
+    "--etag-save",
+    "--etag-compare",
+    "--proto",
+    "--proto-redir",
+    "-P",
+    "--ftp-port",
+    "-Q",
+    "--quote",
+    "-t",
+    "--telnet-option",
+];
+
+/// Short options that take a value, which ends a cluster such as `-sXPOST`
+const SHORT_VALUE_OPTIONS: &[char] = &[
+    'A', 'b', 'c', 'C', 'd', 'D', 'e', 'E', 'F', 'H', 'K', 'm', 'o', 'P', 'Q', 'r', 't', 'T', 'u',
+    'U', 'w', 'x', 'X', 'y', 'Y', 'z',
+];
+
+/// Split a shell command line into words, honouring single quotes, double
+/// quotes, `$'...'` ANSI-C quotes, backslash escapes and `\` line continuations.
+fn shell_words(input: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                // Line continuation
+                Some('\n') => {}
+                Some('\r') if chars.peek() == Some(&'\n') => {
+                    chars.next();
+                }
+                Some(next) => {
+                    current.push(next);
+                    in_word = true;
+                }
+                None => {}
+            },
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(ch) => current.push(ch),
+                        None => return Err("Unterminated single quote".to_string()),
+                    }
+                }
+            }
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => current.push('\n'),
+                            Some('r') => current.push('\r'),
+                            Some('t') => current.push('\t'),
+                            Some('0') => current.push('\0'),
+                            Some('x') => {
+                                let hex: String = (0..2).filter_map(|_| chars.next()).collect();
+                                let byte = u8::from_str_radix(&hex, 16)
+                                    .map_err(|_| format!("Invalid \\x escape: {}", hex))?;
+                                current.push(byte as char);
+                            }
+                            Some('u') => {
+                                let hex: String = (0..4).filter_map(|_| chars.next()).collect();
+                                let ch = u32::from_str_radix(&hex, 16)
+                                    .ok()
+                                    .and_then(char::from_u32)
+                                    .ok_or_else(|| format!("Invalid \\u escape: {}", hex))?;
+                                current.push(ch);
+                            }
+                            Some(other) => current.push(other),
+                            None => return Err("Unterminated $' quote".to_string()),
+                        },
+                        Some(ch) => current.push(ch),
+                        None => return Err("Unterminated $' quote".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('\n') => {}
+                            Some(ch @ ('"' | '\\' | '$' | '`')) => current.push(ch),
+                            Some(ch) => {
+                                current.push('\\');
+                                current.push(ch);
+                            }
+                            None => return Err("Unterminated double quote".to_string()),
+                        },
+                        Some(ch) => current.push(ch),
+                        None => return Err("Unterminated double quote".to_string()),
+                    }
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            c => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(current);
+    }
+    Ok(words)
+}
+
+/// Split a word of clustered short options into separate words: `-sSL`
+/// becomes `-s -S -L` and `-sXPOST` becomes `-s -X POST`. Returns `None`
+/// for long options and lone short flags.
+fn expand_short_options(word: &str) -> Option<Vec<String>> {
+    let cluster = word.strip_prefix('-')?;
+    if cluster.starts_with('-') || cluster.chars().count() < 2 {
+        return None;
+    }
+    let mut words = Vec::new();
+    for (i, c) in cluster.char_indices() {
+        words.push(format!("-{}", c));
+        if SHORT_VALUE_OPTIONS.contains(&c) {
+            let value = &cluster[i + c.len_utf8()..];
+            if !value.is_empty() {
+                words.push(value.to_string());
+            }
+            break;
+        }
+    }
+    Some(words)
+}
+
+/// Encode one `--data-urlencode` argument the way curl does: `content` and
+/// `=content` encode the whole content, `name=content` only the content.
+fn data_urlencode(arg: &str) -> Result<String, String> {
+    let encode =
+        |text: &str| -> String { url::form_urlencoded::byte_serialize(text.as_bytes()).collect() };
+    match arg.find(['=', '@']) {
+        Some(i) if arg[i..].starts_with('@') => Err(format!(
+            "Reading the body from a file ({}) is not supported",
+            arg
+        )),
+        Some(0) => Ok(encode(&arg[1..])),
+        Some(i) => Ok(format!("{}={}", &arg[..i], encode(&arg[i + 1..]))),
+        None => Ok(encode(arg)),
+    }
+}
+
+fn set_header(headers: &mut HashMap<String, String>, name: &str, value: &str) {
+    headers.retain(|k, _| !k.eq_ignore_ascii_case(name));
+    headers.insert(name.to_string(), value.to_string());
+}
+
+fn has_header(headers: &HashMap<String, String>, name: &str) -> bool {
+    headers.keys().any(|k| k.eq_ignore_ascii_case(name))
+}
+
+/// Parse a `curl ...` command into a [`ReplayRequest`].
+pub fn parse_curl_command(command: &str) -> Result<ReplayRequest, String> {
+    let mut args: VecDeque<String> = shell_words(command.trim())?.into();
+    match args.pop_front() {
+        Some(first) if first == "curl" || first.ends_with("/curl") || first == "curl.exe" => {}
+        _ => return Err("Not a curl command".to_string()),
+    }
+
+    let mut method: Option<String> = None;
+    let mut url: Option<String> = None;
+    let mut headers: HashMap<String, String> = HashMap::new();
+    let mut data: Vec<String> = Vec::new();
+    let mut get_with_data = false;
+    let mut follow_redirects = false;
+    let mut max_redirects = None;
+
+    while let Some(arg) = args.pop_front() {
+        // Short options may be clustered or carry their value attached:
+        // -sSL, -XPOST, -HAccept:...
+        if let Some(words) = expand_short_options(&arg) {
+            for word in words.into_iter().rev() {
+                args.push_front(word);
+            }
+            continue;
+        }
+        let flag = arg.clone();
+        let mut value = |name: &str| -> Result<String, String> {
+            args.pop_front()
+                .ok_or_else(|| format!("Missing value for {}", name))
+        };
+
+        match flag.as_str() {
+            "-X" | "--request" => method = Some(value(&flag)?.to_uppercase()),
+            "-H" | "--header" => {
+                let header = value(&flag)?;
+                if let Some((name, val)) = header.split_once(':') {
+                    set_header(&mut headers, name.trim(), val.trim());
+                }
+            }
+            "--data-urlencode" => data.push(data_urlencode(&value(&flag)?)?),
+            "-d" | "--data" | "--data-raw" | "--data-ascii" | "--data-binary" => {
+                let body = value(&flag)?;
+                if body.starts_with('@') && flag != "--data-raw" {
+                    return Err(format!(
+                        "Reading the body from a file ({}) is not supported",
+                        body
+                    ));
+                }
+                data.push(body);
+            }
+            "--json" => {
+                data.push(value(&flag)?);
+                if !has_header(&headers, "content-type") {
+                    set_header(&mut headers, "Content-Type", "application/json");
+                }
+                if !has_header(&headers, "accept") {
+                    set_header(&mut headers, "Accept", "application/json");
+                }
+            }
+            "-F" | "--form" | "--form-string" => {
+                return Err("Multipart form fields (-F) are not supported".to_string());
+            }
+            "-T" | "--upload-file" => {
+                return Err("Uploading a file (-T) is not supported".to_string());
+            }
+            "-u" | "--user" => {
+                let credentials = base64::engine::general_purpose::STANDARD.encode(value(&flag)?);
+                set_header(
+                    &mut headers,
+                    "Authorization",
+                    &format!("Basic {}", credentials),
+                );
+            }
+            "-b" | "--cookie" => {
+                let cookie = value(&flag)?;
+                // Without '=' curl treats the value as a cookie file
+                if cookie.contains('=') {
+                    set_header(&mut headers, "Cookie", &cookie);
+                }
+            }
+            "-A" | "--user-agent" => {
+                let agent = value(&flag)?;
+                set_header(&mut headers, "User-Agent", &agent);
+            }
+            "-e" | "--referer" => {
+                let referer = value(&flag)?;
+                set_header(&mut headers, "Referer", &referer);
+            }
+            "--url" => url = Some(value(&flag)?),
+            "-G" | "--get" => get_with_data = true,
+            "-I" | "--head" => method = Some("HEAD".to_string()),
+            "-L" | "--location" => follow_redirects = true,
+            "--max-redirs" => {
+                let limit = value(&flag)?;
+                // curl's -1 means unlimited; keep the default cap for that
+                if let Ok(limit) = limit.parse::<usize>() {
+                    max_redirects = Some(limit);
+                }
+            }
+            f if IGNORED_VALUE_OPTIONS.contains(&f) => {
+                value(f)?;
+            }
+            f if f.starts_with('-') => {
+                // Flags such as --compressed, -k, -s, -v, -i carry no value
+            }
+            _ => {
+                if url.is_none() {
+                    url = Some(arg);
+                }
+            }
+        }
+    }
+
+    let mut url = url.ok_or_else(|| "No URL found in curl command".to_string())?;
+    let mut body = None;
+    if !data.is_empty() {
+        let joined = data.join("&");
+        if get_with_data {
+            url.push(if url.contains('?') { '&' } else { '?' });
+            url.push_str(&joined);
+        } else {
+            if !has_header(&headers, "content-type") {
+                set_header(
+                    &mut headers,
+                    "Content-Type",
+                    "application/x-www-form-urlencoded",
+                );
+            }
+            body = Some(joined);
+        }
+    }
+    let method = method.unwrap_or_else(|| {
+        if body.is_some() {
+            "POST".to_string()
+        } else {
+            "GET".to_string()
+        }
+    });
+
+    let mut request = ReplayRequest {
+        method,
+        url,
+        headers,
+        body,
+        follow_redirects,
+        ..Default::default()
+    };
+    if let Some(limit) = max_redirects {
+        request.max_redirects = limit;
+    }
+    Ok(request)
+}
+
+/// Quote `value` for a POSIX shell.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Render a flow's request as a single `curl` command line.
+pub fn flow_to_curl_command(flow: &Flow) -> String {
+    let request = &flow.request;
+    let mut parts = vec![
+        "curl".to_string(),
+        "-X".to_string(),
+        request.method.clone(),
+        shell_quote(&request.url),
+    ];
+    // HTTP/2 is only negotiated over TLS; plain-http flows stay on HTTP/1.1
+    let http2 = matches!(
+        request.http_version.to_ascii_uppercase().as_str(),
+        "HTTP/2" | "HTTP/2.0"
+    );
+    if http2 && request.url.starts_with("https://") {
+        parts.push("--http2".to_string());
+    }
+    for (name, value) in replayable_headers(flow) {
+        parts.push("-H".to_string());
+        parts.push(shell_quote(&format!("{}: {}", name, value)));
+    }
+    if let Some(body) = request_body_text(flow) {
+        parts.push("--data-raw".to_string());
+        parts.push(shell_quote(&body));
+    }
+
+    let compressed = flow.response.headers.iter().any(|h| {
+        h.name.eq_ignore_ascii_case("content-encoding")
+            && ["gzip", "br", "deflate", "zstd"]
+                .iter()
+                .any(|enc| h.value.to_ascii_lowercase().contains(enc))
+    });
+    if compressed {
+        parts.push("--compressed".to_string());
+    }
+    parts.join(" ")
+}
+
+/// Copy-pasteable `curl` command for a captured flow.
+#[tauri::command]
+pub fn flow_to_curl(flow: Flow) -> String {
+    flow_to_curl_command(&flow)
+}
+
+/// Turn a pasted cURL command into a request the composer can replay.
+#[tauri::command]
+pub fn parse_curl(command: String) -> Result<ReplayRequest, String> {
+    parse_curl_command(&command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::fixtures::sample_flow;
+
+    #[test]
+    fn test_parse_get_with_headers() {
+        let req = parse_curl_command(
+            "curl 'https://api.example.com/users?page=2' \\\n  -H 'Accept: application/json' \\\n  -H \"Authorization: Bearer abc\" \\\n  --compressed",
+        )
+        .unwrap();
+        assert_eq!(req.method, "GET");
+        assert_eq!(req.url, "https://api.example.com/users?page=2");
+        assert_eq!(req.headers.get("Accept").unwrap(), "application/json");
+        assert_eq!(req.headers.get("Authorization").unwrap(), "Bearer abc");
+        assert_eq!(req.body, None);
+    }
+
+    #[test]
+    fn test_parse_post_with_json_body() {
+        let req = parse_curl_command(
+            r#"curl -X POST https://api.example.com/items -H 'Content-Type: application/json' -d '{"name": "it'\''s", "qty": 2}'"#,
+        )
+        .unwrap();
+        assert_eq!(req.method, "POST");
+        assert_eq!(req.url, "https://api.example.com/items");
+        assert_eq!(req.headers.get("Content-Type").unwrap(), "application/json");
+        assert_eq!(req.body.as_deref(), Some(r#"{"name": "it's", "qty": 2}"#));
+    }
+
+    #[test]
+    fn test_parse_devtools_variants() {
+        // Chrome quotes bodies with special characters as $'...'
+        let req = parse_curl_command(
+            "curl 'https://x.test/a' --data-raw $'line1\\nline2' -XPUT -L -o /dev/null",
+        )
+        .unwrap();
+        assert_eq!(req.method, "PUT");
+        assert_eq!(req.body.as_deref(), Some("line1\nline2"));
+        assert!(req.follow_redirects);
+        assert_eq!(
+            req.headers.get("Content-Type").unwrap(),
+            "application/x-www-form-urlencoded"
+        );
+
+        let req = parse_curl_command("curl -G https://x.test/s -d q=1 -d lang=en").unwrap();
+        assert_eq!(req.method, "GET");
+        assert_eq!(req.url, "https://x.test/s?q=1&lang=en");
+
+        assert!(parse_curl_command("wget https://x.test").is_err());
+        assert!(parse_curl_command("curl -d @body.json https://x.test").is_err());
+        assert!(parse_curl_command("curl 'https://x.test").is_err());
+    }
+
+    #[test]
+    fn test_parse_data_urlencode() {
+        let req = parse_curl_command(
+            "curl https://x.test/f --data-urlencode 'q=a b&c' --data-urlencode '=x/y' --data-urlencode 'plain text'",
+        )
+        .unwrap();
+        assert_eq!(req.body.as_deref(), Some("q=a+b%26c&x%2Fy&plain+text"));
+
+        let req = parse_curl_command("curl -G https://x.test/s --data-urlencode 'q=café'").unwrap();
+        assert_eq!(req.url, "https://x.test/s?q=caf%C3%A9");
+
+        assert!(parse_curl_command("curl --data-urlencode q@query.txt https://x.test").is_err());
+    }
+
+    #[test]
+    fn test_parse_skips_values_of_unhandled_options() {
+        let req = parse_curl_command(
+            "curl --connect-timeout 3 --retry-delay 1 -c jar.txt https://x.test/a --max-redirs 5 -L",
+        )
+        .unwrap();
+        assert_eq!(req.url, "https://x.test/a");
+        assert!(req.follow_redirects);
+        assert_eq!(req.max_redirects, 5);
+
+        let req = parse_curl_command("curl --max-redirs -1 https://x.test/a").unwrap();
+        assert_eq!(req.url, "https://x.test/a");
+        assert_eq!(req.max_redirects, ReplayRequest::default().max_redirects);
+    }
+
+    #[test]
+    fn test_parse_clustered_short_options() {
+        let req = parse_curl_command("curl -sSL https://x.test/a").unwrap();
+        assert!(req.follow_redirects);
+        assert_eq!(req.url, "https://x.test/a");
+
+        let req = parse_curl_command("curl -sLXPOST -dkey=1 https://x.test/a").unwrap();
+        assert!(req.follow_redirects);
+        assert_eq!(req.method, "POST");
+        assert_eq!(req.body.as_deref(), Some("key=1"));
+
+        // A value that looks like a cluster is still a value
+        let req = parse_curl_command("curl -d -sSL https://x.test/a").unwrap();
+        assert_eq!(req.body.as_deref(), Some("-sSL"));
+        assert!(!req.follow_redirects);
+    }
+
+    #[test]
+    fn test_flow_to_curl() {
+        let flow = sample_flow();
+        assert_eq!(
+            flow_to_curl_command(&flow),
+            "curl -X GET 'https://example.com/api' -H 'Host: example.com'"
+        );
+
+        let mut post = flow.clone();
+        post.request.method = "POST".to_string();
+        post.request.http_version = "HTTP/2.0".to_string();
+        post.request.post_data = Some(crate::session::model::HarPostData {
+            mime_type: "application/json".to_string(),
+            text: Some("{\"note\":\"it's\"}".to_string()),
+            ..Default::default()
+        });
+        post.response
+            .headers
+            .push(crate::session::model::HarHeader {
+                name: "Content-Encoding".to_string(),
+                value: "br".to_string(),
+                comment: None,
+            });
+        let command = flow_to_curl_command(&post);
+        assert!(command.contains(" --http2 "));
+        assert!(command.ends_with(" --compressed"));
+
+        // The generated command parses back into the same request
+        let parsed = parse_curl_command(&command).unwrap();
+        assert_eq!(parsed.method, "POST");
+        assert_eq!(parsed.url, "https://example.com/api");
+        assert_eq!(parsed.body.as_deref(), Some("{\"note\":\"it's\"}"));
+    }
+}
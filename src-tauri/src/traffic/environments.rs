@@ -0,0 +1,185 @@
+//! Replaying one request against several environments and comparing results.
+//!
+//! Each environment swaps the scheme, host and port (and an optional base path)
+//! of the request URL. Responses are diffed against the first environment that
+//! answered, using the same rules as baseline comparison.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::baseline::{diff_against_baseline, BaselineDiff, DEFAULT_IGNORED_HEADERS};
+use super::commands::{replay_concurrently, ReplayRequest, ReplayResponse, MAX_REPLAY_CONCURRENCY};
+use crate::session::model::{Flow, HarHeader};
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvTarget {
+    pub name: String,
+    /// Replaces scheme, host and port; a path here is prepended to the request path
+    pub base_url: String,
+    /// Headers set (or replaced) for this environment only
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvReplayResult {
+    pub name: String,
+    pub url: String,
+    pub response: Option<ReplayResponse>,
+    pub error: Option<String>,
+    pub duration_ms: f64,
+    /// Name of the environment this one was compared against
+    pub reference: Option<String>,
+    /// Differences from the reference; `None` for the reference itself or on error
+    pub diff: Option<BaselineDiff>,
+}
+
+/// Rewrite `original` onto `base_url`, keeping the original path and query.
+pub fn retarget_url(original: &str, base_url: &str) -> Result<String, String> {
+    let original = url::Url::parse(original).map_err(|e| format!("Invalid request URL: {}", e))?;
+    let mut target =
+        url::Url::parse(base_url).map_err(|e| format!("Invalid base URL '{}': {}", base_url, e))?;
+
+    let base_path = target.path().trim_end_matches('/').to_string();
+    target.set_path(&format!("{}{}", base_path, original.path()));
+    target.set_query(original.query());
+    target.set_fragment(None);
+    Ok(target.to_string())
+}
+
+fn env_request(base: &ReplayRequest, env: &EnvTarget) -> Result<ReplayRequest, String> {
+    let mut req = base.clone();
+    req.url = retarget_url(&base.url, &env.base_url)?;
+    // Host must follow the new target rather than the captured one
+    req.headers.retain(|k, _| !k.eq_ignore_ascii_case("host"));
+    for (name, value) in &env.headers {
+        req.headers.retain(|k, _| !k.eq_ignore_ascii_case(name));
+        req.headers.insert(name.clone(), value.clone());
+    }
+    Ok(req)
+}
+
+fn response_flow(response: &ReplayResponse) -> Flow {
+    let mut flow = Flow::default();
+    flow.response.status = response.status as i32;
+    flow.response.headers = response
+        .headers
+        .iter()
+        .map(|(name, value)| HarHeader {
+            name: name.clone(),
+            value: value.clone(),
+            comment: None,
+        })
+        .collect();
+    flow.response.content.text = Some(response.body.clone());
+    flow
+}
+
+/// Replay `base` against every environment concurrently and diff the responses.
+#[tauri::command]
+pub async fn replay_multi_env(
+    base: ReplayRequest,
+    environments: Vec<EnvTarget>,
+) -> Result<Vec<EnvReplayResult>, String> {
+    let requests = environments
+        .iter()
+        .map(|env| env_request(&base, env))
+        .collect::<Result<Vec<_>, _>>()?;
+    let urls: Vec<String> = requests.iter().map(|r| r.url.clone()).collect();
+
+    let replies = replay_concurrently(requests, MAX_REPLAY_CONCURRENCY).await;
+
+    let ignored: Vec<String> = DEFAULT_IGNORED_HEADERS
+        .iter()
+        .map(|h| h.to_string())
+        .collect();
+    let reference = environments.iter().zip(&replies).find_map(|(env, reply)| {
+        reply
+            .result
+            .as_ref()
+            .ok()
+            .map(|r| (env.name.clone(), response_flow(r)))
+    });
+
+    let results = environments
+        .into_iter()
+        .zip(urls)
+        .zip(replies)
+        .map(|((env, url), reply)| {
+            let (response, error) = match reply.result {
+                Ok(r) => (Some(r), None),
+                Err(e) => (None, Some(e)),
+            };
+            let diff = match (&reference, &response) {
+                (Some((ref_name, ref_flow)), Some(r)) if *ref_name != env.name => {
+                    Some(diff_against_baseline(ref_flow, &response_flow(r), &ignored))
+                }
+                _ => None,
+            };
+            EnvReplayResult {
+                name: env.name,
+                url,
+                response,
+                error,
+                duration_ms: reply.duration_ms,
+                reference: reference.as_ref().map(|(name, _)| name.clone()),
+                diff,
+            }
+        })
+        .collect();
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retarget_url_keeps_path_and_query() {
+        assert_eq!(
+            retarget_url(
+                "https://api.example.com/v1/users?id=7",
+                "http://localhost:8080"
+            )
+            .unwrap(),
+            "http://localhost:8080/v1/users?id=7"
+        );
+        assert_eq!(
+            retarget_url(
+                "https://api.example.com/v1/users",
+                "https://staging.example.com/gateway/"
+            )
+            .unwrap(),
+            "https://staging.example.com/gateway/v1/users"
+        );
+        assert!(retarget_url("https://api.example.com/", "not a url").is_err());
+    }
+
+    #[test]
+    fn test_env_request_overrides_headers() {
+        let mut headers = HashMap::new();
+        headers.insert("Host".to_string(), "api.example.com".to_string());
+        headers.insert("authorization".to_string(), "Bearer prod".to_string());
+        let base = ReplayRequest {
+            url: "https://api.example.com/ping".into(),
+            headers,
+            ..Default::default()
+        };
+        let mut env_headers = HashMap::new();
+        env_headers.insert("Authorization".to_string(), "Bearer dev".to_string());
+        let env = EnvTarget {
+            name: "dev".into(),
+            base_url: "http://dev.local".into(),
+            headers: env_headers,
+        };
+
+        let req = env_request(&base, &env).unwrap();
+        assert_eq!(req.url, "http://dev.local/ping");
+        assert_eq!(req.headers.len(), 1);
+        assert_eq!(req.headers["Authorization"], "Bearer dev");
+    }
+}
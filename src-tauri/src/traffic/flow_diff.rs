@@ -0,0 +1,266 @@
+//! Structured comparison of two flows, e.g. a capture and its replay.
+//!
+//! Headers are compared case-insensitively with repeated headers joined, the
+//! same way baselines are. Textual bodies get a line diff; binary bodies are
+//! only compared for equality.
+
+use serde::Serialize;
+
+use super::baseline::header_map;
+use super::json_diff::diff_text;
+use crate::session::model::{Flow, HarHeader};
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HeaderDelta {
+    /// Lowercase header name
+    pub name: String,
+    pub kind: ChangeKind,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum LineOp {
+    Equal,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LineDelta {
+    pub op: LineOp,
+    pub text: String,
+}
+
+/// How two bodies differ.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum BodyDiff {
+    Identical,
+    /// Both bodies are text; every line of both sides, in order.
+    Text {
+        lines: Vec<LineDelta>,
+    },
+    /// At least one body is binary (base64), so only sizes are reported.
+    #[serde(rename_all = "camelCase")]
+    Binary {
+        before_size: usize,
+        after_size: usize,
+    },
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusChange {
+    pub before: i32,
+    pub after: i32,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FlowDiff {
+    pub identical: bool,
+    pub request_headers: Vec<HeaderDelta>,
+    pub response_headers: Vec<HeaderDelta>,
+    pub status: Option<StatusChange>,
+    pub request_body: BodyDiff,
+    pub response_body: BodyDiff,
+}
+
+fn diff_headers(a: &[HarHeader], b: &[HarHeader]) -> Vec<HeaderDelta> {
+    let before = header_map(a, &[]);
+    let after = header_map(b, &[]);
+
+    let mut names: Vec<&String> = before.keys().chain(after.keys()).collect();
+    names.sort();
+    names.dedup();
+
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let (old, new) = (before.get(name), after.get(name));
+            let kind = match (old, new) {
+                (Some(x), Some(y)) if x == y => return None,
+                (Some(_), Some(_)) => ChangeKind::Changed,
+                (None, Some(_)) => ChangeKind::Added,
+                _ => ChangeKind::Removed,
+            };
+            Some(HeaderDelta {
+                name: name.clone(),
+                kind,
+                before: old.cloned(),
+                after: new.cloned(),
+            })
+        })
+        .collect()
+}
+
+/// A body as (text, is_binary).
+type BodySide<'a> = (&'a str, bool);
+
+fn diff_body(a: BodySide, b: BodySide) -> BodyDiff {
+    if a == b {
+        return BodyDiff::Identical;
+    }
+    if a.1 || b.1 {
+        return BodyDiff::Binary {
+            before_size: a.0.len(),
+            after_size: b.0.len(),
+        };
+    }
+    let lines = diff_text(a.0, b.0)
+        .into_iter()
+        .map(|line| LineDelta {
+            op: match line.op.as_str() {
+                "added" => LineOp::Added,
+                "removed" => LineOp::Removed,
+                _ => LineOp::Equal,
+            },
+            text: line.text,
+        })
+        .collect();
+    BodyDiff::Text { lines }
+}
+
+fn request_body(flow: &Flow) -> BodySide<'_> {
+    let text = flow
+        .request
+        .post_data
+        .as_ref()
+        .and_then(|p| p.text.as_deref())
+        .unwrap_or("");
+    (text, false)
+}
+
+fn response_body(flow: &Flow) -> BodySide<'_> {
+    let content = &flow.response.content;
+    (
+        content.text.as_deref().unwrap_or(""),
+        content.encoding.as_deref() == Some("base64"),
+    )
+}
+
+/// Compare `b` against `a`: "added" means present only in `b`.
+pub fn diff_flow_pair(a: &Flow, b: &Flow) -> FlowDiff {
+    let request_headers = diff_headers(&a.request.headers, &b.request.headers);
+    let response_headers = diff_headers(&a.response.headers, &b.response.headers);
+    let status = (a.response.status != b.response.status).then_some(StatusChange {
+        before: a.response.status,
+        after: b.response.status,
+    });
+    let request_body = diff_body(request_body(a), request_body(b));
+    let response_body = diff_body(response_body(a), response_body(b));
+
+    FlowDiff {
+        identical: request_headers.is_empty()
+            && response_headers.is_empty()
+            && status.is_none()
+            && request_body == BodyDiff::Identical
+            && response_body == BodyDiff::Identical,
+        request_headers,
+        response_headers,
+        status,
+        request_body,
+        response_body,
+    }
+}
+
+#[tauri::command]
+pub fn diff_flows(a: Flow, b: Flow) -> FlowDiff {
+    diff_flow_pair(&a, &b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::fixtures::header;
+
+    #[test]
+    fn test_header_only_diff() {
+        let mut a = Flow::default();
+        a.request.headers = vec![header("Accept", "*/*"), header("X-Trace", "1")];
+        a.response.headers = vec![header("Content-Type", "text/plain")];
+        let mut b = a.clone();
+        b.request.headers = vec![header("accept", "*/*"), header("Authorization", "Bearer t")];
+        b.response.headers = vec![header("Content-Type", "application/json")];
+
+        let diff = diff_flow_pair(&a, &b);
+        assert!(!diff.identical);
+        assert_eq!(diff.status, None);
+        assert_eq!(diff.request_body, BodyDiff::Identical);
+        assert_eq!(diff.response_body, BodyDiff::Identical);
+
+        let request: Vec<(&str, ChangeKind)> = diff
+            .request_headers
+            .iter()
+            .map(|d| (d.name.as_str(), d.kind))
+            .collect();
+        assert_eq!(
+            request,
+            vec![
+                ("authorization", ChangeKind::Added),
+                ("x-trace", ChangeKind::Removed)
+            ]
+        );
+        assert_eq!(diff.response_headers[0].kind, ChangeKind::Changed);
+        assert_eq!(
+            diff.response_headers[0].after.as_deref(),
+            Some("application/json")
+        );
+
+        assert!(diff_flow_pair(&a, &a.clone()).identical);
+    }
+
+    #[test]
+    fn test_body_and_status_diff() {
+        let mut a = Flow::default();
+        a.response.status = 200;
+        a.response.content.text = Some("line 1\nline 2\nline 3".to_string());
+        let mut b = a.clone();
+        b.response.status = 500;
+        b.response.content.text = Some("line 1\nline two\nline 3".to_string());
+
+        let diff = diff_flow_pair(&a, &b);
+        assert_eq!(
+            diff.status,
+            Some(StatusChange {
+                before: 200,
+                after: 500
+            })
+        );
+        let BodyDiff::Text { lines } = &diff.response_body else {
+            panic!("expected a text diff");
+        };
+        let ops: Vec<LineOp> = lines.iter().map(|l| l.op).collect();
+        assert_eq!(
+            ops,
+            vec![LineOp::Equal, LineOp::Removed, LineOp::Added, LineOp::Equal]
+        );
+        assert_eq!(lines[2].text, "line two");
+
+        b.response.content.text = Some("AAEC".to_string());
+        b.response.content.encoding = Some("base64".to_string());
+        assert_eq!(
+            diff_flow_pair(&a, &b).response_body,
+            BodyDiff::Binary {
+                before_size: 20,
+                after_size: 4
+            }
+        );
+
+        let json = serde_json::to_value(diff_flow_pair(&a, &b)).unwrap();
+        assert_eq!(json["responseBody"]["type"], "binary");
+        assert_eq!(json["responseBody"]["afterSize"], 4);
+    }
+}
@@ -0,0 +1,167 @@
+//! Flows paused in the engine by a breakpoint, and resuming them.
+//!
+//! Engine contract:
+//! - `GET /_relay/intercepted` returns `[{ id, phase, ruleId?, flow }]`, one
+//!   entry per paused flow, where `flow` has the same shape as `/_relay/detail`.
+//! - `POST /_relay/resume` takes `{ id, modifications? }` and answers 404 when
+//!   the flow is not paused. `modifications` holds `requestHeaders`,
+//!   `requestBody`, `responseHeaders`, `responseBody` and `statusCode`; the
+//!   engine only applies the fields of the phase the flow is paused at.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::session::model::{Flow, HarHeader};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InterceptedFlow {
+    pub id: String,
+    /// "request" or "response"
+    pub phase: String,
+    /// Rule (or manual breakpoint) that paused the flow
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_id: Option<String>,
+    pub flow: Flow,
+}
+
+fn engine_url(path: &str) -> String {
+    let config = crate::config::load_config().unwrap_or_default();
+    format!(
+        "http://127.0.0.1:{}{}",
+        crate::proxy::engine_port(&config),
+        path
+    )
+}
+
+/// Repeated headers are joined the way HTTP allows, since the engine takes
+/// a name -> value map.
+fn header_object(headers: &[HarHeader]) -> BTreeMap<String, String> {
+    let mut map: BTreeMap<String, String> = BTreeMap::new();
+    for header in headers.iter().filter(|h| !h.name.starts_with(':')) {
+        map.entry(header.name.clone())
+            .and_modify(|v| {
+                v.push_str(", ");
+                v.push_str(&header.value);
+            })
+            .or_insert_with(|| header.value.clone());
+    }
+    map
+}
+
+/// Translate an edited flow into the engine's `modifications` payload.
+/// Binary (base64) response bodies are left untouched.
+pub fn resume_modifications(flow: &Flow) -> serde_json::Value {
+    let mut modifications = serde_json::json!({
+        "requestHeaders": header_object(&flow.request.headers),
+        "requestBody": flow
+            .request
+            .post_data
+            .as_ref()
+            .and_then(|p| p.text.clone())
+            .unwrap_or_default(),
+        "responseHeaders": header_object(&flow.response.headers),
+    });
+    let content = &flow.response.content;
+    if content.encoding.as_deref() != Some("base64") {
+        modifications["responseBody"] = content.text.clone().unwrap_or_default().into();
+    }
+    if flow.response.status > 0 {
+        modifications["statusCode"] = flow.response.status.into();
+    }
+    modifications
+}
+
+/// Flows currently paused by a breakpoint rule or a manual breakpoint
+#[tauri::command]
+pub async fn list_intercepted() -> Result<Vec<InterceptedFlow>, String> {
+    let response = reqwest::Client::new()
+        .get(engine_url("/_relay/intercepted"))
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("engine_error: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("engine_error: HTTP {}", response.status().as_u16()));
+    }
+
+    response
+        .json::<Vec<InterceptedFlow>>()
+        .await
+        .map_err(|e| format!("Failed to parse intercepted flows: {}", e))
+}
+
+/// Let a paused flow continue, with the user's edits when given
+#[tauri::command]
+pub async fn resume_flow(id: String, edited_flow: Option<Flow>) -> Result<(), String> {
+    let mut body = serde_json::json!({ "id": id });
+    if let Some(flow) = &edited_flow {
+        body["modifications"] = resume_modifications(flow);
+    }
+
+    let response = reqwest::Client::new()
+        .post(engine_url("/_relay/resume"))
+        .header("Content-Type", "application/json")
+        .body(body.to_string())
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("engine_error: {}", e))?;
+
+    if response.status().as_u16() == 404 {
+        return Err(format!("Flow '{}' is not paused", id));
+    }
+    if !response.status().is_success() {
+        return Err(format!("engine_error: HTTP {}", response.status().as_u16()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::fixtures::header;
+    use crate::session::model::HarPostData;
+
+    #[test]
+    fn test_resume_modifications() {
+        let mut flow = Flow::default();
+        flow.request.headers = vec![
+            header(":authority", "example.com"),
+            header("Accept", "text/html"),
+            header("Accept", "application/json"),
+        ];
+        flow.request.post_data = Some(HarPostData {
+            mime_type: "application/json".to_string(),
+            text: Some("{\"a\":1}".to_string()),
+            ..Default::default()
+        });
+        flow.response.status = 418;
+        flow.response.content.text = Some("AAEC".to_string());
+        flow.response.content.encoding = Some("base64".to_string());
+
+        let modifications = resume_modifications(&flow);
+        assert_eq!(
+            modifications["requestHeaders"],
+            serde_json::json!({ "Accept": "text/html, application/json" })
+        );
+        assert_eq!(modifications["requestBody"], "{\"a\":1}");
+        assert_eq!(modifications["statusCode"], 418);
+        assert!(modifications.get("responseBody").is_none());
+    }
+
+    #[test]
+    fn test_intercepted_flow_deserializes() {
+        let listed: Vec<InterceptedFlow> = serde_json::from_value(serde_json::json!([{
+            "id": "f1",
+            "phase": "response",
+            "ruleId": "rule-1",
+            "flow": serde_json::to_value(Flow::default()).unwrap()
+        }]))
+        .unwrap();
+        assert_eq!(listed[0].phase, "response");
+        assert_eq!(listed[0].rule_id.as_deref(), Some("rule-1"));
+    }
+}
@@ -0,0 +1,263 @@
+//! Key-aware comparison of two response bodies.
+//!
+//! JSON bodies are compared structurally, so key order and whitespace never
+//! show up as changes. Anything that does not parse as JSON falls back to a
+//! line diff.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::body::body_bytes;
+use super::commands::fetch_flow;
+
+/// Line diffs above this many cells (lines_a * lines_b) are reported as a
+/// single replacement instead of running the quadratic LCS.
+const MAX_TEXT_DIFF_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum JsonChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// One structural difference, located by a `$.key[0]` style path.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonChange {
+    pub path: String,
+    pub kind: JsonChangeKind,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// One line of a text diff; `op` is "equal", "added" or "removed".
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TextDiffLine {
+    pub op: String,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonDiff {
+    /// "json" when both bodies parsed as JSON, otherwise "text"
+    pub mode: String,
+    pub identical: bool,
+    pub changes: Vec<JsonChange>,
+    pub text_diff: Vec<TextDiffLine>,
+}
+
+fn child_key(path: &str, key: &str) -> String {
+    let simple = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if simple {
+        format!("{}.{}", path, key)
+    } else {
+        format!("{}[{}]", path, Value::String(key.to_string()))
+    }
+}
+
+fn diff_values(path: &str, a: &Value, b: &Value, out: &mut Vec<JsonChange>) {
+    match (a, b) {
+        (Value::Object(ma), Value::Object(mb)) => {
+            for (key, va) in ma {
+                let child = child_key(path, key);
+                match mb.get(key) {
+                    Some(vb) => diff_values(&child, va, vb, out),
+                    None => out.push(JsonChange {
+                        path: child,
+                        kind: JsonChangeKind::Removed,
+                        before: Some(va.clone()),
+                        after: None,
+                    }),
+                }
+            }
+            for (key, vb) in mb {
+                if !ma.contains_key(key) {
+                    out.push(JsonChange {
+                        path: child_key(path, key),
+                        kind: JsonChangeKind::Added,
+                        before: None,
+                        after: Some(vb.clone()),
+                    });
+                }
+            }
+        }
+        (Value::Array(va), Value::Array(vb)) => {
+            for i in 0..va.len().max(vb.len()) {
+                let child = format!("{}[{}]", path, i);
+                match (va.get(i), vb.get(i)) {
+                    (Some(x), Some(y)) => diff_values(&child, x, y, out),
+                    (Some(x), None) => out.push(JsonChange {
+                        path: child,
+                        kind: JsonChangeKind::Removed,
+                        before: Some(x.clone()),
+                        after: None,
+                    }),
+                    (None, Some(y)) => out.push(JsonChange {
+                        path: child,
+                        kind: JsonChangeKind::Added,
+                        before: None,
+                        after: Some(y.clone()),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ if a != b => out.push(JsonChange {
+            path: path.to_string(),
+            kind: JsonChangeKind::Changed,
+            before: Some(a.clone()),
+            after: Some(b.clone()),
+        }),
+        _ => {}
+    }
+}
+
+/// Structural diff of two JSON values. Object key order is irrelevant.
+pub fn diff_json(a: &Value, b: &Value) -> Vec<JsonChange> {
+    let mut out = Vec::new();
+    diff_values("$", a, b, &mut out);
+    out
+}
+
+fn line(op: &str, text: &str) -> TextDiffLine {
+    TextDiffLine {
+        op: op.to_string(),
+        text: text.to_string(),
+    }
+}
+
+/// Line diff based on the longest common subsequence.
+pub fn diff_text(a: &str, b: &str) -> Vec<TextDiffLine> {
+    let la: Vec<&str> = a.lines().collect();
+    let lb: Vec<&str> = b.lines().collect();
+
+    if la.len().saturating_mul(lb.len()) > MAX_TEXT_DIFF_CELLS {
+        return la
+            .iter()
+            .map(|l| line("removed", l))
+            .chain(lb.iter().map(|l| line("added", l)))
+            .collect();
+    }
+
+    // lcs[i][j] = LCS length of la[i..] and lb[j..]
+    let mut lcs = vec![vec![0usize; lb.len() + 1]; la.len() + 1];
+    for i in (0..la.len()).rev() {
+        for j in (0..lb.len()).rev() {
+            lcs[i][j] = if la[i] == lb[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut out = Vec::new();
+    while i < la.len() && j < lb.len() {
+        if la[i] == lb[j] {
+            out.push(line("equal", la[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            out.push(line("removed", la[i]));
+            i += 1;
+        } else {
+            out.push(line("added", lb[j]));
+            j += 1;
+        }
+    }
+    out.extend(la[i..].iter().map(|l| line("removed", l)));
+    out.extend(lb[j..].iter().map(|l| line("added", l)));
+    out
+}
+
+/// Compare two bodies, structurally when both are JSON.
+pub fn diff_bodies(a: &str, b: &str) -> JsonDiff {
+    if let (Ok(va), Ok(vb)) = (
+        serde_json::from_str::<Value>(a),
+        serde_json::from_str::<Value>(b),
+    ) {
+        let changes = diff_json(&va, &vb);
+        return JsonDiff {
+            mode: "json".to_string(),
+            identical: changes.is_empty(),
+            changes,
+            text_diff: Vec::new(),
+        };
+    }
+
+    let text_diff = diff_text(a, b);
+    JsonDiff {
+        mode: "text".to_string(),
+        identical: text_diff.iter().all(|l| l.op == "equal"),
+        changes: Vec::new(),
+        text_diff,
+    }
+}
+
+/// Compare the response bodies of two captured flows.
+#[tauri::command]
+pub async fn json_body_diff(a_flow_id: String, b_flow_id: String) -> Result<JsonDiff, String> {
+    let a = fetch_flow(&a_flow_id).await?;
+    let b = fetch_flow(&b_flow_id).await?;
+    let (a_bytes, _) = body_bytes(&a, "response")?;
+    let (b_bytes, _) = body_bytes(&b, "response")?;
+    Ok(diff_bodies(
+        &String::from_utf8_lossy(&a_bytes),
+        &String::from_utf8_lossy(&b_bytes),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reordered_keys_and_whitespace_are_not_changes() {
+        let a = r#"{"id": 1, "user": {"name": "a", "tags": ["x", "y"]}}"#;
+        let b = "{\n  \"user\": {\"tags\": [\"x\",\"y\"], \"name\": \"a\"},\n  \"id\": 1\n}";
+        let diff = diff_bodies(a, b);
+        assert_eq!(diff.mode, "json");
+        assert!(diff.identical);
+        assert!(diff.changes.is_empty());
+    }
+
+    #[test]
+    fn test_reports_added_removed_and_changed_paths() {
+        let a = r#"{"id": 1, "old": true, "items": [1, 2], "a-b": "x"}"#;
+        let b = r#"{"id": 2, "new": null, "items": [1], "a-b": "y"}"#;
+        let diff = diff_bodies(a, b);
+        assert!(!diff.identical);
+
+        let summary: Vec<(String, JsonChangeKind)> =
+            diff.changes.into_iter().map(|c| (c.path, c.kind)).collect();
+        assert!(summary.contains(&("$.id".to_string(), JsonChangeKind::Changed)));
+        assert!(summary.contains(&("$.old".to_string(), JsonChangeKind::Removed)));
+        assert!(summary.contains(&("$.new".to_string(), JsonChangeKind::Added)));
+        assert!(summary.contains(&("$.items[1]".to_string(), JsonChangeKind::Removed)));
+        assert!(summary.contains(&("$[\"a-b\"]".to_string(), JsonChangeKind::Changed)));
+    }
+
+    #[test]
+    fn test_non_json_falls_back_to_line_diff() {
+        let diff = diff_bodies("a\nb\nc", "a\nc\nd");
+        assert_eq!(diff.mode, "text");
+        assert!(!diff.identical);
+        assert_eq!(
+            diff.text_diff,
+            vec![
+                line("equal", "a"),
+                line("removed", "b"),
+                line("equal", "c"),
+                line("added", "d"),
+            ]
+        );
+    }
+}
@@ -1,2 +1,34 @@
+pub mod assertions;
+pub mod baseline;
+pub mod body;
+pub mod caching;
 pub mod commands;
+pub mod correlation;
+pub mod csrf;
+pub mod curl;
+pub mod environments;
+pub mod flow_diff;
+pub mod intercept;
+pub mod json_diff;
+pub mod recording;
+pub mod signing;
+pub mod templates;
+#[cfg(test)]
+pub(crate) mod test_server;
+pub mod tokens;
+pub use assertions::*;
+pub use baseline::*;
+pub use body::*;
+pub use caching::*;
 pub use commands::*;
+pub use correlation::*;
+pub use csrf::*;
+pub use curl::*;
+pub use environments::*;
+pub use flow_diff::*;
+pub use intercept::*;
+pub use json_diff::*;
+pub use recording::*;
+pub use signing::*;
+pub use templates::*;
+pub use tokens::*;
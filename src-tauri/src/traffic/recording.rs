@@ -0,0 +1,210 @@
+//! Named, bounded capture recordings.
+//!
+//! `start_recording` drops a start marker; `stop_recording` asks the engine
+//! for every flow captured since then and packages them as a `Session`.
+
+use std::sync::Mutex;
+
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+
+use super::commands::fetch_flow;
+use crate::logging;
+use crate::session::model::{Flow, Session, SessionMetadata};
+
+/// Upper bound on flow detail requests in flight while packaging a recording.
+const RECORDING_FETCH_CONCURRENCY: usize = 8;
+
+/// Recording currently in progress.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveRecording {
+    pub name: String,
+    /// Start marker, in milliseconds since the epoch
+    pub started_at: i64,
+}
+
+lazy_static::lazy_static! {
+    static ref ACTIVE_RECORDING: Mutex<Option<ActiveRecording>> = Mutex::new(None);
+}
+
+/// Subset of an engine poll index entry needed to select recorded flows.
+#[derive(Debug, Deserialize)]
+struct PolledIndex {
+    id: String,
+    #[serde(default)]
+    msg_ts: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct PollResponse {
+    indices: Vec<PolledIndex>,
+}
+
+/// IDs of flows whose capture timestamp (seconds) lies inside the window (ms).
+fn ids_in_window(indices: Vec<PolledIndex>, start_ms: i64, stop_ms: i64) -> Vec<String> {
+    let start = start_ms as f64 / 1000.0;
+    let stop = stop_ms as f64 / 1000.0;
+    indices
+        .into_iter()
+        .filter(|idx| idx.msg_ts >= start && idx.msg_ts <= stop)
+        .map(|idx| idx.id)
+        .collect()
+}
+
+/// Build a session from the flows captured during a recording.
+fn package_recording(recording: &ActiveRecording, stopped_at: i64, flows: Vec<Flow>) -> Session {
+    Session {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: recording.name.clone(),
+        description: None,
+        metadata: SessionMetadata {
+            created_at: recording.started_at,
+            duration: (stopped_at - recording.started_at).max(0) as f64,
+            flow_count: flows.len(),
+            size_bytes: flows
+                .iter()
+                .map(|f| f.response.content.size.max(0) as usize)
+                .sum(),
+            ..Default::default()
+        },
+        flows,
+    }
+}
+
+async fn poll_since(start_ms: i64) -> Result<Vec<PolledIndex>, String> {
+    let config = crate::config::load_config().unwrap_or_default();
+    let url = format!(
+        "http://127.0.0.1:{}/_relay/poll?since={}",
+        crate::proxy::engine_port(&config),
+        start_ms as f64 / 1000.0
+    );
+
+    let response = reqwest::Client::new()
+        .get(&url)
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("engine_error: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("engine_error: HTTP {}", response.status().as_u16()));
+    }
+
+    response
+        .json::<PollResponse>()
+        .await
+        .map(|r| r.indices)
+        .map_err(|e| format!("Failed to parse poll response: {}", e))
+}
+
+/// Start a named recording. Fails if one is already running.
+#[tauri::command]
+pub fn start_recording(name: String) -> Result<ActiveRecording, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Recording name cannot be empty".to_string());
+    }
+
+    let mut active = ACTIVE_RECORDING.lock().map_err(|e| e.to_string())?;
+    if let Some(current) = active.as_ref() {
+        return Err(format!("Recording '{}' is already running", current.name));
+    }
+
+    let recording = ActiveRecording {
+        name,
+        started_at: chrono::Utc::now().timestamp_millis(),
+    };
+    *active = Some(recording.clone());
+    let _ = logging::write_domain_log("audit", &format!("Started recording '{}'", recording.name));
+    Ok(recording)
+}
+
+/// Return the recording in progress, if any.
+#[tauri::command]
+pub fn get_active_recording() -> Result<Option<ActiveRecording>, String> {
+    Ok(ACTIVE_RECORDING.lock().map_err(|e| e.to_string())?.clone())
+}
+
+/// Stop the running recording and package the flows captured since it
+/// started into a session. The recording stays active until packaging
+/// succeeds, so a failed stop can be retried.
+#[tauri::command]
+pub async fn stop_recording() -> Result<Session, String> {
+    let recording = ACTIVE_RECORDING
+        .lock()
+        .map_err(|e| e.to_string())?
+        .clone()
+        .ok_or_else(|| "No recording is running".to_string())?;
+    let stopped_at = chrono::Utc::now().timestamp_millis();
+
+    let ids = ids_in_window(
+        poll_since(recording.started_at).await?,
+        recording.started_at,
+        stopped_at,
+    );
+    let flows: Vec<Flow> = futures_util::stream::iter(ids)
+        .map(|id| async move { fetch_flow(&id).await })
+        .buffered(RECORDING_FETCH_CONCURRENCY)
+        .filter_map(|res| async move { res.ok() })
+        .collect()
+        .await;
+
+    let session = package_recording(&recording, stopped_at, flows);
+    {
+        let mut active = ACTIVE_RECORDING.lock().map_err(|e| e.to_string())?;
+        // A concurrent stop may already have cleared it
+        if active.as_ref().map(|r| r.started_at) == Some(recording.started_at) {
+            *active = None;
+        }
+    }
+    let _ = logging::write_domain_log(
+        "audit",
+        &format!(
+            "Stopped recording '{}' ({} flows)",
+            session.name, session.metadata.flow_count
+        ),
+    );
+    Ok(session)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_in_window_uses_second_timestamps() {
+        let indices = vec![
+            PolledIndex {
+                id: "before".to_string(),
+                msg_ts: 999.9,
+            },
+            PolledIndex {
+                id: "inside".to_string(),
+                msg_ts: 1000.5,
+            },
+            PolledIndex {
+                id: "after".to_string(),
+                msg_ts: 1002.1,
+            },
+        ];
+        assert_eq!(ids_in_window(indices, 1_000_000, 1_002_000), vec!["inside"]);
+    }
+
+    #[test]
+    fn test_package_recording_metadata() {
+        let recording = ActiveRecording {
+            name: "checkout flow".to_string(),
+            started_at: 1_000,
+        };
+        let mut flow = Flow::default();
+        flow.response.content.size = 42;
+
+        let session = package_recording(&recording, 4_500, vec![flow.clone(), flow]);
+        assert_eq!(session.name, "checkout flow");
+        assert_eq!(session.metadata.created_at, 1_000);
+        assert_eq!(session.metadata.duration, 3_500.0);
+        assert_eq!(session.metadata.flow_count, 2);
+        assert_eq!(session.metadata.size_bytes, 84);
+    }
+}
@@ -0,0 +1,336 @@
+//! HMAC request signing for replays.
+//!
+//! Many APIs sign `method + path + body + timestamp` with a shared secret, so a
+//! verbatim replay fails once the timestamp moves on. A signing config lets the
+//! replay recompute the signature header right before sending.
+
+use std::collections::HashMap;
+
+use base64::Engine;
+use hmac::{Hmac, KeyInit, Mac};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum HmacAlgorithm {
+    Sha256,
+    Sha1,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ByteEncoding {
+    #[default]
+    Hex,
+    Base64,
+    /// Raw UTF-8 text; only meaningful for keys
+    Utf8,
+}
+
+/// Request fields available to the message template
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SignableRequest {
+    pub method: String,
+    pub url: String,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    #[serde(default)]
+    pub body: Option<String>,
+}
+
+/// HMAC signing parameters.
+///
+/// `message_template` may reference `{method}`, `{url}`, `{host}`, `{path}`
+/// (path plus query), `{query}`, `{body}`, `{timestamp}` and `{header.Name}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HmacParams {
+    pub algorithm: HmacAlgorithm,
+    pub key: String,
+    /// Encoding of `key`; defaults to UTF-8 text
+    #[serde(default = "default_key_encoding")]
+    pub key_encoding: ByteEncoding,
+    pub message_template: String,
+    /// Encoding of the resulting signature; defaults to hex
+    #[serde(default)]
+    pub output: ByteEncoding,
+    #[serde(default)]
+    pub request: Option<SignableRequest>,
+    /// Timestamp substituted for `{timestamp}`; defaults to current Unix seconds
+    #[serde(default)]
+    pub timestamp: Option<String>,
+}
+
+fn default_key_encoding() -> ByteEncoding {
+    ByteEncoding::Utf8
+}
+
+/// Signing config attached to a replay request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplaySigning {
+    pub params: HmacParams,
+    /// Header that receives the signature
+    pub header: String,
+    /// Optional prefix, e.g. `"HMAC-SHA256 "`
+    #[serde(default)]
+    pub prefix: Option<String>,
+    /// Header that receives the timestamp used for signing, if the API expects one
+    #[serde(default)]
+    pub timestamp_header: Option<String>,
+}
+
+fn decode_key(key: &str, encoding: ByteEncoding) -> Result<Vec<u8>, String> {
+    match encoding {
+        ByteEncoding::Utf8 => Ok(key.as_bytes().to_vec()),
+        ByteEncoding::Hex => hex::decode(key.trim()).map_err(|e| format!("Invalid hex key: {}", e)),
+        ByteEncoding::Base64 => base64::engine::general_purpose::STANDARD
+            .decode(key.trim())
+            .map_err(|e| format!("Invalid base64 key: {}", e)),
+    }
+}
+
+/// Fill `template` with fields from `request`
+pub fn render_message(template: &str, request: &SignableRequest, timestamp: &str) -> String {
+    let parsed = url::Url::parse(&request.url).ok();
+    let host = parsed
+        .as_ref()
+        .and_then(|u| u.host_str())
+        .unwrap_or_default()
+        .to_string();
+    let query = parsed
+        .as_ref()
+        .and_then(|u| u.query())
+        .unwrap_or_default()
+        .to_string();
+    let path = match parsed.as_ref() {
+        Some(u) if !query.is_empty() => format!("{}?{}", u.path(), query),
+        Some(u) => u.path().to_string(),
+        None => String::new(),
+    };
+
+    let method = request.method.to_uppercase();
+    let body = request.body.as_deref().unwrap_or("");
+
+    // Single pass, so placeholder-like text inside a value (e.g. a body that
+    // contains "{timestamp}") is emitted verbatim and never expanded again.
+    let mut message = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        message.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        let Some(end) = tail.find('}') else {
+            rest = tail;
+            break;
+        };
+        let name = &tail[1..end];
+        let value = match name {
+            "method" => Some(method.as_str()),
+            "url" => Some(request.url.as_str()),
+            "host" => Some(host.as_str()),
+            "path" => Some(path.as_str()),
+            "query" => Some(query.as_str()),
+            "body" => Some(body),
+            "timestamp" => Some(timestamp),
+            // {header.Name} lookups are case-insensitive
+            _ => name.strip_prefix("header.").map(|header| {
+                request
+                    .headers
+                    .iter()
+                    .find(|(k, _)| k.eq_ignore_ascii_case(header))
+                    .map(|(_, v)| v.as_str())
+                    .unwrap_or_default()
+            }),
+        };
+        match value {
+            Some(value) => {
+                message.push_str(value);
+                rest = &tail[end + 1..];
+            }
+            None => {
+                message.push('{');
+                rest = &tail[1..];
+            }
+        }
+    }
+    message.push_str(rest);
+
+    message
+}
+
+/// Compute an HMAC over `message` with the given key and algorithm
+pub fn hmac_sign(
+    algorithm: HmacAlgorithm,
+    key: &[u8],
+    message: &[u8],
+    output: ByteEncoding,
+) -> Result<String, String> {
+    let bytes = match algorithm {
+        HmacAlgorithm::Sha256 => {
+            let mut mac = Hmac::<sha2::Sha256>::new_from_slice(key).map_err(|e| e.to_string())?;
+            mac.update(message);
+            mac.finalize().into_bytes().to_vec()
+        }
+        HmacAlgorithm::Sha1 => {
+            let mut mac = Hmac::<sha1::Sha1>::new_from_slice(key).map_err(|e| e.to_string())?;
+            mac.update(message);
+            mac.finalize().into_bytes().to_vec()
+        }
+    };
+
+    match output {
+        ByteEncoding::Hex => Ok(hex::encode(bytes)),
+        ByteEncoding::Base64 => Ok(base64::engine::general_purpose::STANDARD.encode(bytes)),
+        ByteEncoding::Utf8 => Err("Signature output must be hex or base64".to_string()),
+    }
+}
+
+fn unix_timestamp() -> String {
+    chrono::Utc::now().timestamp().to_string()
+}
+
+/// Render the template for `params` and sign it
+pub fn sign(params: &HmacParams) -> Result<String, String> {
+    let key = decode_key(&params.key, params.key_encoding)?;
+    let timestamp = params.timestamp.clone().unwrap_or_else(unix_timestamp);
+    let request = params.request.clone().unwrap_or_default();
+    let message = render_message(&params.message_template, &request, &timestamp);
+    hmac_sign(params.algorithm, &key, message.as_bytes(), params.output)
+}
+
+/// Recompute the signature (and timestamp) headers of a replay request
+pub fn apply_replay_signing(
+    signing: &ReplaySigning,
+    method: &str,
+    url: &str,
+    headers: &mut HashMap<String, String>,
+    body: Option<&str>,
+) -> Result<(), String> {
+    let timestamp = signing
+        .params
+        .timestamp
+        .clone()
+        .unwrap_or_else(unix_timestamp);
+
+    if let Some(ts_header) = &signing.timestamp_header {
+        headers.retain(|k, _| !k.eq_ignore_ascii_case(ts_header));
+        headers.insert(ts_header.clone(), timestamp.clone());
+    }
+
+    let params = HmacParams {
+        timestamp: Some(timestamp),
+        request: Some(SignableRequest {
+            method: method.to_string(),
+            url: url.to_string(),
+            headers: headers.clone(),
+            body: body.map(|b| b.to_string()),
+        }),
+        ..signing.params.clone()
+    };
+    let signature = sign(&params)?;
+
+    headers.retain(|k, _| !k.eq_ignore_ascii_case(&signing.header));
+    headers.insert(
+        signing.header.clone(),
+        format!("{}{}", signing.prefix.as_deref().unwrap_or(""), signature),
+    );
+    Ok(())
+}
+
+/// Compute an HMAC signature from a message template and request fields
+#[tauri::command]
+pub fn compute_hmac_signature(params: HmacParams) -> Result<String, String> {
+    sign(&params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 4231 / RFC 2202 test case 2
+    const KEY: &[u8] = b"Jefe";
+    const DATA: &[u8] = b"what do ya want for nothing?";
+
+    #[test]
+    fn test_hmac_known_vectors() {
+        assert_eq!(
+            hmac_sign(HmacAlgorithm::Sha256, KEY, DATA, ByteEncoding::Hex).unwrap(),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            hmac_sign(HmacAlgorithm::Sha1, KEY, DATA, ByteEncoding::Hex).unwrap(),
+            "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79"
+        );
+        assert_eq!(
+            hmac_sign(HmacAlgorithm::Sha256, KEY, DATA, ByteEncoding::Base64).unwrap(),
+            "W9zBRr9gdU5qBCQmCJV1x1oAPwidJzmDnexYuWTsOEM="
+        );
+    }
+
+    #[test]
+    fn test_render_message_template() {
+        let mut headers = HashMap::new();
+        headers.insert("X-Api-Key".to_string(), "abc".to_string());
+        let request = SignableRequest {
+            method: "post".into(),
+            url: "https://api.example.com/v1/orders?page=2".into(),
+            headers,
+            body: Some("{}".into()),
+        };
+
+        let message = render_message(
+            "{method}\n{path}\n{body}\n{timestamp}\n{header.x-api-key}",
+            &request,
+            "1700000000",
+        );
+        assert_eq!(message, "POST\n/v1/orders?page=2\n{}\n1700000000\nabc");
+    }
+
+    #[test]
+    fn test_render_message_does_not_expand_values() {
+        let mut headers = HashMap::new();
+        headers.insert("X-Note".to_string(), "{method}".to_string());
+        let request = SignableRequest {
+            method: "POST".into(),
+            url: "https://api.example.com/v1".into(),
+            headers,
+            body: Some(r#"{"at":"{timestamp}"}"#.into()),
+        };
+
+        let message = render_message(
+            "{body}|{header.x-note}|{timestamp}|{unknown}|{",
+            &request,
+            "42",
+        );
+        assert_eq!(message, r#"{"at":"{timestamp}"}|{method}|42|{unknown}|{"#);
+    }
+
+    #[test]
+    fn test_apply_replay_signing_sets_headers() {
+        let signing = ReplaySigning {
+            params: HmacParams {
+                algorithm: HmacAlgorithm::Sha256,
+                key: "Jefe".into(),
+                key_encoding: ByteEncoding::Utf8,
+                message_template: "what do ya want for nothing?".into(),
+                output: ByteEncoding::Hex,
+                request: None,
+                timestamp: Some("1".into()),
+            },
+            header: "X-Signature".into(),
+            prefix: Some("v1=".into()),
+            timestamp_header: Some("X-Timestamp".into()),
+        };
+
+        let mut headers = HashMap::new();
+        headers.insert("x-signature".to_string(), "stale".to_string());
+        apply_replay_signing(&signing, "GET", "https://a.test/", &mut headers, None).unwrap();
+
+        assert_eq!(headers.len(), 2);
+        assert_eq!(headers["X-Timestamp"], "1");
+        assert_eq!(
+            headers["X-Signature"],
+            "v1=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
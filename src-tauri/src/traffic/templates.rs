@@ -0,0 +1,147 @@
+//! Saved request templates: named `ReplayRequest`s that can be loaded back
+//! into the replay editor. Stored in `data/request_templates.json`, apart from
+//! rules and sessions.
+
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use super::commands::ReplayRequest;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestTemplate {
+    pub name: String,
+    pub request: ReplayRequest,
+    /// Unix millis
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+pub struct TemplateStorage {
+    path: PathBuf,
+}
+
+impl TemplateStorage {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Create storage from app config
+    pub fn from_config() -> Result<Self, String> {
+        let data_dir = crate::config::get_data_dir()?;
+        Ok(Self::new(data_dir.join("request_templates.json")))
+    }
+
+    /// Templates sorted by name
+    pub fn list(&self) -> Result<Vec<RequestTemplate>, String> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&self.path)
+            .map_err(|e| format!("Failed to read request templates: {}", e))?;
+        let mut templates: Vec<RequestTemplate> = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse request templates: {}", e))?;
+        templates.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(templates)
+    }
+
+    fn write(&self, templates: &[RequestTemplate]) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(templates)
+            .map_err(|e| format!("Failed to serialize request templates: {}", e))?;
+        fs::write(&self.path, content)
+            .map_err(|e| format!("Failed to write request templates: {}", e))
+    }
+
+    /// Save under `name`, replacing an existing template with the same name
+    pub fn save(&self, name: &str, request: ReplayRequest) -> Result<(), String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Template name cannot be empty".to_string());
+        }
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut templates = self.list()?;
+        match templates.iter_mut().find(|t| t.name == name) {
+            Some(existing) => {
+                existing.request = request;
+                existing.updated_at = now;
+            }
+            None => templates.push(RequestTemplate {
+                name: name.to_string(),
+                request,
+                created_at: now,
+                updated_at: now,
+            }),
+        }
+        self.write(&templates)
+    }
+
+    pub fn delete(&self, name: &str) -> Result<(), String> {
+        let mut templates = self.list()?;
+        let before = templates.len();
+        templates.retain(|t| t.name != name);
+        if templates.len() == before {
+            return Err(format!("Request template not found: {}", name));
+        }
+        self.write(&templates)
+    }
+}
+
+#[tauri::command]
+pub fn save_request_template(name: String, req: ReplayRequest) -> Result<(), String> {
+    TemplateStorage::from_config()?.save(&name, req)
+}
+
+#[tauri::command]
+pub fn list_request_templates() -> Result<Vec<RequestTemplate>, String> {
+    TemplateStorage::from_config()?.list()
+}
+
+#[tauri::command]
+pub fn delete_request_template(name: String) -> Result<(), String> {
+    TemplateStorage::from_config()?.delete(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn request(url: &str) -> ReplayRequest {
+        ReplayRequest {
+            method: "POST".into(),
+            url: url.into(),
+            body: Some("{}".into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_templates_save_replace_and_delete() {
+        let temp = TempDir::new().unwrap();
+        let storage = TemplateStorage::new(temp.path().join("request_templates.json"));
+        assert!(storage.list().unwrap().is_empty());
+
+        storage
+            .save("login", request("https://a.test/login"))
+            .unwrap();
+        storage
+            .save("create user", request("https://a.test/users"))
+            .unwrap();
+        storage
+            .save("login", request("https://b.test/login"))
+            .unwrap();
+
+        let templates = storage.list().unwrap();
+        let names: Vec<&str> = templates.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["create user", "login"]);
+        assert_eq!(templates[1].request.url, "https://b.test/login");
+
+        assert!(storage.save("  ", request("https://a.test")).is_err());
+        storage.delete("login").unwrap();
+        assert!(storage.delete("login").is_err());
+        assert_eq!(storage.list().unwrap().len(), 1);
+    }
+}
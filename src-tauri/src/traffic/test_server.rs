@@ -0,0 +1,106 @@
+//! Loopback HTTP server shared by the replay tests.
+//!
+//! Each test describes its responses with a closure over the captured request,
+//! so canned, echoing, redirecting and slow servers all use one accept loop.
+
+use std::io::{Read, Write};
+use std::thread::JoinHandle;
+
+/// A request as the server received it.
+pub struct CapturedRequest {
+    pub method: String,
+    pub path: String,
+    /// Header block, lowercased
+    pub head: String,
+    pub body: Vec<u8>,
+}
+
+pub struct TestServer {
+    pub base: String,
+    handle: JoinHandle<Vec<CapturedRequest>>,
+}
+
+impl TestServer {
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base, path)
+    }
+
+    /// Wait for the server to finish and return what it received.
+    pub fn requests(self) -> Vec<CapturedRequest> {
+        self.handle.join().unwrap()
+    }
+}
+
+/// Answer `connections` requests on a loopback port, one per connection.
+pub fn serve<F>(connections: usize, respond: F) -> TestServer
+where
+    F: Fn(&CapturedRequest) -> Vec<u8> + Send + 'static,
+{
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let handle = std::thread::spawn(move || {
+        let mut received = Vec::new();
+        for _ in 0..connections {
+            let (mut stream, _) = listener.accept().unwrap();
+            let request = read_request(&mut stream);
+            let _ = stream.write_all(&respond(&request));
+            received.push(request);
+        }
+        received
+    });
+    TestServer {
+        base: format!("http://{}", addr),
+        handle,
+    }
+}
+
+/// Build a `Connection: close` response with the given extra header lines.
+pub fn response(status: &str, headers: &str, body: &[u8]) -> Vec<u8> {
+    let mut out = format!(
+        "HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        headers,
+        body.len()
+    )
+    .into_bytes();
+    out.extend_from_slice(body);
+    out
+}
+
+fn read_request(stream: &mut std::net::TcpStream) -> CapturedRequest {
+    let mut buf = [0u8; 4096];
+    let mut raw = Vec::new();
+    let header_end = loop {
+        if let Some(pos) = raw.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        let n = stream.read(&mut buf).unwrap();
+        if n == 0 {
+            break raw.len();
+        }
+        raw.extend_from_slice(&buf[..n]);
+    };
+    let head = String::from_utf8_lossy(&raw[..header_end]).to_string();
+    let mut request_line = head.split_whitespace();
+    let method = request_line.next().unwrap_or("").to_string();
+    let path = request_line.next().unwrap_or("").to_string();
+    let head = head.to_lowercase();
+    let length: usize = head
+        .lines()
+        .find_map(|l| l.strip_prefix("content-length:"))
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(0);
+    while raw.len() < header_end + length {
+        let n = stream.read(&mut buf).unwrap();
+        if n == 0 {
+            break;
+        }
+        raw.extend_from_slice(&buf[..n]);
+    }
+    CapturedRequest {
+        method,
+        path,
+        head,
+        body: raw[header_end..].to_vec(),
+    }
+}
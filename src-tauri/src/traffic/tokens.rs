@@ -0,0 +1,293 @@
+//! Extraction of auth tokens from captured traffic and a small in-memory store
+//! so extracted tokens can be injected into replays.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::session::model::{Flow, HarHeader};
+
+/// JSON field names that usually carry a token
+const TOKEN_FIELDS: &[&str] = &[
+    "access_token",
+    "accesstoken",
+    "refresh_token",
+    "refreshtoken",
+    "id_token",
+    "idtoken",
+    "token",
+    "jwt",
+    "auth_token",
+    "authtoken",
+    "session_token",
+    "sessiontoken",
+];
+
+/// Cookie name fragments that usually mark a session cookie
+const SESSION_COOKIE_HINTS: &[&str] = &["session", "sess", "sid", "token", "auth", "jwt"];
+
+/// Maximum JSON nesting walked while looking for token fields
+const MAX_JSON_DEPTH: usize = 8;
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenInfo {
+    pub flow_id: String,
+    /// "request_header", "response_header", "set_cookie" or "response_body"
+    pub source: String,
+    pub name: String,
+    pub value: String,
+    /// Where the token was found, e.g. `response.body.data.access_token`
+    pub location: String,
+}
+
+fn header_tokens(flow_id: &str, headers: &[HarHeader], side: &str, out: &mut Vec<TokenInfo>) {
+    for header in headers {
+        if header.name.eq_ignore_ascii_case("authorization") && !header.value.trim().is_empty() {
+            out.push(TokenInfo {
+                flow_id: flow_id.to_string(),
+                source: format!("{}_header", side),
+                name: header.name.clone(),
+                value: header.value.trim().to_string(),
+                location: format!("{}.headers.{}", side, header.name),
+            });
+        }
+    }
+}
+
+fn cookie_tokens(flow_id: &str, headers: &[HarHeader], out: &mut Vec<TokenInfo>) {
+    for header in headers {
+        if !header.name.eq_ignore_ascii_case("set-cookie") {
+            continue;
+        }
+        let pair = header.value.split(';').next().unwrap_or_default();
+        let Some((name, value)) = pair.split_once('=') else {
+            continue;
+        };
+        let (name, value) = (name.trim(), value.trim());
+        let lower = name.to_ascii_lowercase();
+        if value.is_empty() || !SESSION_COOKIE_HINTS.iter().any(|h| lower.contains(h)) {
+            continue;
+        }
+        out.push(TokenInfo {
+            flow_id: flow_id.to_string(),
+            source: "set_cookie".to_string(),
+            name: name.to_string(),
+            value: value.to_string(),
+            location: format!("response.cookies.{}", name),
+        });
+    }
+}
+
+fn json_tokens(
+    flow_id: &str,
+    value: &serde_json::Value,
+    path: &str,
+    depth: usize,
+    out: &mut Vec<TokenInfo>,
+) {
+    if depth > MAX_JSON_DEPTH {
+        return;
+    }
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, child) in map {
+                let child_path = format!("{}.{}", path, key);
+                if let serde_json::Value::String(s) = child {
+                    if !s.is_empty() && TOKEN_FIELDS.contains(&key.to_ascii_lowercase().as_str()) {
+                        out.push(TokenInfo {
+                            flow_id: flow_id.to_string(),
+                            source: "response_body".to_string(),
+                            name: key.clone(),
+                            value: s.clone(),
+                            location: child_path.clone(),
+                        });
+                    }
+                }
+                json_tokens(flow_id, child, &child_path, depth + 1, out);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for (i, child) in items.iter().enumerate() {
+                json_tokens(flow_id, child, &format!("{}[{}]", path, i), depth + 1, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Scan flows for tokens in Authorization headers, session cookies and JSON
+/// response fields. Identical values found more than once are reported once.
+pub fn extract_tokens_from(flows: &[Flow]) -> Vec<TokenInfo> {
+    let mut found = Vec::new();
+    for flow in flows {
+        header_tokens(&flow.id, &flow.request.headers, "request", &mut found);
+        header_tokens(&flow.id, &flow.response.headers, "response", &mut found);
+        cookie_tokens(&flow.id, &flow.response.headers, &mut found);
+
+        let content = &flow.response.content;
+        if content.encoding.as_deref() != Some("base64") {
+            if let Some(text) = content.text.as_deref() {
+                if let Ok(json) = serde_json::from_str::<serde_json::Value>(text) {
+                    json_tokens(&flow.id, &json, "response.body", 0, &mut found);
+                }
+            }
+        }
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    found.retain(|t| seen.insert((t.name.to_ascii_lowercase(), t.value.clone())));
+    found
+}
+
+/// Find tokens in the given flows
+#[tauri::command]
+pub fn extract_tokens(flows: Vec<Flow>) -> Vec<TokenInfo> {
+    extract_tokens_from(&flows)
+}
+
+// ==================== Token Store ====================
+
+lazy_static::lazy_static! {
+    /// Named tokens kept for the lifetime of the app, never written to disk
+    static ref TOKEN_STORE: Mutex<HashMap<String, String>> = Mutex::new(HashMap::new());
+}
+
+/// Inject a stored token into a replay request header
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenInjection {
+    /// Name of the stored token
+    pub name: String,
+    /// Target header; defaults to `Authorization`
+    #[serde(default)]
+    pub header: Option<String>,
+    /// Prefix for the header value; defaults to `Bearer ` for Authorization
+    #[serde(default)]
+    pub prefix: Option<String>,
+}
+
+/// Set the header described by `injection` from the token store
+pub fn apply_token_injection(
+    injection: &TokenInjection,
+    headers: &mut HashMap<String, String>,
+) -> Result<(), String> {
+    let token = TOKEN_STORE
+        .lock()
+        .map_err(|_| "token store lock poisoned".to_string())?
+        .get(&injection.name)
+        .cloned()
+        .ok_or_else(|| format!("No stored token named '{}'", injection.name))?;
+
+    let header = injection.header.as_deref().unwrap_or("Authorization");
+    let prefix = match &injection.prefix {
+        Some(p) => p.as_str(),
+        None if header.eq_ignore_ascii_case("authorization") && !token.contains(' ') => "Bearer ",
+        None => "",
+    };
+
+    headers.retain(|k, _| !k.eq_ignore_ascii_case(header));
+    headers.insert(header.to_string(), format!("{}{}", prefix, token));
+    Ok(())
+}
+
+#[tauri::command]
+pub fn store_token(name: String, value: String) -> Result<(), String> {
+    TOKEN_STORE
+        .lock()
+        .map_err(|_| "token store lock poisoned".to_string())?
+        .insert(name, value);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_stored_tokens() -> Result<HashMap<String, String>, String> {
+    Ok(TOKEN_STORE
+        .lock()
+        .map_err(|_| "token store lock poisoned".to_string())?
+        .clone())
+}
+
+#[tauri::command]
+pub fn remove_stored_token(name: String) -> Result<(), String> {
+    TOKEN_STORE
+        .lock()
+        .map_err(|_| "token store lock poisoned".to_string())?
+        .remove(&name);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::fixtures::header;
+
+    #[test]
+    fn test_extract_tokens_from_headers_cookies_and_json() {
+        let mut login = Flow {
+            id: "login".to_string(),
+            ..Default::default()
+        };
+        login.response.headers = vec![
+            header("Set-Cookie", "SESSIONID=abc123; Path=/; HttpOnly"),
+            header("Set-Cookie", "theme=dark; Path=/"),
+        ];
+        login.response.content.text = Some(
+            r#"{"data":{"access_token":"at-1","expires_in":3600},"refreshToken":"rt-1"}"#.into(),
+        );
+
+        let mut api = Flow {
+            id: "api".to_string(),
+            ..Default::default()
+        };
+        api.request.headers = vec![header("Authorization", "Bearer at-1")];
+
+        let tokens = extract_tokens_from(&[login, api]);
+        let summary: Vec<(&str, &str, &str)> = tokens
+            .iter()
+            .map(|t| (t.source.as_str(), t.name.as_str(), t.location.as_str()))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("set_cookie", "SESSIONID", "response.cookies.SESSIONID"),
+                (
+                    "response_body",
+                    "access_token",
+                    "response.body.data.access_token"
+                ),
+                (
+                    "response_body",
+                    "refreshToken",
+                    "response.body.refreshToken"
+                ),
+                (
+                    "request_header",
+                    "Authorization",
+                    "request.headers.Authorization"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_token_injection_uses_bearer_prefix() {
+        store_token("test-injection".into(), "xyz".into()).unwrap();
+
+        let mut headers = HashMap::new();
+        headers.insert("authorization".to_string(), "Bearer old".to_string());
+        let injection = TokenInjection {
+            name: "test-injection".into(),
+            header: None,
+            prefix: None,
+        };
+        apply_token_injection(&injection, &mut headers).unwrap();
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers["Authorization"], "Bearer xyz");
+
+        remove_stored_token("test-injection".into()).unwrap();
+        assert!(apply_token_injection(&injection, &mut headers).is_err());
+    }
+}
@@ -3,7 +3,7 @@ import { fetch as tauriFetch } from "@tauri-apps/plugin-http";
 import { exit } from "@tauri-apps/plugin-process";
 import { useMemo } from "react";
 import { notify } from "../../lib/notify";
-import { useSettingsStore } from "../../stores/settingsStore";
+import { useProxyStore } from "../../stores/proxyStore";
 import { useTrafficStore } from "../../stores/trafficStore";
 import { useUIStore } from "../../stores/uiStore";
 import { CommandCenter } from "../ai/CommandCenter";
@@ -31,7 +31,7 @@ export function GlobalModals({ showExitModal, setShowExitModal }: GlobalModalsPr
 
   const handleResumeBreakpoint = async (flowId: string, modifications: any) => {
     try {
-      const port = useSettingsStore.getState().config.proxy_port;
+      const { port } = useProxyStore.getState();
       await tauriFetch(`http://127.0.0.1:${port}/_relay/resume`, {
         method: "POST",
         body: JSON.stringify({ id: flowId, modifications }),
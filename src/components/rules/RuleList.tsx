@@ -9,6 +9,7 @@ import {
   FileSignature,
   Globe,
   LayoutList,
+  PauseCircle,
   ShieldCheck,
   Sparkles,
   Trash2,
@@ -44,11 +45,14 @@ export function RuleList({ rules, onEdit, conflicts = {}, selectedRuleId }: Rule
       case "map_local":
         return <FileCode className="w-3.5 h-3.5" />;
       case "map_remote":
+      case "redirect":
         return <Globe className="w-3.5 h-3.5" />;
       case "rewrite_header":
         return <LayoutList className="w-3.5 h-3.5" />;
       case "throttle":
         return <Wifi className="w-3.5 h-3.5" />;
+      case "breakpoint":
+        return <PauseCircle className="w-3.5 h-3.5" />;
       case "block_request":
         return <Ban className="w-3.5 h-3.5" />;
       default:
@@ -177,6 +181,13 @@ export function RuleList({ rules, onEdit, conflicts = {}, selectedRuleId }: Rule
           }) + actionCountStr
         );
       }
+      case "redirect":
+        return (
+          t("rules.summary.redirect", {
+            status: primaryAction.statusCode,
+            url: primaryAction.targetUrl,
+          }) + actionCountStr
+        );
       case "rewrite_body": {
         const target = t(`rules.summary.types.${primaryAction.target}`);
         let typeKey = "set";
@@ -193,6 +204,12 @@ export function RuleList({ rules, onEdit, conflicts = {}, selectedRuleId }: Rule
         const resCount = primaryAction.headers?.response?.length || 0;
         return t("rules.summary.rewrite_header", { req: reqCount, res: resCount }) + actionCountStr;
       }
+      case "breakpoint":
+        return (
+          t("rules.summary.breakpoint", {
+            phase: t(`rules.summary.types.${primaryAction.phase ?? "request"}`),
+          }) + actionCountStr
+        );
       case "block_request":
         return t("rules.summary.block") + actionCountStr;
       default:
@@ -32,6 +32,12 @@ interface DetailedCertInfo {
   fingerprint?: string;
 }
 
+interface CaRegenerateResult {
+  engine_restarted: boolean;
+  restart_required: boolean;
+  warning?: string | null;
+}
+
 export function CertificateSettings() {
   const { t } = useTranslation();
   const [certInfo, setCertInfo] = useState<DetailedCertInfo | null>(null);
@@ -99,7 +105,15 @@ export function CertificateSettings() {
   const handleInstallAutomated = () =>
     handleAction("install", () => invoke("install_cert_automated"));
   const handleRemoveAutomated = () => handleAction("remove", () => invoke("remove_cert_automated"));
-  const handleRegenerate = () => handleAction("regenerate", () => invoke("regenerate_root_ca"));
+  const handleRegenerate = () =>
+    handleAction("regenerate", async () => {
+      const result = await invoke<CaRegenerateResult>("regenerate_root_ca");
+      if (result.warning) {
+        const { notify } = await import("../../lib/notify");
+        if (result.restart_required) notify.warning(result.warning);
+        else notify.info(result.warning);
+      }
+    });
 
   const handleOpenCertDir = async () => {
     try {
@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { AnimatePresence, motion } from "framer-motion";
 import {
   AlertTriangle,
@@ -23,6 +24,8 @@ import { Button } from "../common/Button";
 import { EmptyState } from "../common/EmptyState";
 import { Tooltip } from "../common/Tooltip";
 
+const MAX_LOG_LINES = 1000;
+
 // --- Components ---
 
 /**
@@ -72,7 +75,7 @@ export function LogViewer({ onClose }: LogViewerProps) {
     try {
       const data = await invoke<string[]>("get_logs", {
         logName: logType,
-        lines: 1000,
+        lines: MAX_LOG_LINES,
       });
       setLogs(data);
     } catch (error) {
@@ -86,6 +89,23 @@ export function LogViewer({ onClose }: LogViewerProps) {
     fetchLogs();
   }, [fetchLogs]);
 
+  // Live tail: the backend only emits while a viewer has streaming switched on
+  useEffect(() => {
+    invoke("set_log_streaming", { enabled: true }).catch(() => {});
+    const unlisten = listen<{ domain: string; line: string; timestamp: string }>(
+      "proxy-log",
+      (event) => {
+        const { domain, line, timestamp } = event.payload;
+        if (domain !== logType) return;
+        setLogs((prev) => [...prev, `[${timestamp}] ${line}`].slice(-MAX_LOG_LINES));
+      },
+    );
+    return () => {
+      unlisten.then((f) => f());
+      invoke("set_log_streaming", { enabled: false }).catch(() => {});
+    };
+  }, [logType]);
+
   // Highlighting Logic - Memoized for stability
   const colorizeLog = useMemo(
     () => (text: string) => {
@@ -287,8 +287,18 @@ export function useAppInit({ setShowExitModal }: UseAppInitProps) {
         source: "System",
       });
     });
+    const unlistenRestarted = listen<number>("proxy-engine-restarted", (event) => {
+      Logger.info(`[ProxyEngine] Restarted after crash (attempt ${event.payload})`);
+      useProxyStore.getState().reattachAfterRestart();
+    });
+    const unlistenGaveUp = listen<string>("proxy-engine-restart-failed", (event) => {
+      Logger.error("[ProxyEngine] Auto-restart gave up:", event.payload);
+      useProxyStore.getState().checkStatus();
+    });
     return () => {
       unlisten.then((f) => f());
+      unlistenRestarted.then((f) => f());
+      unlistenGaveUp.then((f) => f());
     };
   }, [t]);
 
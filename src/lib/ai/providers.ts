@@ -12,6 +12,10 @@ export const AI_PROVIDERS: AIProvider[] = [
     id: "openai",
     description: "OpenAI",
   },
+  {
+    id: "anthropic",
+    description: "Anthropic",
+  },
   {
     id: "deepseek",
     description: "DeepSeek",
@@ -27,11 +27,22 @@ const RULE_TYPE_THEME_MAP: Record<RuleType, RuleTypeTheme> = {
     bg: "bg-rule-map-remote-soft",
     border: "border-rule-map-remote-soft",
   },
+  // Redirects share Map Remote's palette: both send the client elsewhere
+  redirect: {
+    text: "text-rule-map-remote",
+    bg: "bg-rule-map-remote-soft",
+    border: "border-rule-map-remote-soft",
+  },
   throttle: {
     text: "text-rule-throttle",
     bg: "bg-rule-throttle-soft",
     border: "border-rule-throttle-soft",
   },
+  breakpoint: {
+    text: "text-rule-breakpoint",
+    bg: "bg-rule-breakpoint-soft",
+    border: "border-rule-breakpoint-soft",
+  },
   block_request: {
     text: "text-rule-block",
     bg: "bg-rule-block-soft",
@@ -51,6 +62,7 @@ const RULE_TYPE_INDICATOR_BG_MAP: Record<RuleIndicatorType, string> = {
   rewrite_body: "bg-rule-rewrite-body",
   map_local: "bg-rule-map-local",
   map_remote: "bg-rule-map-remote",
+  redirect: "bg-rule-map-remote",
   rewrite_header: "bg-rule-rewrite-header",
   throttle: "bg-rule-throttle",
   block_request: "bg-rule-block",
@@ -1,7 +1,7 @@
 import { fetch as tauriFetch } from "@tauri-apps/plugin-http";
 import { create } from "zustand";
 import { Logger } from "../lib/logger";
-import { useSettingsStore } from "./settingsStore";
+import { useProxyStore } from "./proxyStore";
 
 export interface Breakpoint {
   id: string;
@@ -35,8 +35,8 @@ export const useBreakpointStore = create<BreakpointState>((set) => ({
     };
 
     try {
-      const { config } = useSettingsStore.getState();
-      await tauriFetch(`http://127.0.0.1:${config.proxy_port}/_relay/breakpoints`, {
+      const { port } = useProxyStore.getState();
+      await tauriFetch(`http://127.0.0.1:${port}/_relay/breakpoints`, {
         method: "POST",
         body: JSON.stringify({
           action: "add",
@@ -62,8 +62,8 @@ export const useBreakpointStore = create<BreakpointState>((set) => ({
 
   removeBreakpoint: async (id) => {
     try {
-      const { config } = useSettingsStore.getState();
-      await tauriFetch(`http://127.0.0.1:${config.proxy_port}/_relay/breakpoints`, {
+      const { port } = useProxyStore.getState();
+      await tauriFetch(`http://127.0.0.1:${port}/_relay/breakpoints`, {
         method: "POST",
         body: JSON.stringify({ action: "remove", id }),
         cache: "no-store",
@@ -85,8 +85,8 @@ export const useBreakpointStore = create<BreakpointState>((set) => ({
 
   clearBreakpoints: async () => {
     try {
-      const { config } = useSettingsStore.getState();
-      await tauriFetch(`http://127.0.0.1:${config.proxy_port}/_relay/breakpoints`, {
+      const { port } = useProxyStore.getState();
+      await tauriFetch(`http://127.0.0.1:${port}/_relay/breakpoints`, {
         method: "POST",
         body: JSON.stringify({ action: "clear" }),
         cache: "no-store",
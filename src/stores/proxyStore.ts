@@ -5,9 +5,19 @@ import { notifyProxyEvent } from "../lib/proxyNotifications";
 import { notifyScriptLoadIssues, resetScriptLoadAlertDedupe } from "../lib/scriptLoadAlerts";
 import { finalPollAndStop, startTrafficMonitor, stopTrafficMonitor } from "../lib/traffic";
 import { useScriptStore } from "./scriptStore";
+import { useSettingsStore } from "./settingsStore";
 
 const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
 
+interface RestartError {
+  phase: "stop" | "portRelease" | "config" | "start";
+  message: string;
+}
+
+function isRestartError(error: unknown): error is RestartError {
+  return typeof error === "object" && error !== null && "phase" in error && "message" in error;
+}
+
 function shouldAttemptRecovery(
   status: { running: boolean },
   currentState: { isRecovering: boolean; recoveryAttempts: number },
@@ -32,6 +42,7 @@ interface ProxyStore {
   startProxy: () => Promise<void>; // Now sets active=true
   stopProxy: () => Promise<void>; // Now sets active=false
   restartProxy: () => Promise<void>; // Restart engine to reload scripts
+  reattachAfterRestart: () => Promise<void>; // Resume capture after a backend auto-restart
   checkStatus: () => Promise<void>;
   checkCertTrust: () => Promise<void>;
   setCertWarningIgnored: (ignored: boolean) => Promise<void>;
@@ -69,6 +80,7 @@ export const useProxyStore = create<ProxyStore>((set) => ({
         running: boolean;
         active: boolean;
         active_scripts: string[];
+        port: number | null;
       }>("get_proxy_status");
 
       // Engine should auto-start with the app
@@ -81,9 +93,10 @@ export const useProxyStore = create<ProxyStore>((set) => ({
 
       // Load config to get the port
       const config = await invoke<{ proxy_port: number }>("load_config");
+      const port = status.port ?? config.proxy_port;
 
       // Start Traffic Monitor first (creates session before traffic starts)
-      await startTrafficMonitor(config.proxy_port);
+      await startTrafficMonitor(port);
 
       // Activate traffic AFTER session is created
       await invoke("set_proxy_active", { active: true });
@@ -98,12 +111,12 @@ export const useProxyStore = create<ProxyStore>((set) => ({
       set({
         running: true,
         active: true,
-        port: config.proxy_port,
+        port,
         activeScripts: activeScriptNames,
       });
 
       await notifyProxyEvent("start_success", {
-        port: config.proxy_port,
+        port,
         scriptCount: activeScriptNames.length,
       });
     } catch (error) {
@@ -157,11 +170,13 @@ export const useProxyStore = create<ProxyStore>((set) => ({
       // Restart the engine (stop + start, which reloads scripts)
       await invoke("restart_proxy");
 
-      // Load config to get the port
+      // The engine may have fallen back to another port if the configured one was taken
       const config = await invoke<{ proxy_port: number }>("load_config");
+      const status = await invoke<{ port: number | null }>("get_proxy_status");
+      const port = status.port ?? config.proxy_port;
 
       // Start Traffic Monitor again
-      await startTrafficMonitor(config.proxy_port);
+      await startTrafficMonitor(port);
 
       // Activate traffic AFTER session is created
       await invoke("set_proxy_active", { active: true });
@@ -173,7 +188,7 @@ export const useProxyStore = create<ProxyStore>((set) => ({
       set({
         running: true,
         active: true,
-        port: config.proxy_port,
+        port,
         activeScripts: activeScriptNames,
       });
 
@@ -181,14 +196,16 @@ export const useProxyStore = create<ProxyStore>((set) => ({
       useScriptStore.getState().clearModifiedSinceStart();
 
       await notifyProxyEvent("restart_success", {
-        port: config.proxy_port,
+        port,
         scriptCount: activeScriptNames.length,
       });
 
       resetScriptLoadAlertDedupe();
-      await notifyScriptLoadIssues(config.proxy_port, true);
+      await notifyScriptLoadIssues(port, true);
     } catch (error) {
-      const errorMsg = formatError(error);
+      const errorMsg = isRestartError(error)
+        ? `${error.phase}: ${error.message}`
+        : formatError(error);
       await Logger.error("Failed to restart proxy:", errorMsg);
       set({ error: errorMsg, active: false });
 
@@ -198,17 +215,46 @@ export const useProxyStore = create<ProxyStore>((set) => ({
     }
   },
 
+  reattachAfterRestart: async () => {
+    try {
+      // The old poller points at a dead engine; the session is kept
+      stopTrafficMonitor();
+
+      const status = await invoke<{ running: boolean; active: boolean; port: number | null }>(
+        "get_proxy_status",
+      );
+      if (!status.running) {
+        set({ running: false, active: false });
+        return;
+      }
+
+      const config = await invoke<{ proxy_port: number }>("load_config");
+      const port = status.port ?? config.proxy_port;
+      // The backend re-activates traffic when it was active before the crash
+      if (status.active) {
+        await startTrafficMonitor(port);
+      }
+
+      set({ running: true, active: status.active, port, error: null });
+    } catch (error) {
+      Logger.error("Failed to resume traffic capture after engine restart:", error);
+    }
+  },
+
   checkStatus: async () => {
     try {
       const status = await invoke<{
         running: boolean;
         active: boolean;
         active_scripts: string[];
+        port: number | null;
       }>("get_proxy_status");
       const currentState = useProxyStore.getState();
 
       // Unexpected engine crash detection & auto-recovery
-      if (shouldAttemptRecovery(status, currentState)) {
+      // With auto_restart_on_crash the backend owns recovery
+      const backendRecovers = useSettingsStore.getState().config.auto_restart_on_crash ?? false;
+      if (!backendRecovers && shouldAttemptRecovery(status, currentState)) {
         Logger.warn(
           `Proxy engine crash detected (Attempts: ${currentState.recoveryAttempts + 1}/3). Triggering auto-recovery...`,
         );
@@ -272,7 +318,7 @@ export const useProxyStore = create<ProxyStore>((set) => ({
         active: status.active,
         activeScripts: activeScriptNames,
         ipAddress,
-        port: config.proxy_port,
+        port: status.port ?? config.proxy_port,
         certTrusted: isTrusted,
         certWarningIgnored: config.cert_warning_ignored,
       });
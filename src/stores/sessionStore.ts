@@ -6,7 +6,7 @@ import { startImportPolling } from "../hooks/useImportPolling";
 import { Logger } from "../lib/logger";
 import { setPollTimestamp } from "../lib/traffic";
 import type { Session, SessionMetadata } from "../types/session";
-import { useSettingsStore } from "./settingsStore";
+import { useProxyStore } from "./proxyStore";
 import { useTrafficStore } from "./trafficStore";
 
 // Database session type (from backend)
@@ -14,6 +14,8 @@ export interface DbSessionMetadata extends Partial<SessionMetadata> {
   type?: string;
   status?: "importing" | "ready" | "error" | string;
   error_message?: string;
+  // Flows stored so far by a streaming HAR import
+  imported_count?: number;
   [key: string]: unknown;
 }
 
@@ -66,7 +68,7 @@ export const useSessionStore = create<SessionStore>((set, get) => ({
       set({ loadingSessions: true });
     }
     try {
-      const port = useSettingsStore.getState().config.proxy_port;
+      const port = useProxyStore.getState().port;
       const response = await tauriFetch(`http://127.0.0.1:${port}/_relay/sessions`, {
         cache: "no-store",
       });
@@ -102,7 +104,7 @@ export const useSessionStore = create<SessionStore>((set, get) => ({
   switchDbSession: async (sessionId: string) => {
     set({ loading: true });
     try {
-      const port = useSettingsStore.getState().config.proxy_port;
+      const port = useProxyStore.getState().port;
 
       // Load the session data for viewing (limit initial load to prevent OOM)
       const response = await tauriFetch(
@@ -145,7 +147,7 @@ export const useSessionStore = create<SessionStore>((set, get) => ({
   // Delete a database session
   deleteDbSession: async (sessionId: string) => {
     try {
-      const port = useSettingsStore.getState().config.proxy_port;
+      const port = useProxyStore.getState().port;
       const response = await tauriFetch(`http://127.0.0.1:${port}/_relay/session/delete`, {
         method: "POST",
         headers: { "Content-Type": "application/json" },
@@ -181,7 +183,7 @@ export const useSessionStore = create<SessionStore>((set, get) => ({
   deleteAllDbSessions: async () => {
     set({ loadingSessions: true });
     try {
-      const port = useSettingsStore.getState().config.proxy_port;
+      const port = useProxyStore.getState().port;
       Logger.info("Requesting clearance of all historical sessions...");
       const response = await tauriFetch(`http://127.0.0.1:${port}/_relay/sessions/delete_all`, {
         method: "POST",
@@ -232,7 +234,7 @@ export const useSessionStore = create<SessionStore>((set, get) => ({
   resetDatabase: async () => {
     set({ loadingSessions: true });
     try {
-      const port = useSettingsStore.getState().config.proxy_port;
+      const port = useProxyStore.getState().port;
       Logger.info("Resetting entire database...");
       const response = await tauriFetch(`http://127.0.0.1:${port}/_relay/database/reset`, {
         method: "POST",
@@ -304,7 +306,7 @@ export const useSessionStore = create<SessionStore>((set, get) => ({
       };
 
       // Send metadata to backend to write file
-      const port = useSettingsStore.getState().config.proxy_port;
+      const port = useProxyStore.getState().port;
       const sessionId = get().showSessionId;
       const url = new URL(`http://127.0.0.1:${port}/_relay/export_session`);
       url.searchParams.append("path", path); // fetch handles encoding
@@ -372,7 +374,7 @@ export const useSessionStore = create<SessionStore>((set, get) => ({
         Logger.info(`Loading session from: ${path}`);
 
         // Send the file PATH to Python — Python reads the file directly.
-        const port = useSettingsStore.getState().config.proxy_port;
+        const port = useProxyStore.getState().port;
         const response = await tauriFetch(`http://127.0.0.1:${port}/_relay/import_session_file`, {
           method: "POST",
           headers: { "Content-Type": "application/json" },
@@ -447,7 +449,7 @@ export const useSessionStore = create<SessionStore>((set, get) => ({
       }
 
       // Request backend to export HAR to file
-      const port = useSettingsStore.getState().config.proxy_port;
+      const port = useProxyStore.getState().port;
       const sessionId = get().showSessionId;
       const url = new URL(`http://127.0.0.1:${port}/_relay/export_har`);
       url.searchParams.append("path", path);
@@ -496,7 +498,7 @@ export const useSessionStore = create<SessionStore>((set, get) => ({
 
         // Send the file PATH to Python — Python reads and parses the file directly.
         // Previously: readTextFile → JSON.parse → JSON.stringify → POST (3× memory).
-        const port = useSettingsStore.getState().config.proxy_port;
+        const port = useProxyStore.getState().port;
         const response = await tauriFetch(`http://127.0.0.1:${port}/_relay/import_har_file`, {
           method: "POST",
           headers: { "Content-Type": "application/json" },
@@ -554,7 +556,7 @@ export const useSessionStore = create<SessionStore>((set, get) => ({
     useTrafficStore.getState().clearLocal();
 
     // Clear backend database
-    const port = useSettingsStore.getState().config.proxy_port;
+    const port = useProxyStore.getState().port;
     try {
       const response = await tauriFetch(`http://127.0.0.1:${port}/_relay/session/clear`, {
         method: "POST",
@@ -14,7 +14,12 @@ export interface AppConfig {
     enabled: boolean;
     url: string;
     bypass_domains: string;
+    username?: string;
+    /** Write-only: stored in the secret store on save, never read back */
+    password?: string;
   };
+  /** Backend restarts a crashed engine itself (with backoff) */
+  auto_restart_on_crash?: boolean;
   always_on_top: boolean;
   plugin_registry_url: string;
   auto_check_update: boolean;
@@ -24,6 +29,7 @@ export interface AppConfig {
   enable_vibrancy: boolean;
   disable_gpu_acceleration: boolean;
   ai_config?: any;
+  ai_provider_configs?: Record<string, any>;
   // Optional so that configs written by older versions (which lack this field)
   // deserialise without error. Rust's #[serde(default)] ensures the backend
   // always returns it, but defensive UI code should use `?? { enabled: false, port: 7090 }`.
@@ -84,6 +90,7 @@ export const useSettingsStore = create<SettingsStore>((set, get) => ({
     language: "en",
     upstream_proxy: {
       enabled: false,
+      kind: "http",
       url: "",
       bypass_domains: "localhost, 127.0.0.1",
     },
@@ -116,6 +116,10 @@ export interface HttpSendResponse {
   encoding: "text" | "base64";
   truncated: boolean;
   total_bytes: number;
+  /** Milliseconds from send until the body was fully read. */
+  elapsed_ms: number;
+  /** Milliseconds from send until response headers arrived. */
+  ttfb_ms: number;
 }
 
 export interface ContextMenuItemConfig {
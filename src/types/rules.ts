@@ -3,10 +3,12 @@
 export type RuleType =
   | "map_local"
   | "map_remote"
+  | "redirect"
   | "rewrite_header"
   | "rewrite_body"
   // 'mock_response' removed
   | "throttle"
+  | "breakpoint"
   | "block_request";
 
 export type UrlMatchType = "contains" | "exact" | "regex" | "wildcard";
@@ -39,6 +41,7 @@ export interface MapLocalAction {
   type: "map_local";
   source?: "file" | "manual";
   localPath?: string;
+  dir?: string; // Serve a folder: request paths resolve to files inside it
   content?: string;
   contentType?: string;
   statusCode?: number;
@@ -52,6 +55,13 @@ export interface MapRemoteAction {
   headers?: HeaderConfig;
 }
 
+export interface RedirectAction {
+  type: "redirect";
+  targetUrl: string;
+  statusCode: 301 | 302 | 307 | 308;
+  preserveQuery?: boolean; // Append the original query string to targetUrl
+}
+
 export interface RewriteHeaderAction {
   type: "rewrite_header";
   headers: HeaderConfig;
@@ -92,10 +102,17 @@ export interface ThrottleAction {
   type: "throttle";
   subtype?: "delay" | "drop" | "timeout" | "reset";
   delayMs?: number;
+  jitterMs?: number; // Delay is randomized within delayMs ± jitterMs
+  jitterSeed?: number; // Fixed seed for a reproducible jitter sequence
   packetLoss?: number; // 0-100 percentage
   bandwidthKbps?: number; // Bandwidth limit in Kbps
 }
 
+export interface BreakpointAction {
+  type: "breakpoint";
+  phase?: "request" | "response" | "both"; // Defaults to "request"
+}
+
 export interface BlockRequestAction {
   type: "block_request";
 }
@@ -103,9 +120,11 @@ export interface BlockRequestAction {
 export type RuleAction =
   | MapLocalAction
   | MapRemoteAction
+  | RedirectAction
   | RewriteHeaderAction
   | RewriteBodyAction
   | ThrottleAction
+  | BreakpointAction
   | BlockRequestAction;
 
 // Main Rule Interface
@@ -114,7 +133,8 @@ export interface RuleExecution {
   enabled: boolean;
   priority: number;
   stopOnMatch?: boolean;
-  // times?: number; // Future
+  disableAfterHit?: boolean; // Auto-disable after the first hit
+  times?: number; // Auto-disable after this many hits
 }
 
 export interface RuleMatchConfig {
@@ -148,6 +168,12 @@ export interface RuleGroup {
   collapsed?: boolean;
 }
 
+// Returned by get_rule_stats, keyed by rule id
+export interface RuleStat {
+  hitCount: number;
+  lastHitMs: number; // Epoch ms of the most recent hit
+}
+
 // Helper type guards
 
 export function isMapLocalAction(action: RuleAction): action is MapLocalAction {
@@ -158,6 +184,10 @@ export function isMapRemoteAction(action: RuleAction): action is MapRemoteAction
   return action.type === "map_remote";
 }
 
+export function isRedirectAction(action: RuleAction): action is RedirectAction {
+  return action.type === "redirect";
+}
+
 export function isRewriteHeaderAction(action: RuleAction): action is RewriteHeaderAction {
   return action.type === "rewrite_header";
 }
@@ -170,6 +200,10 @@ export function isThrottleAction(action: RuleAction): action is ThrottleAction {
   return action.type === "throttle";
 }
 
+export function isBreakpointAction(action: RuleAction): action is BreakpointAction {
+  return action.type === "breakpoint";
+}
+
 export function isBlockRequestAction(action: RuleAction): action is BlockRequestAction {
   return action.type === "block_request";
 }